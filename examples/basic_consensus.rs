@@ -6,7 +6,11 @@
 //! - Starting consensus
 //! - Monitoring state and metrics
 
-use proof_of_emotion::{ConsensusConfig, EmotionalValidator, ProofOfEmotionEngine};
+use proof_of_emotion::{
+    ConsensusConfig, EligibilityPolicy, EmotionalValidator, NetworkHealthWeights,
+    ProofOfEmotionEngine, ProposerEligibility, ProposerPolicy, ReputationPenalties, SelectionMode,
+    StakeWeightFn,
+};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time;
@@ -30,6 +34,38 @@ async fn main() -> anyhow::Result<()> {
         voting_timeout: 8_000,
         proposal_timeout: 10_000,
         finality_timeout: 2_000,
+        selection_mode: SelectionMode::TopK,
+        proposer_policy: ProposerPolicy::Primary,
+        proposer_eligibility: ProposerEligibility::AllMembers,
+        stake_weighted_voting: false,
+        max_reorg_depth: 10,
+        assessment_concurrency: 32,
+        device_read_attempts: 3,
+        device_retry_backoff_ms: 100,
+        max_slashes_in_window: 3,
+        slash_window_epochs: 100,
+        jail_epochs: 10,
+        adaptive_epoch_duration: None,
+        degraded_unavailable: false,
+        finalized_tx_filter_blocks: 100,
+        finalized_tx_filter_false_positive_rate: 0.01,
+        network_health_weights: NetworkHealthWeights::default(),
+        require_nonempty_blocks_when_pending: false,
+        committee_overlap_fraction: 0.0,
+        blocks_per_epoch: 1,
+        max_tip_retries: 0,
+        eligibility_policy: EligibilityPolicy::Hard,
+        max_declining_epochs: 0,
+        check_validation_determinism: false,
+        reputation_penalties: ReputationPenalties::default(),
+        replace_byzantine_proposer: true,
+        threshold_schedule: None,
+        collusion_detection: None,
+        temporal_window_ms: 300_000,
+        max_block_weight: 2_000_000,
+        stake_weight_fn: StakeWeightFn::Sqrt,
+        proposer_cooldown: None,
+        chain_id: "poe-devnet".to_string(),
     };
 
     println!("⚙️  Configuration:");
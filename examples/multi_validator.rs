@@ -13,14 +13,13 @@ async fn main() -> anyhow::Result<()> {
 
     println!("🚀 Multi-Validator Consensus Example\n");
 
-    let config = ConsensusConfig {
-        epoch_duration: 20_000,
-        emotional_threshold: 70,
-        byzantine_threshold: 67,
-        committee_size: 10,
-        minimum_stake: 10_000,
-        ..Default::default()
-    };
+    let config = ConsensusConfig::builder()
+        .epoch_duration(20_000)
+        .emotional_threshold(70)
+        .byzantine_threshold(67)
+        .committee_size(10)
+        .minimum_stake(10_000)
+        .build()?;
 
     let engine = Arc::new(ProofOfEmotionEngine::new(config)?);
 
@@ -0,0 +1,178 @@
+//! Proposer-signed summaries of how a block was finalized, for external verification
+
+use crate::crypto::{KeyPair, Signature};
+use crate::types::Block;
+use serde::{Deserialize, Serialize};
+
+/// Domain separator keeping attestation signatures distinct from block signatures
+const ATTESTATION_DOMAIN: &[u8] = b"poe-emotional-attestation";
+
+/// Consensus outcome of a finalized block, signed by its proposer
+///
+/// Lets a third party check the emotional fitness a block was finalized with without
+/// replaying the chain. The signature covers the block hash, so an attestation cannot
+/// be presented for another block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmotionalAttestation {
+    /// Hash of the attested block
+    pub block_hash: String,
+    /// Height of the attested block
+    pub height: u64,
+    /// Epoch the block was finalized in
+    pub epoch: u64,
+    /// Validator who proposed the block
+    pub proposer_id: String,
+    /// Number of validators who participated in the vote
+    pub participant_count: usize,
+    /// Consensus strength achieved (percentage)
+    pub consensus_strength: u8,
+    /// Average emotional fitness of the participants
+    pub emotional_fitness: u8,
+    /// Proposer signature over the fields above (JSON-encoded)
+    pub signature: String,
+}
+
+impl EmotionalAttestation {
+    /// Data covered by the proposer signature
+    pub fn signing_data(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        data.extend_from_slice(ATTESTATION_DOMAIN);
+        data.extend_from_slice(&(self.block_hash.len() as u64).to_le_bytes());
+        data.extend_from_slice(self.block_hash.as_bytes());
+        data.extend_from_slice(&self.height.to_le_bytes());
+        data.extend_from_slice(&self.epoch.to_le_bytes());
+        data.extend_from_slice(&(self.proposer_id.len() as u64).to_le_bytes());
+        data.extend_from_slice(self.proposer_id.as_bytes());
+        data.extend_from_slice(&(self.participant_count as u64).to_le_bytes());
+        data.push(self.consensus_strength);
+        data.push(self.emotional_fitness);
+
+        data
+    }
+}
+
+impl Block {
+    /// Attestation of the consensus this block was finalized with
+    ///
+    /// Fails for blocks without consensus metadata, i.e. blocks that were never
+    /// finalized.
+    pub fn attestation(&self) -> Result<EmotionalAttestation, String> {
+        let metadata = self
+            .consensus_metadata
+            .as_ref()
+            .ok_or_else(|| format!("Block {} has no consensus metadata", self.header.height))?;
+
+        Ok(EmotionalAttestation {
+            block_hash: self.hash.clone(),
+            height: self.header.height,
+            epoch: self.header.epoch,
+            proposer_id: self.header.validator_id.clone(),
+            participant_count: metadata.participant_count,
+            consensus_strength: metadata.consensus_strength,
+            emotional_fitness: metadata.emotional_fitness,
+            signature: metadata.attestation_signature.clone(),
+        })
+    }
+
+    /// Sign the attestation of a finalized block with the proposer's key pair
+    ///
+    /// Must be called after the consensus metadata is attached.
+    pub fn sign_attestation(&mut self, key_pair: &KeyPair) -> Result<(), String> {
+        let attestation = self.attestation()?;
+        let sig = key_pair
+            .sign(&attestation.signing_data())
+            .map_err(|e| format!("Failed to sign attestation: {}", e))?;
+        let signature = serde_json::to_string(&sig)
+            .map_err(|e| format!("Failed to serialize signature: {}", e))?;
+
+        if let Some(metadata) = self.consensus_metadata.as_mut() {
+            metadata.attestation_signature = signature;
+        }
+        Ok(())
+    }
+}
+
+/// Verify an attestation against the proposer's public key (hex)
+pub fn verify_attestation(
+    attestation: &EmotionalAttestation,
+    proposer_public_key: &str,
+) -> Result<bool, String> {
+    if attestation.signature.is_empty() {
+        return Err("Attestation has no signature".to_string());
+    }
+
+    let sig: Signature = serde_json::from_str(&attestation.signature)
+        .map_err(|e| format!("Failed to deserialize signature: {}", e))?;
+
+    KeyPair::verify(&attestation.signing_data(), &sig, proposer_public_key)
+        .map_err(|e| format!("Attestation verification failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConsensusMetadata;
+
+    fn finalized_block(height: u64, key_pair: &KeyPair) -> Block {
+        let mut block = Block::new(
+            height,
+            1,
+            "0".repeat(64),
+            "validator-1".to_string(),
+            85,
+            vec![],
+        );
+        block.sign(key_pair).unwrap();
+        block.consensus_metadata = Some(ConsensusMetadata {
+            participant_count: 4,
+            consensus_strength: 90,
+            emotional_fitness: 82,
+            mean_emotional_fitness: 82,
+            byzantine_failures: 0,
+            finalized_at: 1_000_000,
+            participants: vec![],
+            committee_proof: None,
+            attestation_signature: String::new(),
+        });
+        block.sign_attestation(key_pair).unwrap();
+        block
+    }
+
+    #[test]
+    fn test_attestation_verifies() {
+        let key_pair = KeyPair::generate().unwrap();
+        let block = finalized_block(1, &key_pair);
+
+        let attestation = block.attestation().unwrap();
+        assert_eq!(attestation.block_hash, block.hash);
+        assert_eq!(attestation.emotional_fitness, 82);
+        assert!(verify_attestation(&attestation, &key_pair.public_key_hex()).unwrap());
+
+        let other = KeyPair::generate().unwrap();
+        assert!(!verify_attestation(&attestation, &other.public_key_hex()).unwrap_or(false));
+
+        let mut inflated = attestation;
+        inflated.emotional_fitness = 99;
+        assert!(!verify_attestation(&inflated, &key_pair.public_key_hex()).unwrap_or(false));
+    }
+
+    #[test]
+    fn test_attestation_not_reusable_across_blocks() {
+        let key_pair = KeyPair::generate().unwrap();
+        let first = finalized_block(1, &key_pair);
+        let mut second = finalized_block(2, &key_pair);
+
+        // Same consensus figures, but the signature is bound to the first block's hash
+        let reused = first.attestation().unwrap().signature;
+        if let Some(metadata) = second.consensus_metadata.as_mut() {
+            metadata.attestation_signature = reused;
+        }
+        let attestation = second.attestation().unwrap();
+        assert!(!verify_attestation(&attestation, &key_pair.public_key_hex()).unwrap_or(false));
+
+        let mut retargeted = first.attestation().unwrap();
+        retargeted.block_hash = second.hash.clone();
+        assert!(!verify_attestation(&retargeted, &key_pair.public_key_hex()).unwrap_or(false));
+    }
+}
@@ -0,0 +1,140 @@
+//! Structured audit trail for slashing, finalization, forks and checkpoints
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A single auditable consensus action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditEntry {
+    /// A block was finalized
+    BlockFinalized {
+        /// Block height
+        height: u64,
+        /// Block hash
+        hash: String,
+        /// Epoch the block was produced in
+        epoch: u64,
+        /// Proposing validator
+        proposer: String,
+        /// Number of transactions in the block
+        transaction_count: usize,
+        /// Consensus strength (percentage)
+        consensus_strength: u8,
+        /// Time of finalization (Unix milliseconds)
+        timestamp: u64,
+    },
+    /// A validator was slashed
+    ValidatorSlashed {
+        /// Slashed validator
+        validator_id: String,
+        /// Reason for slashing
+        reason: String,
+        /// Reputation after the penalty
+        reputation_after: u8,
+        /// Time of slashing (Unix milliseconds)
+        timestamp: u64,
+    },
+    /// A fork was resolved
+    ForkResolved {
+        /// Height of the fork
+        height: u64,
+        /// Hash chosen by the fork choice rule
+        winning_hash: String,
+        /// Time of resolution (Unix milliseconds)
+        timestamp: u64,
+    },
+    /// A checkpoint was created
+    CheckpointCreated {
+        /// Checkpoint height
+        height: u64,
+        /// Block hash at the checkpoint
+        block_hash: String,
+        /// Epoch of the checkpoint
+        epoch: u64,
+        /// Time of creation (Unix milliseconds)
+        timestamp: u64,
+    },
+}
+
+/// Destination for audit entries
+pub trait AuditSink: Send + Sync {
+    /// Record an entry
+    fn record(&self, entry: AuditEntry);
+}
+
+/// Optional audit sink shared by the components that emit entries
+#[derive(Clone, Default)]
+pub(crate) struct AuditHandle {
+    sink: Arc<RwLock<Option<Arc<dyn AuditSink>>>>,
+}
+
+impl AuditHandle {
+    /// Install the sink that receives entries
+    pub(crate) fn set(&self, sink: Arc<dyn AuditSink>) {
+        *self.sink.write() = Some(sink);
+    }
+
+    /// Record an entry if a sink is installed
+    pub(crate) fn record(&self, entry: AuditEntry) {
+        if let Some(sink) = self.sink.read().as_ref() {
+            sink.record(entry);
+        }
+    }
+}
+
+/// In-memory audit sink for tests and ad-hoc querying
+#[derive(Debug, Default)]
+pub struct VecAuditSink {
+    entries: RwLock<Vec<AuditEntry>>,
+}
+
+impl VecAuditSink {
+    /// Create an empty sink
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All recorded entries, oldest first
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.read().clone()
+    }
+
+    /// Slashing entries for a validator
+    pub fn slashings_for(&self, validator_id: &str) -> Vec<AuditEntry> {
+        self.entries
+            .read()
+            .iter()
+            .filter(|entry| {
+                matches!(entry, AuditEntry::ValidatorSlashed { validator_id: id, .. } if id == validator_id)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Finalization entries
+    pub fn finalized_blocks(&self) -> Vec<AuditEntry> {
+        self.entries
+            .read()
+            .iter()
+            .filter(|entry| matches!(entry, AuditEntry::BlockFinalized { .. }))
+            .cloned()
+            .collect()
+    }
+
+    /// Number of recorded entries
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    /// Returns true if nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().is_empty()
+    }
+}
+
+impl AuditSink for VecAuditSink {
+    fn record(&self, entry: AuditEntry) {
+        self.entries.write().push(entry);
+    }
+}
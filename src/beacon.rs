@@ -0,0 +1,98 @@
+//! Shared randomness for committee and proposer selection
+//!
+//! Seeding selection from the previous block hash alone lets its proposer grind
+//! candidate blocks for a favourable committee. The default [`HashChainBeacon`] folds
+//! in every finalized block hash, so the seed depends on the whole chain, but the
+//! latest proposer can still influence it through its block. Only an external beacon
+//! (e.g. drand), injected with
+//! [`ProofOfEmotionEngine::with_randomness_beacon`](crate::ProofOfEmotionEngine::with_randomness_beacon),
+//! takes the seed out of proposers' hands.
+
+use parking_lot::RwLock;
+use sha2::{Digest, Sha256};
+
+/// Source of per-epoch randomness
+pub trait RandomnessBeacon: Send + Sync {
+    /// Random value for selection in `epoch`
+    fn value(&self, epoch: u64) -> [u8; 32];
+
+    /// Observe a newly finalized block; beacons that do not depend on the chain ignore it
+    fn on_block_finalized(&self, _block_hash: &str) {}
+
+    /// Reset to the state reached by finalizing `block_hashes`, oldest first
+    ///
+    /// Called when the finalized chain is restored or rolled back; beacons that do not
+    /// depend on the chain ignore it.
+    fn rebuild(&self, _block_hashes: &[&str]) {}
+}
+
+/// Beacon over a hash chain of all finalized block hashes
+///
+/// The chain value is `SHA-256(previous value || block hash)`, starting from zero, and
+/// the value for an epoch is `SHA-256("beacon" || epoch || chain value)`.
+#[derive(Debug, Default)]
+pub struct HashChainBeacon {
+    chain: RwLock<[u8; 32]>,
+}
+
+impl HashChainBeacon {
+    /// Create a beacon with an empty chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn fold(chain: [u8; 32], block_hash: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(chain);
+        hasher.update(block_hash.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
+impl RandomnessBeacon for HashChainBeacon {
+    fn value(&self, epoch: u64) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"beacon");
+        hasher.update(epoch.to_le_bytes());
+        hasher.update(*self.chain.read());
+        hasher.finalize().into()
+    }
+
+    fn on_block_finalized(&self, block_hash: &str) {
+        let mut chain = self.chain.write();
+        *chain = Self::fold(*chain, block_hash);
+    }
+
+    fn rebuild(&self, block_hashes: &[&str]) {
+        *self.chain.write() = block_hashes
+            .iter()
+            .fold([0; 32], |chain, block_hash| Self::fold(chain, block_hash));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_chain_beacon_follows_blocks() {
+        let beacon = HashChainBeacon::new();
+        let initial = beacon.value(1);
+        assert_eq!(initial, beacon.value(1));
+        assert_ne!(initial, beacon.value(2));
+
+        beacon.on_block_finalized("block-1");
+        let after_block = beacon.value(1);
+        assert_ne!(initial, after_block);
+
+        let replica = HashChainBeacon::new();
+        replica.on_block_finalized("block-1");
+        assert_eq!(replica.value(1), after_block);
+
+        beacon.on_block_finalized("block-2");
+        replica.rebuild(&["block-1", "block-2"]);
+        assert_eq!(replica.value(1), beacon.value(1));
+        replica.rebuild(&[]);
+        assert_eq!(replica.value(1), initial);
+    }
+}
@@ -1,10 +1,13 @@
 //! Biometric validation and emotional state monitoring
 
+use crate::clock::{Clock, SystemClock};
 use crate::crypto::KeyPair;
 use crate::error::{ConsensusError, Result};
+use crate::zkp::BiometricProof;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
 use std::sync::Arc;
 
 /// Type of biometric reading
@@ -22,6 +25,18 @@ pub enum BiometricType {
     SkinTemperature,
 }
 
+impl BiometricType {
+    /// Range a calibrated value of this type is clamped to
+    pub fn calibrated_range(&self) -> (f64, f64) {
+        match self {
+            Self::HeartRate => (0.0, 250.0),
+            Self::StressLevel | Self::FocusLevel => (0.0, 100.0),
+            Self::SkinConductance => (0.0, 100.0),
+            Self::SkinTemperature => (0.0, 50.0),
+        }
+    }
+}
+
 /// Biometric reading from a device
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BiometricReading {
@@ -39,6 +54,157 @@ pub struct BiometricReading {
     pub metadata: Option<std::collections::HashMap<String, String>>,
 }
 
+/// Metadata key for the device firmware version, e.g. `2.1.0`
+pub const METADATA_FIRMWARE_VERSION: &str = "firmware_version";
+
+/// Metadata key for the sensor model identifier
+pub const METADATA_SENSOR_MODEL: &str = "sensor_model";
+
+/// Metadata key for the last sensor calibration date (`YYYY-MM-DD`)
+pub const METADATA_CALIBRATION_DATE: &str = "calibration_date";
+
+/// Format check for the value of a known metadata key
+type MetadataCheck = fn(&str) -> bool;
+
+impl BiometricReading {
+    /// Check the known metadata keys for well-formed values
+    ///
+    /// Unknown keys are ignored. With `require_known` every known key must be present.
+    pub fn validate_metadata(&self, require_known: bool) -> std::result::Result<(), String> {
+        let empty = std::collections::HashMap::new();
+        let metadata = self.metadata.as_ref().unwrap_or(&empty);
+
+        let checks: [(&str, MetadataCheck); 3] = [
+            (METADATA_FIRMWARE_VERSION, is_firmware_version),
+            (METADATA_SENSOR_MODEL, is_sensor_model),
+            (METADATA_CALIBRATION_DATE, is_calibration_date),
+        ];
+        for (key, is_valid) in checks {
+            match metadata.get(key) {
+                Some(value) if !is_valid(value) => {
+                    return Err(format!(
+                        "Malformed {} '{}' from device {}",
+                        key, value, self.device_id
+                    ))
+                }
+                None if require_known => {
+                    return Err(format!("Missing {} from device {}", key, self.device_id))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Dot-separated numeric version with one to three components
+fn is_firmware_version(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('.').collect();
+    parts.len() <= 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Up to 64 alphanumeric characters, dashes, underscores or dots
+fn is_sensor_model(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 64
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Calendar date in `YYYY-MM-DD` form
+fn is_calibration_date(value: &str) -> bool {
+    value.len() == 10 && chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+}
+
+/// Smallest accepted calibration scale
+pub const MIN_CALIBRATION_SCALE: f64 = 0.01;
+
+/// Largest accepted calibration scale
+pub const MAX_CALIBRATION_SCALE: f64 = 100.0;
+
+/// Largest accepted absolute calibration offset
+pub const MAX_CALIBRATION_OFFSET: f64 = 1_000.0;
+
+/// Linear correction mapping a device's raw value onto the reference scale
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LinearCalibration {
+    /// Multiplier applied to the raw value
+    pub scale: f64,
+    /// Added after scaling
+    pub offset: f64,
+}
+
+impl LinearCalibration {
+    /// Calibrated value: `raw * scale + offset`
+    pub fn apply(&self, raw: f64) -> f64 {
+        raw * self.scale + self.offset
+    }
+
+    /// Whether the scale is finite and within
+    /// [`MIN_CALIBRATION_SCALE`]..=[`MAX_CALIBRATION_SCALE`] and the offset finite and at
+    /// most [`MAX_CALIBRATION_OFFSET`] in magnitude
+    pub fn is_valid(&self) -> bool {
+        (MIN_CALIBRATION_SCALE..=MAX_CALIBRATION_SCALE).contains(&self.scale)
+            && self.offset.abs() <= MAX_CALIBRATION_OFFSET
+    }
+}
+
+/// Per-device corrections for each biometric type
+///
+/// Types without an entry pass through unchanged, so the default profile is the
+/// identity calibration.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationProfile {
+    /// Correction per biometric type
+    pub adjustments: HashMap<BiometricType, LinearCalibration>,
+}
+
+impl CalibrationProfile {
+    /// Identity calibration
+    pub fn identity() -> Self {
+        Self::default()
+    }
+
+    /// Add a correction for one biometric type
+    pub fn with(mut self, biometric_type: BiometricType, scale: f64, offset: f64) -> Self {
+        self.adjustments
+            .insert(biometric_type, LinearCalibration { scale, offset });
+        self
+    }
+
+    /// Check every correction with [`LinearCalibration::is_valid`]
+    pub fn validate(&self) -> Result<()> {
+        for (biometric_type, calibration) in &self.adjustments {
+            if !calibration.is_valid() {
+                return Err(ConsensusError::config_error(format!(
+                    "Invalid {:?} calibration: scale {} and offset {} must be finite, the scale in [{}, {}] and the offset within ±{}",
+                    biometric_type,
+                    calibration.scale,
+                    calibration.offset,
+                    MIN_CALIBRATION_SCALE,
+                    MAX_CALIBRATION_SCALE,
+                    MAX_CALIBRATION_OFFSET
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Map a raw reading onto the reference scale, clamped to the type's
+    /// [calibrated range](BiometricType::calibrated_range)
+    pub fn apply(&self, reading: &mut BiometricReading) {
+        if let Some(calibration) = self.adjustments.get(&reading.biometric_type) {
+            let (min, max) = reading.biometric_type.calibrated_range();
+            reading.value = calibration.apply(reading.value).clamp(min, max);
+        }
+    }
+}
+
 /// Emotional profile of a validator
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmotionalProfile {
@@ -52,6 +218,88 @@ pub struct EmotionalProfile {
     pub last_updated: u64,
     /// Recent biometric readings
     pub recent_readings: Vec<BiometricReading>,
+    /// Number of anomalous readings detected in the latest update
+    #[serde(default)]
+    pub anomaly_count: usize,
+}
+
+/// Emotional state a validator carries across restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorEmotionalState {
+    /// Validator the state belongs to
+    pub validator_id: String,
+    /// Emotional profile at the time of saving
+    pub profile: Option<EmotionalProfile>,
+    /// `(score, timestamp)` history, oldest first
+    pub score_history: Vec<(u8, u64)>,
+}
+
+/// Tuning parameters for biometric processing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BiometricConfig {
+    /// Readings further than this many standard deviations from the mean are anomalous
+    pub anomaly_std_threshold: f64,
+    /// Number of recent heart-rate/stress values kept per series for anomaly detection
+    pub anomaly_window: usize,
+    /// Minimum series length before anomaly detection is applied
+    pub anomaly_min_samples: usize,
+    /// Confidence reduction (0-100) per anomalous reading
+    pub anomaly_confidence_penalty: u8,
+    /// Slash the validator for invalid biometric data when anomalies are detected
+    pub slash_on_anomaly: bool,
+    /// Weight of the newest score in the exponential moving average (0.0 - 1.0)
+    #[serde(default = "default_ema_alpha")]
+    pub ema_alpha: f64,
+    /// Use the smoothed score instead of the latest score for eligibility
+    #[serde(default)]
+    pub use_smoothed_score: bool,
+    /// Readings with a lower quality (0.0 - 1.0) are discarded before scoring
+    #[serde(default = "default_min_reading_quality")]
+    pub min_reading_quality: f64,
+    /// Updates with more readings than this are rejected
+    #[serde(default = "default_max_readings_per_update")]
+    pub max_readings_per_update: usize,
+    /// Number of readings kept in the profile's `recent_readings`
+    #[serde(default = "default_max_recent_readings")]
+    pub max_recent_readings: usize,
+    /// Reject updates with readings missing the known metadata keys or carrying
+    /// malformed values for them
+    #[serde(default)]
+    pub strict_metadata: bool,
+}
+
+fn default_ema_alpha() -> f64 {
+    0.3
+}
+
+fn default_min_reading_quality() -> f64 {
+    0.5
+}
+
+fn default_max_readings_per_update() -> usize {
+    1_000
+}
+
+fn default_max_recent_readings() -> usize {
+    100
+}
+
+impl Default for BiometricConfig {
+    fn default() -> Self {
+        Self {
+            anomaly_std_threshold: 2.5,
+            anomaly_window: 50,
+            anomaly_min_samples: 8,
+            anomaly_confidence_penalty: 25,
+            slash_on_anomaly: false,
+            ema_alpha: default_ema_alpha(),
+            use_smoothed_score: false,
+            min_reading_quality: default_min_reading_quality(),
+            max_readings_per_update: default_max_readings_per_update(),
+            max_recent_readings: default_max_recent_readings(),
+            strict_metadata: false,
+        }
+    }
 }
 
 /// Trend in emotional score
@@ -65,6 +313,110 @@ pub enum EmotionalTrend {
     Declining,
 }
 
+/// Outcome of assessing a validator for committee eligibility
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EligibilityStatus {
+    /// The validator may be selected for the committee
+    Eligible,
+    /// The validator was excluded from this epoch
+    Ineligible(IneligibilityReason),
+}
+
+/// Why a validator was excluded from committee selection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IneligibilityReason {
+    /// The validator has been deactivated
+    Inactive,
+    /// Stake is below the configured minimum
+    InsufficientStake {
+        /// Current stake
+        stake: u64,
+        /// Required stake
+        minimum: u64,
+    },
+    /// Emotional score is below the configured threshold
+    LowEmotionalScore {
+        /// Score used for eligibility
+        score: u8,
+        /// Required score
+        threshold: u8,
+    },
+    /// The newest biometric reading is older than one epoch
+    StaleProfile {
+        /// Age of the newest reading in milliseconds
+        age_ms: u64,
+    },
+    /// The readings span more time than the temporal window allows
+    ReadingsOutsideWindow {
+        /// Time between the oldest and newest reading in milliseconds
+        spread_ms: u64,
+        /// Allowed span in milliseconds
+        window_ms: u64,
+    },
+    /// The biometric device kept failing after all read attempts
+    DeviceReadFailed(String),
+    /// The device returned readings that could not be scored
+    InvalidReadings(String),
+    /// Anomalous biometric readings were detected and the validator was slashed
+    BiometricAnomaly {
+        /// Number of anomalous readings
+        anomaly_count: usize,
+    },
+    /// Weighted mean of score and confidence is below the soft eligibility cutoff
+    LowCombinedScore {
+        /// Weighted mean of score and confidence
+        combined: u8,
+        /// Required weighted mean
+        cutoff: u8,
+    },
+    /// A biometric proof is required but none was submitted
+    MissingProof,
+    /// The submitted biometric proof did not verify
+    InvalidProof(String),
+}
+
+impl fmt::Display for IneligibilityReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Inactive => write!(f, "validator is inactive"),
+            Self::InsufficientStake { stake, minimum } => {
+                write!(f, "stake {} below minimum {}", stake, minimum)
+            }
+            Self::LowEmotionalScore { score, threshold } => {
+                write!(f, "emotional score {} below threshold {}", score, threshold)
+            }
+            Self::StaleProfile { age_ms } => {
+                write!(f, "biometric readings are {}ms old", age_ms)
+            }
+            Self::ReadingsOutsideWindow {
+                spread_ms,
+                window_ms,
+            } => write!(
+                f,
+                "biometric readings span {}ms, beyond the {}ms window",
+                spread_ms, window_ms
+            ),
+            Self::DeviceReadFailed(reason) => write!(f, "device read failed: {}", reason),
+            Self::InvalidReadings(reason) => write!(f, "invalid readings: {}", reason),
+            Self::BiometricAnomaly { anomaly_count } => {
+                write!(f, "{} anomalous biometric readings", anomaly_count)
+            }
+            Self::LowCombinedScore { combined, cutoff } => {
+                write!(f, "combined score {} below cutoff {}", combined, cutoff)
+            }
+            Self::MissingProof => write!(f, "no biometric proof submitted"),
+            Self::InvalidProof(reason) => write!(f, "invalid biometric proof: {}", reason),
+        }
+    }
+}
+
+/// Recent biometric series per validator
+#[derive(Debug, Default)]
+struct BiometricHistory {
+    heart_rate: VecDeque<f64>,
+    stress_level: VecDeque<f64>,
+}
+
 /// Mock biometric device for testing
 pub trait BiometricDevice: Send + Sync {
     /// Collect biometric readings
@@ -93,8 +445,22 @@ pub struct EmotionalValidator {
     pub emotional_profile: Arc<RwLock<Option<EmotionalProfile>>>,
     /// Historical emotional scores
     score_history: Arc<RwLock<VecDeque<(u8, u64)>>>,
+    /// Recent heart-rate and stress values used for anomaly detection
+    biometric_history: Arc<RwLock<BiometricHistory>>,
+    /// Biometric processing parameters
+    biometric_config: BiometricConfig,
+    /// Time source for profile timestamps and block validation
+    clock: Arc<dyn Clock>,
+    /// Biometric device read each epoch (the simulator is used when unset)
+    device: Option<Arc<dyn BiometricDevice>>,
+    /// Proof of emotional fitness awaiting the next assessment
+    biometric_proof: Arc<RwLock<Option<BiometricProof>>>,
+    /// Calibration per device ID; devices without one use the identity calibration
+    calibrations: Arc<RwLock<HashMap<String, CalibrationProfile>>>,
     /// Reputation score (0-100)
     pub reputation: Arc<RwLock<u8>>,
+    /// Commission charged to delegators (percentage)
+    commission: u8,
 }
 
 impl EmotionalValidator {
@@ -110,7 +476,14 @@ impl EmotionalValidator {
             is_active: Arc::new(RwLock::new(true)),
             emotional_profile: Arc::new(RwLock::new(None)),
             score_history: Arc::new(RwLock::new(VecDeque::with_capacity(100))),
+            biometric_history: Arc::new(RwLock::new(BiometricHistory::default())),
+            biometric_config: BiometricConfig::default(),
+            clock: Arc::new(SystemClock),
+            device: None,
+            biometric_proof: Arc::new(RwLock::new(None)),
+            calibrations: Arc::new(RwLock::new(HashMap::new())),
             reputation: Arc::new(RwLock::new(100)),
+            commission: 0,
         })
     }
 
@@ -124,7 +497,110 @@ impl EmotionalValidator {
             is_active: Arc::new(RwLock::new(true)),
             emotional_profile: Arc::new(RwLock::new(None)),
             score_history: Arc::new(RwLock::new(VecDeque::with_capacity(100))),
+            biometric_history: Arc::new(RwLock::new(BiometricHistory::default())),
+            biometric_config: BiometricConfig::default(),
+            clock: Arc::new(SystemClock),
+            device: None,
+            biometric_proof: Arc::new(RwLock::new(None)),
+            calibrations: Arc::new(RwLock::new(HashMap::new())),
             reputation: Arc::new(RwLock::new(100)),
+            commission: 0,
+        }
+    }
+
+    /// Create validator from a passphrase-encrypted keystore file
+    ///
+    /// Lets a restarted node keep its identity (and therefore its stake association).
+    pub fn from_keyfile(
+        id: impl Into<String>,
+        stake: u64,
+        path: impl AsRef<std::path::Path>,
+        passphrase: &str,
+    ) -> Result<Self> {
+        let key_pair = KeyPair::load_from_file(path, passphrase)?;
+        Ok(Self::from_keypair(id, stake, key_pair))
+    }
+
+    /// Replace the biometric processing parameters
+    pub fn with_biometric_config(mut self, config: BiometricConfig) -> Self {
+        self.biometric_config = config;
+        self
+    }
+
+    /// Replace the time source
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Read biometrics from a specific device instead of the simulator
+    pub fn with_device(mut self, device: Arc<dyn BiometricDevice>) -> Self {
+        self.device = Some(device);
+        self
+    }
+
+    /// Set the commission charged to delegators (percentage)
+    pub fn with_commission(mut self, commission: u8) -> Self {
+        self.commission = commission;
+        self
+    }
+
+    /// Get the attached biometric device, if any
+    pub fn device(&self) -> Option<&Arc<dyn BiometricDevice>> {
+        self.device.as_ref()
+    }
+
+    /// Get the biometric processing parameters
+    pub fn biometric_config(&self) -> &BiometricConfig {
+        &self.biometric_config
+    }
+
+    /// Set the calibration applied to readings from `device_id` before scoring
+    ///
+    /// Fails without changing the calibration if any correction is
+    /// [invalid](LinearCalibration::is_valid).
+    pub fn set_calibration(
+        &self,
+        device_id: impl Into<String>,
+        profile: CalibrationProfile,
+    ) -> Result<()> {
+        profile.validate()?;
+        self.calibrations.write().insert(device_id.into(), profile);
+        Ok(())
+    }
+
+    /// Submit a proof of emotional fitness for the next assessment
+    ///
+    /// Used instead of device readings when the engine requires proofs.
+    pub fn submit_biometric_proof(&self, proof: BiometricProof) {
+        *self.biometric_proof.write() = Some(proof);
+    }
+
+    /// Take the submitted proof, so each proof is assessed only once
+    pub fn take_biometric_proof(&self) -> Option<BiometricProof> {
+        self.biometric_proof.write().take()
+    }
+
+    /// Update emotional state from a verified proof's public score
+    ///
+    /// The profile keeps no readings, since none were disclosed.
+    pub fn apply_proven_score(&self, emotional_score: u8) {
+        let trend = self.analyze_trend(emotional_score);
+        let timestamp = self.clock.now_millis();
+
+        *self.emotional_profile.write() = Some(EmotionalProfile {
+            emotional_score,
+            trend,
+            confidence: 100,
+            last_updated: timestamp,
+            recent_readings: Vec::new(),
+            anomaly_count: 0,
+        });
+
+        let mut history = self.score_history.write();
+        history.push_back((emotional_score, timestamp));
+        if history.len() > 100 {
+            history.pop_front();
         }
     }
 
@@ -135,15 +611,57 @@ impl EmotionalValidator {
                 "No biometric readings provided",
             ));
         }
+        if readings.len() > self.biometric_config.max_readings_per_update {
+            return Err(ConsensusError::biometric_validation_failed(format!(
+                "{} readings exceed the limit of {} per update",
+                readings.len(),
+                self.biometric_config.max_readings_per_update
+            )));
+        }
+
+        if self.biometric_config.strict_metadata {
+            for reading in &readings {
+                reading
+                    .validate_metadata(true)
+                    .map_err(ConsensusError::biometric_validation_failed)?;
+            }
+        }
+
+        let min_quality = self.biometric_config.min_reading_quality;
+        let mut readings: Vec<BiometricReading> = readings
+            .into_iter()
+            .filter(|r| r.quality >= min_quality)
+            .collect();
+
+        let calibrations = self.calibrations.read();
+        for reading in &mut readings {
+            if let Some(profile) = calibrations.get(&reading.device_id) {
+                profile.apply(reading);
+            }
+        }
+        drop(calibrations);
+        if readings.is_empty() {
+            return Err(ConsensusError::biometric_validation_failed(format!(
+                "No biometric readings with quality >= {}",
+                min_quality
+            )));
+        }
 
         let emotional_score = self.calculate_emotional_score(&readings)?;
         let trend = self.analyze_trend(emotional_score);
-        let confidence = self.calculate_confidence(&readings);
+        let anomaly_count = self.detect_anomalies(&readings);
+        let penalty = (anomaly_count as u32)
+            .saturating_mul(self.biometric_config.anomaly_confidence_penalty as u32)
+            .min(100) as u8;
+        let confidence = self.calculate_confidence(&readings).saturating_sub(penalty);
 
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| ConsensusError::internal(format!("System time error: {}", e)))?
-            .as_millis() as u64;
+        let timestamp = self.clock.now_millis();
+
+        // Keep only the newest readings in the profile
+        let excess = readings
+            .len()
+            .saturating_sub(self.biometric_config.max_recent_readings);
+        readings.drain(..excess);
 
         let profile = EmotionalProfile {
             emotional_score,
@@ -151,6 +669,7 @@ impl EmotionalValidator {
             confidence,
             last_updated: timestamp,
             recent_readings: readings,
+            anomaly_count,
         };
 
         *self.emotional_profile.write() = Some(profile);
@@ -208,15 +727,71 @@ impl EmotionalValidator {
         Ok(final_score)
     }
 
+    /// Record heart-rate/stress readings and count how many of them are anomalous
+    ///
+    /// Each series is checked with [`crate::utils::detect_anomalies`] over the recent
+    /// window; only indices belonging to the new readings are counted, so a past spike
+    /// is not penalized twice.
+    fn detect_anomalies(&self, readings: &[BiometricReading]) -> usize {
+        let config = &self.biometric_config;
+        let mut guard = self.biometric_history.write();
+        let history = &mut *guard;
+        let mut anomalies = 0;
+
+        for (biometric_type, series) in [
+            (BiometricType::HeartRate, &mut history.heart_rate),
+            (BiometricType::StressLevel, &mut history.stress_level),
+        ] {
+            let new_values: Vec<f64> = readings
+                .iter()
+                .filter(|r| r.biometric_type == biometric_type)
+                .map(|r| r.value)
+                .collect();
+            if new_values.is_empty() {
+                continue;
+            }
+
+            series.extend(new_values.iter().copied());
+            while series.len() > config.anomaly_window.max(1) {
+                series.pop_front();
+            }
+
+            if series.len() < config.anomaly_min_samples {
+                continue;
+            }
+
+            let values: Vec<f64> = series.iter().copied().collect();
+            let first_new = values.len().saturating_sub(new_values.len());
+            anomalies += crate::utils::detect_anomalies(&values, config.anomaly_std_threshold)
+                .into_iter()
+                .filter(|&i| i >= first_new)
+                .count();
+        }
+
+        anomalies
+    }
+
+    /// Number of anomalous readings detected in the latest update
+    pub fn get_anomaly_count(&self) -> usize {
+        self.emotional_profile
+            .read()
+            .as_ref()
+            .map(|p| p.anomaly_count)
+            .unwrap_or(0)
+    }
+
     /// Analyze trend in emotional scores
-    fn analyze_trend(&self, _current_score: u8) -> EmotionalTrend {
+    fn analyze_trend(&self, current_score: u8) -> EmotionalTrend {
         let history = self.score_history.read();
 
         if history.len() < 3 {
             return EmotionalTrend::Stable;
         }
 
-        let recent: Vec<_> = history.iter().rev().take(5).map(|(s, _)| *s).collect();
+        // Oldest first, ending with the current score, so a positive slope is improving
+        let mut recent: Vec<_> = history.iter().rev().take(4).map(|(s, _)| *s).collect();
+        recent.reverse();
+        recent.push(current_score);
 
         let n = recent.len() as f64;
         let sum_x: f64 = (0..recent.len()).map(|i| i as f64).sum();
@@ -275,6 +850,78 @@ impl EmotionalValidator {
             .unwrap_or(0)
     }
 
+    /// Get the confidence (0-100) behind the current emotional score
+    pub fn get_confidence(&self) -> u8 {
+        self.emotional_profile
+            .read()
+            .as_ref()
+            .map(|p| p.confidence)
+            .unwrap_or(0)
+    }
+
+    /// Up to `count` most recent emotional scores, oldest first
+    pub fn recent_scores(&self, count: usize) -> Vec<u8> {
+        let history = self.score_history.read();
+        let skip = history.len().saturating_sub(count);
+        history.iter().skip(skip).map(|(score, _)| *score).collect()
+    }
+
+    /// Get the exponential moving average of the emotional score history
+    ///
+    /// Damps epoch-to-epoch swings caused by a single noisy biometric batch.
+    /// Returns 0 when no scores have been recorded.
+    pub fn get_smoothed_score(&self) -> u8 {
+        let alpha = self.biometric_config.ema_alpha.clamp(0.0, 1.0);
+        let history = self.score_history.read();
+
+        let mut scores = history.iter().map(|(score, _)| *score as f64);
+        let Some(first) = scores.next() else {
+            return 0;
+        };
+        let ema = scores.fold(first, |ema, score| alpha * score + (1.0 - alpha) * ema);
+
+        ema.round().clamp(0.0, 100.0) as u8
+    }
+
+    /// Capture the emotional profile and score history for a later restart
+    pub fn save_state(&self) -> ValidatorEmotionalState {
+        ValidatorEmotionalState {
+            validator_id: self.id.clone(),
+            profile: self.emotional_profile.read().clone(),
+            score_history: self.score_history.read().iter().copied().collect(),
+        }
+    }
+
+    /// Restore state saved by [`save_state`](Self::save_state)
+    ///
+    /// A profile last updated more than `max_age_ms` ago is dropped, along with history
+    /// entries that old, so stale state cannot make a restarted validator eligible
+    /// before it is assessed again. Fails if the state belongs to another validator.
+    pub fn load_state(&self, state: ValidatorEmotionalState, max_age_ms: u64) -> Result<()> {
+        if state.validator_id != self.id {
+            return Err(ConsensusError::storage_error(format!(
+                "Emotional state of {} cannot be loaded into {}",
+                state.validator_id, self.id
+            )));
+        }
+
+        let cutoff = self.clock.now_millis().saturating_sub(max_age_ms);
+        let profile = state
+            .profile
+            .filter(|profile| profile.last_updated >= cutoff);
+        let mut history: VecDeque<(u8, u64)> = state
+            .score_history
+            .into_iter()
+            .filter(|(_, timestamp)| *timestamp >= cutoff)
+            .collect();
+        let excess = history.len().saturating_sub(100);
+        history.drain(..excess);
+
+        *self.emotional_profile.write() = profile;
+        *self.score_history.write() = history;
+        Ok(())
+    }
+
     /// Get current emotional profile
     pub fn get_emotional_profile(&self) -> Option<EmotionalProfile> {
         self.emotional_profile.read().clone()
@@ -282,9 +929,38 @@ impl EmotionalValidator {
 
     /// Check if validator is eligible for consensus
     pub fn is_eligible(&self, emotional_threshold: u8, minimum_stake: u64) -> bool {
-        *self.is_active.read()
-            && *self.stake.read() >= minimum_stake
-            && self.get_emotional_score() >= emotional_threshold
+        self.eligibility(emotional_threshold, minimum_stake) == EligibilityStatus::Eligible
+    }
+
+    /// Score compared against the emotional threshold: smoothed if so configured
+    pub fn eligibility_score(&self) -> u8 {
+        if self.biometric_config.use_smoothed_score {
+            self.get_smoothed_score()
+        } else {
+            self.get_emotional_score()
+        }
+    }
+
+    /// Check eligibility for consensus, reporting why the validator is excluded
+    pub fn eligibility(&self, emotional_threshold: u8, minimum_stake: u64) -> EligibilityStatus {
+        let score = self.eligibility_score();
+        let stake = *self.stake.read();
+
+        if !*self.is_active.read() {
+            EligibilityStatus::Ineligible(IneligibilityReason::Inactive)
+        } else if stake < minimum_stake {
+            EligibilityStatus::Ineligible(IneligibilityReason::InsufficientStake {
+                stake,
+                minimum: minimum_stake,
+            })
+        } else if score < emotional_threshold {
+            EligibilityStatus::Ineligible(IneligibilityReason::LowEmotionalScore {
+                score,
+                threshold: emotional_threshold,
+            })
+        } else {
+            EligibilityStatus::Eligible
+        }
     }
 
     /// Add reward
@@ -293,14 +969,17 @@ impl EmotionalValidator {
         *balance = balance.saturating_add(amount);
     }
 
-    /// Apply slashing penalty
-    pub fn apply_slashing(&self, amount: u64) {
+    /// Apply slashing penalty, returning the stake actually removed
+    pub fn apply_slashing(&self, amount: u64) -> u64 {
         let mut stake = self.stake.write();
-        *stake = stake.saturating_sub(amount);
+        let slashed = amount.min(*stake);
+        *stake -= slashed;
 
         let mut reputation = self.reputation.write();
         let penalty = ((amount as f64 / *stake as f64) * 10.0).min(20.0) as u8;
         *reputation = reputation.saturating_sub(penalty);
+
+        slashed
     }
 
     /// Get validator ID
@@ -318,6 +997,11 @@ impl EmotionalValidator {
         *self.stake.read()
     }
 
+    /// Commission charged to delegators (percentage)
+    pub fn commission(&self) -> u8 {
+        self.commission
+    }
+
     /// Get current balance
     pub fn get_balance(&self) -> u64 {
         *self.balance.read()
@@ -356,6 +1040,45 @@ impl EmotionalValidator {
         expected_previous_hash: &str,
         expected_height: u64,
         expected_epoch: u64,
+    ) -> std::result::Result<(), String> {
+        self.validate_block_inner(
+            block,
+            expected_previous_hash,
+            expected_height,
+            expected_epoch,
+            None,
+        )
+    }
+
+    /// Validate a proposed block, sharing signature verification results through `cache`
+    ///
+    /// Performs the same checks as [`validate_block`](Self::validate_block). Committee
+    /// members validating the same block can share one cache so each signature is only
+    /// recovered once per epoch.
+    pub fn validate_block_cached(
+        &self,
+        block: &crate::types::Block,
+        expected_previous_hash: &str,
+        expected_height: u64,
+        expected_epoch: u64,
+        cache: &crate::crypto::SignatureCache,
+    ) -> std::result::Result<(), String> {
+        self.validate_block_inner(
+            block,
+            expected_previous_hash,
+            expected_height,
+            expected_epoch,
+            Some(cache),
+        )
+    }
+
+    fn validate_block_inner(
+        &self,
+        block: &crate::types::Block,
+        expected_previous_hash: &str,
+        expected_height: u64,
+        expected_epoch: u64,
+        cache: Option<&crate::crypto::SignatureCache>,
     ) -> std::result::Result<(), String> {
         // 1. Verify block hash matches content
         if !block.verify_hash() {
@@ -393,6 +1116,13 @@ impl EmotionalValidator {
             }
         }
 
+        // 4b. Reject transactions that expired before the block was produced
+        for (i, tx) in block.transactions.iter().enumerate() {
+            if tx.is_expired(block.header.timestamp, crate::TRANSACTION_TTL_MS) {
+                return Err(format!("Transaction {} has expired", i));
+            }
+        }
+
         // 5. Verify merkle root
         let calculated_merkle = crate::types::Block::calculate_merkle_root(&block.transactions);
         if calculated_merkle != block.header.merkle_root {
@@ -403,10 +1133,7 @@ impl EmotionalValidator {
         }
 
         // 6. Verify timestamp is reasonable (not in future, not too old)
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| format!("System time error: {}", e))?
-            .as_millis() as u64;
+        let now = self.clock.now_millis();
 
         // Block timestamp should not be more than 5 seconds in the future
         if block.header.timestamp > now + 5000 {
@@ -424,7 +1151,11 @@ impl EmotionalValidator {
         }
 
         // 8. Verify block signature
-        match block.verify_signature() {
+        let block_signature = match cache {
+            Some(cache) => block.verify_signature_cached(cache),
+            None => block.verify_signature(),
+        };
+        match block_signature {
             Ok(true) => {}
             Ok(false) => return Err("Block signature verification failed".to_string()),
             Err(e) => return Err(format!("Block signature error: {}", e)),
@@ -432,7 +1163,11 @@ impl EmotionalValidator {
 
         // 9. Verify all transaction signatures
         for (i, tx) in block.transactions.iter().enumerate() {
-            match tx.verify_signature() {
+            let tx_signature = match cache {
+                Some(cache) => tx.verify_signature_cached(cache),
+                None => tx.verify_signature(),
+            };
+            match tx_signature {
                 Ok(true) => {}
                 Ok(false) => {
                     return Err(format!("Transaction {} signature verification failed", i))
@@ -460,6 +1195,8 @@ pub struct BiometricSimulator {
     validator_seed: u64,
     /// Random seed unique to this instance (prevents prediction attacks)
     random_seed: u64,
+    /// Emotional score the readings are calibrated to, for deterministic tests
+    target_score: Option<u8>,
 }
 
 impl BiometricSimulator {
@@ -488,9 +1225,91 @@ impl BiometricSimulator {
             device_id,
             validator_seed,
             random_seed,
+            target_score: None,
         }
     }
 
+    /// Create a simulator whose readings score approximately `target`
+    ///
+    /// The noise is seeded from the validator ID only, so together with
+    /// [`collect_readings_at`](Self::collect_readings_at) the readings are fully
+    /// reproducible. Targets below about 17 cannot be reached and are raised to it.
+    pub fn with_target_score(device_id: String, validator_id: &str, target: u8) -> Self {
+        let mut simulator = Self::new(device_id, validator_id);
+        simulator.random_seed = simulator.validator_seed;
+        simulator.target_score = Some(target.min(100));
+        simulator
+    }
+
+    /// Heart rate, stress and focus values that score `target`
+    ///
+    /// The score averages the heart-rate score (100 in the 60-80 BPM band, 50 far
+    /// outside it), `100 - stress` and focus, so focus and stress are set around 50 to
+    /// make up the difference left by the heart rate.
+    fn target_values(target: u8) -> (f64, f64, f64) {
+        let (heart_rate, heart_rate_score) = if target >= 34 {
+            (70.0, 100.0)
+        } else {
+            (110.0, 50.0)
+        };
+        let spread = (3.0 * target as f64 - heart_rate_score - 100.0).clamp(-100.0, 100.0);
+        (heart_rate, 50.0 - spread / 2.0, 50.0 + spread / 2.0)
+    }
+
+    /// Noise in `[-0.5, 0.5)` for a timestamp and salt
+    fn noise(&self, timestamp: u64, salt: u64) -> f64 {
+        let hash = (self.random_seed ^ timestamp ^ salt).wrapping_mul(0x5851_F42D_4C95_7F2D);
+        (hash as f64 / u64::MAX as f64) - 0.5
+    }
+
+    /// Readings taken at the given time (Unix milliseconds)
+    pub fn collect_readings_at(&self, timestamp: u64) -> Vec<BiometricReading> {
+        let (heart_rate, stress, focus) = match self.target_score {
+            // Keep the noise to about a point so the score stays near the target
+            Some(target) => {
+                let (heart_rate, stress, focus) = Self::target_values(target);
+                (
+                    heart_rate + self.noise(timestamp, 0) * 2.0,
+                    (stress + self.noise(timestamp, 0xDEADBEEF) * 2.0).clamp(0.0, 100.0),
+                    (focus + self.noise(timestamp, 0xCAFEBABE) * 2.0).clamp(0.0, 100.0),
+                )
+            }
+            None => (
+                self.generate_heart_rate(timestamp),
+                self.generate_stress_level(timestamp),
+                self.generate_focus_level(timestamp),
+            ),
+        };
+        let quality = 0.85 + ((self.validator_seed % 15) as f64 / 100.0);
+
+        vec![
+            BiometricReading {
+                device_id: format!("{}_heart", self.device_id),
+                biometric_type: BiometricType::HeartRate,
+                value: heart_rate,
+                quality,
+                timestamp,
+                metadata: None,
+            },
+            BiometricReading {
+                device_id: format!("{}_stress", self.device_id),
+                biometric_type: BiometricType::StressLevel,
+                value: stress,
+                quality,
+                timestamp: timestamp + 100,
+                metadata: None,
+            },
+            BiometricReading {
+                device_id: format!("{}_focus", self.device_id),
+                biometric_type: BiometricType::FocusLevel,
+                value: focus,
+                quality,
+                timestamp: timestamp + 200,
+                metadata: None,
+            },
+        ]
+    }
+
     /// Generate realistic heart rate with random noise
     ///
     /// Mixes deterministic patterns with random noise to prevent prediction.
@@ -507,10 +1326,7 @@ impl BiometricSimulator {
         let deterministic = baseline * circadian_factor * stress_variation;
 
         // Add random noise: ±2.5 BPM
-        let random_noise = {
-            let hash = (self.random_seed ^ timestamp).wrapping_mul(0x5851_F42D_4C95_7F2D);
-            (hash as f64 / u64::MAX as f64) - 0.5
-        };
+        let random_noise = self.noise(timestamp, 0);
 
         deterministic + (random_noise * 5.0)
     }
@@ -532,11 +1348,7 @@ impl BiometricSimulator {
         let deterministic = base_stress * work_factor;
 
         // Add random noise: ±5 points
-        let random_noise = {
-            let hash =
-                (self.random_seed ^ timestamp ^ 0xDEADBEEF).wrapping_mul(0x5851_F42D_4C95_7F2D);
-            (hash as f64 / u64::MAX as f64) - 0.5
-        };
+        let random_noise = self.noise(timestamp, 0xDEADBEEF);
 
         (deterministic + (random_noise * 10.0)).clamp(0.0, 100.0)
     }
@@ -559,11 +1371,7 @@ impl BiometricSimulator {
         let deterministic = base_focus * circadian_focus;
 
         // Add random noise: ±3 points
-        let random_noise = {
-            let hash =
-                (self.random_seed ^ timestamp ^ 0xCAFEBABE).wrapping_mul(0x5851_F42D_4C95_7F2D);
-            (hash as f64 / u64::MAX as f64) - 0.5
-        };
+        let random_noise = self.noise(timestamp, 0xCAFEBABE);
 
         (deterministic + (random_noise * 6.0)).clamp(0.0, 100.0)
     }
@@ -576,32 +1384,7 @@ impl BiometricDevice for BiometricSimulator {
             .map_err(|e| ConsensusError::internal(format!("System time error: {}", e)))?
             .as_millis() as u64;
 
-        Ok(vec![
-            BiometricReading {
-                device_id: format!("{}_heart", self.device_id),
-                biometric_type: BiometricType::HeartRate,
-                value: self.generate_heart_rate(timestamp),
-                quality: 0.85 + ((self.validator_seed % 15) as f64 / 100.0),
-                timestamp,
-                metadata: None,
-            },
-            BiometricReading {
-                device_id: format!("{}_stress", self.device_id),
-                biometric_type: BiometricType::StressLevel,
-                value: self.generate_stress_level(timestamp),
-                quality: 0.85 + ((self.validator_seed % 15) as f64 / 100.0),
-                timestamp: timestamp + 100,
-                metadata: None,
-            },
-            BiometricReading {
-                device_id: format!("{}_focus", self.device_id),
-                biometric_type: BiometricType::FocusLevel,
-                value: self.generate_focus_level(timestamp),
-                quality: 0.85 + ((self.validator_seed % 15) as f64 / 100.0),
-                timestamp: timestamp + 200,
-                metadata: None,
-            },
-        ])
+        Ok(self.collect_readings_at(timestamp))
     }
 
     fn device_id(&self) -> &str {
@@ -648,6 +1431,305 @@ mod tests {
         assert!(validator.is_eligible(50, 10000));
     }
 
+    #[tokio::test]
+    async fn test_target_score_simulator() {
+        let validator = EmotionalValidator::new("test-validator", 10000).unwrap();
+        let simulator =
+            BiometricSimulator::with_target_score("device1".to_string(), "test-validator", 90);
+
+        let now = SystemClock.now_millis();
+        let readings = simulator.collect_readings_at(now);
+        let repeated = simulator.collect_readings_at(now);
+        let values = |readings: &[BiometricReading]| -> Vec<f64> {
+            readings.iter().map(|reading| reading.value).collect()
+        };
+        assert_eq!(values(&readings), values(&repeated));
+        validator.update_emotional_state(readings).await.unwrap();
+
+        let score = validator.get_emotional_score();
+        assert!((87..=93).contains(&score), "score {}", score);
+
+        for target in [25u8, 50, 75] {
+            let simulator = BiometricSimulator::with_target_score(
+                "device1".to_string(),
+                "test-validator",
+                target,
+            );
+            let readings = simulator.collect_readings_at(now);
+            let score = validator.calculate_emotional_score(&readings).unwrap();
+            assert!(score.abs_diff(target) <= 3, "target {}: {}", target, score);
+        }
+    }
+
+    fn heart_rate_reading(value: f64) -> BiometricReading {
+        BiometricReading {
+            device_id: "hr-device".to_string(),
+            biometric_type: BiometricType::HeartRate,
+            value,
+            quality: 0.9,
+            timestamp: 1_700_000_000_000,
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_calibration_normalizes_devices() {
+        fn readings(device_id: &str, heart_rate: f64, stress: f64) -> Vec<BiometricReading> {
+            [
+                (BiometricType::HeartRate, heart_rate),
+                (BiometricType::StressLevel, stress),
+            ]
+            .into_iter()
+            .map(|(biometric_type, value)| BiometricReading {
+                device_id: device_id.to_string(),
+                biometric_type,
+                value,
+                quality: 0.9,
+                timestamp: 1_700_000_000_000,
+                metadata: None,
+            })
+            .collect()
+        }
+
+        // Same physiology (72 BPM, stress 30): the second device reads heart rate
+        // 15% high plus 3 BPM and reports stress on a 0-10 scale
+        let reference = EmotionalValidator::new("reference", 10000).unwrap();
+        reference
+            .update_emotional_state(readings("reference-device", 72.0, 30.0))
+            .await
+            .unwrap();
+
+        let skewed = readings("skewed-device", 72.0 * 1.15 + 3.0, 3.0);
+        let uncalibrated = EmotionalValidator::new("uncalibrated", 10000).unwrap();
+        uncalibrated
+            .update_emotional_state(skewed.clone())
+            .await
+            .unwrap();
+        assert_ne!(
+            uncalibrated.get_emotional_score(),
+            reference.get_emotional_score()
+        );
+
+        let calibrated = EmotionalValidator::new("calibrated", 10000).unwrap();
+        calibrated
+            .set_calibration(
+                "skewed-device",
+                CalibrationProfile::identity()
+                    .with(BiometricType::HeartRate, 1.0 / 1.15, -3.0 / 1.15)
+                    .with(BiometricType::StressLevel, 10.0, 0.0),
+            )
+            .unwrap();
+        calibrated.update_emotional_state(skewed).await.unwrap();
+        let difference = calibrated
+            .get_emotional_score()
+            .abs_diff(reference.get_emotional_score());
+        assert!(difference <= 1);
+
+        // Degenerate or unbounded corrections are rejected
+        for (scale, offset) in [
+            (0.0, 0.0),
+            (-1.0, 0.0),
+            (f64::NAN, 0.0),
+            (f64::INFINITY, 0.0),
+            (1.0, f64::NAN),
+            (1.0, MAX_CALIBRATION_OFFSET + 1.0),
+        ] {
+            let profile =
+                CalibrationProfile::identity().with(BiometricType::StressLevel, scale, offset);
+            let result = calibrated.set_calibration("skewed-device", profile);
+            assert!(result.is_err());
+        }
+
+        // Calibrated values stay within the type's range
+        let mut reading = readings("skewed-device", 72.0, 30.0).remove(1);
+        CalibrationProfile::identity()
+            .with(BiometricType::StressLevel, MAX_CALIBRATION_SCALE, 0.0)
+            .apply(&mut reading);
+        assert_eq!(reading.value, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_strict_metadata() {
+        let validator = EmotionalValidator::new("test-validator", 10000)
+            .unwrap()
+            .with_biometric_config(BiometricConfig {
+                strict_metadata: true,
+                ..Default::default()
+            });
+        let with_metadata = |firmware: &str, model: &str, calibrated: &str| {
+            let mut reading = heart_rate_reading(70.0);
+            reading.metadata = Some(
+                [
+                    (METADATA_FIRMWARE_VERSION, firmware),
+                    (METADATA_SENSOR_MODEL, model),
+                    (METADATA_CALIBRATION_DATE, calibrated),
+                ]
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            );
+            reading
+        };
+
+        let valid = with_metadata("2.1.0", "PulseSense-X2", "2024-03-15");
+        validator.update_emotional_state(vec![valid]).await.unwrap();
+
+        for malformed in [
+            with_metadata("v2", "PulseSense-X2", "2024-03-15"),
+            with_metadata("2.1.0", "", "2024-03-15"),
+            with_metadata("2.1.0", "PulseSense-X2", "2024-13-01"),
+            heart_rate_reading(70.0),
+        ] {
+            let valid = with_metadata("2.1.0", "PulseSense-X2", "2024-03-15");
+            let readings = vec![valid, malformed];
+            assert!(matches!(
+                validator.update_emotional_state(readings).await,
+                Err(ConsensusError::BiometricValidationFailed { .. })
+            ));
+        }
+
+        // Without strict mode missing metadata is accepted
+        let lenient = EmotionalValidator::new("test-validator", 10000).unwrap();
+        let readings = vec![heart_rate_reading(70.0)];
+        lenient.update_emotional_state(readings).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reading_count_limits() {
+        let validator = EmotionalValidator::new("test-validator", 10000)
+            .unwrap()
+            .with_biometric_config(BiometricConfig {
+                max_readings_per_update: 100,
+                max_recent_readings: 10,
+                ..Default::default()
+            });
+
+        let flood = vec![heart_rate_reading(70.0); 1_000_000];
+        assert!(matches!(
+            validator.update_emotional_state(flood).await,
+            Err(ConsensusError::BiometricValidationFailed { .. })
+        ));
+        assert!(validator.get_emotional_profile().is_none());
+
+        let readings = vec![heart_rate_reading(70.0); 100];
+        validator.update_emotional_state(readings).await.unwrap();
+        let profile = validator.get_emotional_profile().unwrap();
+        assert_eq!(profile.recent_readings.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_low_quality_readings_discarded() {
+        let validator = EmotionalValidator::new("test-validator", 10000).unwrap();
+        let mut noisy = heart_rate_reading(150.0);
+        noisy.quality = 0.05;
+
+        validator
+            .update_emotional_state(vec![heart_rate_reading(70.0), noisy.clone()])
+            .await
+            .unwrap();
+
+        // Only the in-range reading counts, so neither score nor confidence drop
+        let profile = validator.get_emotional_profile().unwrap();
+        assert_eq!(profile.emotional_score, 100);
+        assert_eq!(profile.recent_readings.len(), 1);
+        assert_eq!(profile.confidence, 100);
+
+        let err = validator.update_emotional_state(vec![noisy]).await;
+        assert!(matches!(
+            err,
+            Err(ConsensusError::BiometricValidationFailed { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_anomalous_heart_rate_detected() {
+        let validator = EmotionalValidator::new("test-validator", 10000).unwrap();
+
+        for hr in [70.0, 72.0, 69.0, 71.0, 70.0, 73.0, 68.0, 71.0, 70.0, 72.0] {
+            validator
+                .update_emotional_state(vec![heart_rate_reading(hr)])
+                .await
+                .unwrap();
+            assert_eq!(validator.get_anomaly_count(), 0);
+        }
+        let steady_confidence = validator.get_emotional_profile().unwrap().confidence;
+
+        // Physiologically implausible jump
+        validator
+            .update_emotional_state(vec![heart_rate_reading(180.0)])
+            .await
+            .unwrap();
+
+        let profile = validator.get_emotional_profile().unwrap();
+        assert_eq!(profile.anomaly_count, 1);
+        assert!(profile.confidence < steady_confidence);
+    }
+
+    #[tokio::test]
+    async fn test_anomaly_threshold_configurable() {
+        let validator = EmotionalValidator::new("test-validator", 10000)
+            .unwrap()
+            .with_biometric_config(BiometricConfig {
+                anomaly_std_threshold: 10.0,
+                ..Default::default()
+            });
+
+        for hr in [
+            70.0, 72.0, 69.0, 71.0, 70.0, 73.0, 68.0, 71.0, 70.0, 72.0, 180.0,
+        ] {
+            validator
+                .update_emotional_state(vec![heart_rate_reading(hr)])
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(validator.get_anomaly_count(), 0);
+    }
+
+    #[test]
+    fn test_smoothed_score_dampens_alternating_scores() {
+        let validator = EmotionalValidator::new("test-validator", 10000).unwrap();
+
+        let mut smoothed = Vec::new();
+        for i in 0..20u64 {
+            let score = if i % 2 == 0 { 90 } else { 40 };
+            validator.score_history.write().push_back((score, i));
+            smoothed.push(validator.get_smoothed_score());
+        }
+
+        // Once warmed up, the EMA oscillates in a much tighter band than the raw 40-90
+        let settled = &smoothed[10..];
+        let band = settled.iter().max().unwrap() - settled.iter().min().unwrap();
+        assert!(band < 20, "smoothed band {} too wide", band);
+        assert!(settled.iter().all(|&s| (50..=80).contains(&s)));
+    }
+
+    #[test]
+    fn test_eligibility_uses_smoothed_score() {
+        let validator = EmotionalValidator::new("test-validator", 10000)
+            .unwrap()
+            .with_biometric_config(BiometricConfig {
+                use_smoothed_score: true,
+                ..Default::default()
+            });
+
+        for (i, score) in [90, 90, 90, 90, 30].into_iter().enumerate() {
+            validator.score_history.write().push_back((score, i as u64));
+        }
+        *validator.emotional_profile.write() = Some(EmotionalProfile {
+            emotional_score: 30,
+            trend: EmotionalTrend::Declining,
+            confidence: 80,
+            last_updated: 0,
+            recent_readings: Vec::new(),
+            anomaly_count: 0,
+        });
+
+        // A single low batch does not drop the validator below the threshold
+        assert!(validator.get_smoothed_score() >= 70);
+        assert!(validator.is_eligible(70, 10000));
+    }
+
     #[test]
     fn test_biometric_simulator() {
         let simulator = BiometricSimulator::new("device1".to_string(), "validator-123");
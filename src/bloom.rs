@@ -0,0 +1,228 @@
+//! Membership checks on recently finalized transactions
+
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+
+/// Default number of recent blocks whose transactions are tracked
+pub const DEFAULT_FINALIZED_TX_FILTER_BLOCKS: usize = 100;
+
+/// Default false-positive rate of the bloom filter in front of the exact set
+pub const DEFAULT_FINALIZED_TX_FILTER_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// Smallest number of keys the bloom filter is sized for
+const MIN_BLOOM_CAPACITY: usize = 1_024;
+
+/// Fixed-size bloom filter over string keys
+#[derive(Debug, Clone, Default)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    bit_count: u64,
+    hash_count: u32,
+}
+
+impl BloomFilter {
+    /// Create a filter sized for `expected_items` at the given false-positive rate
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        if expected_items == 0 {
+            return Self {
+                bits: Vec::new(),
+                bit_count: 0,
+                hash_count: 0,
+            };
+        }
+
+        let ln2 = std::f64::consts::LN_2;
+        let items = expected_items as f64;
+        let bit_count = (-items * false_positive_rate.ln() / (ln2 * ln2))
+            .ceil()
+            .max(64.0) as u64;
+        let hash_count = (-false_positive_rate.ln() / ln2).round().max(1.0) as u32;
+
+        Self {
+            bits: vec![0; bit_count.div_ceil(64) as usize],
+            bit_count,
+            hash_count,
+        }
+    }
+
+    /// Add a key
+    pub fn insert(&mut self, key: &str) {
+        for index in self.indexes(key) {
+            self.bits[(index / 64) as usize] |= 1 << (index % 64);
+        }
+    }
+
+    /// Returns false if the key was definitely never inserted
+    pub fn might_contain(&self, key: &str) -> bool {
+        self.bit_count > 0
+            && self
+                .indexes(key)
+                .all(|index| self.bits[(index / 64) as usize] & (1 << (index % 64)) != 0)
+    }
+
+    /// Bit positions for a key, by double hashing one SHA-256 digest
+    fn indexes(&self, key: &str) -> impl Iterator<Item = u64> {
+        let digest = Sha256::digest(key.as_bytes());
+        let mut h1 = [0u8; 8];
+        let mut h2 = [0u8; 8];
+        h1.copy_from_slice(&digest[..8]);
+        h2.copy_from_slice(&digest[8..16]);
+        let h1 = u64::from_le_bytes(h1);
+        // An odd step keeps the probes from cycling through a few bits
+        let h2 = u64::from_le_bytes(h2) | 1;
+        let bit_count = self.bit_count.max(1);
+
+        (0..u64::from(self.hash_count))
+            .map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % bit_count)
+    }
+}
+
+/// Transaction hashes of the last N finalized blocks
+///
+/// A bloom filter answers most lookups for fresh transactions; only a bloom hit
+/// is checked against the exact set, so there are no false positives. Each
+/// block's hashes are kept so the oldest block can be dropped as new ones arrive.
+/// A bloom filter cannot forget keys, so it is rebuilt from the exact set once
+/// evicted and new keys fill its capacity.
+#[derive(Debug, Default)]
+pub struct RecentTransactionFilter {
+    blocks: VecDeque<Vec<String>>,
+    /// Tracked hashes with the number of tracked blocks carrying each
+    hashes: HashMap<String, usize>,
+    bloom: BloomFilter,
+    /// Keys inserted into the bloom filter since it was last rebuilt
+    bloom_items: usize,
+    /// Keys the bloom filter was sized for
+    bloom_capacity: usize,
+    bloom_false_positive_rate: f64,
+}
+
+impl RecentTransactionFilter {
+    /// Create an empty filter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track a newly finalized block, keeping at most `window` blocks
+    pub fn record_block<'a>(
+        &mut self,
+        tx_hashes: impl Iterator<Item = &'a str>,
+        window: usize,
+        false_positive_rate: f64,
+    ) {
+        let block: Vec<String> = tx_hashes.map(str::to_string).collect();
+        for tx_hash in &block {
+            *self.hashes.entry(tx_hash.clone()).or_insert(0) += 1;
+        }
+
+        self.blocks.push_back(block);
+        while self.blocks.len() > window {
+            let Some(oldest) = self.blocks.pop_front() else {
+                break;
+            };
+            for tx_hash in oldest {
+                if let Some(count) = self.hashes.get_mut(&tx_hash) {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.hashes.remove(&tx_hash);
+                    }
+                }
+            }
+        }
+
+        let last = self.blocks.back().map_or(&[][..], Vec::as_slice);
+        if self.bloom_items + last.len() > self.bloom_capacity
+            || self.bloom_false_positive_rate != false_positive_rate
+        {
+            self.bloom_capacity = (2 * self.hashes.len()).max(MIN_BLOOM_CAPACITY);
+            self.bloom_false_positive_rate = false_positive_rate;
+            self.bloom = BloomFilter::new(self.bloom_capacity, false_positive_rate);
+            for tx_hash in self.hashes.keys() {
+                self.bloom.insert(tx_hash);
+            }
+            self.bloom_items = self.hashes.len();
+        } else {
+            for tx_hash in last {
+                self.bloom.insert(tx_hash);
+            }
+            self.bloom_items += last.len();
+        }
+    }
+
+    /// Whether the transaction is in a tracked block
+    pub fn contains(&self, tx_hash: &str) -> bool {
+        self.bloom.might_contain(tx_hash) && self.hashes.contains_key(tx_hash)
+    }
+
+    /// Number of most recent blocks currently tracked
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bloom_filter_membership() {
+        let mut filter = BloomFilter::new(1_000, 0.01);
+        for i in 0..1_000 {
+            filter.insert(&format!("tx-{}", i));
+        }
+
+        assert!((0..1_000).all(|i| filter.might_contain(&format!("tx-{}", i))));
+        let false_positives = (1_000..11_000)
+            .filter(|i| filter.might_contain(&format!("tx-{}", i)))
+            .count();
+        assert!(false_positives < 300, "{} false positives", false_positives);
+        assert!(!BloomFilter::new(0, 0.01).might_contain("tx-0"));
+    }
+
+    #[test]
+    fn test_recent_filter_rolls_over() {
+        let mut filter = RecentTransactionFilter::new();
+        filter.record_block(["tx-1", "tx-3"].into_iter(), 2, 0.01);
+        filter.record_block(["tx-2", "tx-3"].into_iter(), 2, 0.01);
+        assert!(filter.contains("tx-1"));
+
+        filter.record_block(std::iter::empty(), 2, 0.01);
+        assert_eq!(filter.block_count(), 2);
+        assert!(!filter.contains("tx-1"));
+        assert!(filter.contains("tx-2"));
+        // Still carried by a tracked block
+        assert!(filter.contains("tx-3"));
+
+        filter.record_block(std::iter::empty(), 2, 0.01);
+        assert!(!filter.contains("tx-3"));
+    }
+
+    #[test]
+    fn test_recent_filter_has_no_false_positives() {
+        let mut filter = RecentTransactionFilter::new();
+        for block in 0..100 {
+            let hashes: Vec<String> = (0..100).map(|i| format!("tx-{}-{}", block, i)).collect();
+            filter.record_block(hashes.iter().map(String::as_str), 100, 0.01);
+        }
+
+        assert!(filter.contains("tx-0-0"));
+        assert!((0..10_000).all(|i| !filter.contains(&format!("other-{}", i))));
+    }
+
+    #[test]
+    fn test_recent_filter_rebuilds_bloom_after_evictions() {
+        let mut filter = RecentTransactionFilter::new();
+        for block in 0..50 {
+            let hashes: Vec<String> = (0..200).map(|i| format!("tx-{}-{}", block, i)).collect();
+            filter.record_block(hashes.iter().map(String::as_str), 5, 0.01);
+        }
+        assert!(filter.bloom_items <= filter.bloom_capacity);
+        assert!(!filter.contains("tx-0-0"));
+        assert!((0..200).all(|i| filter.contains(&format!("tx-49-{}", i))));
+
+        // A new rate takes effect without losing tracked hashes
+        filter.record_block(std::iter::empty(), 5, 0.001);
+        assert_eq!(filter.bloom_false_positive_rate, 0.001);
+        assert!(filter.contains("tx-49-0"));
+    }
+}
@@ -0,0 +1,129 @@
+//! Signed per-epoch audit bundles for compliance export
+
+use crate::crypto::{KeyPair, Signature};
+use crate::error::{ConsensusError, Result};
+use crate::staking::SlashingEvent;
+use crate::types::{Block, VotingResult};
+use serde::{Deserialize, Serialize};
+
+/// Domain separator keeping bundle signatures distinct from block and vote signatures
+const BUNDLE_DOMAIN: &[u8] = b"poe-epoch-bundle";
+
+/// Default number of recent epochs whose bundles the engine keeps
+pub const DEFAULT_EPOCH_BUNDLE_RETENTION: usize = 32;
+
+/// A block proposed in an epoch together with the vote on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledRound {
+    /// Block as proposed, before finalization metadata was attached
+    pub block: Block,
+    /// Outcome of the vote, including every counted vote
+    pub voting_result: VotingResult,
+}
+
+/// Everything that happened in one epoch, signed by its proposer
+///
+/// A single artifact for auditors: the committee, each proposed block with its votes
+/// and voting result, and the slashing events raised during the epoch. The signature
+/// covers all of it, so nothing can be dropped or altered after export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpochBundle {
+    /// Epoch number
+    pub epoch: u64,
+    /// Committee members, proposer first
+    pub committee: Vec<String>,
+    /// Rounds of the epoch in order; the last one failed if the epoch did
+    pub rounds: Vec<BundledRound>,
+    /// Slashing events detected during the epoch
+    pub slashing_events: Vec<SlashingEvent>,
+    /// Public key (hex) of the proposer who signed the bundle
+    pub proposer_public_key: String,
+    /// Proposer signature over the fields above
+    pub signature: Signature,
+}
+
+/// Fields of a bundle covered by its signature
+#[derive(Serialize)]
+struct BundleContents<'a> {
+    epoch: u64,
+    committee: &'a [String],
+    rounds: &'a [BundledRound],
+    slashing_events: &'a [SlashingEvent],
+    proposer_public_key: &'a str,
+}
+
+impl BundleContents<'_> {
+    fn signing_data(&self) -> Result<Vec<u8>> {
+        let json = serde_json::to_vec(self)
+            .map_err(|e| ConsensusError::internal(format!("Serialization error: {}", e)))?;
+
+        let mut data = BUNDLE_DOMAIN.to_vec();
+        data.extend_from_slice(&json);
+        Ok(data)
+    }
+}
+
+impl EpochBundle {
+    /// Assemble a bundle and sign it with the proposer's key pair
+    pub fn new(
+        epoch: u64,
+        committee: Vec<String>,
+        rounds: Vec<BundledRound>,
+        slashing_events: Vec<SlashingEvent>,
+        proposer_key: &KeyPair,
+    ) -> Result<Self> {
+        let proposer_public_key = proposer_key.public_key_hex();
+        let data = BundleContents {
+            epoch,
+            committee: &committee,
+            rounds: &rounds,
+            slashing_events: &slashing_events,
+            proposer_public_key: &proposer_public_key,
+        }
+        .signing_data()?;
+        let signature = proposer_key.sign(&data)?;
+
+        Ok(Self {
+            epoch,
+            committee,
+            rounds,
+            slashing_events,
+            proposer_public_key,
+            signature,
+        })
+    }
+
+    /// Check the proposer signature and that every round's votes match its result
+    ///
+    /// The signature is checked against the embedded public key; callers should also
+    /// check that key against the proposer's registered key.
+    pub fn verify(&self) -> Result<bool> {
+        let consistent = self.rounds.iter().all(|round| {
+            round.voting_result.participant_count == round.voting_result.votes.len()
+                && round
+                    .voting_result
+                    .votes
+                    .iter()
+                    .all(|vote| vote.block_hash == round.block.hash)
+        });
+        if !consistent {
+            return Ok(false);
+        }
+
+        let data = BundleContents {
+            epoch: self.epoch,
+            committee: &self.committee,
+            rounds: &self.rounds,
+            slashing_events: &self.slashing_events,
+            proposer_public_key: &self.proposer_public_key,
+        }
+        .signing_data()?;
+        KeyPair::verify(&data, &self.signature, &self.proposer_public_key)
+    }
+
+    /// Serialize the bundle to JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| ConsensusError::internal(format!("Serialization error: {}", e)))
+    }
+}
@@ -5,14 +5,27 @@
 //! - Double signing: proposing multiple blocks at the same height
 //! - Equivocation: making conflicting statements
 
+use crate::clock::{Clock, SystemClock};
 use crate::staking::{SlashingEvent, SlashingOffense, SlashingSeverity};
-use crate::types::Vote;
+use crate::types::{Block, LightBlock, Vote, VoteRejectionReason};
+use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
+use lru::LruCache;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
+/// Default number of (validator, epoch) vote entries and (validator, height) proposal
+/// entries each kept for detection
+pub const DEFAULT_DETECTION_CAPACITY: usize = 10_000;
+
+/// Key of a tracked vote or proposal list: (validator_id, epoch or height)
+type DetectionKey = (String, u64);
+
 /// Evidence of a block proposal
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProposalEvidence {
@@ -24,6 +37,62 @@ pub struct ProposalEvidence {
     pub block_hash: String,
     /// Timestamp of proposal
     pub timestamp: u64,
+    /// The signed block header, if the proposal was recorded from the block itself
+    #[serde(default)]
+    pub signed_header: Option<LightBlock>,
+}
+
+/// Two different blocks signed by one validator at the same height
+///
+/// Lets anyone holding the validator's public key confirm the offense without
+/// trusting whoever reported it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DoubleSigningEvidence {
+    /// The proposal recorded first
+    pub first: LightBlock,
+    /// A later, conflicting proposal
+    pub second: LightBlock,
+}
+
+impl DoubleSigningEvidence {
+    /// Whether both headers are distinct blocks at `height`, proposed by `validator_id`
+    /// and validly signed with `public_key` (hex)
+    pub fn verify(&self, validator_id: &str, height: u64, public_key: &str) -> bool {
+        self.first.hash != self.second.hash
+            && [&self.first, &self.second].iter().all(|header| {
+                header.header.validator_id == validator_id
+                    && header.header.height == height
+                    && header.proposer_public_key == public_key
+                    && header.verify().unwrap_or(false)
+            })
+    }
+}
+
+/// How detected offenses are penalized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SlashingPolicy {
+    /// Every offense is slashed at full severity
+    #[default]
+    ZeroTolerance,
+    /// A validator's first offense within `window_ms` is only a warning
+    ///
+    /// Warnings are recorded as [`SlashingSeverity::Minor`] events with a zero slashing
+    /// rate; further offenses inside the window are slashed as under `ZeroTolerance`.
+    Graduated {
+        /// Rolling window in milliseconds
+        window_ms: u64,
+    },
+}
+
+/// Outcome of recording a vote that is not Byzantine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteRecord {
+    /// The vote was recorded
+    Recorded,
+    /// The exact same vote was already recorded; the replay is ignored
+    Replayed,
+    /// The vote was dropped without being recorded or treated as an offense
+    Rejected(VoteRejectionReason),
 }
 
 /// Byzantine fault detector
@@ -36,31 +105,177 @@ pub struct ByzantineDetector {
     /// Maps (validator_id, height) -> list of block hashes
     proposals: Arc<DashMap<(String, u64), Vec<ProposalEvidence>>>,
 
+    /// Vote keys by recency; the least recently used is evicted once over capacity
+    vote_recency: Arc<Mutex<LruCache<DetectionKey, ()>>>,
+
+    /// Proposal keys by recency; the least recently used is evicted once over capacity
+    proposal_recency: Arc<Mutex<LruCache<DetectionKey, ()>>>,
+
     /// Slashing events detected
     slashing_events: Arc<RwLock<Vec<SlashingEvent>>>,
+
+    /// Penalty policy for detected offenses
+    policy: Mutex<SlashingPolicy>,
+
+    /// Time source for proposal evidence and slashing events
+    clock: Mutex<Arc<dyn Clock>>,
+
+    /// Offense timestamps per validator, for graduated slashing
+    offenses: Arc<DashMap<String, Vec<u64>>>,
+
+    /// Public keys (hex) that votes from each validator must be signed with
+    validator_keys: Arc<DashMap<String, String>>,
+
+    /// Epoch votes must belong to, or 0 before the first epoch
+    current_epoch: Arc<AtomicU64>,
+
+    /// IDs of recorded votes, mapped to their epoch
+    processed_votes: Arc<DashMap<String, u64>>,
 }
 
 impl ByzantineDetector {
     /// Create a new Byzantine detector
     pub fn new() -> Self {
+        Self::with_detection_capacity(DEFAULT_DETECTION_CAPACITY)
+    }
+
+    /// Create a detector tracking at most `capacity` vote entries and `capacity`
+    /// proposal entries
+    ///
+    /// Each entry holds one validator's votes in an epoch or proposals at a height.
+    /// Offenses are only detected within an entry, so evicting the least recently used
+    /// ones bounds memory without missing conflicts between current messages.
+    pub fn with_detection_capacity(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
         Self {
             votes: Arc::new(DashMap::new()),
             proposals: Arc::new(DashMap::new()),
+            vote_recency: Arc::new(Mutex::new(LruCache::new(capacity))),
+            proposal_recency: Arc::new(Mutex::new(LruCache::new(capacity))),
             slashing_events: Arc::new(RwLock::new(Vec::new())),
+            policy: Mutex::new(SlashingPolicy::default()),
+            clock: Mutex::new(Arc::new(SystemClock)),
+            offenses: Arc::new(DashMap::new()),
+            validator_keys: Arc::new(DashMap::new()),
+            current_epoch: Arc::new(AtomicU64::new(0)),
+            processed_votes: Arc::new(DashMap::new()),
         }
     }
 
+    /// Require votes from a validator to be signed with the given public key (hex)
+    pub fn register_validator_key(&self, validator_id: &str, public_key: &str) {
+        self.validator_keys
+            .insert(validator_id.to_string(), public_key.to_string());
+    }
+
+    /// Stop accepting votes from a validator
+    pub fn remove_validator_key(&self, validator_id: &str) {
+        self.validator_keys.remove(validator_id);
+    }
+
+    /// Only accept votes for the given epoch from now on
+    pub fn set_current_epoch(&self, epoch: u64) {
+        self.current_epoch.store(epoch, Ordering::SeqCst);
+    }
+
+    /// Reason to reject an unsigned or forged vote, or a vote from a validator without a
+    /// registered key
+    fn signature_rejection(&self, vote: &Vote) -> Option<VoteRejectionReason> {
+        let Some(public_key) = self.validator_keys.get(&vote.validator_id) else {
+            return Some(VoteRejectionReason::UnknownValidator);
+        };
+
+        match vote.verify_signature(public_key.value()) {
+            Ok(true) => None,
+            Ok(false) | Err(_) => Some(VoteRejectionReason::InvalidSignature),
+        }
+    }
+
+    /// Penalize offenses according to the given policy
+    pub fn with_slashing_policy(self, policy: SlashingPolicy) -> Self {
+        self.set_slashing_policy(policy);
+        self
+    }
+
+    /// Penalize offenses detected from now on according to the given policy
+    ///
+    /// Recorded votes, proposals, keys and offenses are kept.
+    pub fn set_slashing_policy(&self, policy: SlashingPolicy) {
+        *self.policy.lock() = policy;
+    }
+
+    /// Timestamp evidence and slashing events with the given clock
+    pub fn with_clock(self, clock: Arc<dyn Clock>) -> Self {
+        self.set_clock(clock);
+        self
+    }
+
+    /// Timestamp evidence and slashing events with the given clock from now on
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        *self.clock.lock() = clock;
+    }
+
+    fn now_millis(&self) -> u64 {
+        self.clock.lock().now_millis()
+    }
+
+    /// Downgrade a first offense to a warning under the graduated policy
+    fn apply_policy(&self, mut event: SlashingEvent) -> SlashingEvent {
+        let SlashingPolicy::Graduated { window_ms } = *self.policy.lock() else {
+            return event;
+        };
+
+        let mut offenses = self.offenses.entry(event.validator_id.clone()).or_default();
+        offenses.retain(|&timestamp| event.timestamp.saturating_sub(timestamp) <= window_ms);
+        if offenses.is_empty() {
+            event.severity = SlashingSeverity::Minor;
+            event.slashing_rate = 0.0;
+            event.evidence = format!("Warning (first offense): {}", event.evidence);
+        }
+
+        offenses.push(event.timestamp);
+        event
+    }
+
     /// Record a vote for Byzantine detection
     ///
-    /// This stores the vote and checks for double voting
-    pub async fn record_vote(&self, vote: &Vote) -> Result<(), String> {
+    /// This stores the vote and checks for double voting. Votes must carry a valid
+    /// signature from the validator's [registered key](Self::register_validator_key) and
+    /// belong to the [current epoch](Self::set_current_epoch); other votes are
+    /// [rejected](VoteRecord::Rejected) without being recorded or treated as offenses.
+    /// A replay of an already recorded vote is ignored so it is not counted twice.
+    ///
+    /// Returns an error only for Byzantine behavior.
+    pub async fn record_vote(&self, vote: &Vote) -> Result<VoteRecord, String> {
+        if let Some(rejection) = self.signature_rejection(vote) {
+            return Ok(VoteRecord::Rejected(rejection));
+        }
+
+        let current_epoch = self.current_epoch.load(Ordering::SeqCst);
+        if current_epoch != 0 && vote.epoch != current_epoch {
+            return Ok(VoteRecord::Rejected(VoteRejectionReason::WrongEpoch));
+        }
+
         let key = (vote.validator_id.clone(), vote.epoch);
+        if let Some(evicted) = Self::touch(&self.vote_recency, &self.votes, &key) {
+            for evicted_vote in evicted {
+                self.processed_votes.remove(&evicted_vote.id());
+            }
+        }
 
-        // Get or create vote list for this validator/epoch
+        // Get or create vote list for this validator/epoch. Holding it serializes
+        // concurrent votes from the same validator, including replays of one vote.
         let mut votes = self.votes.entry(key.clone()).or_default();
 
+        let Entry::Vacant(processed) = self.processed_votes.entry(vote.id()) else {
+            return Ok(VoteRecord::Replayed);
+        };
+
         // Check for double voting before adding
         if let Some(event) = self.detect_double_voting_internal(&votes, vote) {
+            // Mark the conflicting vote processed too, so replaying it is not another offense
+            processed.insert(vote.epoch);
+            let event = self.apply_policy(event);
             warn!(
                 "🚨 Double voting detected: validator {} voted differently in epoch {}",
                 vote.validator_id, vote.epoch
@@ -78,8 +293,9 @@ impl ByzantineDetector {
 
         // Add the vote
         votes.push(vote.clone());
+        processed.insert(vote.epoch);
 
-        Ok(())
+        Ok(VoteRecord::Recorded)
     }
 
     /// Record a block proposal for Byzantine detection
@@ -90,6 +306,31 @@ impl ByzantineDetector {
         validator_id: &str,
         height: u64,
         block_hash: &str,
+    ) -> Result<(), String> {
+        self.record_proposal_evidence(validator_id, height, block_hash, None)
+            .await
+    }
+
+    /// Record a signed block proposal for Byzantine detection
+    ///
+    /// Like [`record_proposal`](Self::record_proposal), but keeps the signed header so
+    /// a conflict can be backed by [`DoubleSigningEvidence`].
+    pub async fn record_signed_proposal(&self, block: &Block) -> Result<(), String> {
+        self.record_proposal_evidence(
+            &block.header.validator_id,
+            block.header.height,
+            &block.hash,
+            Some(block.to_header_only()),
+        )
+        .await
+    }
+
+    async fn record_proposal_evidence(
+        &self,
+        validator_id: &str,
+        height: u64,
+        block_hash: &str,
+        signed_header: Option<LightBlock>,
     ) -> Result<(), String> {
         let key = (validator_id.to_string(), height);
 
@@ -97,17 +338,18 @@ impl ByzantineDetector {
             validator_id: validator_id.to_string(),
             height,
             block_hash: block_hash.to_string(),
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map_err(|e| format!("System time error: {}", e))?
-                .as_millis() as u64,
+            timestamp: self.now_millis(),
+            signed_header,
         };
 
+        Self::touch(&self.proposal_recency, &self.proposals, &key);
+
         // Get or create proposal list for this validator/height
         let mut proposals = self.proposals.entry(key.clone()).or_default();
 
         // Check for double signing
         if let Some(event) = self.detect_double_signing_internal(&proposals, &evidence) {
+            let event = self.apply_policy(event);
             warn!(
                 "🚨 Double signing detected: validator {} proposed multiple blocks at height {}",
                 validator_id, height
@@ -117,6 +359,9 @@ impl ByzantineDetector {
             events.push(event.clone());
             drop(events);
 
+            // Keep the conflicting proposal as evidence
+            proposals.push(evidence);
+
             return Err(format!(
                 "Double signing detected for validator {} at height {}",
                 validator_id, height
@@ -172,7 +417,8 @@ impl ByzantineDetector {
         existing_votes: &[Vote],
         new_vote: &Vote,
     ) -> Option<SlashingEvent> {
-        for existing_vote in existing_votes {
+        // Votes from other rounds of the epoch are on other blocks by design
+        for existing_vote in existing_votes.iter().filter(|v| v.round == new_vote.round) {
             // Same block hash but different approval - double voting
             if existing_vote.block_hash == new_vote.block_hash
                 && existing_vote.approved != new_vote.approved
@@ -186,7 +432,7 @@ impl ByzantineDetector {
                 ));
             }
 
-            // Different block hash in same epoch - equivocation
+            // Different block hash in the same round - equivocation
             if existing_vote.block_hash != new_vote.block_hash {
                 let mut votes = existing_votes.to_vec();
                 votes.push(new_vote.clone());
@@ -245,6 +491,26 @@ impl ByzantineDetector {
         None
     }
 
+    /// Signed headers of two different blocks the validator proposed at `height`
+    ///
+    /// Only proposals recorded with [`record_signed_proposal`](Self::record_signed_proposal)
+    /// can serve as evidence.
+    pub fn double_signing_evidence(
+        &self,
+        validator_id: &str,
+        height: u64,
+    ) -> Option<DoubleSigningEvidence> {
+        let proposals = self.proposals.get(&(validator_id.to_string(), height))?;
+        let mut signed = proposals.iter().filter_map(|p| p.signed_header.as_ref());
+        let first = signed.next()?;
+        let second = signed.find(|header| header.hash != first.hash)?;
+
+        Some(DoubleSigningEvidence {
+            first: first.clone(),
+            second: second.clone(),
+        })
+    }
+
     /// Detect equivocation (conflicting statements)
     ///
     /// Equivocation occurs when a validator votes on multiple different blocks in the same epoch
@@ -292,10 +558,7 @@ impl ByzantineDetector {
             severity: SlashingSeverity::Critical,
             slashing_rate: 15.0, // Critical offense: 15% slash
             amount: 0,           // Will be calculated based on stake
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .expect("System time before UNIX_EPOCH - clock may be misconfigured")
-                .as_millis() as u64,
+            timestamp: self.now_millis(),
             evidence,
         }
     }
@@ -322,10 +585,7 @@ impl ByzantineDetector {
             severity: SlashingSeverity::Critical,
             slashing_rate: 15.0, // Critical offense: 15% slash
             amount: 0,           // Will be calculated based on stake
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .expect("System time before UNIX_EPOCH - clock may be misconfigured")
-                .as_millis() as u64,
+            timestamp: self.now_millis(),
             evidence,
         }
     }
@@ -353,10 +613,7 @@ impl ByzantineDetector {
             severity: SlashingSeverity::Major,
             slashing_rate: 5.0, // Major offense: 5% slash
             amount: 0,          // Will be calculated based on stake
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .expect("System time before UNIX_EPOCH - clock may be misconfigured")
-                .as_millis() as u64,
+            timestamp: self.now_millis(),
             evidence,
         }
     }
@@ -366,20 +623,113 @@ impl ByzantineDetector {
         self.slashing_events.read().await.clone()
     }
 
+    /// Slashing events timestamped at or after `since_ms`
+    pub async fn slashing_events_since(&self, since_ms: u64) -> Vec<SlashingEvent> {
+        self.slashing_events
+            .read()
+            .await
+            .iter()
+            .filter(|event| event.timestamp >= since_ms)
+            .cloned()
+            .collect()
+    }
+
+    /// Most recent slashing event for a validator
+    pub async fn latest_event_for(&self, validator_id: &str) -> Option<SlashingEvent> {
+        self.slashing_events
+            .read()
+            .await
+            .iter()
+            .rev()
+            .find(|event| event.validator_id == validator_id)
+            .cloned()
+    }
+
     /// Clear old detection data (for memory management)
     ///
-    /// Removes data older than the specified number of epochs
-    pub fn cleanup_old_data(&self, current_epoch: u64, retention_epochs: u64) {
+    /// Removes votes older than `retention_epochs` epochs, and proposals more than
+    /// `retention_heights` heights below the finalized height. Proposals are pruned
+    /// relative to the chain the caller finalized rather than to any height a validator
+    /// claimed, so a proposal far ahead of the chain cannot age out the rest.
+    pub fn cleanup_old_data(
+        &self,
+        current_epoch: u64,
+        retention_epochs: u64,
+        finalized_height: u64,
+        retention_heights: u64,
+    ) {
         let cutoff_epoch = current_epoch.saturating_sub(retention_epochs);
+        let cutoff_height = finalized_height.saturating_sub(retention_heights);
 
         // Clean up old votes
         self.votes.retain(|(_, epoch), _| *epoch >= cutoff_epoch);
+        self.processed_votes
+            .retain(|_, epoch| *epoch >= cutoff_epoch);
+        Self::forget_before(&self.vote_recency, cutoff_epoch);
+
+        // Clean up old proposals
+        self.proposals
+            .retain(|(_, height), _| *height >= cutoff_height);
+        Self::forget_before(&self.proposal_recency, cutoff_height);
+
+        // Offenses outside the graduated window no longer affect any penalty
+        match *self.policy.lock() {
+            SlashingPolicy::Graduated { window_ms } => {
+                let now = self.now_millis();
+                self.offenses.retain(|_, timestamps| {
+                    timestamps.retain(|&timestamp| now.saturating_sub(timestamp) <= window_ms);
+                    !timestamps.is_empty()
+                });
+            }
+            SlashingPolicy::ZeroTolerance => self.offenses.clear(),
+        }
 
         info!(
-            "🧹 Byzantine detector cleanup: retained data from epoch {} onwards",
-            cutoff_epoch
+            "🧹 Byzantine detector cleanup: retained votes from epoch {} and proposals from height {} onwards",
+            cutoff_epoch, cutoff_height
         );
     }
+
+    /// Mark `key` as most recently used, evicting the least recently used entry from
+    /// `map` once over capacity
+    ///
+    /// Must be called without holding a reference into `map`.
+    fn touch<V>(
+        recency: &Mutex<LruCache<DetectionKey, ()>>,
+        map: &DashMap<DetectionKey, V>,
+        key: &DetectionKey,
+    ) -> Option<V> {
+        let evicted = recency.lock().push(key.clone(), ());
+        match evicted {
+            Some((evicted_key, ())) if evicted_key != *key => {
+                map.remove(&evicted_key).map(|(_, value)| value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Drop recency entries for keys below `cutoff`
+    fn forget_before(recency: &Mutex<LruCache<DetectionKey, ()>>, cutoff: u64) {
+        let mut recency = recency.lock();
+        let stale: Vec<DetectionKey> = recency
+            .iter()
+            .filter(|((_, position), _)| *position < cutoff)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale {
+            recency.pop(&key);
+        }
+    }
+
+    /// Number of tracked (validator, epoch) vote entries
+    pub fn tracked_vote_entries(&self) -> usize {
+        self.votes.len()
+    }
+
+    /// Number of tracked (validator, height) proposal entries
+    pub fn tracked_proposal_entries(&self) -> usize {
+        self.proposals.len()
+    }
 }
 
 impl Default for ByzantineDetector {
@@ -402,9 +752,11 @@ mod tests {
     #[tokio::test]
     async fn test_double_voting_detection() {
         let detector = ByzantineDetector::new();
+        let key_pair = crate::crypto::KeyPair::generate().unwrap();
+        detector.register_validator_key("validator-1", &key_pair.public_key_hex());
 
         // Create first vote (approve)
-        let vote1 = Vote::new(
+        let mut vote1 = Vote::new(
             "validator-1".to_string(),
             "block-hash-1".to_string(),
             1, // epoch
@@ -412,11 +764,12 @@ mod tests {
             80,
             true, // approved
         );
+        vote1.sign(&key_pair).unwrap();
 
         detector.record_vote(&vote1).await.unwrap();
 
         // Create conflicting vote (reject same block)
-        let vote2 = Vote::new(
+        let mut vote2 = Vote::new(
             "validator-1".to_string(),
             "block-hash-1".to_string(),
             1, // same epoch
@@ -424,6 +777,7 @@ mod tests {
             80,
             false, // rejected - conflicting!
         );
+        vote2.sign(&key_pair).unwrap();
 
         // This should fail and create a slashing event
         let result = detector.record_vote(&vote2).await;
@@ -434,6 +788,133 @@ mod tests {
         assert_eq!(events[0].offense, SlashingOffense::DoubleSigning);
     }
 
+    #[tokio::test]
+    async fn test_replayed_conflicting_vote_not_slashed_again() {
+        let detector = ByzantineDetector::new()
+            .with_slashing_policy(SlashingPolicy::Graduated { window_ms: 60_000 });
+        let key_pair = crate::crypto::KeyPair::generate().unwrap();
+        detector.register_validator_key("validator-1", &key_pair.public_key_hex());
+
+        let vote = |approved| {
+            let mut vote = Vote::new(
+                "validator-1".to_string(),
+                "block-hash-1".to_string(),
+                1,
+                0,
+                80,
+                approved,
+            );
+            vote.sign(&key_pair).unwrap();
+            vote
+        };
+        let conflicting = vote(false);
+
+        detector.record_vote(&vote(true)).await.unwrap();
+        assert!(detector.record_vote(&conflicting).await.is_err());
+        assert_eq!(
+            detector.record_vote(&conflicting).await,
+            Ok(VoteRecord::Replayed)
+        );
+
+        // The replay neither adds an event nor escalates the first-offense warning
+        let events = detector.get_slashing_events().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].severity, SlashingSeverity::Minor);
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_and_forged_votes_rejected() {
+        let detector = ByzantineDetector::new();
+        let key_pair = crate::crypto::KeyPair::generate().unwrap();
+        detector.register_validator_key("validator-1", &key_pair.public_key_hex());
+
+        let vote = |epoch| {
+            Vote::new(
+                "validator-1".to_string(),
+                "block-hash-1".to_string(),
+                epoch,
+                0,
+                80,
+                true,
+            )
+        };
+
+        let invalid_signature = Ok(VoteRecord::Rejected(VoteRejectionReason::InvalidSignature));
+        assert_eq!(detector.record_vote(&vote(1)).await, invalid_signature);
+
+        let attacker = crate::crypto::KeyPair::generate().unwrap();
+        let mut forged = vote(1);
+        forged.sign(&attacker).unwrap();
+        assert_eq!(detector.record_vote(&forged).await, invalid_signature);
+
+        let mut signed = vote(1);
+        signed.sign(&key_pair).unwrap();
+        detector.record_vote(&signed).await.unwrap();
+
+        // Rejected votes are neither recorded nor treated as slashable offenses
+        assert!(detector.get_slashing_events().await.is_empty());
+        let recorded = detector.votes.get(&("validator-1".to_string(), 1)).unwrap();
+        assert_eq!(recorded.len(), 1);
+        drop(recorded);
+
+        // Votes are rejected once the validator's key is removed, signed or not
+        detector.remove_validator_key("validator-1");
+        let mut signed = vote(1);
+        signed.block_hash = "block-hash-2".to_string();
+        signed.sign(&key_pair).unwrap();
+        let unknown_validator = Ok(VoteRecord::Rejected(VoteRejectionReason::UnknownValidator));
+        assert_eq!(detector.record_vote(&signed).await, unknown_validator);
+        let mut unknown = Vote::new(
+            "validator-2".to_string(),
+            "block-hash-1".to_string(),
+            1,
+            0,
+            80,
+            true,
+        );
+        unknown.sign(&attacker).unwrap();
+        assert_eq!(detector.record_vote(&unknown).await, unknown_validator);
+        assert!(detector.get_slashing_events().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replayed_vote_ignored() {
+        let detector = ByzantineDetector::new();
+        let key_pair = crate::crypto::KeyPair::generate().unwrap();
+        detector.register_validator_key("validator-1", &key_pair.public_key_hex());
+        detector.set_current_epoch(2);
+
+        let mut vote = Vote::new(
+            "validator-1".to_string(),
+            "block-hash-1".to_string(),
+            2,
+            0,
+            80,
+            true,
+        );
+        vote.sign(&key_pair).unwrap();
+
+        assert_eq!(detector.record_vote(&vote).await, Ok(VoteRecord::Recorded));
+        assert_eq!(detector.record_vote(&vote).await, Ok(VoteRecord::Replayed));
+
+        let recorded = detector.votes.get(&("validator-1".to_string(), 2)).unwrap();
+        assert_eq!(recorded.len(), 1);
+        drop(recorded);
+        assert!(detector.get_slashing_events().await.is_empty());
+
+        // A signed vote from an earlier epoch cannot be replayed into the current one
+        let mut stale = vote.clone();
+        stale.epoch = 1;
+        stale.sign(&key_pair).unwrap();
+        assert_eq!(
+            detector.record_vote(&stale).await,
+            Ok(VoteRecord::Rejected(VoteRejectionReason::WrongEpoch))
+        );
+        let stale_key = ("validator-1".to_string(), 1);
+        assert!(detector.votes.get(&stale_key).is_none());
+        assert!(detector.get_slashing_events().await.is_empty());
+    }
+
     #[tokio::test]
     async fn test_double_signing_detection() {
         let detector = ByzantineDetector::new();
@@ -454,14 +935,53 @@ mod tests {
         let events = detector.get_slashing_events().await;
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].offense, SlashingOffense::DoubleSigning);
+
+        // Bare hashes are detected but cannot be proven to others
+        assert!(detector.double_signing_evidence("validator-1", 1).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_signed_proposals_yield_double_signing_evidence() {
+        let detector = ByzantineDetector::new();
+        let key_pair = crate::crypto::KeyPair::generate().unwrap();
+        let signed_block = |score| {
+            let mut block = Block::new(
+                1,
+                1,
+                "0".repeat(64),
+                "validator-1".to_string(),
+                score,
+                vec![],
+            );
+            block.sign(&key_pair).unwrap();
+            block
+        };
+
+        let first = signed_block(70);
+        detector.record_signed_proposal(&first).await.unwrap();
+        assert!(detector.double_signing_evidence("validator-1", 1).is_none());
+        assert!(detector
+            .record_signed_proposal(&signed_block(90))
+            .await
+            .is_err());
+
+        let evidence = detector.double_signing_evidence("validator-1", 1).unwrap();
+        assert_eq!(evidence.first.hash, first.hash);
+        assert!(evidence.verify("validator-1", 1, &key_pair.public_key_hex()));
+        assert!(!evidence.verify("validator-1", 2, &key_pair.public_key_hex()));
+
+        let other_key = crate::crypto::KeyPair::generate().unwrap();
+        assert!(!evidence.verify("validator-1", 1, &other_key.public_key_hex()));
     }
 
     #[tokio::test]
     async fn test_equivocation_detection() {
         let detector = ByzantineDetector::new();
+        let key_pair = crate::crypto::KeyPair::generate().unwrap();
+        detector.register_validator_key("validator-1", &key_pair.public_key_hex());
 
         // Vote on first block
-        let vote1 = Vote::new(
+        let mut vote1 = Vote::new(
             "validator-1".to_string(),
             "block-hash-1".to_string(),
             1, // epoch
@@ -469,10 +989,11 @@ mod tests {
             80,
             true,
         );
+        vote1.sign(&key_pair).unwrap();
         detector.record_vote(&vote1).await.unwrap();
 
         // Vote on different block in same epoch - equivocation!
-        let vote2 = Vote::new(
+        let mut vote2 = Vote::new(
             "validator-1".to_string(),
             "block-hash-2".to_string(),
             1, // same epoch
@@ -480,9 +1001,157 @@ mod tests {
             80,
             true,
         );
+        vote2.sign(&key_pair).unwrap();
 
         let result = detector.record_vote(&vote2).await;
         // This will be detected as equivocation
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_graduated_policy_warns_then_slashes() {
+        let detector = ByzantineDetector::new().with_slashing_policy(SlashingPolicy::Graduated {
+            window_ms: 60 * 60 * 1000,
+        });
+
+        detector
+            .record_proposal("validator-1", 1, "block-hash-1")
+            .await
+            .unwrap();
+        assert!(detector
+            .record_proposal("validator-1", 1, "block-hash-2")
+            .await
+            .is_err());
+
+        let warning = detector.latest_event_for("validator-1").await.unwrap();
+        assert_eq!(warning.severity, SlashingSeverity::Minor);
+        assert_eq!(warning.slashing_rate, 0.0);
+
+        detector
+            .record_proposal("validator-1", 2, "block-hash-3")
+            .await
+            .unwrap();
+        assert!(detector
+            .record_proposal("validator-1", 2, "block-hash-4")
+            .await
+            .is_err());
+
+        let slash = detector.latest_event_for("validator-1").await.unwrap();
+        assert_eq!(slash.severity, SlashingSeverity::Critical);
+        assert_eq!(slash.slashing_rate, 15.0);
+        assert_eq!(detector.get_slashing_events().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_graduated_window_follows_detector_clock() {
+        let clock = Arc::new(crate::clock::MockClock::new(1_000_000));
+        let detector = ByzantineDetector::new()
+            .with_clock(clock.clone())
+            .with_slashing_policy(SlashingPolicy::Graduated { window_ms: 60_000 });
+
+        async fn offend(detector: &ByzantineDetector, height: u64) -> SlashingEvent {
+            detector
+                .record_proposal("validator-1", height, "block-hash-1")
+                .await
+                .unwrap();
+            assert!(detector
+                .record_proposal("validator-1", height, "block-hash-2")
+                .await
+                .is_err());
+            detector.latest_event_for("validator-1").await.unwrap()
+        }
+
+        let warning = offend(&detector, 1).await;
+        assert_eq!(warning.severity, SlashingSeverity::Minor);
+        assert_eq!(warning.timestamp, 1_000_000);
+
+        // Once the first offense falls outside the window it is pruned and the next
+        // offense is a first offense again
+        clock.advance(60_001);
+        detector.cleanup_old_data(1, 100, 0, 100);
+        assert!(detector.offenses.is_empty());
+        assert_eq!(offend(&detector, 2).await.severity, SlashingSeverity::Minor);
+
+        clock.advance(1_000);
+        assert_eq!(
+            offend(&detector, 3).await.severity,
+            SlashingSeverity::Critical
+        );
+
+        // Events are selected by timestamp, not by their position in the log
+        assert_eq!(detector.slashing_events_since(1_000_000).await.len(), 3);
+        assert_eq!(detector.slashing_events_since(1_060_001).await.len(), 2);
+        assert!(detector
+            .slashing_events_since(clock.now_millis() + 1)
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_zero_tolerance_slashes_first_offense() {
+        let detector = ByzantineDetector::new();
+
+        detector
+            .record_proposal("validator-1", 1, "block-hash-1")
+            .await
+            .unwrap();
+        assert!(detector
+            .record_proposal("validator-1", 1, "block-hash-2")
+            .await
+            .is_err());
+
+        let slash = detector.latest_event_for("validator-1").await.unwrap();
+        assert_eq!(slash.severity, SlashingSeverity::Critical);
+    }
+
+    #[tokio::test]
+    async fn test_old_proposals_evicted() {
+        let detector = ByzantineDetector::with_detection_capacity(10);
+        for height in 1..=50 {
+            detector
+                .record_proposal("validator-1", height, &format!("block-hash-{}", height))
+                .await
+                .unwrap();
+        }
+
+        // Only the most recent heights are kept once over capacity
+        assert_eq!(detector.tracked_proposal_entries(), 10);
+        assert!(detector
+            .proposals
+            .get(&("validator-1".to_string(), 1))
+            .is_none());
+        assert!(detector
+            .proposals
+            .get(&("validator-1".to_string(), 50))
+            .is_some());
+
+        // Double signing at a tracked height is still detected
+        assert!(detector
+            .record_proposal("validator-1", 50, "conflicting-hash")
+            .await
+            .is_err());
+
+        // Cleanup prunes proposals by height, not just votes
+        let detector = ByzantineDetector::new();
+        for height in 1..=200 {
+            detector
+                .record_proposal("validator-1", height, &format!("block-hash-{}", height))
+                .await
+                .unwrap();
+        }
+        detector.cleanup_old_data(200, 100, 200, 100);
+        assert_eq!(detector.tracked_proposal_entries(), 101);
+        assert!(detector
+            .proposals
+            .get(&("validator-1".to_string(), 99))
+            .is_none());
+
+        // A proposal far ahead of the finalized chain does not age out the rest
+        detector
+            .record_proposal("validator-2", u64::MAX, "block-hash-far")
+            .await
+            .unwrap();
+        detector.cleanup_old_data(200, 100, 200, 100);
+        assert_eq!(detector.tracked_proposal_entries(), 102);
+    }
 }
@@ -0,0 +1,163 @@
+//! Offline verification of chain segments received from peers
+
+use crate::error::{ConsensusError, Result};
+use crate::types::Block;
+
+/// Verify a contiguous run of blocks before importing it
+///
+/// `start_previous_hash` is the hash the first block must build on, normally the local
+/// tip. Every block must belong to `chain_id`, extend its predecessor by one height
+/// without going back an epoch, and carry a correct hash, Merkle root and proposer
+/// signature.
+pub fn verify_chain_segment(
+    blocks: &[Block],
+    start_previous_hash: &str,
+    chain_id: &str,
+) -> Result<()> {
+    let mut previous: Option<&Block> = None;
+
+    for block in blocks {
+        let height = block.header.height;
+
+        if block.header.chain_id != chain_id {
+            return Err(ConsensusError::invalid_block(format!(
+                "Block {} is for chain {:?}, expected {:?}",
+                height, block.header.chain_id, chain_id
+            )));
+        }
+        let expected_previous_hash = previous.map_or(start_previous_hash, |prev| &prev.hash);
+
+        if block.header.previous_hash != expected_previous_hash {
+            return Err(ConsensusError::invalid_block(format!(
+                "Block {} does not link to the previous block",
+                height
+            )));
+        }
+
+        if let Some(prev) = previous {
+            if height != prev.header.height + 1 {
+                return Err(ConsensusError::invalid_block(format!(
+                    "Chain discontinuity: block {} -> {}",
+                    prev.header.height, height
+                )));
+            }
+            if block.header.epoch < prev.header.epoch {
+                return Err(ConsensusError::invalid_block(format!(
+                    "Block {} epoch {} is before previous epoch {}",
+                    height, block.header.epoch, prev.header.epoch
+                )));
+            }
+        }
+
+        if block.header.merkle_root != Block::calculate_merkle_root(&block.transactions) {
+            return Err(ConsensusError::invalid_block(format!(
+                "Invalid merkle root in block {}",
+                height
+            )));
+        }
+
+        if !block.verify_hash() {
+            return Err(ConsensusError::invalid_block(format!(
+                "Invalid hash for block {}",
+                height
+            )));
+        }
+
+        match block.verify_signature() {
+            Ok(true) => {}
+            Ok(false) => {
+                return Err(ConsensusError::signature_verification_failed(format!(
+                    "Invalid proposer signature on block {}",
+                    height
+                )))
+            }
+            Err(e) => {
+                return Err(ConsensusError::signature_verification_failed(format!(
+                    "Block {}: {}",
+                    height, e
+                )))
+            }
+        }
+
+        previous = Some(block);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::KeyPair;
+
+    const CHAIN_ID: &str = "poe-testnet";
+
+    fn signed_segment(length: u64, start_previous_hash: &str) -> Vec<Block> {
+        let key_pair = KeyPair::generate().unwrap();
+        let mut previous_hash = start_previous_hash.to_string();
+
+        (1..=length)
+            .map(|height| {
+                let mut block = Block::new(
+                    height,
+                    height / 2,
+                    previous_hash.clone(),
+                    "validator-1".to_string(),
+                    85,
+                    vec![],
+                );
+                block.set_chain_id(CHAIN_ID);
+                block.sign(&key_pair).unwrap();
+                previous_hash = block.hash.clone();
+                block
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_segment() {
+        let start = "0".repeat(64);
+        let blocks = signed_segment(5, &start);
+
+        verify_chain_segment(&blocks, &start, CHAIN_ID).unwrap();
+        verify_chain_segment(&blocks[2..], &blocks[1].hash, CHAIN_ID).unwrap();
+        verify_chain_segment(&[], &start, CHAIN_ID).unwrap();
+    }
+
+    #[test]
+    fn test_segment_from_other_chain() {
+        let start = "0".repeat(64);
+        let blocks = signed_segment(3, &start);
+
+        assert!(matches!(
+            verify_chain_segment(&blocks, &start, "poe-mainnet"),
+            Err(ConsensusError::InvalidBlock { .. })
+        ));
+    }
+
+    #[test]
+    fn test_broken_link_segment() {
+        let start = "0".repeat(64);
+        let mut blocks = signed_segment(5, &start);
+        blocks.remove(2);
+
+        assert!(matches!(
+            verify_chain_segment(&blocks, &start, CHAIN_ID),
+            Err(ConsensusError::InvalidBlock { .. })
+        ));
+        assert!(verify_chain_segment(&blocks[..2], &"f".repeat(64), CHAIN_ID).is_err());
+    }
+
+    #[test]
+    fn test_tampered_signature_segment() {
+        let start = "0".repeat(64);
+        let mut blocks = signed_segment(5, &start);
+        let other = KeyPair::generate().unwrap();
+        blocks[3].proposer_public_key = other.public_key_hex();
+
+        assert!(matches!(
+            verify_chain_segment(&blocks, &start, CHAIN_ID),
+            Err(ConsensusError::SignatureVerificationFailed { .. })
+        ));
+    }
+}
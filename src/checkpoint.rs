@@ -1,9 +1,12 @@
 //! Checkpoint system for crash recovery and fast sync
 
+use crate::audit::{AuditEntry, AuditHandle};
+use crate::clock::{Clock, SystemClock};
 use crate::crypto::{KeyPair, Signature};
 use crate::error::{ConsensusError, Result};
 use crate::types::Block;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn};
@@ -23,8 +26,74 @@ pub struct Checkpoint {
     pub validator_signatures: Vec<ValidatorSignature>,
     /// Total stake that signed this checkpoint
     pub total_stake_signed: u64,
-    /// Merkle root of all finalized blocks up to this point
+    /// Commitment to the block's execution root and `consensus_root`
     pub state_root: String,
+    /// Consensus evidence of every checkpointed block up to this point, see
+    /// [`accumulate_consensus_root`]
+    #[serde(default)]
+    pub consensus_root: String,
+    /// Committee signatures packed for batch verification, replacing
+    /// `validator_signatures` when present
+    #[serde(default)]
+    pub aggregate_signature: Option<AggregateSignature>,
+}
+
+impl Checkpoint {
+    /// Check that this checkpoint commits to `block` and its consensus metadata
+    ///
+    /// `previous_consensus_root` is the consensus root of the preceding checkpoint, or
+    /// empty for the first one.
+    pub fn verify_state(&self, block: &Block, previous_consensus_root: &str) -> bool {
+        let consensus_root = accumulate_consensus_root(previous_consensus_root, block);
+        self.block_hash == block.hash
+            && self.consensus_root == consensus_root
+            && self.state_root == checkpoint_state_root(execution_root(block), &consensus_root)
+    }
+}
+
+/// Fold a block's consensus evidence into the running consensus root
+///
+/// Covers participation, consensus strength and emotional fitness from the block's
+/// [`ConsensusMetadata`](crate::types::ConsensusMetadata), which the block hash does not.
+pub fn accumulate_consensus_root(previous_consensus_root: &str, block: &Block) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(previous_consensus_root.as_bytes());
+    hasher.update(block.header.height.to_le_bytes());
+    hasher.update(block.hash.as_bytes());
+    hasher.update([block.header.committee_emotional_fitness]);
+
+    match &block.consensus_metadata {
+        Some(metadata) => {
+            hasher.update([1]);
+            hasher.update((metadata.participant_count as u64).to_le_bytes());
+            hasher.update([metadata.consensus_strength, metadata.emotional_fitness]);
+            hasher.update((metadata.byzantine_failures as u64).to_le_bytes());
+            for participant in &metadata.participants {
+                hasher.update((participant.len() as u64).to_le_bytes());
+                hasher.update(participant.as_bytes());
+            }
+        }
+        None => hasher.update([0]),
+    }
+
+    hex::encode(hasher.finalize())
+}
+
+/// Checkpoint state root over the execution root and the consensus root
+pub fn checkpoint_state_root(execution_root: &str, consensus_root: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(execution_root.as_bytes());
+    hasher.update(consensus_root.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// State root of the block, or its transaction Merkle root without a state machine
+fn execution_root(block: &Block) -> &str {
+    if block.header.state_root.is_empty() {
+        &block.header.merkle_root
+    } else {
+        &block.header.state_root
+    }
 }
 
 /// A validator's signature on a checkpoint
@@ -40,29 +109,148 @@ pub struct ValidatorSignature {
     pub public_key: String,
 }
 
+/// A signer included in an aggregate checkpoint signature
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AggregateSigner {
+    /// Validator ID
+    pub validator_id: String,
+    /// Validator's stake at checkpoint time
+    pub stake: u64,
+    /// Validator's public key
+    pub public_key: String,
+}
+
+/// Committee signatures over a checkpoint packed for batch verification
+///
+/// Signatures are kept as concatenated 64-byte compact ECDSA signatures, without the
+/// per-signature recovery id and algorithm tag, and are checked against the listed
+/// signer keys in one batch instead of recovering each key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AggregateSignature {
+    /// Signers, in the same order as their signatures
+    pub signers: Vec<AggregateSigner>,
+    /// Concatenated compact signatures (hex encoded)
+    pub signatures: String,
+}
+
+impl AggregateSignature {
+    /// Pack individual validator signatures into an aggregate
+    pub fn from_signatures(validator_signatures: &[ValidatorSignature]) -> Result<Self> {
+        let mut signatures = Vec::with_capacity(validator_signatures.len() * 64);
+        let mut signers = Vec::with_capacity(validator_signatures.len());
+
+        for validator_sig in validator_signatures {
+            let bytes = hex::decode(&validator_sig.signature.signature)
+                .map_err(|e| ConsensusError::internal(format!("Invalid signature hex: {}", e)))?;
+            if bytes.len() != 64 {
+                return Err(ConsensusError::internal(format!(
+                    "Invalid signature length from {}: {} bytes",
+                    validator_sig.validator_id,
+                    bytes.len()
+                )));
+            }
+            signatures.extend_from_slice(&bytes);
+            signers.push(AggregateSigner {
+                validator_id: validator_sig.validator_id.clone(),
+                stake: validator_sig.stake,
+                public_key: validator_sig.public_key.clone(),
+            });
+        }
+
+        Ok(Self {
+            signers,
+            signatures: hex::encode(signatures),
+        })
+    }
+
+    /// Combined stake of all signers
+    pub fn total_stake(&self) -> u64 {
+        self.signers.iter().map(|signer| signer.stake).sum()
+    }
+
+    /// Verify every signer's signature over `message`
+    pub fn verify(&self, message: &[u8]) -> Result<bool> {
+        let bytes = hex::decode(&self.signatures)
+            .map_err(|e| ConsensusError::internal(format!("Invalid signature hex: {}", e)))?;
+        if bytes.len() != self.signers.len() * 64 {
+            return Ok(false);
+        }
+
+        let mut public_keys: Vec<&str> = Vec::with_capacity(self.signers.len());
+        for signer in &self.signers {
+            if public_keys.contains(&signer.public_key.as_str()) {
+                warn!(
+                    "Duplicate signer {} in aggregate signature",
+                    signer.validator_id
+                );
+                return Ok(false);
+            }
+            public_keys.push(&signer.public_key);
+        }
+
+        let signatures: Vec<[u8; 64]> = bytes
+            .chunks_exact(64)
+            .map(|chunk| chunk.try_into().expect("chunk is 64 bytes"))
+            .collect();
+
+        KeyPair::verify_batch(message, &signatures, &public_keys)
+    }
+}
+
 /// Manages checkpoint creation and verification
 pub struct CheckpointManager {
     /// Stored checkpoints
     checkpoints: Arc<RwLock<Vec<Checkpoint>>>,
     /// Checkpoint interval (create checkpoint every N blocks)
     checkpoint_interval: u64,
-    /// Minimum stake percentage required for checkpoint (67%)
+    /// Minimum stake percentage required for checkpoint
     minimum_stake_percentage: u8,
     /// Total stake in the network (for calculating percentages)
     total_network_stake: Arc<RwLock<u64>>,
+    /// Audit trail for created checkpoints
+    audit: AuditHandle,
 }
 
 impl CheckpointManager {
-    /// Create a new checkpoint manager
+    /// Create a new checkpoint manager requiring the Byzantine threshold of stake
     pub fn new(checkpoint_interval: u64) -> Self {
         Self {
             checkpoints: Arc::new(RwLock::new(Vec::new())),
             checkpoint_interval,
-            minimum_stake_percentage: 67, // Byzantine threshold
+            minimum_stake_percentage: crate::DEFAULT_BYZANTINE_THRESHOLD,
             total_network_stake: Arc::new(RwLock::new(0)),
+            audit: AuditHandle::default(),
         }
     }
 
+    /// Create a checkpoint manager with its own signing quorum
+    ///
+    /// `minimum_stake_percentage` must be 51-100 and is independent of the consensus
+    /// Byzantine threshold, so checkpoints can demand a stronger quorum for fast sync.
+    pub fn with_quorum(checkpoint_interval: u64, minimum_stake_percentage: u8) -> Result<Self> {
+        if !(51..=100).contains(&minimum_stake_percentage) {
+            return Err(ConsensusError::config_error(
+                "Checkpoint quorum must be 51-100",
+            ));
+        }
+
+        Ok(Self {
+            minimum_stake_percentage,
+            ..Self::new(checkpoint_interval)
+        })
+    }
+
+    /// Minimum percentage of network stake that must sign a checkpoint
+    pub fn minimum_stake_percentage(&self) -> u8 {
+        self.minimum_stake_percentage
+    }
+
+    /// Record checkpoint creation through a shared audit handle
+    pub(crate) fn with_audit(mut self, audit: AuditHandle) -> Self {
+        self.audit = audit;
+        self
+    }
+
     /// Check if a checkpoint should be created at this height
     pub fn should_create_checkpoint(&self, height: u64) -> bool {
         height.is_multiple_of(self.checkpoint_interval)
@@ -73,14 +261,33 @@ impl CheckpointManager {
         &self,
         block: &Block,
         validator_signatures: Vec<ValidatorSignature>,
+    ) -> Result<Checkpoint> {
+        self.build_checkpoint(block, validator_signatures, false)
+            .await
+    }
+
+    /// Create a new checkpoint carrying an aggregate of the committee signatures
+    pub async fn create_aggregated_checkpoint(
+        &self,
+        block: &Block,
+        validator_signatures: Vec<ValidatorSignature>,
+    ) -> Result<Checkpoint> {
+        self.build_checkpoint(block, validator_signatures, true)
+            .await
+    }
+
+    async fn build_checkpoint(
+        &self,
+        block: &Block,
+        validator_signatures: Vec<ValidatorSignature>,
+        aggregate: bool,
     ) -> Result<Checkpoint> {
         // Calculate total stake that signed
         let total_stake_signed: u64 = validator_signatures.iter().map(|vs| vs.stake).sum();
         let total_stake = *self.total_network_stake.read().await;
 
-        // Verify we have at least 67% of stake
-        if total_stake > 0 {
-            let stake_percentage = (total_stake_signed * 100) / total_stake;
+        // Verify the signers meet the checkpoint quorum
+        if let Some(stake_percentage) = (total_stake_signed * 100).checked_div(total_stake) {
             if stake_percentage < self.minimum_stake_percentage as u64 {
                 return Err(ConsensusError::config_error(format!(
                     "Insufficient stake for checkpoint: {}% < {}%",
@@ -89,26 +296,45 @@ impl CheckpointManager {
             }
         }
 
+        let (validator_signatures, aggregate_signature) = if aggregate {
+            let aggregate_signature = AggregateSignature::from_signatures(&validator_signatures)?;
+            (Vec::new(), Some(aggregate_signature))
+        } else {
+            (validator_signatures, None)
+        };
+
         // Create checkpoint
+        let consensus_root = self.next_consensus_root(block).await;
         let checkpoint = Checkpoint {
             height: block.header.height,
             block_hash: block.hash.clone(),
             epoch: block.header.epoch,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .expect("System time before UNIX_EPOCH")
-                .as_millis() as u64,
+            timestamp: SystemClock.now_millis(),
             validator_signatures,
             total_stake_signed,
-            state_root: block.header.merkle_root.clone(),
+            state_root: checkpoint_state_root(execution_root(block), &consensus_root),
+            consensus_root,
+            aggregate_signature,
         };
 
         // Verify the checkpoint
-        self.verify_checkpoint(&checkpoint).await?;
+        if !self.verify_checkpoint(&checkpoint).await? {
+            return Err(ConsensusError::signature_verification_failed(format!(
+                "Checkpoint at height {} failed verification",
+                checkpoint.height
+            )));
+        }
 
         // Store the checkpoint
         self.checkpoints.write().await.push(checkpoint.clone());
 
+        self.audit.record(AuditEntry::CheckpointCreated {
+            height: checkpoint.height,
+            block_hash: checkpoint.block_hash.clone(),
+            epoch: checkpoint.epoch,
+            timestamp: checkpoint.timestamp,
+        });
+
         info!(
             "✅ Checkpoint created at height {} with {}% stake signed",
             checkpoint.height,
@@ -120,7 +346,7 @@ impl CheckpointManager {
 
     /// Verify a checkpoint's signatures
     pub async fn verify_checkpoint(&self, checkpoint: &Checkpoint) -> Result<bool> {
-        if checkpoint.validator_signatures.is_empty() {
+        if checkpoint.validator_signatures.is_empty() && checkpoint.aggregate_signature.is_none() {
             return Err(ConsensusError::signature_verification_failed(
                 "No validator signatures in checkpoint",
             ));
@@ -134,7 +360,29 @@ impl CheckpointManager {
             &checkpoint.state_root,
         );
 
-        // Verify each signature
+        if let Some(aggregate) = &checkpoint.aggregate_signature {
+            if aggregate.signers.is_empty() {
+                return Err(ConsensusError::signature_verification_failed(
+                    "No signers in checkpoint aggregate signature",
+                ));
+            }
+            if checkpoint.total_stake_signed > aggregate.total_stake() {
+                warn!(
+                    "Checkpoint at height {} claims more stake than its signers hold",
+                    checkpoint.height
+                );
+                return Ok(false);
+            }
+            if !aggregate.verify(checkpoint_data.as_bytes())? {
+                warn!(
+                    "Invalid aggregate signature in checkpoint at height {}",
+                    checkpoint.height
+                );
+                return Ok(false);
+            }
+        }
+
+        // Verify each individual signature
         for validator_sig in &checkpoint.validator_signatures {
             let is_valid = match KeyPair::verify(
                 checkpoint_data.as_bytes(),
@@ -162,8 +410,8 @@ impl CheckpointManager {
 
         // Verify stake percentage
         let total_stake = *self.total_network_stake.read().await;
-        if total_stake > 0 {
-            let stake_percentage = (checkpoint.total_stake_signed * 100) / total_stake;
+        let signed_stake = checkpoint.total_stake_signed * 100;
+        if let Some(stake_percentage) = signed_stake.checked_div(total_stake) {
             if stake_percentage < self.minimum_stake_percentage as u64 {
                 warn!(
                     "Checkpoint at height {} has insufficient stake: {}% < {}%",
@@ -176,6 +424,34 @@ impl CheckpointManager {
         Ok(true)
     }
 
+    /// State root a checkpoint at `block` would carry, for validators to sign
+    pub async fn checkpoint_state_root(&self, block: &Block) -> String {
+        let consensus_root = self.next_consensus_root(block).await;
+        checkpoint_state_root(execution_root(block), &consensus_root)
+    }
+
+    /// Consensus root after folding `block` into the latest checkpoint's
+    async fn next_consensus_root(&self, block: &Block) -> String {
+        let checkpoints = self.checkpoints.read().await;
+        let previous = checkpoints
+            .last()
+            .map(|cp| cp.consensus_root.as_str())
+            .unwrap_or("");
+        accumulate_consensus_root(previous, block)
+    }
+
+    /// Consensus root of the last checkpoint below `height`, or empty if there is none
+    pub async fn consensus_root_before(&self, height: u64) -> String {
+        self.checkpoints
+            .read()
+            .await
+            .iter()
+            .rev()
+            .find(|cp| cp.height < height)
+            .map(|cp| cp.consensus_root.clone())
+            .unwrap_or_default()
+    }
+
     /// Get the latest checkpoint
     pub async fn get_latest_checkpoint(&self) -> Option<Checkpoint> {
         self.checkpoints.read().await.last().cloned()
@@ -263,6 +539,7 @@ impl CheckpointManager {
             checkpoint_interval: self.checkpoint_interval,
             average_stake_signed,
             total_network_stake: *self.total_network_stake.read().await,
+            minimum_stake_percentage: self.minimum_stake_percentage,
         }
     }
 
@@ -290,6 +567,9 @@ pub struct CheckpointStatistics {
     pub average_stake_signed: u64,
     /// Total network stake
     pub total_network_stake: u64,
+    /// Minimum percentage of network stake required to sign a checkpoint
+    #[serde(default)]
+    pub minimum_stake_percentage: u8,
 }
 
 #[cfg(test)]
@@ -304,12 +584,15 @@ mod tests {
                 epoch: height / 10,
                 previous_hash: "0".repeat(64),
                 merkle_root: "merkle_root".to_string(),
+                state_root: String::new(),
                 timestamp: 1000000,
                 difficulty: 0,
                 nonce: 0,
                 validator_id: "validator1".to_string(),
                 emotional_score: 85,
+                committee_emotional_fitness: 0,
                 consensus_strength: 80,
+                chain_id: String::new(),
             },
             hash: hash.to_string(),
             transactions: vec![],
@@ -339,11 +622,12 @@ mod tests {
 
         // Create validator signature
         let keypair = KeyPair::generate().unwrap();
+        let state_root = manager.checkpoint_state_root(&block).await;
         let checkpoint_data = manager.create_checkpoint_data(
             100,
             "hash100",
             10,
-            "merkle_root",
+            &state_root,
         );
         let signature = keypair.sign(checkpoint_data.as_bytes()).unwrap();
 
@@ -364,6 +648,30 @@ mod tests {
         assert_eq!(checkpoint.total_stake_signed, 7_000);
     }
 
+    #[tokio::test]
+    async fn test_stricter_checkpoint_quorum() {
+        assert!(CheckpointManager::with_quorum(100, 50).is_err());
+        assert!(CheckpointManager::with_quorum(100, 101).is_err());
+
+        let manager = CheckpointManager::with_quorum(100, 80).unwrap();
+        manager.update_total_stake(10_000).await;
+        let stats = manager.get_checkpoint_statistics().await;
+        assert_eq!(stats.minimum_stake_percentage, 80);
+
+        let block = create_test_block(100, "hash100");
+
+        let state_root = manager.checkpoint_state_root(&block).await;
+
+        // 70% clears the default quorum but not this one
+        let signatures = committee_signatures(&manager, &state_root, 7, 1_000);
+        let result = manager.create_checkpoint(&block, signatures).await;
+        assert!(result.is_err());
+        assert!(manager.get_latest_checkpoint().await.is_none());
+
+        let signatures = committee_signatures(&manager, &state_root, 8, 1_000);
+        manager.create_checkpoint(&block, signatures).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_verify_checkpoint() {
         let manager = CheckpointManager::new(100);
@@ -392,22 +700,133 @@ mod tests {
             validator_signatures: vec![validator_sig],
             total_stake_signed: 7_000,
             state_root: "merkle_root".to_string(),
+            consensus_root: String::new(),
+            aggregate_signature: None,
         };
 
         let is_valid = manager.verify_checkpoint(&checkpoint).await.unwrap();
         assert!(is_valid);
     }
 
+    fn committee_signatures(
+        manager: &CheckpointManager,
+        state_root: &str,
+        count: usize,
+        stake: u64,
+    ) -> Vec<ValidatorSignature> {
+        (0..count)
+            .map(|i| {
+                let keypair = KeyPair::generate().unwrap();
+                ValidatorSignature {
+                    validator_id: format!("validator{}", i),
+                    stake,
+                    signature: manager
+                        .sign_checkpoint(100, "hash100", 10, state_root, &keypair)
+                        .unwrap(),
+                    public_key: keypair.public_key_hex(),
+                }
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_checkpoint_verifies() {
+        let manager = CheckpointManager::new(100);
+        manager.update_total_stake(10_000).await;
+
+        let block = create_test_block(100, "hash100");
+        let state_root = manager.checkpoint_state_root(&block).await;
+        let signatures = committee_signatures(&manager, &state_root, 4, 2_000);
+
+        let checkpoint = manager
+            .create_aggregated_checkpoint(&block, signatures)
+            .await
+            .unwrap();
+
+        let aggregate = checkpoint.aggregate_signature.as_ref().unwrap();
+        assert!(checkpoint.validator_signatures.is_empty());
+        assert_eq!(aggregate.signers.len(), 4);
+        assert_eq!(aggregate.total_stake(), 8_000);
+        assert_eq!(checkpoint.total_stake_signed, 8_000);
+        assert!(manager.verify_checkpoint(&checkpoint).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_consensus_metadata_in_state_root() {
+        let manager = CheckpointManager::new(100);
+        manager.update_total_stake(10_000).await;
+
+        let mut block = create_test_block(100, "hash100");
+        block.consensus_metadata = Some(crate::types::ConsensusMetadata {
+            participant_count: 3,
+            consensus_strength: 80,
+            emotional_fitness: 85,
+            mean_emotional_fitness: 85,
+            byzantine_failures: 0,
+            finalized_at: 1000000,
+            participants: vec!["validator1".to_string(), "validator2".to_string()],
+            committee_proof: None,
+            attestation_signature: String::new(),
+        });
+        let state_root = manager.checkpoint_state_root(&block).await;
+        let signatures = committee_signatures(&manager, &state_root, 4, 2_000);
+
+        // Inflated consensus strength yields a different root, so the signatures no
+        // longer cover it
+        let mut tampered = block.clone();
+        if let Some(metadata) = tampered.consensus_metadata.as_mut() {
+            metadata.consensus_strength = 100;
+        }
+        assert_ne!(manager.checkpoint_state_root(&tampered).await, state_root);
+        assert!(manager
+            .create_checkpoint(&tampered, signatures.clone())
+            .await
+            .is_err());
+
+        let checkpoint = manager.create_checkpoint(&block, signatures).await.unwrap();
+        assert_eq!(checkpoint.state_root, state_root);
+        assert!(checkpoint.verify_state(&block, ""));
+        assert!(!checkpoint.verify_state(&tampered, ""));
+
+        // The next checkpoint accumulates on top of this one
+        let next = create_test_block(200, "hash200");
+        assert_eq!(
+            manager.next_consensus_root(&next).await,
+            accumulate_consensus_root(&checkpoint.consensus_root, &next)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_checkpoint_rejects_swapped_signer() {
+        let manager = CheckpointManager::new(100);
+        manager.update_total_stake(10_000).await;
+
+        let block = create_test_block(100, "hash100");
+        let state_root = manager.checkpoint_state_root(&block).await;
+        let signatures = committee_signatures(&manager, &state_root, 4, 2_000);
+        let mut checkpoint = manager
+            .create_aggregated_checkpoint(&block, signatures)
+            .await
+            .unwrap();
+
+        let impostor = KeyPair::generate().unwrap();
+        let aggregate = checkpoint.aggregate_signature.as_mut().unwrap();
+        aggregate.signers[2].public_key = impostor.public_key_hex();
+
+        assert!(!manager.verify_checkpoint(&checkpoint).await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_insufficient_stake() {
         let manager = CheckpointManager::new(100);
         manager.update_total_stake(10_000).await;
 
         let block = create_test_block(100, "hash100");
+        let state_root = manager.checkpoint_state_root(&block).await;
 
         let keypair = KeyPair::generate().unwrap();
         let signature = manager
-            .sign_checkpoint(100, "hash100", 10, "merkle_root", &keypair)
+            .sign_checkpoint(100, "hash100", 10, &state_root, &keypair)
             .unwrap();
 
         let validator_sig = ValidatorSignature {
@@ -429,9 +848,10 @@ mod tests {
         assert!(manager.get_latest_checkpoint().await.is_none());
 
         let block1 = create_test_block(100, "hash100");
+        let state_root = manager.checkpoint_state_root(&block1).await;
         let keypair = KeyPair::generate().unwrap();
         let sig = manager
-            .sign_checkpoint(100, "hash100", 10, "merkle_root", &keypair)
+            .sign_checkpoint(100, "hash100", 10, &state_root, &keypair)
             .unwrap();
 
         let validator_sig = ValidatorSignature {
@@ -461,9 +881,10 @@ mod tests {
 
         // Create a checkpoint
         let block = create_test_block(100, "hash100");
+        let state_root = manager.checkpoint_state_root(&block).await;
         let keypair = KeyPair::generate().unwrap();
         let sig = manager
-            .sign_checkpoint(100, "hash100", 10, "merkle_root", &keypair)
+            .sign_checkpoint(100, "hash100", 10, &state_root, &keypair)
             .unwrap();
 
         let validator_sig = ValidatorSignature {
@@ -0,0 +1,153 @@
+//! Time sources for time-dependent consensus logic
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of wall-clock time in Unix milliseconds
+pub trait Clock: Send + Sync {
+    /// Current time in Unix milliseconds
+    fn now_millis(&self) -> u64;
+}
+
+/// Latest time observed from the operating system, shared by every [`SystemClock`]
+static LAST_SYSTEM_MILLIS: AtomicU64 = AtomicU64::new(0);
+
+/// Return `now`, or the latest time recorded in `last` if the source stepped backwards
+fn clamp_to_last(last: &AtomicU64, now: u64) -> u64 {
+    last.fetch_max(now, Ordering::SeqCst).max(now)
+}
+
+/// Clock backed by the operating system time
+///
+/// Never goes backwards: if the system clock steps back (e.g. an NTP correction) the
+/// last observed time is returned until the system clock catches up.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0);
+        clamp_to_last(&LAST_SYSTEM_MILLIS, now)
+    }
+}
+
+/// Wraps a clock so that it never goes backwards
+///
+/// Readings earlier than the latest one are clamped to it instead of being returned.
+pub struct MonotonicClock {
+    inner: Arc<dyn Clock>,
+    last: AtomicU64,
+}
+
+impl MonotonicClock {
+    /// Wrap a clock
+    pub fn new(inner: Arc<dyn Clock>) -> Self {
+        Self {
+            inner,
+            last: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn now_millis(&self) -> u64 {
+        clamp_to_last(&self.last, self.inner.now_millis())
+    }
+}
+
+/// Manually controlled clock for deterministic tests
+///
+/// Time only moves when [`set`](Self::set) or [`advance`](Self::advance) is called,
+/// so TTLs and staleness windows can be exercised without sleeping.
+#[derive(Debug, Default)]
+pub struct MockClock {
+    now: AtomicU64,
+}
+
+impl MockClock {
+    /// Create a mock clock starting at the given Unix millisecond timestamp
+    pub fn new(start_millis: u64) -> Self {
+        Self {
+            now: AtomicU64::new(start_millis),
+        }
+    }
+
+    /// Set the current time
+    pub fn set(&self, millis: u64) {
+        self.now.store(millis, Ordering::SeqCst);
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, millis: u64) {
+        self.now.fetch_add(millis, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_millis(&self) -> u64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_advance() {
+        let clock = MockClock::new(1_000);
+        assert_eq!(clock.now_millis(), 1_000);
+
+        clock.advance(500);
+        assert_eq!(clock.now_millis(), 1_500);
+
+        clock.set(42);
+        assert_eq!(clock.now_millis(), 42);
+    }
+
+    #[test]
+    fn test_monotonic_clock_survives_backward_step() {
+        let source = Arc::new(MockClock::new(10_000));
+        let clock = MonotonicClock::new(source.clone());
+        assert_eq!(clock.now_millis(), 10_000);
+
+        // NTP-style correction stepping back by five seconds
+        source.set(5_000);
+        assert_eq!(clock.now_millis(), 10_000);
+
+        let block = crate::types::Block::new_with_clock(
+            1,
+            1,
+            "0".repeat(64),
+            "validator-1".to_string(),
+            80,
+            vec![],
+            &clock,
+        );
+        let tx = crate::types::Transaction::new_with_clock(
+            "alice".to_string(),
+            "bob".to_string(),
+            1,
+            0,
+            &clock,
+        );
+        assert_eq!(block.header.timestamp, 10_000);
+        assert_eq!(tx.timestamp, 10_000);
+
+        source.set(12_000);
+        assert_eq!(clock.now_millis(), 12_000);
+    }
+
+    #[test]
+    fn test_system_clock_is_monotonic_enough() {
+        let clock = SystemClock;
+        let first = clock.now_millis();
+        let second = clock.now_millis();
+        assert!(second >= first);
+        assert!(first > 1_600_000_000_000);
+    }
+}
@@ -0,0 +1,79 @@
+//! Detection of validators reporting suspiciously similar emotional scores
+//!
+//! Independent people do not share a score trajectory. Validators whose recent scores
+//! move in near lockstep are likely fed by one spoofed source, so they are flagged for
+//! investigation; nothing is slashed automatically.
+
+use crate::utils::calculate_correlation;
+use serde::{Deserialize, Serialize};
+
+/// Window and correlation above which a pair of validators is flagged
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CollusionDetection {
+    /// Number of most recent scores compared
+    pub window: usize,
+    /// Pearson correlation at or above which a pair is flagged (0.0 - 1.0)
+    pub correlation_threshold: f64,
+}
+
+impl CollusionDetection {
+    /// Whether the window can hold a correlation and the threshold is a valid one
+    pub fn is_valid(&self) -> bool {
+        self.window >= 3 && self.correlation_threshold > 0.0 && self.correlation_threshold <= 1.0
+    }
+
+    /// Pairs of validators whose score series correlate at or above the threshold
+    ///
+    /// `series` holds each validator's recent scores, oldest first. Validators with
+    /// fewer than `window` scores are skipped. Identical windows are treated as perfectly
+    /// correlated, including constant ones that have no variance to correlate.
+    pub fn find_suspects(&self, series: &[(String, Vec<f64>)]) -> Vec<CollusionSuspect> {
+        let windows: Vec<(&str, &[f64])> = series
+            .iter()
+            .filter(|(_, scores)| scores.len() >= self.window)
+            .map(|(id, scores)| (id.as_str(), &scores[scores.len() - self.window..]))
+            .collect();
+
+        let mut suspects = Vec::new();
+        for (i, (first, first_scores)) in windows.iter().enumerate() {
+            for (second, second_scores) in &windows[i + 1..] {
+                let correlation = if first_scores == second_scores {
+                    1.0
+                } else {
+                    calculate_correlation(first_scores, second_scores)
+                };
+                if correlation >= self.correlation_threshold {
+                    suspects.push(CollusionSuspect::new(first, second, correlation));
+                }
+            }
+        }
+        suspects
+    }
+}
+
+/// Two validators whose recent scores correlate suspiciously closely
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollusionSuspect {
+    /// Validator ID that sorts first
+    pub first: String,
+    /// Validator ID that sorts second
+    pub second: String,
+    /// Pearson correlation of their recent scores
+    pub correlation: f64,
+}
+
+impl CollusionSuspect {
+    fn new(a: &str, b: &str, correlation: f64) -> Self {
+        let (first, second) = if a <= b { (a, b) } else { (b, a) };
+        Self {
+            first: first.to_string(),
+            second: second.to_string(),
+            correlation,
+        }
+    }
+
+    /// Whether the pair is the same as another suspect's
+    pub fn same_pair(&self, other: &CollusionSuspect) -> bool {
+        self.first == other.first && self.second == other.second
+    }
+}
@@ -0,0 +1,945 @@
+//! Verifiable committee selection
+//!
+//! Selection is a pure function of the eligible candidates, their weights and a seed
+//! derived from the epoch and the [randomness beacon](crate::beacon) value, or the
+//! previous block hash for proofs without one. Finalized blocks carry a
+//! [`CommitteeProof`] so anyone holding the eligible validator set can recompute the
+//! committee.
+//!
+//! The proposer is the committee's first member. Under
+//! [`ProposerPolicy::StakeWeightedRandom`] it is drawn from the committee by stake with
+//! a separate seed and moved to the front.
+
+use crate::byzantine::DoubleSigningEvidence;
+use crate::consensus::SelectionMode;
+use crate::error::{ConsensusError, Result};
+use crate::types::Block;
+use crate::utils::{calculate_stake_weight, StakeWeightFn};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use tracing::warn;
+
+/// An eligible validator and its selection weight
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitteeCandidate {
+    /// Validator ID
+    pub validator_id: String,
+    /// Selection weight (combined score scaled by 1000)
+    pub weight: u64,
+}
+
+impl CommitteeCandidate {
+    /// Create a candidate
+    pub fn new(validator_id: impl Into<String>, weight: u64) -> Self {
+        Self {
+            validator_id: validator_id.into(),
+            weight,
+        }
+    }
+}
+
+/// Reduced selection weight for validators that proposed recently
+///
+/// Spreads block production: under top-k selection the highest-weighted validator
+/// would otherwise lead every committee and propose every block.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProposerCooldown {
+    /// Number of epochs after proposing during which the weight stays reduced
+    pub epochs: u64,
+    /// Factor the selection weight is multiplied by during the cooldown (0.0 - 1.0)
+    pub weight_factor: f64,
+}
+
+impl ProposerCooldown {
+    /// Whether the cooldown lasts at least an epoch and the factor is a valid one
+    pub fn is_valid(&self) -> bool {
+        self.epochs > 0 && (0.0..=1.0).contains(&self.weight_factor)
+    }
+
+    /// Whether a validator that last proposed in `last_proposed` is cooling down in `epoch`
+    pub fn is_cooling_down(&self, last_proposed: u64, epoch: u64) -> bool {
+        last_proposed < epoch && epoch - last_proposed <= self.epochs
+    }
+
+    /// Scale down the weight of the candidates in `recent_proposers`
+    pub fn apply(&self, candidates: &mut [CommitteeCandidate], recent_proposers: &[String]) {
+        for candidate in candidates {
+            if recent_proposers.contains(&candidate.validator_id) {
+                candidate.weight = (candidate.weight as f64 * self.weight_factor) as u64;
+            }
+        }
+    }
+}
+
+/// Smallest proposer weight, so every committee member can be drawn as proposer
+pub const MIN_PROPOSER_WEIGHT: f64 = 1.0;
+
+/// Strategy for choosing the block proposer within the selected committee
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposerPolicy {
+    /// The first selected member proposes
+    #[default]
+    Primary,
+    /// Draw the proposer with probability proportional to its stake weight
+    ///
+    /// Weights are floored at [`MIN_PROPOSER_WEIGHT`] so small validators still
+    /// propose occasionally.
+    StakeWeightedRandom,
+}
+
+/// Which committee members may propose blocks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposerEligibility {
+    /// Every committee member may propose
+    #[default]
+    AllMembers,
+    /// Only the given number of members with the highest reputation may propose; the
+    /// rest only vote
+    TopByReputation(usize),
+}
+
+impl ProposerEligibility {
+    /// Members allowed to propose, given in selection order with their reputation
+    ///
+    /// Returns an empty list when every member may propose. Ties in reputation are
+    /// broken by selection order.
+    pub fn eligible_proposers(&self, members: &[(String, u8)]) -> Vec<String> {
+        match *self {
+            Self::AllMembers => Vec::new(),
+            Self::TopByReputation(count) => {
+                let mut ranked: Vec<_> = members.iter().collect();
+                ranked.sort_by_key(|(_, reputation)| std::cmp::Reverse(*reputation));
+                let allowed: Vec<&String> = ranked.iter().take(count).map(|(id, _)| id).collect();
+                members
+                    .iter()
+                    .filter(|(id, _)| allowed.contains(&id))
+                    .map(|(id, _)| id.clone())
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Evidence that a block's committee was selected correctly
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitteeProof {
+    /// Epoch the committee was selected for
+    pub epoch: u64,
+    /// Chain tip the committee was selected on
+    pub previous_hash: String,
+    /// Selection strategy in effect
+    pub selection_mode: SelectionMode,
+    /// Configured maximum committee size
+    pub committee_size: usize,
+    /// Merkle root over the eligible candidates
+    pub eligible_root: String,
+    /// Selected validator IDs; the first member proposes the block
+    pub members: Vec<String>,
+    /// How the proposer was chosen from the members
+    #[serde(default)]
+    pub proposer_policy: ProposerPolicy,
+    /// Stake of each member in `members` order when a stake-weighted proposer was drawn
+    ///
+    /// Informational; verifiers redraw the proposer from their own stake ledger.
+    #[serde(default)]
+    pub member_stakes: Vec<u64>,
+    /// Curve the member stakes were weighted by when drawing the proposer
+    #[serde(default)]
+    pub stake_weight_fn: StakeWeightFn,
+    /// Beacon value (hex) the selection was seeded with; empty if seeded from
+    /// `previous_hash`
+    #[serde(default)]
+    pub randomness: String,
+    /// Members carried over from the previous committee, selected ahead of the rest
+    #[serde(default)]
+    pub retained: Vec<String>,
+    /// Members allowed to propose; empty if every member may
+    #[serde(default)]
+    pub proposers: Vec<String>,
+    /// Proposers passed over after being caught double-signing, in order
+    #[serde(default)]
+    pub replaced_proposers: Vec<String>,
+    /// Evidence against each proposer in `replaced_proposers`, in the same order
+    #[serde(default)]
+    pub double_signing_evidence: Vec<DoubleSigningEvidence>,
+    /// Eligible validators whose weight was reduced by the proposer cooldown, by ID
+    #[serde(default)]
+    pub cooled_down: Vec<String>,
+}
+
+impl CommitteeProof {
+    /// Record a committee selected from `candidates`
+    pub fn new(
+        epoch: u64,
+        previous_hash: String,
+        selection_mode: SelectionMode,
+        committee_size: usize,
+        candidates: &[CommitteeCandidate],
+        members: Vec<String>,
+    ) -> Self {
+        Self {
+            epoch,
+            previous_hash,
+            selection_mode,
+            committee_size,
+            eligible_root: eligible_root(candidates),
+            members,
+            proposer_policy: ProposerPolicy::Primary,
+            member_stakes: Vec::new(),
+            stake_weight_fn: StakeWeightFn::default(),
+            randomness: String::new(),
+            retained: Vec::new(),
+            proposers: Vec::new(),
+            replaced_proposers: Vec::new(),
+            double_signing_evidence: Vec::new(),
+            cooled_down: Vec::new(),
+        }
+    }
+
+    /// Record the beacon value (hex) the selection was seeded with
+    pub fn with_randomness(mut self, randomness: String) -> Self {
+        self.randomness = randomness;
+        self
+    }
+
+    /// Record the members carried over from the previous committee
+    pub fn with_retained(mut self, retained: Vec<String>) -> Self {
+        self.retained = retained;
+        self
+    }
+
+    /// Record the members allowed to propose
+    pub fn with_proposers(mut self, proposers: Vec<String>) -> Self {
+        self.proposers = proposers;
+        self
+    }
+
+    /// Record the eligible validators cooled down as recent proposers
+    pub fn with_cooled_down(mut self, cooled_down: Vec<String>) -> Self {
+        self.cooled_down = cooled_down;
+        self
+    }
+
+    /// Pass over the proposer the evidence convicts of double-signing
+    pub fn replace_proposer(&mut self, evidence: DoubleSigningEvidence) {
+        self.replaced_proposers
+            .push(evidence.first.header.validator_id.clone());
+        self.double_signing_evidence.push(evidence);
+    }
+
+    /// Whether a member may propose: it is allowed to and has not been replaced
+    pub fn may_propose(&self, validator_id: &str) -> bool {
+        let allowed =
+            self.proposers.is_empty() || self.proposers.iter().any(|id| id == validator_id);
+        allowed && !self.replaced_proposers.iter().any(|id| id == validator_id)
+    }
+
+    /// Input the selection and proposer seeds were derived from
+    pub fn seed_source(&self) -> &str {
+        if self.randomness.is_empty() {
+            &self.previous_hash
+        } else {
+            &self.randomness
+        }
+    }
+
+    /// Record how the proposer was chosen, with the stake of each member and the curve
+    /// they were weighted by
+    pub fn with_proposer_policy(
+        mut self,
+        proposer_policy: ProposerPolicy,
+        member_stakes: Vec<u64>,
+        stake_weight_fn: StakeWeightFn,
+    ) -> Self {
+        self.proposer_policy = proposer_policy;
+        self.member_stakes = member_stakes;
+        self.stake_weight_fn = stake_weight_fn;
+        self
+    }
+}
+
+/// A verifier's own view of the chain, which committee proofs are checked against
+///
+/// Everything here comes from the verifier's ledger and configuration rather than
+/// from the proof, so a proposer cannot vouch for its own committee.
+#[derive(Debug, Clone, Default)]
+pub struct VerificationContext {
+    /// Members of the committee that finalized the previous block
+    pub previous_committee: Vec<String>,
+    /// Configured fraction of the previous committee carried into the next
+    pub committee_overlap_fraction: f64,
+    /// Stake of each validator as of the block's epoch, by validator ID
+    pub stakes: HashMap<String, u64>,
+    /// The verifier's own beacon value (hex) for the block's epoch; empty if selection
+    /// is seeded from the previous block hash
+    pub randomness: String,
+    /// Reputation of each validator as of the block's epoch, by validator ID
+    pub reputations: HashMap<String, u8>,
+    /// Configured rule for which committee members may propose
+    pub proposer_eligibility: ProposerEligibility,
+    /// Public key (hex) of each validator, by validator ID
+    pub public_keys: HashMap<String, String>,
+    /// Configured curve stakes are weighted by
+    pub stake_weight_fn: StakeWeightFn,
+    /// Validators the verifier's chain shows still cooling down after proposing
+    pub recent_proposers: Vec<String>,
+}
+
+/// Seed for committee sampling: `SHA-256(epoch || source)`
+///
+/// `source` is the hex beacon value, or the previous block hash for proofs without one.
+pub fn selection_seed(epoch: u64, source: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(epoch.to_le_bytes());
+    hasher.update(source.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Seed for proposer sampling: `SHA-256("proposer" || epoch || source)`
+///
+/// Kept apart from [`selection_seed`] so the proposer draw is independent of the
+/// committee sample.
+pub fn proposer_seed(epoch: u64, source: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"proposer");
+    hasher.update(epoch.to_le_bytes());
+    hasher.update(source.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Draw a proposer from committee members given in selection order with their stake
+///
+/// Returns the index of the drawn member.
+pub fn select_proposer(
+    members: &[(String, u64)],
+    weight_fn: StakeWeightFn,
+    seed: [u8; 32],
+) -> Result<usize> {
+    let mut rng = StdRng::from_seed(seed);
+    let weights: Vec<f64> = members
+        .iter()
+        .map(|(_, stake)| calculate_stake_weight(*stake, weight_fn).max(MIN_PROPOSER_WEIGHT))
+        .collect();
+    let index = WeightedIndex::new(&weights).map_err(|e| {
+        ConsensusError::committee_selection_failed(format!("Proposer sampling failed: {}", e))
+    })?;
+    Ok(index.sample(&mut rng))
+}
+
+/// Choose the proposer among members given in selection order
+///
+/// Only members in `proposers` are considered unless it is empty. Under
+/// [`ProposerPolicy::Primary`] the first of them proposes; under
+/// [`ProposerPolicy::StakeWeightedRandom`] one is drawn using `stakes`, which holds the
+/// stake of each member in `members` order, weighted by `weight_fn`. Returns the index
+/// into `members`.
+pub fn draw_proposer(
+    members: &[String],
+    stakes: &[u64],
+    proposers: &[String],
+    policy: ProposerPolicy,
+    weight_fn: StakeWeightFn,
+    seed: [u8; 32],
+) -> Result<usize> {
+    let eligible: Vec<usize> = (0..members.len())
+        .filter(|&i| proposers.is_empty() || proposers.contains(&members[i]))
+        .collect();
+    if eligible.is_empty() {
+        return Err(ConsensusError::committee_selection_failed(
+            "No committee member is eligible to propose",
+        ));
+    }
+
+    match policy {
+        ProposerPolicy::Primary => Ok(eligible[0]),
+        ProposerPolicy::StakeWeightedRandom => {
+            let staked: Vec<(String, u64)> = eligible
+                .iter()
+                .map(|&i| (members[i].clone(), stakes.get(i).copied().unwrap_or(0)))
+                .collect();
+            Ok(eligible[select_proposer(&staked, weight_fn, seed)?])
+        }
+    }
+}
+
+/// Move the drawn proposer to the front, keeping the order of the other members
+pub fn promote_proposer<T>(members: &mut Vec<T>, index: usize) {
+    let proposer = members.remove(index);
+    members.insert(0, proposer);
+}
+
+/// Merkle root over the candidates, independent of their order
+pub fn eligible_root(candidates: &[CommitteeCandidate]) -> String {
+    if candidates.is_empty() {
+        return hex::encode(Sha256::digest(b"empty"));
+    }
+
+    let mut hashes: Vec<Vec<u8>> = sorted_candidates(candidates)
+        .iter()
+        .map(|candidate| {
+            let mut hasher = Sha256::new();
+            hasher.update((candidate.validator_id.len() as u64).to_le_bytes());
+            hasher.update(candidate.validator_id.as_bytes());
+            hasher.update(candidate.weight.to_le_bytes());
+            hasher.finalize().to_vec()
+        })
+        .collect();
+
+    while hashes.len() > 1 {
+        let mut next_level = Vec::new();
+
+        for chunk in hashes.chunks(2) {
+            let mut hasher = Sha256::new();
+            hasher.update(&chunk[0]);
+            if chunk.len() > 1 {
+                hasher.update(&chunk[1]);
+            } else {
+                hasher.update(&chunk[0]);
+            }
+            next_level.push(hasher.finalize().to_vec());
+        }
+
+        hashes = next_level;
+    }
+
+    hex::encode(&hashes[0])
+}
+
+/// Select up to `committee_size` validator IDs from the candidates
+///
+/// Candidates are ordered by ID first, so the result does not depend on the order the
+/// caller collected them in. If every candidate fits, all are selected.
+pub fn select_committee(
+    candidates: &[CommitteeCandidate],
+    committee_size: usize,
+    selection_mode: SelectionMode,
+    seed: [u8; 32],
+) -> Result<Vec<String>> {
+    let candidates = sorted_candidates(candidates);
+    if candidates.len() <= committee_size {
+        return Ok(candidates
+            .iter()
+            .map(|candidate| candidate.validator_id.clone())
+            .collect());
+    }
+
+    match selection_mode {
+        SelectionMode::TopK => Ok(select_top_k(&candidates, committee_size)),
+        SelectionMode::WeightedRandom => select_weighted_random(&candidates, committee_size, seed),
+    }
+}
+
+/// Previous committee members to carry into the next committee
+///
+/// Keeps `ceil(overlap_fraction * previous.len())` of the previous members that are
+/// still candidates, highest weight first with ties broken by ID, capped at
+/// `committee_size`. Fewer are kept if not enough previous members remain eligible.
+pub fn retained_members(
+    candidates: &[CommitteeCandidate],
+    previous: &[String],
+    overlap_fraction: f64,
+    committee_size: usize,
+) -> Vec<String> {
+    let target = (overlap_fraction * previous.len() as f64).ceil() as usize;
+
+    let mut carried: Vec<&CommitteeCandidate> = candidates
+        .iter()
+        .filter(|candidate| previous.contains(&candidate.validator_id))
+        .collect();
+    carried.sort_by(|a, b| {
+        b.weight
+            .cmp(&a.weight)
+            .then_with(|| a.validator_id.cmp(&b.validator_id))
+    });
+
+    carried
+        .into_iter()
+        .take(target.min(committee_size))
+        .map(|candidate| candidate.validator_id.clone())
+        .collect()
+}
+
+/// Select a committee that starts with the `retained` members
+///
+/// The remaining seats are filled by [`select_committee`] over the other candidates.
+pub fn select_committee_with_retained(
+    candidates: &[CommitteeCandidate],
+    committee_size: usize,
+    selection_mode: SelectionMode,
+    seed: [u8; 32],
+    retained: &[String],
+) -> Result<Vec<String>> {
+    if retained.is_empty() {
+        return select_committee(candidates, committee_size, selection_mode, seed);
+    }
+
+    let remaining: Vec<CommitteeCandidate> = candidates
+        .iter()
+        .filter(|candidate| !retained.contains(&candidate.validator_id))
+        .cloned()
+        .collect();
+    let open_seats = committee_size.saturating_sub(retained.len());
+
+    let mut members = retained.to_vec();
+    if open_seats > 0 {
+        members.extend(select_committee(
+            &remaining,
+            open_seats,
+            selection_mode,
+            seed,
+        )?);
+    }
+    Ok(members)
+}
+
+/// Probability of each candidate joining a committee of `committee_size`
+///
+/// Retained members are always selected. The open seats are exact under
+/// [`SelectionMode::TopK`]; under [`SelectionMode::WeightedRandom`] each candidate
+/// gets a share of them proportional to its weight, capped at 1 with the excess spread
+/// over the others, which approximates sampling without replacement. The result is
+/// ordered by validator ID and its probabilities sum to the committee size, or the
+/// candidate count if smaller.
+pub fn selection_probabilities(
+    candidates: &[CommitteeCandidate],
+    committee_size: usize,
+    selection_mode: SelectionMode,
+    retained: &[String],
+) -> Vec<(String, f64)> {
+    let remaining: Vec<&CommitteeCandidate> = sorted_candidates(candidates)
+        .into_iter()
+        .filter(|candidate| !retained.contains(&candidate.validator_id))
+        .collect();
+    let open_seats = committee_size.saturating_sub(retained.len());
+
+    let probabilities: Vec<f64> = if remaining.len() <= open_seats {
+        vec![1.0; remaining.len()]
+    } else {
+        match selection_mode {
+            SelectionMode::TopK => {
+                let top = select_top_k(&remaining, open_seats);
+                remaining
+                    .iter()
+                    .map(|candidate| {
+                        if top.contains(&candidate.validator_id) {
+                            1.0
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect()
+            }
+            SelectionMode::WeightedRandom => proportional_inclusion(&remaining, open_seats),
+        }
+    };
+
+    let mut result: Vec<(String, f64)> = sorted_candidates(candidates)
+        .into_iter()
+        .filter(|candidate| retained.contains(&candidate.validator_id))
+        .map(|candidate| (candidate.validator_id.clone(), 1.0))
+        .collect();
+    result.extend(
+        remaining
+            .iter()
+            .map(|candidate| candidate.validator_id.clone())
+            .zip(probabilities),
+    );
+    result.sort_by(|a, b| a.0.cmp(&b.0));
+    result
+}
+
+/// Check a finalized block's committee proof against the eligible validator set
+///
+/// Returns `Ok(false)` if the proof does not match the block or the committee
+/// recomputed from `validator_set` and `context`, and an error if the block carries no
+/// proof. The retained members are recomputed from the previous committee in
+/// `context`, a stake-weighted proposer is redrawn from the stakes in it, the members
+/// allowed to propose are recomputed from the reputations in it, and the beacon value
+/// the selection was seeded with, the stake weighting curve and the validators cooled
+/// down as recent proposers must be the ones in it. Every replaced proposer must be
+/// convicted by double-signing evidence signed with its key in `context`. The weights
+/// in `validator_set` are expected to already reflect the cooldown.
+pub fn verify_committee_proof(
+    block: &Block,
+    validator_set: &[CommitteeCandidate],
+    context: &VerificationContext,
+) -> Result<bool> {
+    let proof = block
+        .consensus_metadata
+        .as_ref()
+        .and_then(|metadata| metadata.committee_proof.as_ref())
+        .ok_or_else(|| ConsensusError::invalid_block("Block has no committee proof"))?;
+
+    if proof.epoch != block.header.epoch || proof.previous_hash != block.header.previous_hash {
+        warn!(
+            "Committee proof for block {} was made for a different seed",
+            block.header.height
+        );
+        return Ok(false);
+    }
+
+    if proof.stake_weight_fn != context.stake_weight_fn {
+        warn!(
+            "Committee proof for block {} weighs stake by another curve",
+            block.header.height
+        );
+        return Ok(false);
+    }
+
+    if proof.randomness != context.randomness {
+        warn!(
+            "Committee proof for block {} was seeded with another beacon value",
+            block.header.height
+        );
+        return Ok(false);
+    }
+
+    let mut cooled_down: Vec<&String> = context
+        .recent_proposers
+        .iter()
+        .filter(|id| {
+            validator_set
+                .iter()
+                .any(|candidate| &candidate.validator_id == *id)
+        })
+        .collect();
+    cooled_down.sort();
+    cooled_down.dedup();
+    if !proof.cooled_down.iter().eq(cooled_down) {
+        warn!(
+            "Committee proof for block {} cools down other validators",
+            block.header.height
+        );
+        return Ok(false);
+    }
+
+    if proof.eligible_root != eligible_root(validator_set) {
+        warn!(
+            "Committee proof for block {} does not match the eligible set",
+            block.header.height
+        );
+        return Ok(false);
+    }
+
+    let retained = retained_members(
+        validator_set,
+        &context.previous_committee,
+        context.committee_overlap_fraction,
+        proof.committee_size,
+    );
+    if proof.retained != retained {
+        warn!(
+            "Committee proof for block {} does not retain the previous committee's members",
+            block.header.height
+        );
+        return Ok(false);
+    }
+
+    let mut members = select_committee_with_retained(
+        validator_set,
+        proof.committee_size,
+        proof.selection_mode,
+        selection_seed(proof.epoch, proof.seed_source()),
+        &proof.retained,
+    )?;
+
+    let reputations: Option<Vec<(String, u8)>> = match context.proposer_eligibility {
+        ProposerEligibility::AllMembers => Some(Vec::new()),
+        ProposerEligibility::TopByReputation(_) => members
+            .iter()
+            .map(|id| Some((id.clone(), *context.reputations.get(id)?)))
+            .collect(),
+    };
+    let Some(reputations) = reputations else {
+        warn!(
+            "No reputation is known for the members of block {}'s committee",
+            block.header.height
+        );
+        return Ok(false);
+    };
+    let proposers = context
+        .proposer_eligibility
+        .eligible_proposers(&reputations);
+    if proof.proposers != proposers {
+        warn!(
+            "Committee proof for block {} allows other members to propose",
+            block.header.height
+        );
+        return Ok(false);
+    }
+    let stakes = match proof.proposer_policy {
+        ProposerPolicy::Primary => Vec::new(),
+        ProposerPolicy::StakeWeightedRandom => {
+            let stakes: Option<Vec<u64>> = members
+                .iter()
+                .map(|id| context.stakes.get(id).copied())
+                .collect();
+            let Some(stakes) = stakes else {
+                warn!(
+                    "No stake is known for the members of block {}'s committee",
+                    block.header.height
+                );
+                return Ok(false);
+            };
+            stakes
+        }
+    };
+    let Ok(index) = draw_proposer(
+        &members,
+        &stakes,
+        &proposers,
+        proof.proposer_policy,
+        context.stake_weight_fn,
+        proposer_seed(proof.epoch, proof.seed_source()),
+    ) else {
+        warn!(
+            "Committee proof for block {} has no eligible proposer",
+            block.header.height
+        );
+        return Ok(false);
+    };
+    promote_proposer(&mut members, index);
+    if members != proof.members {
+        warn!(
+            "Committee proof for block {} claims members that were not selected",
+            block.header.height
+        );
+        return Ok(false);
+    }
+
+    let replacements_proven = proof.replaced_proposers.len() == proof.double_signing_evidence.len()
+        && proof
+            .replaced_proposers
+            .iter()
+            .zip(&proof.double_signing_evidence)
+            .all(|(id, evidence)| {
+                context
+                    .public_keys
+                    .get(id)
+                    .is_some_and(|key| evidence.verify(id, block.header.height, key))
+            });
+    if !replacements_proven {
+        warn!(
+            "Committee proof for block {} replaces proposers without evidence of double signing",
+            block.header.height
+        );
+        return Ok(false);
+    }
+
+    // The primary proposes unless it was replaced, then the next member allowed to
+    let proposer = members.iter().find(|id| proof.may_propose(id));
+    if proposer != Some(&block.header.validator_id) {
+        warn!(
+            "Block {} was not proposed by the committee primary or its replacement",
+            block.header.height
+        );
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+fn sorted_candidates(candidates: &[CommitteeCandidate]) -> Vec<&CommitteeCandidate> {
+    let mut sorted: Vec<_> = candidates.iter().collect();
+    sorted.sort_by(|a, b| a.validator_id.cmp(&b.validator_id));
+    sorted
+}
+
+/// Select the k highest-weighted candidates (optimized with BinaryHeap)
+///
+/// Uses a min-heap to keep the top k candidates, so complexity is O(n log k).
+/// Ties are broken by validator ID and the result is ordered by descending weight.
+fn select_top_k(candidates: &[&CommitteeCandidate], committee_size: usize) -> Vec<String> {
+    // Helper struct for ordering candidates by weight in a heap
+    struct OrderedCandidate<'a>(&'a CommitteeCandidate);
+
+    impl PartialEq for OrderedCandidate<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.cmp(other) == Ordering::Equal
+        }
+    }
+
+    impl Eq for OrderedCandidate<'_> {}
+
+    impl PartialOrd for OrderedCandidate<'_> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for OrderedCandidate<'_> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            // Reverse ordering so the heap pops the lowest weight first
+            other
+                .0
+                .weight
+                .cmp(&self.0.weight)
+                .then_with(|| self.0.validator_id.cmp(&other.0.validator_id))
+        }
+    }
+
+    let mut heap = BinaryHeap::with_capacity(committee_size + 1);
+
+    for candidate in candidates {
+        heap.push(OrderedCandidate(candidate));
+
+        // Keep heap size bounded to committee_size
+        if heap.len() > committee_size {
+            heap.pop();
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|ordered| ordered.0.validator_id.clone())
+        .collect()
+}
+
+/// Sample k candidates without replacement, weighted by their weight
+fn select_weighted_random(
+    candidates: &[&CommitteeCandidate],
+    committee_size: usize,
+    seed: [u8; 32],
+) -> Result<Vec<String>> {
+    let mut rng = StdRng::from_seed(seed);
+
+    // Keep candidates with a zero weight selectable, just very unlikely
+    let members = candidates
+        .choose_multiple_weighted(&mut rng, committee_size, |candidate| {
+            (candidate.weight as f64).max(f64::MIN_POSITIVE)
+        })
+        .map_err(|e| {
+            ConsensusError::committee_selection_failed(format!("Weighted sampling failed: {}", e))
+        })?
+        .map(|candidate| candidate.validator_id.clone())
+        .collect();
+
+    Ok(members)
+}
+
+/// Weight-proportional shares of `seats`, capped at 1 per candidate
+///
+/// Candidates whose share reaches 1 are certain to be selected; their seats are
+/// removed and the rest are shared again until no share exceeds 1.
+fn proportional_inclusion(candidates: &[&CommitteeCandidate], seats: usize) -> Vec<f64> {
+    // Zero weights stay selectable, matching the sampler
+    let weights: Vec<f64> = candidates
+        .iter()
+        .map(|candidate| (candidate.weight as f64).max(f64::MIN_POSITIVE))
+        .collect();
+    let mut certain = vec![false; weights.len()];
+    let mut probabilities = vec![0.0; weights.len()];
+
+    loop {
+        let seats_left = seats - certain.iter().filter(|&&c| c).count();
+        let total: f64 = weights
+            .iter()
+            .zip(&certain)
+            .filter(|(_, &c)| !c)
+            .map(|(weight, _)| weight)
+            .sum();
+
+        let mut capped = false;
+        for (i, weight) in weights.iter().enumerate() {
+            if certain[i] {
+                continue;
+            }
+            let share = seats_left as f64 * weight / total;
+            if share >= 1.0 {
+                certain[i] = true;
+                capped = true;
+            } else {
+                probabilities[i] = share;
+            }
+        }
+
+        if !capped {
+            break;
+        }
+    }
+
+    for (probability, &c) in probabilities.iter_mut().zip(&certain) {
+        if c {
+            *probability = 1.0;
+        }
+    }
+    probabilities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stake_weighted_proposer_frequency() {
+        let members = vec![
+            ("small".to_string(), 10_000),
+            ("medium".to_string(), 90_000),
+            ("large".to_string(), 1_000_000),
+        ];
+
+        let mut proposals = [0usize; 3];
+        for epoch in 0..3_000 {
+            let seed = proposer_seed(epoch, &"0".repeat(64));
+            proposals[select_proposer(&members, StakeWeightFn::Sqrt, seed).unwrap()] += 1;
+        }
+
+        // sqrt weights 100 : 300 : 1000
+        assert!(proposals[0] < proposals[1]);
+        assert!(proposals[1] < proposals[2]);
+        assert!(proposals[0] > 0);
+
+        let zero_stake = vec![("zero".to_string(), 0), ("large".to_string(), 10_000)];
+        let zero_proposals = (0..5_000)
+            .filter(|epoch| {
+                let seed = proposer_seed(*epoch, "");
+                select_proposer(&zero_stake, StakeWeightFn::Sqrt, seed).unwrap() == 0
+            })
+            .count();
+        assert!(zero_proposals > 0);
+    }
+
+    #[test]
+    fn test_retained_members_prefer_highest_weight() {
+        let candidates = vec![
+            CommitteeCandidate::new("a", 10),
+            CommitteeCandidate::new("b", 30),
+            CommitteeCandidate::new("c", 20),
+            CommitteeCandidate::new("d", 50),
+        ];
+        let previous: Vec<String> = ["a", "b", "c", "x"].map(String::from).to_vec();
+
+        // ceil(0.5 * 4) = 2 of the previous members that are still candidates
+        let half = retained_members(&candidates, &previous, 0.5, 3);
+        assert_eq!(half, vec!["b", "c"]);
+        let capped = retained_members(&candidates, &previous, 1.0, 2);
+        assert_eq!(capped, vec!["b", "c"]);
+        assert!(retained_members(&candidates, &previous, 0.0, 3).is_empty());
+
+        let retained = vec!["b".to_string()];
+        let members =
+            select_committee_with_retained(&candidates, 2, SelectionMode::TopK, [0; 32], &retained)
+                .unwrap();
+        assert_eq!(members, vec!["b", "d"]);
+    }
+
+    #[test]
+    fn test_proposer_draw_is_deterministic() {
+        let members = vec![("a".to_string(), 10_000), ("b".to_string(), 40_000)];
+        let seed = proposer_seed(7, "abc");
+        assert_eq!(
+            select_proposer(&members, StakeWeightFn::Log, seed).unwrap(),
+            select_proposer(&members, StakeWeightFn::Log, seed).unwrap()
+        );
+
+        let mut ids = vec!["a", "b", "c"];
+        promote_proposer(&mut ids, 2);
+        assert_eq!(ids, vec!["c", "a", "b"]);
+    }
+}
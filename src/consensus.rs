@@ -1,19 +1,51 @@
 //! Main Proof of Emotion consensus engine
 
-use crate::biometric::{BiometricDevice, BiometricSimulator, EmotionalValidator};
-use crate::byzantine::ByzantineDetector;
+use crate::audit::{AuditEntry, AuditHandle, AuditSink};
+use crate::beacon::{HashChainBeacon, RandomnessBeacon};
+use crate::biometric::{
+    BiometricDevice, BiometricReading, BiometricSimulator, EligibilityStatus, EmotionalTrend,
+    EmotionalValidator, IneligibilityReason,
+};
+use crate::bloom::RecentTransactionFilter;
+use crate::bundle::{BundledRound, EpochBundle, DEFAULT_EPOCH_BUNDLE_RETENTION};
+use crate::byzantine::{ByzantineDetector, SlashingPolicy, VoteRecord};
+use crate::clock::{Clock, MonotonicClock, SystemClock};
+use crate::collusion::{CollusionDetection, CollusionSuspect};
+use crate::committee::{
+    self, CommitteeCandidate, CommitteeProof, ProposerCooldown, ProposerEligibility, ProposerPolicy,
+};
+use crate::crypto::KeyPair;
 use crate::error::{ConsensusError, Result};
-use crate::types::{Block, Transaction, Vote, VotingResult};
+use crate::events::{event_channel, ConsensusEvent};
+use crate::metrics::PrometheusMetrics;
+use crate::replay::{EpochRecord, EpochRecorder, RecordedValidator};
+use crate::scoreboard::{ValidatorActivity, ValidatorScore};
+use crate::snapshot::{SnapshotStore, StateSnapshot};
+use crate::staking::{ReputationPenalties, SlashingOffense, SlashingSeverity};
+use crate::state::StateMachine;
+use crate::types::{
+    Block, LightBlock, Transaction, TxStatus, Vote, VoteRejectionReason, VotingResult,
+};
+use crate::utils::{calculate_stake_weight, StakeWeightFn};
+use crate::validator_set::{ValidatorRecord, ValidatorSet};
+use crate::zkp::ZkBiometricProvider;
 use dashmap::DashMap;
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{broadcast, Mutex, RwLock};
 use tokio::time;
 use tracing::{error, info, warn};
 
+/// Maximum number of signature verification results cached per epoch
+const SIGNATURE_CACHE_CAPACITY: usize = 10_000;
+
+/// Maximum number of historical blocks a finalized block stream copies per read
+const BLOCK_STREAM_BATCH: usize = 64;
+
 /// Configuration for consensus engine
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusConfig {
@@ -33,1058 +65,7686 @@ pub struct ConsensusConfig {
     pub proposal_timeout: u64,
     /// Finality timeout in milliseconds
     pub finality_timeout: u64,
+    /// Committee selection strategy
+    #[serde(default)]
+    pub selection_mode: SelectionMode,
+    /// How the proposer is chosen within the committee
+    #[serde(default)]
+    pub proposer_policy: ProposerPolicy,
+    /// Which committee members may propose
+    #[serde(default)]
+    pub proposer_eligibility: ProposerEligibility,
+    /// Weight votes by stake, scaled by each voter's confidence, instead of counting heads
+    #[serde(default)]
+    pub stake_weighted_voting: bool,
+    /// Maximum number of canonical blocks a fork resolution may replace
+    #[serde(default = "default_max_reorg_depth")]
+    pub max_reorg_depth: u64,
+    /// Maximum number of validators assessed concurrently
+    #[serde(default = "default_assessment_concurrency")]
+    pub assessment_concurrency: usize,
+    /// Attempts at reading a biometric device before the validator is skipped
+    #[serde(default = "default_device_read_attempts")]
+    pub device_read_attempts: u32,
+    /// Delay before the first device read retry in milliseconds, doubled per retry
+    #[serde(default = "default_device_retry_backoff_ms")]
+    pub device_retry_backoff_ms: u64,
+    /// Slashes within `slash_window_epochs` that get a validator jailed (0 disables jailing)
+    #[serde(default = "default_max_slashes_in_window")]
+    pub max_slashes_in_window: u32,
+    /// Number of recent epochs in which slashes are counted towards jailing
+    #[serde(default = "default_slash_window_epochs")]
+    pub slash_window_epochs: u64,
+    /// Number of epochs a jailed validator is kept out of committees
+    #[serde(default = "default_jail_epochs")]
+    pub jail_epochs: u64,
+    /// Tune the epoch interval to the measured epoch duration instead of keeping
+    /// `epoch_duration` fixed
+    #[serde(default)]
+    pub adaptive_epoch_duration: Option<AdaptiveEpochDuration>,
+    /// Report a degraded node as unavailable (HTTP 503) to health probes instead of 200
+    #[serde(default)]
+    pub degraded_unavailable: bool,
+    /// Number of recent blocks checked for already-finalized transactions (0 disables)
+    #[serde(default = "default_finalized_tx_filter_blocks")]
+    pub finalized_tx_filter_blocks: usize,
+    /// False-positive rate of the bloom filter consulted before the exact recent set
+    #[serde(default = "default_finalized_tx_filter_false_positive_rate")]
+    pub finalized_tx_filter_false_positive_rate: f64,
+    /// Weighting of the inputs to the network health score
+    #[serde(default)]
+    pub network_health_weights: NetworkHealthWeights,
+    /// Reject empty block proposals while unexpired transactions are pending
+    #[serde(default)]
+    pub require_nonempty_blocks_when_pending: bool,
+    /// Minimum fraction of the previous committee carried into the next one (0 disables)
+    #[serde(default)]
+    pub committee_overlap_fraction: f64,
+    /// Number of blocks proposed and finalized by each epoch's committee
+    #[serde(default = "default_blocks_per_epoch")]
+    pub blocks_per_epoch: usize,
+    /// Consecutive failed epochs at one chain tip before the engine halts (0 disables)
+    #[serde(default)]
+    pub max_tip_retries: u32,
+    /// How emotional score and confidence decide eligibility
+    #[serde(default)]
+    pub eligibility_policy: EligibilityPolicy,
+    /// Consecutive assessments with a declining trend that deactivate a validator
+    /// (0 disables)
+    #[serde(default)]
+    pub max_declining_epochs: u32,
+    /// Flag rounds in which committee members validating the same block disagree
+    #[serde(default)]
+    pub check_validation_determinism: bool,
+    /// Reputation docked from slashed validators, by offense severity; staking built
+    /// with [`EmotionalStaking::from_config`](crate::staking::EmotionalStaking::from_config)
+    /// applies the same penalties
+    #[serde(default)]
+    pub reputation_penalties: ReputationPenalties,
+    /// Let the next committee member propose when the proposer is caught
+    /// double-signing with signed evidence, instead of failing the epoch
+    #[serde(default = "default_replace_byzantine_proposer")]
+    pub replace_byzantine_proposer: bool,
+    /// Thresholds by network size; when set, its tier overrides `byzantine_threshold`
+    /// and `emotional_threshold` for each epoch without changing the configured values
+    #[serde(default)]
+    pub threshold_schedule: Option<ThresholdSchedule>,
+    /// Flag validators whose recent scores move in lockstep (disabled when unset)
+    #[serde(default)]
+    pub collusion_detection: Option<CollusionDetection>,
+    /// Span in milliseconds that the readings behind a score must fall within; also
+    /// the window emotional proofs should be created with
+    #[serde(default = "default_temporal_window_ms")]
+    pub temporal_window_ms: u64,
+    /// Maximum total weight of the transactions in a proposed block
+    #[serde(default = "default_max_block_weight")]
+    pub max_block_weight: u64,
+    /// Curve mapping stake to influence in committee and proposer selection
+    #[serde(default)]
+    pub stake_weight_fn: StakeWeightFn,
+    /// Reduce the selection weight of recent proposers; disabled when unset
+    #[serde(default)]
+    pub proposer_cooldown: Option<ProposerCooldown>,
+    /// Chain the engine proposes and accepts blocks for
+    #[serde(default = "default_chain_id")]
+    pub chain_id: String,
 }
 
-impl Default for ConsensusConfig {
-    fn default() -> Self {
-        Self {
-            epoch_duration: 30_000,
-            emotional_threshold: 75,
-            byzantine_threshold: 67,
-            committee_size: 21,
-            minimum_stake: 10_000,
-            voting_timeout: 8_000,
-            proposal_timeout: 10_000,
-            finality_timeout: 2_000,
-        }
-    }
+/// Bounds and target for tuning the epoch interval to how long epochs actually take
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveEpochDuration {
+    /// Shortest allowed epoch interval in milliseconds
+    pub min_ms: u64,
+    /// Longest allowed epoch interval in milliseconds
+    pub max_ms: u64,
+    /// Share of the interval an epoch should take to execute (0.0 - 1.0)
+    pub target_utilization: f64,
 }
 
-/// Current state of consensus
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ConsensusState {
-    /// Current epoch number
-    pub current_epoch: u64,
-    /// Network health (0-100)
-    pub network_health: u8,
-    /// Current consensus strength (percentage)
-    pub consensus_strength: u8,
-    /// Average emotional fitness
-    pub emotional_fitness: u8,
-    /// Participation rate (percentage)
-    pub participation_rate: u8,
-    /// Last finalized block height
-    pub last_finalized_height: u64,
-    /// Number of pending transactions
-    pub pending_transactions: usize,
-    /// Total number of registered validators
-    pub total_validators: usize,
-    /// Number of currently active validators
-    pub active_validators: usize,
+impl AdaptiveEpochDuration {
+    /// Interval at which an epoch that took `measured_ms` hits the target utilization
+    pub fn next_duration(&self, measured_ms: u64) -> u64 {
+        let ideal = measured_ms as f64 / self.target_utilization;
+        (ideal.round() as u64).clamp(self.min_ms, self.max_ms)
+    }
 }
 
-/// Phase of a consensus round
+/// Thresholds in effect while the validator count is within a range
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum RoundPhase {
-    /// Proposing phase
-    Propose,
-    /// Voting phase
-    Vote,
-    /// Commit phase
-    Commit,
-    /// Finalized
-    Finalized,
-    /// Aborted
-    Aborted,
+pub struct ThresholdTier {
+    /// Smallest validator count the tier applies to
+    pub min_validators: usize,
+    /// Largest validator count the tier applies to; `None` for no upper bound
+    pub max_validators: Option<usize>,
+    /// Byzantine threshold (percentage) for this tier
+    pub byzantine_threshold: u8,
+    /// Emotional threshold for this tier
+    pub emotional_threshold: u8,
 }
 
-/// Consensus round
-pub struct ConsensusRound {
-    /// Round ID
-    pub id: String,
-    /// Current phase
-    pub phase: RwLock<RoundPhase>,
-    /// Proposed block
-    pub proposed_block: Option<Block>,
-    /// Votes collected
-    pub votes: DashMap<String, Vote>,
-    /// Round start time
-    pub start_time: std::time::Instant,
+/// Byzantine and emotional thresholds that vary with the number of validators
+///
+/// Tiers are ordered and cover every validator count from 0 upward without gaps or
+/// overlaps; only the last tier is open-ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThresholdSchedule {
+    /// Tiers in ascending order of validator count
+    pub tiers: Vec<ThresholdTier>,
 }
 
-/// Metrics for consensus performance
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-pub struct ConsensusMetrics {
-    // Existing metrics
-    /// Total epochs processed
-    pub total_epochs: u64,
-    /// Successful epochs
-    pub successful_epochs: u64,
-    /// Failed epochs
-    pub failed_epochs: u64,
-    /// Average epoch duration in milliseconds
-    pub average_duration_ms: u64,
-    /// Average emotional score across all epochs
-    pub average_emotional_score: u8,
-    /// Total Byzantine failures detected
-    pub byzantine_failures: u64,
-
-    // NEW: Detailed metrics
-    /// Total blocks rejected during validation
-    pub rejected_blocks: u64,
-    /// Total votes rejected (invalid signatures, wrong epoch, etc.)
-    pub rejected_votes: u64,
-    /// Number of rounds that timed out
-    pub timeout_rounds: u64,
-    /// Epochs failed due to low emotional fitness
-    pub emotional_failures: u64,
-    /// Detected network partitions (future use)
-    pub network_partitions: u64,
-    /// Fork detections (conflicting blocks at same height)
-    pub fork_detections: u64,
+impl ThresholdSchedule {
+    /// Create a schedule from tiers in ascending order
+    pub fn new(tiers: Vec<ThresholdTier>) -> Self {
+        Self { tiers }
+    }
 
-    // NEW: Performance metrics
-    /// Total blocks successfully finalized
-    pub blocks_finalized: u64,
-    /// Total transactions processed across all blocks
-    pub transactions_processed: u64,
-    /// Average committee size across epochs
-    pub average_committee_size: f64,
-    /// Average validator participation rate (percentage)
-    pub average_participation_rate: f64,
+    /// Tier covering `validator_count`
+    pub fn tier_for(&self, validator_count: usize) -> Option<&ThresholdTier> {
+        self.tiers.iter().find(|tier| {
+            validator_count >= tier.min_validators
+                && tier.max_validators.is_none_or(|max| validator_count <= max)
+        })
+    }
 
-    // NEW: Timing metrics (in milliseconds)
-    /// Average time spent in block proposal phase
-    pub average_proposal_time_ms: u64,
-    /// Average time spent in voting phase
-    pub average_voting_time_ms: u64,
-    /// Average time spent in finalization phase
-    pub average_finalization_time_ms: u64,
+    /// Check that the tiers are contiguous from 0, only the last is open-ended, and
+    /// their thresholds are in range
+    pub fn validate(&self) -> Result<()> {
+        let Some(last) = self.tiers.last() else {
+            return Err(ConsensusError::config_error(
+                "Threshold schedule needs at least one tier",
+            ));
+        };
+        if last.max_validators.is_some() {
+            return Err(ConsensusError::config_error(
+                "Last threshold tier must have no upper bound",
+            ));
+        }
 
-    // NEW: Economic metrics
-    /// Total rewards distributed to validators (future integration)
-    pub total_rewards_distributed: u64,
-    /// Total stake slashed from validators
-    pub total_stake_slashed: u64,
-    /// Number of currently active validators
-    pub active_validators: usize,
+        let mut next_min = 0;
+        for (index, tier) in self.tiers.iter().enumerate() {
+            if tier.min_validators != next_min {
+                return Err(ConsensusError::config_error(format!(
+                    "Threshold tier starting at {} validators leaves a gap or overlap (expected {})",
+                    tier.min_validators, next_min
+                )));
+            }
+            if tier.byzantine_threshold < 51 || tier.byzantine_threshold > 100 {
+                return Err(ConsensusError::config_error(
+                    "Tier byzantine threshold must be 51-100",
+                ));
+            }
+            if tier.emotional_threshold > 100 {
+                return Err(ConsensusError::config_error(
+                    "Tier emotional threshold must be <= 100",
+                ));
+            }
+            match tier.max_validators {
+                Some(max) if max < tier.min_validators => {
+                    return Err(ConsensusError::config_error(
+                        "Threshold tier upper bound is below its lower bound",
+                    ));
+                }
+                Some(max) => {
+                    next_min = max.checked_add(1).ok_or_else(|| {
+                        ConsensusError::config_error(
+                            "Threshold tier upper bound leaves no room for the last tier",
+                        )
+                    })?;
+                }
+                None if index + 1 < self.tiers.len() => {
+                    return Err(ConsensusError::config_error(
+                        "Only the last threshold tier may have no upper bound",
+                    ));
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
 }
 
-/// Main Proof of Emotion consensus engine
-pub struct ProofOfEmotionEngine {
-    /// Configuration
-    pub config: ConsensusConfig,
-    /// Registered validators
-    validators: Arc<DashMap<String, Arc<EmotionalValidator>>>,
-    /// Pending transactions
-    pending_transactions: Arc<Mutex<Vec<Transaction>>>,
-    /// Current consensus state
-    state: Arc<RwLock<ConsensusState>>,
-    /// Is engine running
-    is_running: Arc<RwLock<bool>>,
-    /// Metrics
-    metrics: Arc<RwLock<ConsensusMetrics>>,
-    /// Finalized blocks
-    finalized_blocks: Arc<RwLock<Vec<Block>>>,
-    /// Shutdown signal for graceful termination
-    shutdown_signal: Arc<tokio::sync::Notify>,
-    /// Byzantine fault detector
-    byzantine_detector: Arc<ByzantineDetector>,
-    /// Fork detector and resolver
-    fork_detector: Arc<crate::fork::ForkDetector>,
-    /// Checkpoint manager for crash recovery
-    checkpoint_manager: Arc<crate::checkpoint::CheckpointManager>,
+/// How emotional score and confidence decide committee eligibility
+///
+/// Stake and activity requirements apply under every policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum EligibilityPolicy {
+    /// Eligible only with a score of at least `emotional_threshold`
+    #[default]
+    Hard,
+    /// Eligible when the weighted mean of score and confidence reaches `combined_cutoff`
+    ///
+    /// Admits a confident validator marginally below `emotional_threshold` and excludes
+    /// one above it whose score is too uncertain to trust.
+    Soft {
+        /// Weight of the emotional score
+        score_weight: f64,
+        /// Weight of the score's confidence
+        confidence_weight: f64,
+        /// Minimum weighted mean (0-100)
+        combined_cutoff: u8,
+    },
 }
 
-impl ProofOfEmotionEngine {
-    /// Create a new consensus engine
-    pub fn new(config: ConsensusConfig) -> Result<Self> {
-        if config.emotional_threshold > 100 {
-            return Err(ConsensusError::config_error(
-                "Emotional threshold must be <= 100",
-            ));
+impl EligibilityPolicy {
+    /// Eligibility of a validator under this policy
+    pub fn evaluate(
+        &self,
+        validator: &EmotionalValidator,
+        emotional_threshold: u8,
+        minimum_stake: u64,
+    ) -> EligibilityStatus {
+        let status = validator.eligibility(emotional_threshold, minimum_stake);
+        let Self::Soft {
+            score_weight,
+            confidence_weight,
+            combined_cutoff,
+        } = *self
+        else {
+            return status;
+        };
+
+        match status {
+            EligibilityStatus::Eligible
+            | EligibilityStatus::Ineligible(IneligibilityReason::LowEmotionalScore { .. }) => {}
+            other => return other,
         }
-        if config.byzantine_threshold < 51 || config.byzantine_threshold > 100 {
-            return Err(ConsensusError::config_error(
-                "Byzantine threshold must be 51-100",
-            ));
+
+        let weighted = score_weight * f64::from(validator.eligibility_score())
+            + confidence_weight * f64::from(validator.get_confidence());
+        let combined = (weighted / (score_weight + confidence_weight))
+            .round()
+            .clamp(0.0, 100.0) as u8;
+
+        if combined >= combined_cutoff {
+            EligibilityStatus::Eligible
+        } else {
+            EligibilityStatus::Ineligible(IneligibilityReason::LowCombinedScore {
+                combined,
+                cutoff: combined_cutoff,
+            })
         }
-        if config.committee_size == 0 {
-            return Err(ConsensusError::config_error("Committee size must be > 0"));
+    }
+
+    fn is_valid(&self) -> bool {
+        match *self {
+            Self::Hard => true,
+            Self::Soft {
+                score_weight,
+                confidence_weight,
+                combined_cutoff,
+            } => {
+                let weights = [score_weight, confidence_weight];
+                weights.iter().all(|w| w.is_finite() && *w >= 0.0)
+                    && weights.iter().sum::<f64>() > 0.0
+                    && combined_cutoff <= 100
+            }
         }
+    }
+}
 
-        // Checkpoint interval: every 100 blocks (configurable)
-        let checkpoint_interval = 100;
+/// Relative weights of the inputs combined into `ConsensusState::network_health`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NetworkHealthWeights {
+    /// Weight of the participation rate of the last finalized block
+    pub participation: f64,
+    /// Weight of the consensus strength of the last finalized block
+    pub consensus_strength: f64,
+    /// Weight of the share of recent epochs that succeeded
+    pub epoch_success: f64,
+    /// Weight of the share of recent epochs without Byzantine behavior
+    pub byzantine_free: f64,
+}
 
-        Ok(Self {
-            config,
-            validators: Arc::new(DashMap::new()),
-            pending_transactions: Arc::new(Mutex::new(Vec::new())),
-            state: Arc::new(RwLock::new(ConsensusState {
-                current_epoch: 0,
-                network_health: 100,
-                consensus_strength: 0,
-                emotional_fitness: 0,
-                participation_rate: 0,
-                last_finalized_height: 0,
-                pending_transactions: 0,
-                total_validators: 0,
-                active_validators: 0,
-            })),
-            is_running: Arc::new(RwLock::new(false)),
-            metrics: Arc::new(RwLock::new(ConsensusMetrics::default())),
-            finalized_blocks: Arc::new(RwLock::new(Vec::new())),
-            shutdown_signal: Arc::new(tokio::sync::Notify::new()),
-            byzantine_detector: Arc::new(ByzantineDetector::new()),
-            fork_detector: Arc::new(crate::fork::ForkDetector::new()),
-            checkpoint_manager: Arc::new(crate::checkpoint::CheckpointManager::new(checkpoint_interval)),
-        })
+impl Default for NetworkHealthWeights {
+    fn default() -> Self {
+        Self {
+            participation: 0.3,
+            consensus_strength: 0.3,
+            epoch_success: 0.25,
+            byzantine_free: 0.15,
+        }
     }
+}
 
-    /// Register a validator
-    pub async fn register_validator(&self, validator: EmotionalValidator) -> Result<()> {
-        if validator.get_stake() < self.config.minimum_stake {
-            return Err(ConsensusError::insufficient_stake(
-                validator.get_stake(),
-                self.config.minimum_stake,
-            ));
+impl NetworkHealthWeights {
+    /// Combine the inputs into a 0-100 score
+    ///
+    /// `failed_ratio` and `byzantine_ratio` are the shares (0.0 - 1.0) of recent epochs
+    /// that failed and that saw Byzantine behavior.
+    pub fn score(
+        &self,
+        participation_rate: u8,
+        consensus_strength: u8,
+        failed_ratio: f64,
+        byzantine_ratio: f64,
+    ) -> u8 {
+        let total =
+            self.participation + self.consensus_strength + self.epoch_success + self.byzantine_free;
+        if total <= 0.0 {
+            return 100;
         }
 
-        let id = validator.id().to_string();
-        let stake = validator.get_stake();
-        self.validators.insert(id.clone(), Arc::new(validator));
-
-        info!(
-            "✅ Validator {} registered with {} POE stake",
-            id,
-            stake
-        );
+        let weighted = self.participation * f64::from(participation_rate)
+            + self.consensus_strength * f64::from(consensus_strength)
+            + self.epoch_success * (1.0 - failed_ratio) * 100.0
+            + self.byzantine_free * (1.0 - byzantine_ratio) * 100.0;
+        (weighted / total).round().clamp(0.0, 100.0) as u8
+    }
 
-        Ok(())
+    fn is_valid(&self) -> bool {
+        let weights = [
+            self.participation,
+            self.consensus_strength,
+            self.epoch_success,
+            self.byzantine_free,
+        ];
+        weights.iter().all(|w| w.is_finite() && *w >= 0.0) && weights.iter().sum::<f64>() > 0.0
     }
+}
 
-    /// Start consensus engine
-    pub async fn start(self: Arc<Self>) -> Result<()> {
-        let mut running = self.is_running.write().await;
-        if *running {
-            return Err(ConsensusError::AlreadyRunning);
-        }
-        *running = true;
-        drop(running);
+fn default_max_reorg_depth() -> u64 {
+    crate::fork::DEFAULT_MAX_REORG_DEPTH
+}
 
-        info!("🚀 Starting Proof of Emotion consensus engine");
-        info!("⚙️  Epoch duration: {}ms", self.config.epoch_duration);
-        info!(
-            "💓 Emotional threshold: {}%",
-            self.config.emotional_threshold
-        );
-        info!(
-            "🛡️  Byzantine threshold: {}%",
-            self.config.byzantine_threshold
-        );
+fn default_assessment_concurrency() -> usize {
+    DEFAULT_ASSESSMENT_CONCURRENCY
+}
 
-        let engine = Arc::clone(&self);
+fn default_device_read_attempts() -> u32 {
+    DEFAULT_DEVICE_READ_ATTEMPTS
+}
 
-        tokio::spawn(async move {
-            engine.epoch_loop().await;
-        });
+fn default_device_retry_backoff_ms() -> u64 {
+    DEFAULT_DEVICE_RETRY_BACKOFF_MS
+}
 
-        // Spawn periodic cleanup task to prevent memory leak from expired transactions
-        let cleanup_engine = Arc::clone(&self);
-        tokio::spawn(async move {
-            let mut cleanup_interval = time::interval(Duration::from_secs(60));
-            loop {
-                cleanup_interval.tick().await;
-                if !*cleanup_engine.is_running.read().await {
-                    break;
-                }
-                cleanup_engine.cleanup_transaction_pool().await;
-            }
-        });
+fn default_max_slashes_in_window() -> u32 {
+    DEFAULT_MAX_SLASHES_IN_WINDOW
+}
+
+fn default_slash_window_epochs() -> u64 {
+    DEFAULT_SLASH_WINDOW_EPOCHS
+}
+
+fn default_jail_epochs() -> u64 {
+    DEFAULT_JAIL_EPOCHS
+}
+
+fn default_blocks_per_epoch() -> usize {
+    1
+}
+
+fn default_replace_byzantine_proposer() -> bool {
+    true
+}
+
+fn default_temporal_window_ms() -> u64 {
+    crate::crypto::DEFAULT_TEMPORAL_WINDOW_MS
+}
+
+fn default_max_block_weight() -> u64 {
+    crate::DEFAULT_MAX_BLOCK_WEIGHT
+}
+
+fn default_finalized_tx_filter_blocks() -> usize {
+    crate::bloom::DEFAULT_FINALIZED_TX_FILTER_BLOCKS
+}
+
+fn default_finalized_tx_filter_false_positive_rate() -> f64 {
+    crate::bloom::DEFAULT_FINALIZED_TX_FILTER_FALSE_POSITIVE_RATE
+}
+
+fn default_chain_id() -> String {
+    crate::DEFAULT_CHAIN_ID.to_string()
+}
+
+/// Default number of validators assessed concurrently
+pub const DEFAULT_ASSESSMENT_CONCURRENCY: usize = 32;
+
+/// Default number of attempts at reading a biometric device
+pub const DEFAULT_DEVICE_READ_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first device read retry in milliseconds
+pub const DEFAULT_DEVICE_RETRY_BACKOFF_MS: u64 = 100;
+
+/// Default number of slashes within the window that gets a validator jailed
+pub const DEFAULT_MAX_SLASHES_IN_WINDOW: u32 = 3;
+
+/// Default number of recent epochs in which slashes are counted
+pub const DEFAULT_SLASH_WINDOW_EPOCHS: u64 = 100;
+
+/// Default number of epochs a jailed validator sits out
+pub const DEFAULT_JAIL_EPOCHS: u64 = 10;
+
+/// Default number of per-epoch metrics snapshots kept
+pub const DEFAULT_METRICS_HISTORY_DEPTH: usize = 1_000;
+
+/// Number of recent epochs whose outcomes feed into the network health score
+pub const NETWORK_HEALTH_WINDOW_EPOCHS: usize = 20;
+
+/// Number of transaction statuses remembered before the oldest are forgotten
+const TX_STATUS_CAPACITY: usize = 100_000;
+
+/// Pending transactions in submission order, with a hash set to reject duplicates
+#[derive(Debug, Default)]
+struct Mempool {
+    transactions: Vec<Transaction>,
+    hashes: HashSet<String>,
+}
+
+impl Mempool {
+    /// Add a transaction; returns false if one with the same hash is already pending
+    fn insert(&mut self, transaction: Transaction) -> bool {
+        if !self.hashes.insert(transaction.hash.clone()) {
+            return false;
+        }
+        self.transactions.push(transaction);
+        true
+    }
+
+    fn retain(&mut self, mut keep: impl FnMut(&Transaction) -> bool) {
+        let hashes = &mut self.hashes;
+        self.transactions.retain(|tx| {
+            let kept = keep(tx);
+            if !kept {
+                hashes.remove(&tx.hash);
+            }
+            kept
+        });
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Transaction> {
+        self.transactions.iter()
+    }
+
+    fn len(&self) -> usize {
+        self.transactions.len()
+    }
+}
+
+/// Bounded transaction hash to status index, forgetting the oldest entries first
+#[derive(Debug, Default)]
+struct TxStatusIndex {
+    statuses: HashMap<String, TxStatus>,
+    order: VecDeque<String>,
+}
+
+impl TxStatusIndex {
+    fn set(&mut self, tx_hash: &str, status: TxStatus) {
+        if self.statuses.insert(tx_hash.to_string(), status).is_none() {
+            self.order.push_back(tx_hash.to_string());
+        }
+        while self.order.len() > TX_STATUS_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.statuses.remove(&oldest);
+            }
+        }
+    }
+
+    fn get(&self, tx_hash: &str) -> Option<TxStatus> {
+        self.statuses.get(tx_hash).copied()
+    }
+}
+
+/// Strategy for choosing the committee from eligible validators
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectionMode {
+    /// Deterministically pick the validators with the highest combined score
+    #[default]
+    TopK,
+    /// Sample without replacement with probability proportional to combined score
+    ///
+    /// The sample is seeded by the epoch and previous block hash so any node can
+    /// reproduce and verify the committee.
+    WeightedRandom,
+}
+
+/// Policy deciding which validators may register
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Permissioning {
+    /// Any validator may register
+    #[default]
+    Open,
+    /// Only validators whose public key (hex) is listed may register
+    Allowlist(HashSet<String>),
+}
+
+impl Permissioning {
+    /// Whether a validator with this public key may register
+    pub fn permits(&self, public_key_hex: &str) -> bool {
+        match self {
+            Self::Open => true,
+            Self::Allowlist(keys) => keys.contains(public_key_hex),
+        }
+    }
+}
+
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self {
+            epoch_duration: 30_000,
+            emotional_threshold: 75,
+            byzantine_threshold: 67,
+            committee_size: 21,
+            minimum_stake: 10_000,
+            voting_timeout: 8_000,
+            proposal_timeout: 10_000,
+            finality_timeout: 2_000,
+            selection_mode: SelectionMode::default(),
+            proposer_policy: ProposerPolicy::default(),
+            proposer_eligibility: ProposerEligibility::default(),
+            stake_weighted_voting: false,
+            max_reorg_depth: crate::fork::DEFAULT_MAX_REORG_DEPTH,
+            assessment_concurrency: DEFAULT_ASSESSMENT_CONCURRENCY,
+            device_read_attempts: DEFAULT_DEVICE_READ_ATTEMPTS,
+            device_retry_backoff_ms: DEFAULT_DEVICE_RETRY_BACKOFF_MS,
+            max_slashes_in_window: DEFAULT_MAX_SLASHES_IN_WINDOW,
+            slash_window_epochs: DEFAULT_SLASH_WINDOW_EPOCHS,
+            jail_epochs: DEFAULT_JAIL_EPOCHS,
+            adaptive_epoch_duration: None,
+            degraded_unavailable: false,
+            finalized_tx_filter_blocks: crate::bloom::DEFAULT_FINALIZED_TX_FILTER_BLOCKS,
+            finalized_tx_filter_false_positive_rate:
+                crate::bloom::DEFAULT_FINALIZED_TX_FILTER_FALSE_POSITIVE_RATE,
+            network_health_weights: NetworkHealthWeights::default(),
+            require_nonempty_blocks_when_pending: false,
+            committee_overlap_fraction: 0.0,
+            blocks_per_epoch: default_blocks_per_epoch(),
+            max_tip_retries: 0,
+            eligibility_policy: EligibilityPolicy::default(),
+            max_declining_epochs: 0,
+            check_validation_determinism: false,
+            reputation_penalties: ReputationPenalties::default(),
+            replace_byzantine_proposer: true,
+            threshold_schedule: None,
+            collusion_detection: None,
+            temporal_window_ms: crate::crypto::DEFAULT_TEMPORAL_WINDOW_MS,
+            max_block_weight: crate::DEFAULT_MAX_BLOCK_WEIGHT,
+            stake_weight_fn: StakeWeightFn::Sqrt,
+            proposer_cooldown: None,
+            chain_id: default_chain_id(),
+        }
+    }
+}
+
+impl ConsensusConfig {
+    /// Start building a configuration from the defaults
+    pub fn builder() -> ConsensusConfigBuilder {
+        ConsensusConfigBuilder::new()
+    }
+
+    /// Check that all parameters are within their allowed ranges
+    pub fn validate(&self) -> Result<()> {
+        if self.emotional_threshold > 100 {
+            return Err(ConsensusError::config_error(
+                "Emotional threshold must be <= 100",
+            ));
+        }
+        if self.byzantine_threshold < 51 || self.byzantine_threshold > 100 {
+            return Err(ConsensusError::config_error(
+                "Byzantine threshold must be 51-100",
+            ));
+        }
+        if self.committee_size == 0 {
+            return Err(ConsensusError::config_error("Committee size must be > 0"));
+        }
+        if self.epoch_duration == 0 {
+            return Err(ConsensusError::config_error("Epoch duration must be > 0"));
+        }
+        if self.temporal_window_ms == 0 {
+            return Err(ConsensusError::config_error("Temporal window must be > 0"));
+        }
+        if self.max_slashes_in_window > 0 && self.slash_window_epochs == 0 {
+            return Err(ConsensusError::config_error(
+                "Slash window must be > 0 when jailing is enabled",
+            ));
+        }
+        if !self.stake_weight_fn.is_valid() {
+            return Err(ConsensusError::config_error(
+                "Stake weight exponent must be in (0, 1000] thousandths",
+            ));
+        }
+        if self.max_block_weight < crate::TX_BASE_WEIGHT {
+            return Err(ConsensusError::config_error(format!(
+                "Max block weight must fit a transaction (>= {})",
+                crate::TX_BASE_WEIGHT
+            )));
+        }
+        if self.proposer_eligibility == ProposerEligibility::TopByReputation(0) {
+            return Err(ConsensusError::config_error(
+                "At least one committee member must be eligible to propose",
+            ));
+        }
+        if self.blocks_per_epoch == 0 {
+            return Err(ConsensusError::config_error("Blocks per epoch must be > 0"));
+        }
+        if self.chain_id.is_empty() {
+            return Err(ConsensusError::config_error("Chain ID must not be empty"));
+        }
+        if self.assessment_concurrency == 0 {
+            return Err(ConsensusError::config_error(
+                "Assessment concurrency must be > 0",
+            ));
+        }
+        if self.device_read_attempts == 0 {
+            return Err(ConsensusError::config_error(
+                "Device read attempts must be > 0",
+            ));
+        }
+        if let Some(adaptive) = &self.adaptive_epoch_duration {
+            if adaptive.min_ms == 0 || adaptive.min_ms > adaptive.max_ms {
+                return Err(ConsensusError::config_error(
+                    "Adaptive epoch duration needs 0 < min_ms <= max_ms",
+                ));
+            }
+            if !(adaptive.target_utilization > 0.0 && adaptive.target_utilization <= 1.0) {
+                return Err(ConsensusError::config_error(
+                    "Target utilization must be in (0, 1]",
+                ));
+            }
+        }
+        if !(self.finalized_tx_filter_false_positive_rate > 0.0
+            && self.finalized_tx_filter_false_positive_rate < 1.0)
+        {
+            return Err(ConsensusError::config_error(
+                "Finalized transaction filter false-positive rate must be in (0, 1)",
+            ));
+        }
+        if !self.network_health_weights.is_valid() {
+            return Err(ConsensusError::config_error(
+                "Network health weights must be non-negative with a positive sum",
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.committee_overlap_fraction) {
+            return Err(ConsensusError::config_error(
+                "Committee overlap fraction must be in [0, 1]",
+            ));
+        }
+        if let Some(schedule) = &self.threshold_schedule {
+            schedule.validate()?;
+        }
+        if self
+            .collusion_detection
+            .is_some_and(|detection| !detection.is_valid())
+        {
+            return Err(ConsensusError::config_error(
+                "Collusion detection needs a window of at least 3 and a threshold in (0, 1]",
+            ));
+        }
+        if self
+            .proposer_cooldown
+            .is_some_and(|cooldown| !cooldown.is_valid())
+        {
+            return Err(ConsensusError::config_error(
+                "Proposer cooldown needs at least one epoch and a weight factor in [0, 1]",
+            ));
+        }
+        if !self.eligibility_policy.is_valid() {
+            return Err(ConsensusError::config_error(
+                "Soft eligibility weights must be non-negative with a positive sum and the cutoff at most 100",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Return a copy of this configuration with the patch applied
+    pub fn patched(&self, patch: &ConfigPatch) -> Self {
+        let mut config = self.clone();
+        if let Some(emotional_threshold) = patch.emotional_threshold {
+            config.emotional_threshold = emotional_threshold;
+        }
+        if let Some(byzantine_threshold) = patch.byzantine_threshold {
+            config.byzantine_threshold = byzantine_threshold;
+        }
+        if let Some(committee_size) = patch.committee_size {
+            config.committee_size = committee_size;
+        }
+        if let Some(epoch_duration) = patch.epoch_duration {
+            config.epoch_duration = epoch_duration;
+        }
+        config
+    }
+}
+
+/// Fluent builder for [`ConsensusConfig`]
+///
+/// Starts from the defaults. [`build`](Self::build) applies the same range checks
+/// as [`ProofOfEmotionEngine::new`] plus cross-field sanity checks on the timeouts.
+#[derive(Debug, Clone, Default)]
+pub struct ConsensusConfigBuilder {
+    config: ConsensusConfig,
+}
+
+impl ConsensusConfigBuilder {
+    /// Create a builder initialized with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the epoch duration in milliseconds
+    pub fn epoch_duration(mut self, epoch_duration: u64) -> Self {
+        self.config.epoch_duration = epoch_duration;
+        self
+    }
+
+    /// Set the minimum emotional fitness threshold (0-100)
+    pub fn emotional_threshold(mut self, emotional_threshold: u8) -> Self {
+        self.config.emotional_threshold = emotional_threshold;
+        self
+    }
+
+    /// Set the Byzantine fault tolerance threshold (51-100)
+    pub fn byzantine_threshold(mut self, byzantine_threshold: u8) -> Self {
+        self.config.byzantine_threshold = byzantine_threshold;
+        self
+    }
+
+    /// Set the number of validators in the committee
+    pub fn committee_size(mut self, committee_size: usize) -> Self {
+        self.config.committee_size = committee_size;
+        self
+    }
+
+    /// Set the minimum stake required (in POE tokens)
+    pub fn minimum_stake(mut self, minimum_stake: u64) -> Self {
+        self.config.minimum_stake = minimum_stake;
+        self
+    }
+
+    /// Set the voting timeout in milliseconds
+    pub fn voting_timeout(mut self, voting_timeout: u64) -> Self {
+        self.config.voting_timeout = voting_timeout;
+        self
+    }
+
+    /// Set the proposal timeout in milliseconds
+    pub fn proposal_timeout(mut self, proposal_timeout: u64) -> Self {
+        self.config.proposal_timeout = proposal_timeout;
+        self
+    }
+
+    /// Set the finality timeout in milliseconds
+    pub fn finality_timeout(mut self, finality_timeout: u64) -> Self {
+        self.config.finality_timeout = finality_timeout;
+        self
+    }
+
+    /// Set the committee selection strategy
+    pub fn selection_mode(mut self, selection_mode: SelectionMode) -> Self {
+        self.config.selection_mode = selection_mode;
+        self
+    }
+
+    /// Set how the proposer is chosen within the committee
+    pub fn proposer_policy(mut self, proposer_policy: ProposerPolicy) -> Self {
+        self.config.proposer_policy = proposer_policy;
+        self
+    }
+
+    /// Set which committee members may propose
+    pub fn proposer_eligibility(mut self, proposer_eligibility: ProposerEligibility) -> Self {
+        self.config.proposer_eligibility = proposer_eligibility;
+        self
+    }
+
+    /// Weight votes by stake, scaled by each voter's confidence, instead of counting heads
+    pub fn stake_weighted_voting(mut self, stake_weighted_voting: bool) -> Self {
+        self.config.stake_weighted_voting = stake_weighted_voting;
+        self
+    }
+
+    /// Set the maximum number of canonical blocks a fork resolution may replace
+    pub fn max_reorg_depth(mut self, max_reorg_depth: u64) -> Self {
+        self.config.max_reorg_depth = max_reorg_depth;
+        self
+    }
+
+    /// Set the maximum number of validators assessed concurrently
+    pub fn assessment_concurrency(mut self, assessment_concurrency: usize) -> Self {
+        self.config.assessment_concurrency = assessment_concurrency;
+        self
+    }
+
+    /// Set how often a failing biometric device is read before the validator is skipped
+    pub fn device_read_attempts(mut self, device_read_attempts: u32) -> Self {
+        self.config.device_read_attempts = device_read_attempts;
+        self
+    }
+
+    /// Set the delay before the first device read retry, doubled per retry
+    pub fn device_retry_backoff_ms(mut self, device_retry_backoff_ms: u64) -> Self {
+        self.config.device_retry_backoff_ms = device_retry_backoff_ms;
+        self
+    }
+
+    /// Set how many slashes within the window get a validator jailed (0 disables jailing)
+    pub fn max_slashes_in_window(mut self, max_slashes_in_window: u32) -> Self {
+        self.config.max_slashes_in_window = max_slashes_in_window;
+        self
+    }
+
+    /// Set the number of recent epochs in which slashes are counted
+    pub fn slash_window_epochs(mut self, slash_window_epochs: u64) -> Self {
+        self.config.slash_window_epochs = slash_window_epochs;
+        self
+    }
+
+    /// Set the number of epochs a jailed validator is kept out of committees
+    pub fn jail_epochs(mut self, jail_epochs: u64) -> Self {
+        self.config.jail_epochs = jail_epochs;
+        self
+    }
+
+    /// Tune the epoch interval to the measured epoch duration
+    pub fn adaptive_epoch_duration(mut self, adaptive: AdaptiveEpochDuration) -> Self {
+        self.config.adaptive_epoch_duration = Some(adaptive);
+        self
+    }
+
+    /// Set whether a degraded node reports HTTP 503 to health probes
+    pub fn degraded_unavailable(mut self, degraded_unavailable: bool) -> Self {
+        self.config.degraded_unavailable = degraded_unavailable;
+        self
+    }
+
+    /// Set how many recent blocks are checked for already-finalized transactions
+    pub fn finalized_tx_filter_blocks(mut self, finalized_tx_filter_blocks: usize) -> Self {
+        self.config.finalized_tx_filter_blocks = finalized_tx_filter_blocks;
+        self
+    }
+
+    /// Set the false-positive rate of the finalized transaction bloom filter
+    pub fn finalized_tx_filter_false_positive_rate(mut self, false_positive_rate: f64) -> Self {
+        self.config.finalized_tx_filter_false_positive_rate = false_positive_rate;
+        self
+    }
+
+    /// Set the weighting of the inputs to the network health score
+    pub fn network_health_weights(mut self, weights: NetworkHealthWeights) -> Self {
+        self.config.network_health_weights = weights;
+        self
+    }
+
+    /// Set whether empty blocks are rejected while transactions are pending
+    pub fn require_nonempty_blocks_when_pending(mut self, require: bool) -> Self {
+        self.config.require_nonempty_blocks_when_pending = require;
+        self
+    }
+
+    /// Set the minimum fraction of the previous committee kept in the next one
+    pub fn committee_overlap_fraction(mut self, fraction: f64) -> Self {
+        self.config.committee_overlap_fraction = fraction;
+        self
+    }
+
+    /// Set the number of blocks finalized per epoch
+    pub fn blocks_per_epoch(mut self, blocks_per_epoch: usize) -> Self {
+        self.config.blocks_per_epoch = blocks_per_epoch;
+        self
+    }
+
+    /// Set how many consecutive failed epochs at one tip halt the engine (0 disables)
+    pub fn max_tip_retries(mut self, max_tip_retries: u32) -> Self {
+        self.config.max_tip_retries = max_tip_retries;
+        self
+    }
+
+    /// Set how emotional score and confidence decide eligibility
+    pub fn eligibility_policy(mut self, eligibility_policy: EligibilityPolicy) -> Self {
+        self.config.eligibility_policy = eligibility_policy;
+        self
+    }
+
+    /// Set how many consecutive declining assessments deactivate a validator (0 disables)
+    pub fn max_declining_epochs(mut self, max_declining_epochs: u32) -> Self {
+        self.config.max_declining_epochs = max_declining_epochs;
+        self
+    }
+
+    /// Set whether diverging validation outcomes within a committee are flagged
+    pub fn check_validation_determinism(mut self, check: bool) -> Self {
+        self.config.check_validation_determinism = check;
+        self
+    }
+
+    /// Set the reputation docked from slashed validators
+    pub fn reputation_penalties(mut self, penalties: ReputationPenalties) -> Self {
+        self.config.reputation_penalties = penalties;
+        self
+    }
+
+    /// Set whether a double-signing proposer is replaced instead of failing the epoch
+    pub fn replace_byzantine_proposer(mut self, replace: bool) -> Self {
+        self.config.replace_byzantine_proposer = replace;
+        self
+    }
+
+    /// Vary the byzantine and emotional thresholds with the validator count
+    pub fn threshold_schedule(mut self, schedule: ThresholdSchedule) -> Self {
+        self.config.threshold_schedule = Some(schedule);
+        self
+    }
+
+    /// Flag validators whose recent scores correlate at or above a threshold
+    pub fn collusion_detection(mut self, detection: CollusionDetection) -> Self {
+        self.config.collusion_detection = Some(detection);
+        self
+    }
+
+    /// Set the span that the readings behind a score must fall within
+    pub fn temporal_window_ms(mut self, temporal_window_ms: u64) -> Self {
+        self.config.temporal_window_ms = temporal_window_ms;
+        self
+    }
+
+    /// Set the maximum total transaction weight of a block
+    pub fn max_block_weight(mut self, max_block_weight: u64) -> Self {
+        self.config.max_block_weight = max_block_weight;
+        self
+    }
+
+    /// Set the curve mapping stake to selection influence
+    pub fn stake_weight_fn(mut self, stake_weight_fn: StakeWeightFn) -> Self {
+        self.config.stake_weight_fn = stake_weight_fn;
+        self
+    }
+
+    /// Reduce the selection weight of validators that proposed recently
+    pub fn proposer_cooldown(mut self, cooldown: ProposerCooldown) -> Self {
+        self.config.proposer_cooldown = Some(cooldown);
+        self
+    }
+
+    /// Set the chain blocks are proposed and accepted for
+    pub fn chain_id(mut self, chain_id: impl Into<String>) -> Self {
+        self.config.chain_id = chain_id.into();
+        self
+    }
+
+    /// Validate and return the configuration
+    pub fn build(self) -> Result<ConsensusConfig> {
+        let config = self.config;
+        config.validate()?;
+
+        if config.finality_timeout >= config.voting_timeout {
+            return Err(ConsensusError::config_error(
+                "Finality timeout must be shorter than voting timeout",
+            ));
+        }
+        if config.voting_timeout > config.epoch_duration {
+            return Err(ConsensusError::config_error(
+                "Voting timeout must not exceed epoch duration",
+            ));
+        }
+        if config.proposal_timeout > config.epoch_duration {
+            return Err(ConsensusError::config_error(
+                "Proposal timeout must not exceed epoch duration",
+            ));
+        }
+
+        Ok(config)
+    }
+}
+
+/// Subset of consensus parameters that can be changed while the engine is running
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigPatch {
+    /// New minimum emotional fitness threshold (0-100)
+    pub emotional_threshold: Option<u8>,
+    /// New Byzantine fault tolerance threshold (percentage)
+    pub byzantine_threshold: Option<u8>,
+    /// New committee size
+    pub committee_size: Option<usize>,
+    /// New epoch duration in milliseconds
+    pub epoch_duration: Option<u64>,
+}
+
+/// Jail term of a validator
+#[derive(Debug, Clone, Copy)]
+struct Jail {
+    /// Epoch the validator is released at
+    release_epoch: u64,
+    /// Whether the validator is reactivated on release; false if it was already
+    /// inactive for another reason when jailed
+    reactivate: bool,
+}
+
+/// Current state of consensus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusState {
+    /// Current epoch number
+    pub current_epoch: u64,
+    /// Network health (0-100)
+    pub network_health: u8,
+    /// Current consensus strength (percentage)
+    pub consensus_strength: u8,
+    /// Average emotional fitness
+    pub emotional_fitness: u8,
+    /// Participation rate (percentage)
+    pub participation_rate: u8,
+    /// Last finalized block height
+    pub last_finalized_height: u64,
+    /// Number of pending transactions
+    pub pending_transactions: usize,
+    /// Total number of registered validators
+    pub total_validators: usize,
+    /// Number of currently active validators
+    pub active_validators: usize,
+    /// Epoch execution is suspended; transactions are still accepted
+    #[serde(default)]
+    pub paused: bool,
+    /// Interval between epochs currently in effect, in milliseconds
+    #[serde(default)]
+    pub effective_epoch_duration: u64,
+    /// Byzantine threshold in effect for the current epoch, from the threshold schedule
+    /// or the configuration
+    #[serde(default)]
+    pub effective_byzantine_threshold: u8,
+    /// Emotional threshold in effect for the current epoch, from the threshold schedule
+    /// or the configuration
+    #[serde(default)]
+    pub effective_emotional_threshold: u8,
+    /// Committee of the epoch in progress or last run, primary first; empty while idle
+    #[serde(default)]
+    pub current_committee: Vec<String>,
+}
+
+/// Phase of a consensus round
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundPhase {
+    /// Proposing phase
+    Propose,
+    /// Voting phase
+    Vote,
+    /// Commit phase
+    Commit,
+    /// Finalized
+    Finalized,
+    /// Aborted
+    Aborted,
+}
+
+/// Consensus round
+pub struct ConsensusRound {
+    /// Round ID
+    pub id: String,
+    /// Current phase
+    pub phase: RwLock<RoundPhase>,
+    /// Proposed block
+    pub proposed_block: Option<Block>,
+    /// Votes collected
+    pub votes: DashMap<String, Vote>,
+    /// Round start time
+    pub start_time: std::time::Instant,
+}
+
+/// Metrics for consensus performance
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsensusMetrics {
+    // Existing metrics
+    /// Total epochs processed
+    pub total_epochs: u64,
+    /// Successful epochs
+    pub successful_epochs: u64,
+    /// Failed epochs
+    pub failed_epochs: u64,
+    /// Average epoch duration in milliseconds
+    pub average_duration_ms: u64,
+    /// Average emotional score across all epochs
+    pub average_emotional_score: u8,
+    /// Total Byzantine failures detected
+    pub byzantine_failures: u64,
+
+    // NEW: Detailed metrics
+    /// Total blocks rejected during validation
+    pub rejected_blocks: u64,
+    /// Total votes rejected (invalid signatures, wrong epoch, etc.)
+    pub rejected_votes: u64,
+    /// Number of rounds that timed out
+    pub timeout_rounds: u64,
+    /// Epochs failed due to low emotional fitness
+    pub emotional_failures: u64,
+    /// Detected network partitions (future use)
+    pub network_partitions: u64,
+    /// Fork detections (conflicting blocks at same height)
+    pub fork_detections: u64,
+    /// Voting rounds in which committee members validated the same block differently
+    #[serde(default)]
+    pub validation_divergences: u64,
+    /// Proposers replaced by the next committee member after double-signing
+    #[serde(default)]
+    pub proposer_replacements: u64,
+
+    // NEW: Performance metrics
+    /// Total blocks successfully finalized
+    pub blocks_finalized: u64,
+    /// Total transactions processed across all blocks
+    pub transactions_processed: u64,
+    /// Average committee size across epochs
+    pub average_committee_size: f64,
+    /// Average validator participation rate (percentage)
+    pub average_participation_rate: f64,
+
+    // NEW: Timing metrics (in milliseconds)
+    /// Average time spent in block proposal phase
+    pub average_proposal_time_ms: u64,
+    /// Average time spent in voting phase
+    pub average_voting_time_ms: u64,
+    /// Average time spent in finalization phase
+    pub average_finalization_time_ms: u64,
+
+    // NEW: Economic metrics
+    /// Total rewards distributed to validators (future integration)
+    pub total_rewards_distributed: u64,
+    /// Total transaction fees credited to block proposers
+    #[serde(default)]
+    pub total_fees_collected: u64,
+    /// Total stake slashed from validators
+    pub total_stake_slashed: u64,
+    /// Number of currently active validators
+    pub active_validators: usize,
+}
+
+/// Main Proof of Emotion consensus engine
+pub struct ProofOfEmotionEngine {
+    /// Active configuration
+    config: Arc<RwLock<ConsensusConfig>>,
+    /// Configuration to apply at the next epoch boundary
+    pending_config: Arc<Mutex<Option<ConsensusConfig>>>,
+    /// Registered validators
+    validators: Arc<DashMap<String, Arc<EmotionalValidator>>>,
+    /// Sum of the stake of all registered validators
+    total_stake: Arc<AtomicU64>,
+    /// Pending transactions
+    pending_transactions: Arc<Mutex<Mempool>>,
+    /// Status of recently submitted transactions
+    tx_statuses: Arc<RwLock<TxStatusIndex>>,
+    /// Bloom filters over the transactions of recently finalized blocks
+    finalized_tx_filter: Arc<RwLock<RecentTransactionFilter>>,
+    /// Current consensus state
+    state: Arc<RwLock<ConsensusState>>,
+    /// Is engine running
+    is_running: Arc<RwLock<bool>>,
+    /// Metrics
+    metrics: Arc<RwLock<ConsensusMetrics>>,
+    /// Whether each recent epoch failed and whether it saw Byzantine behavior, oldest first
+    recent_epoch_outcomes: Arc<RwLock<VecDeque<(bool, bool)>>>,
+    /// Number of votes dropped for each non-Byzantine reason
+    vote_rejection_reasons: Arc<RwLock<HashMap<VoteRejectionReason, u64>>>,
+    /// Members of the last selected committee, primary first
+    previous_committee: Arc<RwLock<Vec<String>>>,
+    /// Metrics snapshots taken at each epoch boundary, oldest first
+    metrics_history: Arc<RwLock<VecDeque<(u64, ConsensusMetrics)>>>,
+    /// Maximum number of metrics snapshots kept
+    metrics_history_depth: usize,
+    /// Signed audit bundles of recent epochs, oldest first
+    epoch_bundles: Arc<RwLock<VecDeque<EpochBundle>>>,
+    /// Maximum number of epoch bundles kept (0 disables bundling)
+    epoch_bundle_retention: usize,
+    /// Finalized blocks
+    finalized_blocks: Arc<RwLock<Vec<Block>>>,
+    /// Shutdown signal for graceful termination
+    shutdown_signal: Arc<tokio::sync::Notify>,
+    /// Held by the epoch loop while it runs an epoch, so pausing can wait for it
+    epoch_in_progress: Arc<Mutex<()>>,
+    /// Byzantine fault detector
+    byzantine_detector: Arc<ByzantineDetector>,
+    /// Fork detector and resolver
+    fork_detector: Arc<crate::fork::ForkDetector>,
+    /// Publisher for consensus events
+    events: broadcast::Sender<ConsensusEvent>,
+    /// Publisher for newly finalized blocks
+    finalized_block_sender: broadcast::Sender<Block>,
+    /// Checkpoint manager for crash recovery
+    checkpoint_manager: Arc<crate::checkpoint::CheckpointManager>,
+    /// Signature verification cache shared by committee members (cleared every epoch)
+    signature_cache: Arc<crate::crypto::SignatureCache>,
+    /// Time source for block timestamps and transaction expiry
+    clock: Arc<dyn Clock>,
+    /// Randomness for committee and proposer selection
+    beacon: Arc<dyn RandomnessBeacon>,
+    /// Verifier of biometric proofs; when set, proofs replace raw readings in assessment
+    zk_verifier: Option<Arc<dyn ZkBiometricProvider>>,
+    /// Whether the last committee consisted of a single validator
+    solo_mode: Arc<AtomicBool>,
+    /// Consecutive failed epochs since the chain tip last advanced
+    tip_retries: Arc<AtomicU64>,
+    /// Eligibility of every validator at the most recent assessment
+    last_assessment: Arc<RwLock<Vec<(String, EligibilityStatus)>>>,
+    /// Epochs of recent slashes per validator, for jailing
+    slash_history: Arc<DashMap<String, VecDeque<u64>>>,
+    /// Jailed validators and when and how they are released
+    jailed: Arc<DashMap<String, Jail>>,
+    /// Consecutive assessments each validator's emotional trend was declining
+    declining_epochs: Arc<DashMap<String, u32>>,
+    /// Counted activity per validator, for the scoreboard
+    activity: Arc<DashMap<String, ValidatorActivity>>,
+    /// Validator pairs flagged by the most recent collusion check
+    collusion_suspects: Arc<RwLock<Vec<CollusionSuspect>>>,
+    /// State machine that finalized transactions are applied to
+    state_machine: Option<Arc<dyn StateMachine>>,
+    /// Audit trail shared with the fork detector and checkpoint manager
+    audit: AuditHandle,
+    /// Which validators may register
+    permissioning: Arc<RwLock<Permissioning>>,
+    /// Optional log of epoch inputs for replay
+    epoch_recorder: Option<Arc<EpochRecorder>>,
+    /// Prometheus exporter flushed on shutdown
+    prometheus: Option<Arc<PrometheusMetrics>>,
+    /// Where the state snapshot is persisted on shutdown
+    snapshot_store: Option<Arc<dyn SnapshotStore>>,
+    /// Whether snapshots carry each validator's emotional state
+    persist_emotional_state: bool,
+}
+
+impl ProofOfEmotionEngine {
+    /// Create a new consensus engine
+    pub fn new(config: ConsensusConfig) -> Result<Self> {
+        config.validate()?;
+
+        // Checkpoint interval: every 100 blocks (configurable)
+        let checkpoint_interval = 100;
+        let events = event_channel();
+        let audit = AuditHandle::default();
+        let fork_detector = crate::fork::ForkDetector::new()
+            .with_max_reorg_depth(config.max_reorg_depth)
+            .with_event_sender(events.clone())
+            .with_audit(audit.clone());
+
+        let epoch_duration = config.epoch_duration;
+        let (byzantine_threshold, emotional_threshold) =
+            (config.byzantine_threshold, config.emotional_threshold);
+
+        Ok(Self {
+            config: Arc::new(RwLock::new(config)),
+            pending_config: Arc::new(Mutex::new(None)),
+            validators: Arc::new(DashMap::new()),
+            total_stake: Arc::new(AtomicU64::new(0)),
+            pending_transactions: Arc::new(Mutex::new(Mempool::default())),
+            tx_statuses: Arc::new(RwLock::new(TxStatusIndex::default())),
+            finalized_tx_filter: Arc::new(RwLock::new(RecentTransactionFilter::new())),
+            state: Arc::new(RwLock::new(ConsensusState {
+                current_epoch: 0,
+                network_health: 100,
+                consensus_strength: 0,
+                emotional_fitness: 0,
+                participation_rate: 0,
+                last_finalized_height: 0,
+                pending_transactions: 0,
+                total_validators: 0,
+                active_validators: 0,
+                paused: false,
+                effective_epoch_duration: epoch_duration,
+                effective_byzantine_threshold: byzantine_threshold,
+                effective_emotional_threshold: emotional_threshold,
+                current_committee: Vec::new(),
+            })),
+            is_running: Arc::new(RwLock::new(false)),
+            metrics: Arc::new(RwLock::new(ConsensusMetrics::default())),
+            recent_epoch_outcomes: Arc::new(RwLock::new(VecDeque::new())),
+            vote_rejection_reasons: Arc::new(RwLock::new(HashMap::new())),
+            previous_committee: Arc::new(RwLock::new(Vec::new())),
+            metrics_history: Arc::new(RwLock::new(VecDeque::new())),
+            metrics_history_depth: DEFAULT_METRICS_HISTORY_DEPTH,
+            epoch_bundles: Arc::new(RwLock::new(VecDeque::new())),
+            epoch_bundle_retention: DEFAULT_EPOCH_BUNDLE_RETENTION,
+            finalized_blocks: Arc::new(RwLock::new(Vec::new())),
+            shutdown_signal: Arc::new(tokio::sync::Notify::new()),
+            epoch_in_progress: Arc::new(Mutex::new(())),
+            byzantine_detector: Arc::new(ByzantineDetector::new()),
+            fork_detector: Arc::new(fork_detector),
+            events,
+            finalized_block_sender: broadcast::channel(crate::events::EVENT_CHANNEL_CAPACITY).0,
+            checkpoint_manager: Arc::new(
+                crate::checkpoint::CheckpointManager::new(checkpoint_interval)
+                    .with_audit(audit.clone()),
+            ),
+            signature_cache: Arc::new(crate::crypto::SignatureCache::new(SIGNATURE_CACHE_CAPACITY)),
+            clock: Arc::new(SystemClock),
+            beacon: Arc::new(HashChainBeacon::new()),
+            zk_verifier: None,
+            solo_mode: Arc::new(AtomicBool::new(false)),
+            tip_retries: Arc::new(AtomicU64::new(0)),
+            last_assessment: Arc::new(RwLock::new(Vec::new())),
+            slash_history: Arc::new(DashMap::new()),
+            jailed: Arc::new(DashMap::new()),
+            declining_epochs: Arc::new(DashMap::new()),
+            activity: Arc::new(DashMap::new()),
+            collusion_suspects: Arc::new(RwLock::new(Vec::new())),
+            state_machine: None,
+            audit,
+            permissioning: Arc::new(RwLock::new(Permissioning::default())),
+            epoch_recorder: None,
+            prometheus: None,
+            snapshot_store: None,
+            persist_emotional_state: false,
+        })
+    }
+
+    /// Replace the time source
+    ///
+    /// The clock is wrapped in a [`MonotonicClock`] so a backward step cannot produce
+    /// out-of-order timestamps.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Arc::new(MonotonicClock::new(clock));
+        self.byzantine_detector.set_clock(self.clock.clone());
+        self
+    }
+
+    /// Seed committee and proposer selection from the given beacon
+    ///
+    /// Defaults to a [`HashChainBeacon`] over the finalized block hashes.
+    pub fn with_randomness_beacon(mut self, beacon: Arc<dyn RandomnessBeacon>) -> Self {
+        self.beacon = beacon;
+        self
+    }
+
+    /// Assess validators from submitted biometric proofs instead of raw readings
+    ///
+    /// Each validator must submit a proof with
+    /// [`EmotionalValidator::submit_biometric_proof`] before every assessment; only the
+    /// public emotional score it carries reaches the engine.
+    pub fn with_zk_verifier(mut self, verifier: Arc<dyn ZkBiometricProvider>) -> Self {
+        self.zk_verifier = Some(verifier);
+        self
+    }
+
+    /// Restrict which validators may register
+    pub fn with_permissioning(self, permissioning: Permissioning) -> Self {
+        Self {
+            permissioning: Arc::new(RwLock::new(permissioning)),
+            ..self
+        }
+    }
+
+    /// Allow a validator public key (hex) to register
+    ///
+    /// Fails if the engine is open to every validator.
+    pub async fn add_to_allowlist(&self, public_key_hex: impl Into<String>) -> Result<()> {
+        match &mut *self.permissioning.write().await {
+            Permissioning::Open => Err(ConsensusError::config_error(
+                "Engine is not using an allowlist",
+            )),
+            Permissioning::Allowlist(keys) => {
+                keys.insert(public_key_hex.into());
+                Ok(())
+            }
+        }
+    }
+
+    /// Stop allowing a validator public key (hex) to register
+    ///
+    /// Returns whether the key was on the allowlist. Validators that already registered
+    /// are not removed.
+    pub async fn remove_from_allowlist(&self, public_key_hex: &str) -> Result<bool> {
+        match &mut *self.permissioning.write().await {
+            Permissioning::Open => Err(ConsensusError::config_error(
+                "Engine is not using an allowlist",
+            )),
+            Permissioning::Allowlist(keys) => Ok(keys.remove(public_key_hex)),
+        }
+    }
+
+    /// Keep at most `depth` per-epoch metrics snapshots
+    pub fn with_metrics_history_depth(mut self, depth: usize) -> Self {
+        self.metrics_history_depth = depth;
+        self
+    }
+
+    /// Keep signed audit bundles of the `epochs` most recent epochs (0 disables them)
+    pub fn with_epoch_bundle_retention(mut self, epochs: usize) -> Self {
+        self.epoch_bundle_retention = epochs;
+        self
+    }
+
+    /// Record the inputs and outcome of every epoch for [`replay_epoch`](Self::replay_epoch)
+    pub fn with_epoch_recorder(mut self, recorder: Arc<EpochRecorder>) -> Self {
+        self.epoch_recorder = Some(recorder);
+        self
+    }
+
+    /// Penalize detected double voting and double signing according to a policy
+    pub fn with_slashing_policy(self, policy: SlashingPolicy) -> Self {
+        self.byzantine_detector.set_slashing_policy(policy);
+        self
+    }
+
+    /// Get the active configuration
+    pub async fn get_config(&self) -> ConsensusConfig {
+        self.config.read().await.clone()
+    }
+
+    /// Execute finalized transactions against a state machine
+    ///
+    /// Proposed blocks then carry the resulting state root, and blocks whose state
+    /// root does not match local execution are rejected.
+    pub fn with_state_machine(mut self, state_machine: Arc<dyn StateMachine>) -> Self {
+        self.state_machine = Some(state_machine);
+        self
+    }
+
+    /// Record slashing, finalization, fork resolution and checkpoint creation
+    pub fn with_audit_sink(self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit.set(sink);
+        self
+    }
+
+    /// Push a final metrics snapshot to `prometheus` on [`stop_and_flush`](Self::stop_and_flush)
+    pub fn with_prometheus_metrics(mut self, prometheus: Arc<PrometheusMetrics>) -> Self {
+        self.prometheus = Some(prometheus);
+        self
+    }
+
+    /// Persist a state snapshot to `store` on [`stop_and_flush`](Self::stop_and_flush)
+    pub fn with_snapshot_store(mut self, store: Arc<dyn SnapshotStore>) -> Self {
+        self.snapshot_store = Some(store);
+        self
+    }
+
+    /// Include validator emotional profiles and score history in snapshots
+    pub fn with_emotional_state_persistence(mut self, enabled: bool) -> Self {
+        self.persist_emotional_state = enabled;
+        self
+    }
+
+    /// Schedule a configuration change for the next epoch boundary
+    ///
+    /// The patch is validated against the configuration it will produce; patches
+    /// submitted before the boundary is reached are merged.
+    pub async fn update_config(&self, patch: ConfigPatch) -> Result<()> {
+        let mut pending = self.pending_config.lock().await;
+        let base = match pending.as_ref() {
+            Some(config) => config.clone(),
+            None => self.config.read().await.clone(),
+        };
+
+        let updated = base.patched(&patch);
+        updated.validate()?;
+
+        info!(
+            "⚙️  Configuration update scheduled for next epoch: {:?}",
+            patch
+        );
+        *pending = Some(updated);
+
+        Ok(())
+    }
+
+    /// Apply a scheduled configuration change, if any
+    async fn apply_pending_config(&self) {
+        if let Some(config) = self.pending_config.lock().await.take() {
+            info!("⚙️  Applying configuration update: {:?}", config);
+            *self.config.write().await = config;
+        }
+    }
+
+    /// Set the effective thresholds from the schedule tier covering the current validator
+    /// count, or from the configuration without a schedule
+    async fn apply_threshold_schedule(&self) {
+        let config = self.get_config().await;
+        let tier = config
+            .threshold_schedule
+            .as_ref()
+            .and_then(|schedule| schedule.tier_for(self.validators.len()));
+        let thresholds = match tier {
+            Some(tier) => (tier.byzantine_threshold, tier.emotional_threshold),
+            None => (config.byzantine_threshold, config.emotional_threshold),
+        };
+
+        let mut state = self.state.write().await;
+        if (
+            state.effective_byzantine_threshold,
+            state.effective_emotional_threshold,
+        ) != thresholds
+        {
+            info!(
+                "📏 {} validators: byzantine threshold {}%, emotional threshold {}",
+                self.validators.len(),
+                thresholds.0,
+                thresholds.1
+            );
+        }
+        (
+            state.effective_byzantine_threshold,
+            state.effective_emotional_threshold,
+        ) = thresholds;
+    }
+
+    /// Configuration with the thresholds in effect for the current epoch
+    async fn effective_config(&self) -> ConsensusConfig {
+        let mut config = self.get_config().await;
+        let state = self.state.read().await;
+        config.byzantine_threshold = state.effective_byzantine_threshold;
+        config.emotional_threshold = state.effective_emotional_threshold;
+        config
+    }
+
+    /// Register a validator
+    pub async fn register_validator(&self, validator: EmotionalValidator) -> Result<()> {
+        self.check_registration(&validator).await?;
+        self.insert_validator(validator);
+        self.state.write().await.total_validators = self.validators.len();
+        Ok(())
+    }
+
+    /// Register several validators as one all-or-nothing operation
+    ///
+    /// Every validator is checked against the permissioning policy and minimum stake
+    /// before any is inserted, so a rejected validator leaves the set unchanged.
+    pub async fn register_validators(&self, validators: Vec<EmotionalValidator>) -> Result<()> {
+        for validator in &validators {
+            self.check_registration(validator).await?;
+        }
+
+        for validator in validators {
+            self.insert_validator(validator);
+        }
+        self.state.write().await.total_validators = self.validators.len();
+
+        Ok(())
+    }
+
+    /// Check that a validator may register
+    async fn check_registration(&self, validator: &EmotionalValidator) -> Result<()> {
+        if !self
+            .permissioning
+            .read()
+            .await
+            .permits(&validator.public_key_hex())
+        {
+            return Err(ConsensusError::validator_not_permitted(validator.id()));
+        }
+
+        let minimum_stake = self.config.read().await.minimum_stake;
+        if validator.get_stake() < minimum_stake {
+            return Err(ConsensusError::insufficient_stake(
+                validator.get_stake(),
+                minimum_stake,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Add a checked validator, replacing any registered under the same ID
+    fn insert_validator(&self, validator: EmotionalValidator) {
+        let id = validator.id().to_string();
+        let stake = validator.get_stake();
+        self.byzantine_detector
+            .register_validator_key(&id, &validator.public_key_hex());
+        self.total_stake.fetch_add(stake, AtomicOrdering::SeqCst);
+        if let Some(replaced) = self.validators.insert(id.clone(), Arc::new(validator)) {
+            self.total_stake
+                .fetch_sub(replaced.get_stake(), AtomicOrdering::SeqCst);
+        }
+
+        info!(
+            "✅ Validator {} registered with {} POE stake",
+            id,
+            stake
+        );
+    }
+
+    /// Remove a registered validator
+    pub fn deregister_validator(&self, validator_id: &str) -> Result<()> {
+        let (_, validator) = self
+            .validators
+            .remove(validator_id)
+            .ok_or_else(|| ConsensusError::validator_not_found(validator_id))?;
+        self.total_stake
+            .fetch_sub(validator.get_stake(), AtomicOrdering::SeqCst);
+        self.byzantine_detector.remove_validator_key(validator_id);
+
+        info!("👋 Validator {} deregistered", validator_id);
+        Ok(())
+    }
+
+    /// Slash part of a validator's stake, returning the amount actually removed
+    pub fn slash_stake(&self, validator_id: &str, amount: u64) -> Result<u64> {
+        // Holding the entry keeps a concurrent deregistration from counting the stake twice
+        let validator = self
+            .validators
+            .get(validator_id)
+            .ok_or_else(|| ConsensusError::validator_not_found(validator_id))?;
+        let slashed = validator.apply_slashing(amount);
+        self.total_stake.fetch_sub(slashed, AtomicOrdering::SeqCst);
+        drop(validator);
+
+        warn!(
+            "⚖️  Slashed {} POE stake from validator {}",
+            slashed, validator_id
+        );
+        Ok(slashed)
+    }
+
+    /// Total stake of all registered validators
+    pub fn total_stake(&self) -> u64 {
+        self.total_stake.load(AtomicOrdering::SeqCst)
+    }
+
+    /// Register every validator defined in a JSON validator set file
+    ///
+    /// Validators listed with a public key are registered with the matching entry of
+    /// `key_pairs`; the others get a generated key pair. Nothing is registered if any
+    /// validator is rejected. Returns the number registered.
+    pub async fn register_validators_from_file(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        key_pairs: &[KeyPair],
+    ) -> Result<usize> {
+        let validators = ValidatorSet::from_json(path)?.into_validators(key_pairs)?;
+        let count = validators.len();
+        self.register_validators(validators).await?;
+        Ok(count)
+    }
+
+    /// Export the registered validators as a validator set
+    pub fn export_validator_set(&self) -> ValidatorSet {
+        let mut validators: Vec<ValidatorRecord> = self
+            .validators
+            .iter()
+            .map(|entry| ValidatorRecord {
+                id: entry.id().to_string(),
+                stake: entry.get_stake(),
+                commission: entry.commission(),
+                public_key: Some(entry.public_key_hex()),
+            })
+            .collect();
+        validators.sort_by(|a, b| a.id.cmp(&b.id));
+        ValidatorSet { validators }
+    }
+
+    /// Start consensus engine
+    pub async fn start(self: Arc<Self>) -> Result<()> {
+        let mut running = self.is_running.write().await;
+        if *running {
+            return Err(ConsensusError::AlreadyRunning);
+        }
+        *running = true;
+        drop(running);
+        self.state.write().await.paused = false;
+
+        let config = self.get_config().await;
+        info!("🚀 Starting Proof of Emotion consensus engine");
+        info!("⚙️  Epoch duration: {}ms", config.epoch_duration);
+        info!("💓 Emotional threshold: {}%", config.emotional_threshold);
+        info!("🛡️  Byzantine threshold: {}%", config.byzantine_threshold);
+
+        let engine = Arc::clone(&self);
+
+        tokio::spawn(async move {
+            engine.epoch_loop().await;
+        });
+
+        // Spawn periodic cleanup task to prevent memory leak from expired transactions
+        let cleanup_engine = Arc::clone(&self);
+        tokio::spawn(async move {
+            let mut cleanup_interval = time::interval(Duration::from_secs(60));
+            loop {
+                cleanup_interval.tick().await;
+                if !*cleanup_engine.is_running.read().await {
+                    break;
+                }
+                cleanup_engine.cleanup_transaction_pool().await;
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Stop consensus engine
+    pub async fn stop(&self) -> Result<()> {
+        let mut running = self.is_running.write().await;
+        if !*running {
+            return Err(ConsensusError::NotRunning);
+        }
+        *running = false;
+        drop(running);
+        self.state.write().await.current_committee.clear();
+
+        info!("🛑 Stopping Proof of Emotion consensus engine");
+
+        // Notify shutdown signal to immediately stop epoch loop
+        self.shutdown_signal.notify_waiters();
+
+        Ok(())
+    }
+
+    /// Stop the engine and flush what would otherwise be lost on shutdown
+    ///
+    /// Checkpoints the finalized tip, pushes the metrics to the Prometheus exporter and
+    /// persists a state snapshot, the latter two only if configured. The flush also runs
+    /// if the engine was not running, e.g. when only driven epoch by epoch. It waits for
+    /// an epoch in progress to finish, so everything reflects the same tip. A checkpoint
+    /// that cannot be signed, e.g. without enough active stake, is logged and skipped.
+    pub async fn stop_and_flush(&self) -> Result<()> {
+        match self.stop().await {
+            Ok(()) | Err(ConsensusError::NotRunning) => {}
+            Err(e) => return Err(e),
+        }
+        let _epoch_in_progress = self.epoch_in_progress.lock().await;
+
+        let tip = self.finalized_blocks.read().await.last().cloned();
+        if let Some(tip) = &tip {
+            if let Err(e) = self.create_final_checkpoint(tip).await {
+                warn!(
+                    "Skipping final checkpoint at height {}: {}",
+                    tip.header.height, e
+                );
+            }
+        }
+
+        if let Some(prometheus) = &self.prometheus {
+            prometheus.update_from_consensus(&*self.metrics.read().await);
+        }
+
+        if let Some(store) = &self.snapshot_store {
+            store.persist(&self.snapshot().await)?;
+        }
+
+        info!("💾 Flushed checkpoint, metrics and snapshot on shutdown");
+        Ok(())
+    }
+
+    /// Capture the consensus state and metrics, and validator emotional state if
+    /// persistence is enabled
+    pub async fn snapshot(&self) -> StateSnapshot {
+        let tip_hash = self
+            .finalized_blocks
+            .read()
+            .await
+            .last()
+            .map(|block| block.hash.clone());
+        let state = self.state.read().await.clone();
+        let validator_states = if self.persist_emotional_state {
+            self.validators
+                .iter()
+                .map(|entry| entry.value().save_state())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        StateSnapshot {
+            taken_at: self.clock.now_millis(),
+            finalized_height: state.last_finalized_height,
+            tip_hash,
+            state,
+            metrics: self.metrics.read().await.clone(),
+            validator_states,
+        }
+    }
+
+    /// Reload validator emotional state from a snapshot
+    ///
+    /// Profiles older than one epoch are dropped, matching the staleness check applied
+    /// at assessment. Validators that are not registered are skipped. Returns the
+    /// number of validators restored.
+    pub async fn restore_emotional_state(&self, snapshot: &StateSnapshot) -> Result<usize> {
+        let max_age_ms = self.config.read().await.epoch_duration;
+        let mut restored = 0;
+        for state in &snapshot.validator_states {
+            if let Some(validator) = self.validators.get(&state.validator_id) {
+                validator.load_state(state.clone(), max_age_ms)?;
+                restored += 1;
+            }
+        }
+
+        info!("Restored emotional state of {} validators", restored);
+        Ok(restored)
+    }
+
+    /// Suspend epoch execution without stopping the engine
+    ///
+    /// The epoch loop keeps running and state is retained; transactions submitted while
+    /// paused stay pending until [`resume`](Self::resume) is called. Returns once an
+    /// epoch already in progress has finished, so no block is finalized afterwards.
+    pub async fn pause(&self) -> Result<()> {
+        if !*self.is_running.read().await {
+            return Err(ConsensusError::NotRunning);
+        }
+
+        let mut state = self.state.write().await;
+        if state.paused {
+            return Ok(());
+        }
+        state.paused = true;
+        drop(state);
+
+        // The loop checks the flag under this lock, so later ticks skip their epoch
+        drop(self.epoch_in_progress.lock().await);
+
+        let mut state = self.state.write().await;
+        state.current_committee.clear();
+        info!("⏸️  Consensus paused at epoch {}", state.current_epoch);
+
+        Ok(())
+    }
+
+    /// Resume epoch execution after [`pause`](Self::pause)
+    pub async fn resume(&self) -> Result<()> {
+        if !*self.is_running.read().await {
+            return Err(ConsensusError::NotRunning);
+        }
+
+        let mut state = self.state.write().await;
+        if state.paused {
+            state.paused = false;
+            info!("▶️  Consensus resumed at epoch {}", state.current_epoch);
+        }
+
+        Ok(())
+    }
+
+    /// Main epoch processing loop
+    async fn epoch_loop(&self) {
+        let mut epoch_duration = self.state.read().await.effective_epoch_duration;
+        let mut interval = time::interval(Duration::from_millis(epoch_duration));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    // Check if we should continue running
+                    if !*self.is_running.read().await {
+                        break;
+                    }
+
+                    let epoch_in_progress = self.epoch_in_progress.lock().await;
+                    // Stopping may have waited for this lock to flush the final state
+                    if !*self.is_running.read().await {
+                        break;
+                    }
+                    if self.state.read().await.paused {
+                        continue;
+                    }
+
+                    if let Err(e) = self.run_epoch().await {
+                        error!("🛑 Halting consensus: {}", e);
+                        *self.is_running.write().await = false;
+                        self.state.write().await.current_committee.clear();
+                        break;
+                    }
+                    drop(epoch_in_progress);
+
+                    // Re-arm the interval if the epoch duration was changed or re-tuned
+                    let current_duration = self.state.read().await.effective_epoch_duration;
+                    if current_duration != epoch_duration {
+                        epoch_duration = current_duration;
+                        let period = Duration::from_millis(epoch_duration);
+                        interval = time::interval_at(time::Instant::now() + period, period);
+                        info!("⚙️  Epoch duration changed to {}ms", epoch_duration);
+                    }
+                }
+                _ = self.shutdown_signal.notified() => {
+                    info!("🛑 Shutdown signal received, stopping epoch loop");
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Execute an epoch, count its outcome and snapshot the metrics
+    ///
+    /// Fails with [`ConsensusError::StorageError`] once `max_tip_retries` consecutive
+    /// epochs have failed without the chain tip advancing, which stops the epoch loop.
+    async fn run_epoch(&self) -> Result<()> {
+        let start_ms = self.clock.now_millis();
+        let byzantine_before = self.metrics.read().await.byzantine_failures;
+        let tip_before = self.state.read().await.last_finalized_height;
+        let result = self.execute_epoch().await;
+        self.tune_epoch_duration(self.clock.now_millis().saturating_sub(start_ms))
+            .await;
+
+        let mut metrics = self.metrics.write().await;
+        let byzantine = metrics.byzantine_failures > byzantine_before;
+        match &result {
+            Ok(_) => metrics.successful_epochs += 1,
+            Err(e) => {
+                error!("❌ Epoch failed: {}", e);
+                metrics.failed_epochs += 1;
+            }
+        }
+        drop(metrics);
+
+        self.update_network_health(result.is_err(), byzantine).await;
+        self.record_metrics_snapshot().await;
+
+        let tip = self.state.read().await.last_finalized_height;
+        if result.is_ok() || tip != tip_before {
+            self.tip_retries.store(0, AtomicOrdering::Relaxed);
+            return Ok(());
+        }
+
+        let retries = self.tip_retries.fetch_add(1, AtomicOrdering::Relaxed) + 1;
+        let max_retries = self.get_config().await.max_tip_retries;
+        if max_retries > 0 && retries >= max_retries as u64 {
+            return Err(ConsensusError::storage_error(format!(
+                "chain tip at height {} failed {} consecutive epochs; the tip block or its \
+                 parent may be corrupted, inspect storage before restarting",
+                tip, retries
+            )));
+        }
+        Ok(())
+    }
+
+    /// Consecutive failed epochs since the chain tip last advanced
+    pub fn current_tip_retry_count(&self) -> u64 {
+        self.tip_retries.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Recompute the network health score after an epoch
+    async fn update_network_health(&self, failed: bool, byzantine: bool) {
+        let mut outcomes = self.recent_epoch_outcomes.write().await;
+        outcomes.push_back((failed, byzantine));
+        while outcomes.len() > NETWORK_HEALTH_WINDOW_EPOCHS {
+            outcomes.pop_front();
+        }
+
+        let epochs = outcomes.len() as f64;
+        let failed_ratio = outcomes.iter().filter(|(failed, _)| *failed).count() as f64 / epochs;
+        let byzantine_ratio =
+            outcomes.iter().filter(|(_, byzantine)| *byzantine).count() as f64 / epochs;
+        drop(outcomes);
+
+        let weights = self.get_config().await.network_health_weights;
+        let mut state = self.state.write().await;
+        state.network_health = weights.score(
+            state.participation_rate,
+            state.consensus_strength,
+            failed_ratio,
+            byzantine_ratio,
+        );
+    }
+
+    /// Set the interval for the next epoch from the configuration and the last epoch's duration
+    async fn tune_epoch_duration(&self, measured_ms: u64) {
+        let config = self.get_config().await;
+        let duration = match &config.adaptive_epoch_duration {
+            Some(adaptive) => adaptive.next_duration(measured_ms),
+            None => config.epoch_duration,
+        };
+        self.state.write().await.effective_epoch_duration = duration;
+    }
+
+    /// Append the current metrics to the history, dropping the oldest beyond its depth
+    async fn record_metrics_snapshot(&self) {
+        let snapshot = self.metrics.read().await.clone();
+        let mut history = self.metrics_history.write().await;
+        history.push_back((self.clock.now_millis(), snapshot));
+        while history.len() > self.metrics_history_depth {
+            history.pop_front();
+        }
+    }
+
+    /// Execute a single epoch
+    async fn execute_epoch(&self) -> Result<()> {
+        let start_time = std::time::Instant::now();
+
+        self.apply_pending_config().await;
+        self.apply_threshold_schedule().await;
+
+        let mut state = self.state.write().await;
+        state.current_epoch += 1;
+        state.current_committee.clear();
+        let epoch = state.current_epoch;
+        drop(state);
+
+        self.signature_cache.clear();
+        self.byzantine_detector.set_current_epoch(epoch);
+        self.release_jailed_validators(epoch);
+
+        info!("⏰ Starting epoch {}", epoch);
+
+        let eligible_validators = self.perform_emotional_assessment().await?;
+
+        if eligible_validators.is_empty() {
+            // Without any validator there was no one to be unfit
+            let assessed = self.last_assessment.read().await.len();
+            if assessed > 0 {
+                self.metrics.write().await.emotional_failures += 1;
+                warn!(
+                    "😞 Epoch {}: none of {} validators is eligible",
+                    epoch, assessed
+                );
+                let _ = self
+                    .events
+                    .send(ConsensusEvent::NoEligibleValidators { epoch, assessed });
+            }
+            return Err(ConsensusError::committee_selection_failed(
+                "No validators meet emotional fitness threshold",
+            ));
+        }
+
+        info!(
+            "💓 {}/{} validators eligible",
+            eligible_validators.len(),
+            self.validators.len()
+        );
+
+        let (committee, committee_proof) = self.select_committee(&eligible_validators).await?;
+
+        info!("👥 Committee selected: {} validators", committee.len());
+        self.state.write().await.current_committee = committee_proof.members.clone();
+        *self.previous_committee.write().await = committee_proof.members.clone();
+
+        self.check_solo_mode(committee.len());
+
+        // The detector shares the engine's clock, so its events are keyed on the same timeline
+        let slashing_start = self.clock.now_millis();
+        let mut rounds = Vec::new();
+
+        let blocks_per_epoch = self.get_config().await.blocks_per_epoch;
+        let mut committee_proof = committee_proof;
+        for round in 0..blocks_per_epoch as u32 {
+            if round > 0 {
+                // The seed comes from the beacon, so only the chain tip moves between rounds
+                committee_proof.previous_hash = self
+                    .finalized_blocks
+                    .read()
+                    .await
+                    .last()
+                    .map(|block| block.hash.clone())
+                    .unwrap_or_else(|| "0".repeat(64));
+            }
+
+            let proposed_block = self.propose_block(&committee, &mut committee_proof).await?;
+
+            info!(
+                "📦 Block {} proposed by {}",
+                proposed_block.header.height, proposed_block.header.validator_id
+            );
+
+            let voting_result = self
+                .execute_voting(&committee, &proposed_block, round)
+                .await?;
+
+            if let Some(recorder) = &self.epoch_recorder {
+                let record = self
+                    .epoch_record(
+                        &eligible_validators,
+                        &committee_proof,
+                        &proposed_block,
+                        &voting_result,
+                    )
+                    .await;
+                recorder.record(record);
+            }
+
+            if self.epoch_bundle_retention > 0 {
+                rounds.push(BundledRound {
+                    block: proposed_block.clone(),
+                    voting_result: voting_result.clone(),
+                });
+            }
+
+            if !voting_result.success {
+                self.store_epoch_bundle(epoch, &committee, rounds, slashing_start)
+                    .await;
+                warn!("❌ Voting failed: {:?}", voting_result.reason);
+                return Err(ConsensusError::invalid_block(
+                    voting_result
+                        .reason
+                        .unwrap_or_else(|| "Voting failed".to_string()),
+                ));
+            }
+
+            info!(
+                "✅ Consensus reached: {}% strength",
+                voting_result.consensus_strength
+            );
+
+            self.finalize_block(proposed_block, voting_result, committee_proof.clone())
+                .await?;
+        }
+        self.store_epoch_bundle(epoch, &committee, rounds, slashing_start)
+            .await;
+
+        let duration = start_time.elapsed().as_millis() as u64;
+        let mut metrics = self.metrics.write().await;
+        metrics.total_epochs += 1;
+        metrics.average_duration_ms = (metrics.average_duration_ms * (metrics.total_epochs - 1)
+            + duration)
+            / metrics.total_epochs;
+
+        info!("✨ Epoch {} completed in {}ms", epoch, duration);
+
+        Ok(())
+    }
+
+    /// Sign an epoch's rounds and slashing events into a bundle and keep it
+    ///
+    /// The committee's proposer signs; the oldest bundles beyond the retention are dropped.
+    async fn store_epoch_bundle(
+        &self,
+        epoch: u64,
+        committee: &[Arc<EmotionalValidator>],
+        rounds: Vec<BundledRound>,
+        slashing_start: u64,
+    ) {
+        if self.epoch_bundle_retention == 0 {
+            return;
+        }
+        let Some(proposer) = committee.first() else {
+            return;
+        };
+
+        let members = committee.iter().map(|v| v.id().to_string()).collect();
+        let slashing_events = self
+            .byzantine_detector
+            .slashing_events_since(slashing_start)
+            .await;
+        let bundle =
+            match EpochBundle::new(epoch, members, rounds, slashing_events, &proposer.key_pair) {
+                Ok(bundle) => bundle,
+                Err(e) => {
+                    warn!("Failed to sign audit bundle for epoch {}: {}", epoch, e);
+                    return;
+                }
+            };
+
+        let mut bundles = self.epoch_bundles.write().await;
+        bundles.push_back(bundle);
+        while bundles.len() > self.epoch_bundle_retention {
+            bundles.pop_front();
+        }
+    }
+
+    /// Signed audit bundle of an epoch, if it is among the retained ones
+    pub async fn export_epoch_bundle(&self, epoch: u64) -> Option<EpochBundle> {
+        self.epoch_bundles
+            .read()
+            .await
+            .iter()
+            .find(|bundle| bundle.epoch == epoch)
+            .cloned()
+    }
+
+    /// Phase 1: Perform emotional assessment
+    async fn perform_emotional_assessment(&self) -> Result<Vec<Arc<EmotionalValidator>>> {
+        let assessment = self.assess_validators().await?;
+
+        Ok(assessment
+            .iter()
+            .filter(|(_, status)| *status == EligibilityStatus::Eligible)
+            .filter_map(|(id, _)| self.validators.get(id).map(|v| Arc::clone(v.value())))
+            .collect())
+    }
+
+    /// Read biometrics for every validator and report its eligibility
+    ///
+    /// Up to `assessment_concurrency` validators are assessed at once. The result is
+    /// ordered by validator ID, logs ineligible validators with the reason they were
+    /// excluded, and is kept for [`get_last_assessment`](Self::get_last_assessment).
+    pub async fn assess_validators(&self) -> Result<Vec<(String, EligibilityStatus)>> {
+        let config = self.effective_config().await;
+
+        // Snapshot the validators so no DashMap guard is held across an await
+        let validators: Vec<Arc<EmotionalValidator>> = self
+            .validators
+            .iter()
+            .map(|entry| Arc::clone(entry.value()))
+            .collect();
+
+        let results: Vec<_> = stream::iter(validators)
+            .map(|validator| {
+                let config = &config;
+                async move {
+                    let status = self.assess_validator(&validator, config).await;
+                    (validator, status)
+                }
+            })
+            .buffer_unordered(config.assessment_concurrency)
+            .collect()
+            .await;
+
+        let mut assessment = Vec::with_capacity(results.len());
+        for (validator, status) in results {
+            let status = status?;
+            if let EligibilityStatus::Ineligible(reason) = &status {
+                info!("🚫 Validator {} ineligible: {}", validator.id(), reason);
+            }
+            let mut activity = self.activity.entry(validator.id().to_string()).or_default();
+            activity.assessments += 1;
+            if status == EligibilityStatus::Eligible {
+                activity.eligible_assessments += 1;
+            }
+            drop(activity);
+            assessment.push((validator.id().to_string(), status));
+        }
+        assessment.sort_by(|a, b| a.0.cmp(&b.0));
+
+        *self.last_assessment.write().await = assessment.clone();
+        if let Some(detection) = config.collusion_detection {
+            self.detect_collusion(detection).await;
+        }
+
+        Ok(assessment)
+    }
+
+    /// Flag validator pairs whose recent scores correlate too closely
+    ///
+    /// Newly flagged pairs are announced with a [`ConsensusEvent::CollusionSuspected`]
+    /// for investigation; no one is slashed.
+    async fn detect_collusion(&self, detection: CollusionDetection) {
+        let series: Vec<(String, Vec<f64>)> = self
+            .validators
+            .iter()
+            .map(|entry| {
+                let scores = entry.value().recent_scores(detection.window);
+                (
+                    entry.key().clone(),
+                    scores.into_iter().map(f64::from).collect(),
+                )
+            })
+            .collect();
+        let suspects = detection.find_suspects(&series);
+
+        let mut previous = self.collusion_suspects.write().await;
+        for suspect in &suspects {
+            if previous.iter().any(|known| known.same_pair(suspect)) {
+                continue;
+            }
+            warn!(
+                "🕵️  Possible collusion: {} and {} scores correlate at {:.2}",
+                suspect.first, suspect.second, suspect.correlation
+            );
+            let _ = self.events.send(ConsensusEvent::CollusionSuspected {
+                first: suspect.first.clone(),
+                second: suspect.second.clone(),
+            });
+        }
+        *previous = suspects;
+    }
+
+    /// Validator pairs flagged by the most recent collusion check
+    pub async fn get_collusion_suspects(&self) -> Vec<CollusionSuspect> {
+        self.collusion_suspects.read().await.clone()
+    }
+
+    /// Eligibility of every validator at the most recent assessment
+    pub async fn get_last_assessment(&self) -> Vec<(String, EligibilityStatus)> {
+        self.last_assessment.read().await.clone()
+    }
+
+    async fn assess_validator(
+        &self,
+        validator: &EmotionalValidator,
+        config: &ConsensusConfig,
+    ) -> Result<EligibilityStatus> {
+        if let Some(verifier) = &self.zk_verifier {
+            let epoch = self.state.read().await.current_epoch;
+            let status = Self::assess_biometric_proof(validator, verifier.as_ref(), config, epoch);
+            return Ok(status);
+        }
+
+        let readings = match self.collect_readings_with_retry(validator, config).await {
+            Ok(readings) => readings,
+            Err(e) => {
+                return Ok(EligibilityStatus::Ineligible(
+                    IneligibilityReason::DeviceReadFailed(e.to_string()),
+                ))
+            }
+        };
+
+        if let Some(newest) = readings.iter().map(|reading| reading.timestamp).max() {
+            let age_ms = self.clock.now_millis().saturating_sub(newest);
+            if age_ms > config.epoch_duration {
+                return Ok(EligibilityStatus::Ineligible(
+                    IneligibilityReason::StaleProfile { age_ms },
+                ));
+            }
+
+            // Every reading behind the score must come from the same window
+            let oldest = readings.iter().map(|reading| reading.timestamp).min();
+            let spread_ms = newest - oldest.unwrap_or(newest);
+            if spread_ms > config.temporal_window_ms {
+                return Ok(EligibilityStatus::Ineligible(
+                    IneligibilityReason::ReadingsOutsideWindow {
+                        spread_ms,
+                        window_ms: config.temporal_window_ms,
+                    },
+                ));
+            }
+        }
+
+        if let Err(e) = validator.update_emotional_state(readings).await {
+            return Ok(EligibilityStatus::Ineligible(
+                IneligibilityReason::InvalidReadings(e.to_string()),
+            ));
+        }
+        self.track_emotional_decline(validator, config.max_declining_epochs);
+
+        let anomaly_count = validator.get_anomaly_count();
+        if anomaly_count > 0 && validator.biometric_config().slash_on_anomaly {
+            self.slash_validator(
+                validator.id(),
+                SlashingOffense::InvalidBiometric.severity(),
+                &format!(
+                    "Invalid biometric data: {} anomalous readings",
+                    anomaly_count
+                ),
+            )
+            .await?;
+            return Ok(EligibilityStatus::Ineligible(
+                IneligibilityReason::BiometricAnomaly { anomaly_count },
+            ));
+        }
+
+        Ok(config.eligibility_policy.evaluate(
+            validator,
+            config.emotional_threshold,
+            config.minimum_stake,
+        ))
+    }
+
+    /// Assess a validator from its submitted biometric proof for `epoch`
+    fn assess_biometric_proof(
+        validator: &EmotionalValidator,
+        verifier: &dyn ZkBiometricProvider,
+        config: &ConsensusConfig,
+        epoch: u64,
+    ) -> EligibilityStatus {
+        let Some(proof) = validator.take_biometric_proof() else {
+            return EligibilityStatus::Ineligible(IneligibilityReason::MissingProof);
+        };
+        let Some(score) = proof.emotional_score() else {
+            return EligibilityStatus::Ineligible(IneligibilityReason::InvalidProof(
+                "public inputs must be a single score of at most 100".to_string(),
+            ));
+        };
+
+        match verifier.verify(&proof, validator.id(), epoch, &[score]) {
+            Ok(true) => {}
+            Ok(false) => {
+                return EligibilityStatus::Ineligible(IneligibilityReason::InvalidProof(
+                    "verification failed".to_string(),
+                ))
+            }
+            Err(e) => {
+                return EligibilityStatus::Ineligible(IneligibilityReason::InvalidProof(
+                    e.to_string(),
+                ))
+            }
+        }
+
+        validator.apply_proven_score(score);
+        config.eligibility_policy.evaluate(
+            validator,
+            config.emotional_threshold,
+            config.minimum_stake,
+        )
+    }
+
+    /// Capture the inputs and outcome of the current epoch
+    async fn epoch_record(
+        &self,
+        eligible: &[Arc<EmotionalValidator>],
+        committee_proof: &CommitteeProof,
+        block: &Block,
+        voting_result: &VotingResult,
+    ) -> EpochRecord {
+        let config = self.effective_config().await;
+        let eligible = eligible
+            .iter()
+            .zip(Self::selection_candidates(
+                eligible,
+                &config,
+                &committee_proof.cooled_down,
+            ))
+            .map(|(validator, candidate)| RecordedValidator {
+                validator_id: candidate.validator_id,
+                stake: validator.get_stake(),
+                emotional_score: validator.get_emotional_score(),
+                weight: candidate.weight,
+            })
+            .collect();
+
+        EpochRecord {
+            epoch: committee_proof.epoch,
+            previous_hash: committee_proof.previous_hash.clone(),
+            randomness: committee_proof.randomness.clone(),
+            selection_mode: committee_proof.selection_mode,
+            committee_size: committee_proof.committee_size,
+            byzantine_threshold: config.byzantine_threshold,
+            stake_weighted_voting: config.stake_weighted_voting,
+            eligible,
+            retained: committee_proof.retained.clone(),
+            committee: committee_proof.members.clone(),
+            block_hash: block.hash.clone(),
+            voting_result: voting_result.clone(),
+        }
+    }
+
+    /// Re-run committee selection and vote counting for a recorded epoch
+    ///
+    /// Works from the record alone, without live biometrics or engine state, and fails
+    /// with [`ConsensusError::ReplayDiverged`] if the committee or voting outcome differs
+    /// from what was recorded.
+    pub fn replay_epoch(&self, record: &EpochRecord) -> Result<VotingResult> {
+        let epoch = record.epoch;
+        let seed_source = if record.randomness.is_empty() {
+            &record.previous_hash
+        } else {
+            &record.randomness
+        };
+        let candidates: Vec<CommitteeCandidate> = record
+            .eligible
+            .iter()
+            .map(|validator| CommitteeCandidate::new(&validator.validator_id, validator.weight))
+            .collect();
+
+        let members = committee::select_committee_with_retained(
+            &candidates,
+            record.committee_size,
+            record.selection_mode,
+            committee::selection_seed(epoch, seed_source),
+            &record.retained,
+        )?;
+        if members != record.committee {
+            return Err(ConsensusError::replay_diverged(
+                epoch,
+                format!(
+                    "selected committee {:?}, recorded {:?}",
+                    members, record.committee
+                ),
+            ));
+        }
+
+        let member_stakes: Vec<(String, u64)> = members
+            .iter()
+            .map(|id| {
+                let stake = record
+                    .eligible
+                    .iter()
+                    .find(|validator| validator.validator_id == *id)
+                    .map_or(0, |validator| validator.stake);
+                (id.clone(), stake)
+            })
+            .collect();
+
+        let recorded = &record.voting_result;
+        if let Some(vote) = recorded.votes.iter().find(|vote| {
+            vote.block_hash != record.block_hash || !members.contains(&vote.validator_id)
+        }) {
+            return Err(ConsensusError::replay_diverged(
+                epoch,
+                format!("unexpected vote from {}", vote.validator_id),
+            ));
+        }
+
+        let replayed = Self::tally_votes(
+            &member_stakes,
+            recorded.votes.clone(),
+            recorded.byzantine_count,
+            record.byzantine_threshold,
+            record.stake_weighted_voting,
+        );
+
+        let outcome = |result: &VotingResult| {
+            (
+                result.success,
+                result.consensus_strength,
+                result.headcount_strength,
+                result.stake_strength,
+                result.participant_count,
+                result.average_emotional_score,
+            )
+        };
+        if outcome(&replayed) != outcome(recorded) {
+            return Err(ConsensusError::replay_diverged(
+                epoch,
+                format!(
+                    "voting outcome {:?}, recorded {:?}",
+                    outcome(&replayed),
+                    outcome(recorded)
+                ),
+            ));
+        }
+
+        Ok(replayed)
+    }
+
+    /// Read the validator's biometric device, retrying transient failures
+    ///
+    /// Makes up to `device_read_attempts` attempts, waiting `device_retry_backoff_ms`
+    /// before the first retry and twice as long before each further one.
+    async fn collect_readings_with_retry(
+        &self,
+        validator: &EmotionalValidator,
+        config: &ConsensusConfig,
+    ) -> Result<Vec<BiometricReading>> {
+        let mut backoff_ms = config.device_retry_backoff_ms;
+        let mut attempt = 1;
+
+        loop {
+            let readings = match validator.device() {
+                Some(device) => device.collect_readings(),
+                None => {
+                    BiometricSimulator::new(format!("device_{}", validator.id()), validator.id())
+                        .collect_readings()
+                }
+            };
+
+            match readings {
+                Ok(readings) => return Ok(readings),
+                Err(e) if attempt >= config.device_read_attempts => {
+                    return Err(ConsensusError::biometric_validation_failed(format!(
+                        "{} (after {} attempts)",
+                        e, attempt
+                    )))
+                }
+                Err(e) => {
+                    warn!(
+                        "Biometric read {} of {} failed for validator {}: {}",
+                        attempt,
+                        config.device_read_attempts,
+                        validator.id(),
+                        e
+                    );
+                    time::sleep(Duration::from_millis(backoff_ms)).await;
+                    backoff_ms = backoff_ms.saturating_mul(2);
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Phase 2: Select committee
+    ///
+    /// Uses the configured [`SelectionMode`]; every mode weighs validators by
+    /// [`combined_score`](Self::combined_score). Returns the committee together with
+    /// the proof attached to the block it finalizes.
+    async fn select_committee(
+        &self,
+        eligible: &[Arc<EmotionalValidator>],
+    ) -> Result<(Vec<Arc<EmotionalValidator>>, CommitteeProof)> {
+        let config = self.get_config().await;
+        let max_size = config.committee_size;
+        let epoch = self.state.read().await.current_epoch;
+        let previous_hash = self
+            .finalized_blocks
+            .read()
+            .await
+            .last()
+            .map(|block| block.hash.clone())
+            .unwrap_or_else(|| "0".repeat(64));
+
+        let randomness = hex::encode(self.beacon.value(epoch));
+
+        let recent_proposers = self.recent_proposers(&config, epoch).await;
+        let candidates = Self::selection_candidates(eligible, &config, &recent_proposers);
+        let cooled_down: Vec<String> = recent_proposers
+            .into_iter()
+            .filter(|id| candidates.iter().any(|c| &c.validator_id == id))
+            .collect();
+        let retained = committee::retained_members(
+            &candidates,
+            &self.previous_committee.read().await,
+            config.committee_overlap_fraction,
+            max_size,
+        );
+        let mut members = committee::select_committee_with_retained(
+            &candidates,
+            max_size,
+            config.selection_mode,
+            committee::selection_seed(epoch, &randomness),
+            &retained,
+        )?;
+        let mut committee: Vec<_> = members
+            .iter()
+            .filter_map(|id| eligible.iter().find(|v| v.id() == id).cloned())
+            .collect();
+
+        let proposers = Self::eligible_proposers(&committee, config.proposer_eligibility);
+        let stakes: Vec<u64> = committee.iter().map(|v| v.get_stake()).collect();
+        let proposer = committee::draw_proposer(
+            &members,
+            &stakes,
+            &proposers,
+            config.proposer_policy,
+            config.stake_weight_fn,
+            committee::proposer_seed(epoch, &randomness),
+        )?;
+        committee::promote_proposer(&mut members, proposer);
+        committee::promote_proposer(&mut committee, proposer);
+
+        let mut member_stakes = Vec::new();
+        if config.proposer_policy == ProposerPolicy::StakeWeightedRandom {
+            member_stakes = committee.iter().map(|v| v.get_stake()).collect();
+        }
+
+        let proof = CommitteeProof::new(
+            epoch,
+            previous_hash,
+            config.selection_mode,
+            max_size,
+            &candidates,
+            members,
+        )
+        .with_proposer_policy(
+            config.proposer_policy,
+            member_stakes,
+            config.stake_weight_fn,
+        )
+        .with_randomness(randomness)
+        .with_retained(retained)
+        .with_proposers(proposers)
+        .with_cooled_down(cooled_down);
+
+        if eligible.len() <= max_size {
+            return Ok((committee, proof));
+        }
+
+        // Update committee size metrics
+        let mut metrics = self.metrics.write().await;
+        let committee_size = committee.len() as f64;
+        if metrics.total_epochs == 0 {
+            metrics.average_committee_size = committee_size;
+        } else {
+            metrics.average_committee_size =
+                (metrics.average_committee_size * metrics.total_epochs as f64 + committee_size)
+                / (metrics.total_epochs + 1) as f64;
+        }
+        drop(metrics);
+
+        // TODO: Integrate stake locking when EmotionalStaking is added to consensus engine
+        // This prevents nothing-at-stake attacks by locking validator stake during consensus
+        // Example integration:
+        // for validator in &committee {
+        //     self.staking.lock_stake(validator.id(), validator.get_stake(), 1)?;
+        // }
+
+        Ok((committee, proof))
+    }
+
+    /// Selection weights of the eligible validators, with `recent_proposers` cooled
+    /// down if configured
+    fn selection_candidates(
+        eligible: &[Arc<EmotionalValidator>],
+        config: &ConsensusConfig,
+        recent_proposers: &[String],
+    ) -> Vec<CommitteeCandidate> {
+        let mut candidates = Self::committee_candidates(eligible, config.stake_weight_fn);
+        if let Some(cooldown) = config.proposer_cooldown {
+            cooldown.apply(&mut candidates, recent_proposers);
+        }
+        candidates
+    }
+
+    /// Proposers of finalized blocks still cooling down in `epoch`, ordered by ID
+    ///
+    /// Read from the chain rather than in-memory activity, so every node holding the
+    /// same blocks cools down the same validators.
+    async fn recent_proposers(&self, config: &ConsensusConfig, epoch: u64) -> Vec<String> {
+        let Some(cooldown) = config.proposer_cooldown else {
+            return Vec::new();
+        };
+
+        let blocks = self.finalized_blocks.read().await;
+        let mut proposers: Vec<String> = blocks
+            .iter()
+            .rev()
+            .take_while(|block| block.header.epoch.saturating_add(cooldown.epochs) >= epoch)
+            .filter(|block| cooldown.is_cooling_down(block.header.epoch, epoch))
+            .map(|block| block.header.validator_id.clone())
+            .collect();
+        proposers.sort();
+        proposers.dedup();
+        proposers
+    }
+
+    /// Selection weights of the eligible validators, as recorded in committee proofs
+    fn committee_candidates(
+        eligible: &[Arc<EmotionalValidator>],
+        weight_fn: StakeWeightFn,
+    ) -> Vec<CommitteeCandidate> {
+        eligible
+            .iter()
+            .map(|validator| {
+                // Scale by 1000 to preserve precision in an integer weight
+                let weight = (Self::combined_score(validator, weight_fn) * 1000.0) as u64;
+                CommitteeCandidate::new(validator.id(), weight)
+            })
+            .collect()
+    }
+
+    /// Estimated probability that a validator joins the next committee
+    ///
+    /// Uses the eligible set of the most recent assessment, the configured
+    /// [`SelectionMode`] and the members the committee overlap would carry over; see
+    /// [`committee::selection_probabilities`]. Validators that were not eligible get 0.
+    pub async fn selection_probability(&self, validator_id: &str) -> f64 {
+        let config = self.get_config().await;
+        let eligible: Vec<Arc<EmotionalValidator>> = self
+            .last_assessment
+            .read()
+            .await
+            .iter()
+            .filter(|(_, status)| *status == EligibilityStatus::Eligible)
+            .filter_map(|(id, _)| self.validators.get(id).map(|v| Arc::clone(v.value())))
+            .collect();
+
+        let next_epoch = self.state.read().await.current_epoch + 1;
+        let recent_proposers = self.recent_proposers(&config, next_epoch).await;
+        let candidates = Self::selection_candidates(&eligible, &config, &recent_proposers);
+        let retained = committee::retained_members(
+            &candidates,
+            &self.previous_committee.read().await,
+            config.committee_overlap_fraction,
+            config.committee_size,
+        );
+
+        committee::selection_probabilities(
+            &candidates,
+            config.committee_size,
+            config.selection_mode,
+            &retained,
+        )
+        .into_iter()
+        .find(|(id, _)| id == validator_id)
+        .map_or(0.0, |(_, probability)| probability)
+    }
+
+    /// Warn when consensus runs with a single-validator committee
+    ///
+    /// A committee of one trivially reaches any vote threshold, so the network keeps
+    /// producing blocks but has no Byzantine fault tolerance. The warning is emitted
+    /// once on entering solo mode rather than every epoch.
+    fn check_solo_mode(&self, committee_size: usize) {
+        let solo = committee_size == 1;
+        let was_solo = self.solo_mode.swap(solo, AtomicOrdering::Relaxed);
+
+        if solo && !was_solo {
+            warn!("⚠️  Solo mode: committee has a single validator, no Byzantine fault tolerance");
+        } else if !solo && was_solo {
+            info!(
+                "👥 Leaving solo mode: committee has {} validators",
+                committee_size
+            );
+        }
+    }
+
+    /// Returns true if the last committee consisted of a single validator
+    pub fn is_solo_mode(&self) -> bool {
+        self.solo_mode.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Combined selection score: emotional score x stake weight x reputation
+    fn combined_score(validator: &EmotionalValidator, weight_fn: StakeWeightFn) -> f64 {
+        let score = validator.get_emotional_score() as f64;
+        let stake_weight = calculate_stake_weight(validator.get_stake(), weight_fn);
+        let reputation = validator.get_reputation() as f64 / 100.0;
+        score * stake_weight * reputation
+    }
+
+    /// Phase 3: Propose block
+    ///
+    /// The primary proposes. If it is caught double-signing with signed evidence and
+    /// replacement is enabled, it is recorded in the committee proof together with the
+    /// evidence and the next member allowed to propose takes its place.
+    async fn propose_block(
+        &self,
+        committee: &[Arc<EmotionalValidator>],
+        committee_proof: &mut CommitteeProof,
+    ) -> Result<Block> {
+        if committee.is_empty() {
+            return Err(ConsensusError::committee_selection_failed(
+                "Empty committee",
+            ));
+        }
+        let replace = self.get_config().await.replace_byzantine_proposer;
+        let height = self.finalized_blocks.read().await.len() as u64 + 1;
+
+        for proposer in committee {
+            if !committee_proof.may_propose(proposer.id()) {
+                continue;
+            }
+            match self.propose_block_as(proposer, committee).await {
+                Err(e @ ConsensusError::InvalidBlock { .. }) if replace => {
+                    // Only a proven offense justifies passing the proposer over
+                    let Some(evidence) = self
+                        .byzantine_detector
+                        .double_signing_evidence(proposer.id(), height)
+                    else {
+                        return Err(e);
+                    };
+                    warn!(
+                        "🔁 Replacing proposer {} after double signing: {}",
+                        proposer.id(),
+                        e
+                    );
+                    committee_proof.replace_proposer(evidence);
+                    self.metrics.write().await.proposer_replacements += 1;
+                }
+                result => return result,
+            }
+        }
+
+        Err(ConsensusError::byzantine_failure(
+            "No committee member left to propose",
+        ))
+    }
+
+    /// Build and sign a block proposed by `primary` to `committee`
+    ///
+    /// The header commits to the committee's fitness and volatility here, so the block
+    /// the committee votes on is the block that gets finalized. Fails with an invalid
+    /// block error if `primary` already proposed another block at this height.
+    async fn propose_block_as(
+        &self,
+        primary: &EmotionalValidator,
+        committee: &[Arc<EmotionalValidator>],
+    ) -> Result<Block> {
+        let now = self.clock.now_millis();
+        let (max_block_weight, chain_id) = {
+            let config = self.config.read().await;
+            (config.max_block_weight, config.chain_id.clone())
+        };
+        let filter = self.finalized_tx_filter.read().await;
+        let pending_txs = self.pending_transactions.lock().await;
+        let mut included = HashSet::new();
+        let mut block_weight = 0u64;
+        let mut transactions: Vec<_> = pending_txs
+            .iter()
+            .filter(|tx| !tx.is_expired(now, crate::TRANSACTION_TTL_MS))
+            .filter(|tx| !filter.contains(&tx.hash))
+            .filter(|tx| included.insert(tx.hash.as_str()))
+            // Skip transactions too heavy for the space left; lighter ones may still fit
+            .filter(|tx| {
+                let weight = block_weight.saturating_add(tx.weight());
+                if weight > max_block_weight {
+                    return false;
+                }
+                block_weight = weight;
+                true
+            })
+            .take(crate::MAX_BLOCK_TRANSACTIONS)
+            .cloned()
+            .collect();
+        drop(pending_txs);
+        drop(filter);
+
+        // Execute speculatively so the block only carries transactions that apply
+        let post_state = self.state_machine.as_ref().map(|state_machine| {
+            let scratch = state_machine.fork();
+            transactions.retain(|tx| match scratch.apply(tx) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("Excluding transaction {} from block: {}", tx.hash, e);
+                    false
+                }
+            });
+            scratch
+        });
+
+        let finalized_blocks = self.finalized_blocks.read().await;
+        let last_height = finalized_blocks.len() as u64;
+        let previous_hash = finalized_blocks
+            .last()
+            .map(|block| block.hash.clone())
+            .unwrap_or_else(|| "0".repeat(64));
+        drop(finalized_blocks);
+
+        // Get current epoch for replay attack prevention
+        let current_epoch = self.state.read().await.current_epoch;
+
+        let mut block = Block::new_with_clock(
+            last_height + 1,
+            current_epoch,
+            previous_hash,
+            primary.id().to_string(),
+            primary.get_emotional_score(),
+            transactions,
+            self.clock.as_ref(),
+        );
+
+        block.set_chain_id(chain_id);
+        if let Some(scratch) = post_state {
+            block.set_state_root(scratch.root());
+        }
+        block.set_committee_emotional_fitness(Self::committee_emotional_fitness(committee));
+        block.set_emotional_volatility(Self::emotional_volatility(committee));
+
+        // Sign the block with the proposer's key pair
+        block
+            .sign(&primary.key_pair)
+            .map_err(|e| ConsensusError::internal(format!("Failed to sign block: {}", e)))?;
+
+        // Record proposal for Byzantine detection (double signing detection)
+        if let Err(e) = self.byzantine_detector.record_signed_proposal(&block).await {
+            error!("🚨 Byzantine behavior detected during proposal: {}", e);
+            // Slash the validator for double signing
+            self.slash_byzantine(primary.id(), "Double signing detected")
+                .await?;
+            return Err(ConsensusError::invalid_block(e));
+        }
+
+        Ok(block)
+    }
+
+    /// Phase 4: Execute voting
+    ///
+    /// `round` is the index of the block within the epoch, so votes on successive
+    /// blocks of one epoch are not mistaken for equivocation.
+    async fn execute_voting(
+        &self,
+        committee: &[Arc<EmotionalValidator>],
+        block: &Block,
+        round: u32,
+    ) -> Result<VotingResult> {
+        let mut votes = Vec::new();
+        let mut byzantine_count = 0;
+        let mut rejections = Vec::new();
+
+        // Get expected previous hash, height, and epoch for validation
+        let finalized_blocks = self.finalized_blocks.read().await;
+        let expected_previous_hash = finalized_blocks
+            .last()
+            .map(|block| block.hash.clone())
+            .unwrap_or_else(|| "0".repeat(64));
+        let expected_height = finalized_blocks.len() as u64 + 1;
+        drop(finalized_blocks);
+
+        let expected_epoch = self.state.read().await.current_epoch;
+        let config = self.effective_config().await;
+        let empty_block_rejection = if config.require_nonempty_blocks_when_pending {
+            self.empty_block_rejection(block).await
+        } else {
+            None
+        };
+        let proposer_rejection =
+            Self::proposer_rejection(block, committee, config.proposer_eligibility);
+        let chain_rejection = (block.header.chain_id != config.chain_id).then(|| {
+            format!(
+                "Block is for chain {:?}, expected {:?}",
+                block.header.chain_id, config.chain_id
+            )
+        });
+        let block_rejection = chain_rejection
+            .or(proposer_rejection)
+            .or(empty_block_rejection);
+        let mut outcomes = Vec::new();
+
+        if Block::calculate_merkle_root(&block.transactions) != block.header.merkle_root {
+            self.log_merkle_mismatch(block).await;
+        }
+
+        for validator in committee {
+            // Perform actual block validation (includes epoch check for replay attack prevention)
+            let validation_result = validator.validate_block_cached(
+                block,
+                &expected_previous_hash,
+                expected_height,
+                expected_epoch,
+                &self.signature_cache,
+            );
+            if config.check_validation_determinism {
+                outcomes.push((validator.id(), validation_result.clone()));
+            }
+            let validation_result =
+                validation_result.and_then(|()| block_rejection.clone().map_or(Ok(()), Err));
+
+            let (approved, reason) = match validation_result {
+                Ok(()) => (true, None),
+                Err(err_msg) => {
+                    warn!("Validator {} rejected block: {}", validator.id(), err_msg);
+                    (false, Some(err_msg))
+                }
+            };
+
+            let mut vote = Vote::new_with_clock(
+                validator.id().to_string(),
+                block.hash.clone(),
+                block.header.epoch,
+                round,
+                validator.get_emotional_score(),
+                approved,
+                self.clock.as_ref(),
+            );
+            vote.confidence = validator.get_confidence();
+            vote.reason = reason.clone();
+            vote.sign(&validator.key_pair)
+                .map_err(|e| ConsensusError::internal(format!("Failed to sign vote: {}", e)))?;
+
+            let public_key = validator.key_pair.public_key_hex();
+            let rejection = vote.rejection_reason(&block.hash, expected_epoch, &public_key);
+            if let Some(rejection) = rejection {
+                warn!("Dropped vote from {}: {}", validator.id(), rejection);
+                rejections.push(rejection);
+                continue;
+            }
+
+            // Record vote for Byzantine detection (double voting & equivocation detection)
+            match self.byzantine_detector.record_vote(&vote).await {
+                Ok(VoteRecord::Recorded) => {}
+                // Replay of an already counted vote
+                Ok(VoteRecord::Replayed) => continue,
+                Ok(VoteRecord::Rejected(rejection)) => {
+                    warn!("Dropped vote from {}: {}", validator.id(), rejection);
+                    rejections.push(rejection);
+                    continue;
+                }
+                Err(e) => {
+                    warn!("🚨 Byzantine behavior detected during voting: {}", e);
+                    byzantine_count += 1;
+
+                    // Slash the validator for double voting or equivocation
+                    if let Err(slash_err) = self
+                        .slash_byzantine(validator.id(), "Double voting or equivocation detected")
+                        .await
+                    {
+                        error!(
+                            "Failed to slash validator {}: {}",
+                            validator.id(),
+                            slash_err
+                        );
+                    }
+
+                    // Skip this vote - don't count Byzantine votes
+                    continue;
+                }
+            }
+
+            votes.push(vote);
+        }
+
+        for vote in &votes {
+            let mut activity = self.activity.entry(vote.validator_id.clone()).or_default();
+            activity.votes_cast += 1;
+            if vote.approved {
+                activity.approvals += 1;
+            }
+        }
+
+        if Self::validation_diverged(block, &outcomes) {
+            self.metrics.write().await.validation_divergences += 1;
+        }
+
+        // Update Byzantine failure metrics
+        if byzantine_count > 0 {
+            let mut metrics = self.metrics.write().await;
+            metrics.byzantine_failures += byzantine_count as u64;
+        }
+        if !rejections.is_empty() {
+            self.metrics.write().await.rejected_votes += rejections.len() as u64;
+            let mut reasons = self.vote_rejection_reasons.write().await;
+            for rejection in rejections {
+                *reasons.entry(rejection).or_insert(0) += 1;
+            }
+        }
+
+        Ok(Self::tally_votes(
+            &Self::member_stakes(committee),
+            votes,
+            byzantine_count,
+            config.byzantine_threshold,
+            config.stake_weighted_voting,
+        ))
+    }
+
+    /// Log one summary of how a block's transactions differ from the local pool
+    ///
+    /// Called on a Merkle root mismatch. The pool's unexpired transactions, in the order
+    /// a local proposer would take them, stand in for the list the header was built from.
+    /// Only the number of differences and the first one are logged, so a block that
+    /// differs everywhere does not flood the log.
+    async fn log_merkle_mismatch(&self, block: &Block) {
+        let pending = self.pending_transactions.lock().await;
+        let local: Vec<Transaction> = pending
+            .iter()
+            .filter(|tx| !tx.is_expired(block.header.timestamp, crate::TRANSACTION_TTL_MS))
+            .cloned()
+            .collect();
+        drop(pending);
+
+        let diffs = block.diff_transactions(&local);
+        let Some(first) = diffs.first() else {
+            warn!(
+                "Merkle root mismatch in block {}: header root is wrong for transactions matching the local pool",
+                block.header.height
+            );
+            return;
+        };
+        warn!(
+            "Merkle root mismatch in block {}: {} of {} transactions differ from the local pool of {} (first: {})",
+            block.header.height,
+            diffs.len(),
+            block.transactions.len(),
+            local.len(),
+            first
+        );
+    }
+
+    /// Reason to reject an empty block while unexpired transactions are pending
+    async fn empty_block_rejection(&self, block: &Block) -> Option<String> {
+        if !block.transactions.is_empty() {
+            return None;
+        }
+
+        let pending = self.pending_transactions.lock().await;
+        let includable = pending
+            .iter()
+            .filter(|tx| !tx.is_expired(block.header.timestamp, crate::TRANSACTION_TTL_MS))
+            .count();
+        (includable > 0).then(|| {
+            format!(
+                "Empty block proposed while {} transactions are pending",
+                includable
+            )
+        })
+    }
+
+    /// Committee members allowed to propose; empty if every member may
+    fn eligible_proposers(
+        committee: &[Arc<EmotionalValidator>],
+        eligibility: ProposerEligibility,
+    ) -> Vec<String> {
+        let reputations: Vec<(String, u8)> = committee
+            .iter()
+            .map(|v| (v.id().to_string(), v.get_reputation()))
+            .collect();
+        eligibility.eligible_proposers(&reputations)
+    }
+
+    /// Reason to reject a block proposed by a committee member not allowed to propose
+    fn proposer_rejection(
+        block: &Block,
+        committee: &[Arc<EmotionalValidator>],
+        eligibility: ProposerEligibility,
+    ) -> Option<String> {
+        let proposers = Self::eligible_proposers(committee, eligibility);
+        let proposer = &block.header.validator_id;
+        (!proposers.is_empty() && !proposers.contains(proposer))
+            .then(|| format!("Validator {} is not eligible to propose", proposer))
+    }
+
+    /// Whether committee members reached different verdicts on the same block
+    ///
+    /// Every member validates identical inputs, so disagreement points at
+    /// non-determinism such as clock skew rather than at the block.
+    fn validation_diverged(
+        block: &Block,
+        outcomes: &[(&str, std::result::Result<(), String>)],
+    ) -> bool {
+        let (accepted, rejected): (Vec<_>, Vec<_>) =
+            outcomes.iter().partition(|(_, outcome)| outcome.is_ok());
+        if accepted.is_empty() || rejected.is_empty() {
+            return false;
+        }
+
+        warn!(
+            "🔀 Validation diverged on block {}: {} accepted, {} rejected",
+            block.header.height,
+            accepted.len(),
+            rejected.len()
+        );
+        for (validator_id, outcome) in rejected {
+            if let Err(reason) = outcome {
+                warn!("   {} rejected: {}", validator_id, reason);
+            }
+        }
+        true
+    }
+
+    /// Committee member IDs with their current stake
+    fn member_stakes(committee: &[Arc<EmotionalValidator>]) -> Vec<(String, u64)> {
+        committee
+            .iter()
+            .map(|v| (v.id().to_string(), v.get_stake()))
+            .collect()
+    }
+
+    /// Count the votes of a committee, given as member IDs with their stake
+    ///
+    /// `votes` holds only the counted votes; `byzantine_count` votes were discarded.
+    fn tally_votes(
+        members: &[(String, u64)],
+        votes: Vec<Vote>,
+        byzantine_count: usize,
+        byzantine_threshold: u8,
+        stake_weighted_voting: bool,
+    ) -> VotingResult {
+        let participant_count = votes.len();
+        let approved_count = votes.iter().filter(|vote| vote.approved).count();
+        let total_emotional_score: u32 = votes.iter().map(|vote| vote.emotional_score as u32).sum();
+
+        // Threshold is relative to the committee actually selected, which may be
+        // smaller than the configured committee size when few validators are eligible
+        let required_votes =
+            (members.len() as f64 * (byzantine_threshold as f64 / 100.0)).ceil() as usize;
+
+        let headcount_strength = crate::utils::calculate_percentage(approved_count, members.len());
+        let stake_strength = Self::approving_stake_strength(members, &votes);
+
+        // An empty committee, or one whose votes were all discarded, never reaches consensus
+        let (success, consensus_strength) = if stake_weighted_voting {
+            (stake_strength >= byzantine_threshold, stake_strength)
+        } else {
+            (
+                approved_count > 0 && approved_count >= required_votes,
+                headcount_strength,
+            )
+        };
+        let average_emotional_score = total_emotional_score
+            .checked_div(participant_count as u32)
+            .unwrap_or(0) as u8;
+
+        VotingResult {
+            success,
+            consensus_strength,
+            headcount_strength,
+            stake_strength,
+            participant_count,
+            byzantine_count,
+            average_emotional_score,
+            participants: members.iter().map(|(id, _)| id.clone()).collect(),
+            votes,
+            reason: if success {
+                None
+            } else {
+                Some("Insufficient votes".to_string())
+            },
+        }
+    }
+
+    /// Percentage of total committee stake held by members that approved
+    ///
+    /// Each approving member's stake counts in proportion to its vote's confidence, so an
+    /// approval backed by flaky readings carries less weight.
+    fn approving_stake_strength(members: &[(String, u64)], votes: &[Vote]) -> u8 {
+        let total_stake: u128 = members.iter().map(|(_, stake)| *stake as u128).sum();
+        if total_stake == 0 {
+            return 0;
+        }
+
+        let approving_stake: u128 = members
+            .iter()
+            .filter_map(|(id, stake)| {
+                let vote = votes
+                    .iter()
+                    .find(|vote| vote.approved && vote.validator_id == *id)?;
+                Some(*stake as u128 * vote.confidence.min(100) as u128 / 100)
+            })
+            .sum();
+
+        ((approving_stake as f64 / total_stake as f64) * 100.0) as u8
+    }
+
+    /// Stake-weighted average emotional score of the committee members
+    ///
+    /// Computed at proposal, since the header it goes into is signed before voting.
+    fn committee_emotional_fitness(committee: &[Arc<EmotionalValidator>]) -> u8 {
+        let (weighted_score, total_stake) = committee
+            .iter()
+            .map(|validator| {
+                let stake = validator.get_stake() as u128;
+                (validator.get_emotional_score() as u128 * stake, stake)
+            })
+            .fold((0u128, 0u128), |(score_sum, stake_sum), (score, stake)| {
+                (score_sum + score, stake_sum + stake)
+            });
+
+        weighted_score.checked_div(total_stake).unwrap_or(0) as u8
+    }
+
+    /// Average emotional score of the voters, weighted by their current stake
+    fn stake_weighted_emotional_score<'a>(&self, votes: impl Iterator<Item = &'a Vote>) -> u8 {
+        let (weighted_score, total_stake) = votes
+            .filter_map(|vote| {
+                let stake = self.validators.get(&vote.validator_id)?.get_stake() as u128;
+                Some((vote.emotional_score as u128 * stake, stake))
+            })
+            .fold((0u128, 0u128), |(score_sum, stake_sum), (score, stake)| {
+                (score_sum + score, stake_sum + stake)
+            });
+
+        if total_stake == 0 {
+            return 0;
+        }
+        (weighted_score / total_stake) as u8
+    }
+
+    /// Variance of the committee's emotional scores, in hundredths
+    fn emotional_volatility(committee: &[Arc<EmotionalValidator>]) -> u32 {
+        let scores: Vec<f64> = committee
+            .iter()
+            .map(|validator| validator.get_emotional_score() as f64)
+            .collect();
+        (crate::utils::calculate_variance(&scores) * 100.0).round() as u32
+    }
+
+    /// Phase 5: Finalize block
+    async fn finalize_block(
+        &self,
+        mut block: Block,
+        voting_result: VotingResult,
+        committee_proof: CommitteeProof,
+    ) -> Result<()> {
+        // Finalized blocks are irreversible, so a second block at a finalized height
+        // loses the fork before it can touch any state
+        let finalized_height = self.finalized_blocks.read().await.len() as u64;
+        if block.header.height <= finalized_height {
+            self.metrics.write().await.rejected_blocks += 1;
+            return Err(self.reject_competing_block(&block).await);
+        }
+        if let Err(e) = self.verify_state_transition(&block) {
+            self.metrics.write().await.rejected_blocks += 1;
+            return Err(e);
+        }
+        let (filter_blocks, filter_false_positive_rate, stake_weighted_voting) = {
+            let config = self.config.read().await;
+            (
+                config.finalized_tx_filter_blocks,
+                config.finalized_tx_filter_false_positive_rate,
+                config.stake_weighted_voting,
+            )
+        };
+        // Under stake-weighted voting a low-stake participant counts for as little in
+        // the recorded fitness as it does in the vote
+        let emotional_fitness = if stake_weighted_voting {
+            self.stake_weighted_emotional_score(voting_result.votes.iter())
+        } else {
+            voting_result.average_emotional_score
+        };
+
+        // The header and signature stay as voted on; the outcome goes into the metadata,
+        // which the block hash does not cover
+        let proposer = self.validators.get(&block.header.validator_id);
+        block.consensus_metadata = Some(crate::types::ConsensusMetadata {
+            participant_count: voting_result.participant_count,
+            consensus_strength: voting_result.consensus_strength,
+            emotional_fitness,
+            mean_emotional_fitness: voting_result.average_emotional_score,
+            byzantine_failures: voting_result.byzantine_count,
+            finalized_at: self.clock.now_millis(),
+            participants: voting_result.participants,
+            committee_proof: Some(committee_proof),
+            attestation_signature: String::new(),
+        });
+        if let Some(proposer) = &proposer {
+            block.sign_attestation(&proposer.key_pair).map_err(|e| {
+                ConsensusError::internal(format!("Failed to sign attestation: {}", e))
+            })?;
+        }
+
+        // Only touch the state once signing can no longer fail
+        if let Some(state_machine) = &self.state_machine {
+            for tx in &block.transactions {
+                state_machine.apply(tx)?;
+            }
+        }
+        // Fees go to the proposer, separately from the epoch reward pool
+        let fees = block.total_fees();
+        if let Some(proposer) = proposer {
+            proposer.add_reward(fees);
+        }
+
+        let mut activity = self
+            .activity
+            .entry(block.header.validator_id.clone())
+            .or_default();
+        activity.blocks_proposed += 1;
+        drop(activity);
+
+        let mut blocks = self.finalized_blocks.write().await;
+        blocks.push(block.clone());
+        // Competing blocks are rejected above, so the beacon only follows the finalized
+        // chain; restoring that chain rebuilds it
+        self.beacon.on_block_finalized(&block.hash);
+        self.finalized_tx_filter.write().await.record_block(
+            block.transactions.iter().map(|tx| tx.hash.as_str()),
+            filter_blocks,
+            filter_false_positive_rate,
+        );
+
+        let mut state = self.state.write().await;
+        state.last_finalized_height = block.header.height;
+        state.consensus_strength = voting_result.consensus_strength;
+        state.emotional_fitness = emotional_fitness;
+        state.participation_rate = crate::utils::calculate_percentage(
+            voting_result.participant_count,
+            self.validators.len(),
+        );
+
+        let mut pending = self.pending_transactions.lock().await;
+        let finalized_hashes: std::collections::HashSet<_> = block
+            .transactions
+            .iter()
+            .map(|tx| tx.hash.clone())
+            .collect();
+
+        // Remove finalized AND expired transactions to prevent memory leak
+        let now = self.clock.now_millis();
+
+        let mut tx_statuses = self.tx_statuses.write().await;
+        for tx_hash in &finalized_hashes {
+            tx_statuses.set(
+                tx_hash,
+                TxStatus::Finalized {
+                    height: block.header.height,
+                },
+            );
+        }
+
+        let initial_count = pending.len();
+        pending.retain(|tx| {
+            if finalized_hashes.contains(&tx.hash) {
+                return false;
+            }
+            if tx.is_expired(now, crate::TRANSACTION_TTL_MS) {
+                tx_statuses.set(&tx.hash, TxStatus::Expired);
+                return false;
+            }
+            true
+        });
+        drop(tx_statuses);
+        let removed_count = initial_count - pending.len();
+
+        if removed_count > 0 {
+            info!(
+                "🧹 Cleaned up {} transactions ({} finalized, {} expired)",
+                removed_count,
+                finalized_hashes.len(),
+                removed_count.saturating_sub(finalized_hashes.len())
+            );
+        }
+
+        state.pending_transactions = pending.len();
+        drop(pending);
+        drop(state);
+        drop(blocks);
+
+        info!(
+            "🎉 Block {} finalized with {} transactions",
+            block.header.height,
+            block.transactions.len()
+        );
+
+        // No subscribers is fine; streams catch up from history if they fall behind
+        let _ = self.finalized_block_sender.send(block.clone());
+
+        if let Err(e) = self.fork_detector.record_block(&block).await {
+            warn!("🍴 {}", e);
+        }
+
+        self.audit.record(AuditEntry::BlockFinalized {
+            height: block.header.height,
+            hash: block.hash.clone(),
+            epoch: block.header.epoch,
+            proposer: block.header.validator_id.clone(),
+            transaction_count: block.transactions.len(),
+            consensus_strength: voting_result.consensus_strength,
+            timestamp: self.clock.now_millis(),
+        });
+
+        // Update comprehensive metrics
+        let mut metrics = self.metrics.write().await;
+        metrics.blocks_finalized += 1;
+        metrics.transactions_processed += block.transactions.len() as u64;
+        metrics.total_fees_collected = metrics.total_fees_collected.saturating_add(fees);
+        metrics.active_validators = self.validators.len();
+
+        // Update average participation rate
+        let new_participation = if self.validators.is_empty() {
+            0.0
+        } else {
+            (voting_result.participant_count as f64 / self.validators.len() as f64) * 100.0
+        };
+        if metrics.blocks_finalized == 1 {
+            metrics.average_participation_rate = new_participation;
+        } else {
+            metrics.average_participation_rate =
+                (metrics.average_participation_rate * (metrics.blocks_finalized - 1) as f64
+                 + new_participation) / metrics.blocks_finalized as f64;
+        }
+
+        Ok(())
+    }
+
+    /// Report a block competing with the one finalized at its height, and reject it
+    ///
+    /// The fork is resolved in favour of the finalized block. Transactions only the
+    /// competing block carries return to the mempool so they are proposed again; none of
+    /// them was applied.
+    async fn reject_competing_block(&self, block: &Block) -> ConsensusError {
+        let height = block.header.height;
+        let Some(finalized) = self
+            .finalized_blocks
+            .read()
+            .await
+            .get(height.saturating_sub(1) as usize)
+            .cloned()
+        else {
+            return ConsensusError::invalid_block(format!("No block at height {}", height));
+        };
+        if finalized.hash == block.hash {
+            return ConsensusError::invalid_block(format!(
+                "Block {} is already finalized",
+                block.hash
+            ));
+        }
+
+        if let Err(e) = self.fork_detector.record_block(block).await {
+            warn!("🍴 {}", e);
+            self.metrics.write().await.fork_detections += 1;
+        }
+        if let Err(e) = self
+            .fork_detector
+            .resolve_fork_for_finalized(height, &finalized.hash)
+            .await
+        {
+            warn!("Fork at height {} left unresolved: {}", height, e);
+        }
+        self.reinsert_losing_transactions(block, &finalized).await;
+
+        ConsensusError::invalid_block(format!(
+            "Height {} is already finalized as {}",
+            height, finalized.hash
+        ))
+    }
+
+    /// Return transactions only in a block that lost the fork at its height to the mempool
+    ///
+    /// Transactions the finalized block also carries stay finalized; expired ones, ones
+    /// already pending and ones finalized elsewhere on the chain are not reinserted.
+    async fn reinsert_losing_transactions(&self, losing: &Block, finalized: &Block) {
+        let filter = self.finalized_tx_filter.read().await;
+
+        let finalized_txs: HashSet<&str> = finalized
+            .transactions
+            .iter()
+            .map(|tx| tx.hash.as_str())
+            .collect();
+
+        let now = self.clock.now_millis();
+        let mut pending = self.pending_transactions.lock().await;
+        let mut tx_statuses = self.tx_statuses.write().await;
+        let mut reinserted = 0;
+        for tx in &losing.transactions {
+            if finalized_txs.contains(tx.hash.as_str())
+                || tx.is_expired(now, crate::TRANSACTION_TTL_MS)
+                || filter.contains(&tx.hash)
+            {
+                continue;
+            }
+            if pending.insert(tx.clone()) {
+                tx_statuses.set(&tx.hash, TxStatus::Pending);
+                reinserted += 1;
+            }
+        }
+        drop(tx_statuses);
+        let pending_count = pending.len();
+        drop(pending);
+        drop(filter);
+
+        if reinserted > 0 {
+            self.state.write().await.pending_transactions = pending_count;
+            info!(
+                "♻️ Returned {} transactions from the losing block at height {} to the mempool",
+                reinserted, losing.header.height
+            );
+        }
+    }
+
+    /// Check that applying the block's transactions yields its declared state root
+    ///
+    /// Executes against a fork of the state machine, so the local state is untouched.
+    fn verify_state_transition(&self, block: &Block) -> Result<()> {
+        let Some(state_machine) = &self.state_machine else {
+            return Ok(());
+        };
+
+        let scratch = state_machine.fork();
+        for tx in &block.transactions {
+            scratch.apply(tx).map_err(|e| {
+                ConsensusError::invalid_block(format!(
+                    "Transaction {} failed to apply: {}",
+                    tx.hash, e
+                ))
+            })?;
+        }
+
+        let state_root = scratch.root();
+        if state_root != block.header.state_root {
+            return Err(ConsensusError::invalid_block(format!(
+                "State root mismatch: expected {}, got {}",
+                state_root, block.header.state_root
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Submit a transaction
+    ///
+    /// Fails if a transaction with the same hash is already pending.
+    pub async fn submit_transaction(&self, transaction: Transaction) -> Result<()> {
+        let filter = self.finalized_tx_filter.read().await;
+        if filter.contains(&transaction.hash) {
+            return Err(ConsensusError::transaction_already_finalized(
+                transaction.hash,
+            ));
+        }
+
+        let mut pending = self.pending_transactions.lock().await;
+        let tx_hash = transaction.hash.clone();
+        if !pending.insert(transaction) {
+            return Err(ConsensusError::transaction_already_pending(tx_hash));
+        }
+        self.tx_statuses
+            .write()
+            .await
+            .set(&tx_hash, TxStatus::Pending);
+
+        let mut state = self.state.write().await;
+        state.pending_transactions = pending.len();
+
+        Ok(())
+    }
+
+    /// Cleanup expired transactions from the transaction pool
+    ///
+    /// This method removes transactions that have exceeded their TTL (5 minutes) or
+    /// their own `valid_until` time.
+    /// It runs periodically to prevent memory leaks from rejected/invalid transactions.
+    async fn cleanup_transaction_pool(&self) {
+        let now = self.clock.now_millis();
+
+        let mut pending = self.pending_transactions.lock().await;
+        let mut tx_statuses = self.tx_statuses.write().await;
+        let initial_count = pending.len();
+        pending.retain(|tx| {
+            let expired = tx.is_expired(now, crate::TRANSACTION_TTL_MS);
+            if expired {
+                tx_statuses.set(&tx.hash, TxStatus::Expired);
+            }
+            !expired
+        });
+        drop(tx_statuses);
+        let removed_count = initial_count - pending.len();
+
+        if removed_count > 0 {
+            info!(
+                "🧹 Periodic cleanup: removed {} expired transactions",
+                removed_count
+            );
+
+            // Update state
+            let mut state = self.state.write().await;
+            state.pending_transactions = pending.len();
+        }
+    }
+
+    /// Get the confirmation status of a submitted transaction
+    pub async fn get_transaction_status(&self, tx_hash: &str) -> TxStatus {
+        self.tx_statuses
+            .read()
+            .await
+            .get(tx_hash)
+            .unwrap_or(TxStatus::Unknown)
+    }
+
+    /// Get current consensus state
+    pub async fn get_state(&self) -> ConsensusState {
+        self.state.read().await.clone()
+    }
+
+    /// Validator IDs of the current committee, primary first
+    ///
+    /// Empty before the first epoch, while a new epoch is assessing validators, and
+    /// while the engine is stopped or paused.
+    pub async fn get_current_committee(&self) -> Vec<String> {
+        self.state.read().await.current_committee.clone()
+    }
+
+    /// Get metrics
+    pub async fn get_metrics(&self) -> ConsensusMetrics {
+        self.metrics.read().await.clone()
+    }
+
+    /// Number of votes dropped for each non-Byzantine reason since startup
+    pub async fn get_vote_rejection_reasons(&self) -> HashMap<VoteRejectionReason, u64> {
+        self.vote_rejection_reasons.read().await.clone()
+    }
+
+    /// Get `(timestamp, metrics)` snapshots taken at each epoch boundary, oldest first
+    pub async fn get_metrics_history(&self) -> Vec<(u64, ConsensusMetrics)> {
+        self.metrics_history.read().await.iter().cloned().collect()
+    }
+
+    /// Number of validation divergences over the last `epochs` epochs
+    ///
+    /// Measured against the metrics history; once the history is shorter than `epochs`
+    /// because of its depth, the oldest retained snapshot is used as the baseline.
+    pub async fn recent_validation_divergences(&self, epochs: usize) -> u64 {
+        let current = self.metrics.read().await.validation_divergences;
+        let history = self.metrics_history.read().await;
+        let baseline = match history.iter().rev().nth(epochs) {
+            Some((_, snapshot)) => snapshot.validation_divergences,
+            None if history.len() >= self.metrics_history_depth => history
+                .front()
+                .map_or(0, |(_, snapshot)| snapshot.validation_divergences),
+            None => 0,
+        };
+        current.saturating_sub(baseline)
+    }
+
+    /// Get validator count
+    pub fn get_validator_count(&self) -> usize {
+        self.validators.len()
+    }
+
+    /// Get finalized blocks
+    pub async fn get_finalized_blocks(&self) -> Vec<Block> {
+        self.finalized_blocks.read().await.clone()
+    }
+
+    /// Get finalized blocks at heights `from..=to` without their transactions
+    pub async fn get_light_blocks_range(&self, from: u64, to: u64) -> Vec<LightBlock> {
+        self.finalized_blocks
+            .read()
+            .await
+            .iter()
+            .filter(|block| (from..=to).contains(&block.header.height))
+            .map(Block::to_header_only)
+            .collect()
+    }
+
+    /// Get `(height, emotional volatility index)` for finalized blocks at heights `from..=to`
+    pub async fn get_volatility_series(&self, from: u64, to: u64) -> Vec<(u64, u32)> {
+        self.finalized_blocks
+            .read()
+            .await
+            .iter()
+            .filter(|block| (from..=to).contains(&block.header.height))
+            .map(|block| (block.header.height, block.emotional_volatility()))
+            .collect()
+    }
+
+    /// Slash a validator for an offense of the given severity
+    ///
+    /// This reduces the validator's reputation by the configured penalty and logs the offense
+    async fn slash_validator(
+        &self,
+        validator_id: &str,
+        severity: SlashingSeverity,
+        reason: &str,
+    ) -> Result<()> {
+        let penalty = self
+            .config
+            .read()
+            .await
+            .reputation_penalties
+            .for_severity(severity);
+        // Clone the validator out so no DashMap guard is held across an await
+        let validator = self
+            .validators
+            .get(validator_id)
+            .map(|v| Arc::clone(v.value()));
+        if let Some(validator) = validator {
+            validator.adjust_reputation(-(penalty as i16));
+            self.activity
+                .entry(validator_id.to_string())
+                .or_default()
+                .slashes += 1;
+
+            warn!(
+                "⚖️  Slashed validator {} (reputation now {}): {}",
+                validator_id,
+                validator.get_reputation(),
+                reason
+            );
+
+            self.audit.record(AuditEntry::ValidatorSlashed {
+                validator_id: validator_id.to_string(),
+                reason: reason.to_string(),
+                reputation_after: validator.get_reputation(),
+                timestamp: self.clock.now_millis(),
+            });
+
+            self.jail_repeat_offender(&validator).await;
+
+            Ok(())
+        } else {
+            Err(ConsensusError::invalid_block(format!(
+                "Validator {} not found for slashing",
+                validator_id
+            )))
+        }
+    }
+
+    /// Count a slash and jail the validator once it reaches `max_slashes_in_window`
+    async fn jail_repeat_offender(&self, validator: &EmotionalValidator) {
+        let config = self.get_config().await;
+        if config.max_slashes_in_window == 0 {
+            return;
+        }
+
+        let epoch = self.state.read().await.current_epoch;
+        let validator_id = validator.id().to_string();
+        let mut history = self.slash_history.entry(validator_id.clone()).or_default();
+        history.push_back(epoch);
+        while history
+            .front()
+            .is_some_and(|&slashed| slashed + config.slash_window_epochs <= epoch)
+        {
+            history.pop_front();
+        }
+
+        if history.len() < config.max_slashes_in_window as usize {
+            return;
+        }
+        history.clear();
+        drop(history);
+
+        let release_epoch = epoch + config.jail_epochs;
+        let was_active = std::mem::replace(&mut *validator.is_active.write(), false);
+        warn!(
+            "⛓️  Jailed validator {} until epoch {} after {} slashes",
+            validator_id, release_epoch, config.max_slashes_in_window
+        );
+        // Extending a jail term keeps the state from before the first one
+        self.jailed
+            .entry(validator_id)
+            .and_modify(|jail| jail.release_epoch = release_epoch)
+            .or_insert(Jail {
+                release_epoch,
+                reactivate: was_active,
+            });
+    }
+
+    /// Release jailed validators whose jail period ends by `epoch`, reactivating those
+    /// that were active when jailed
+    fn release_jailed_validators(&self, epoch: u64) {
+        self.jailed.retain(|validator_id, jail| {
+            if jail.release_epoch > epoch {
+                return true;
+            }
+            if jail.reactivate {
+                if let Some(validator) = self.validators.get(validator_id) {
+                    *validator.is_active.write() = true;
+                }
+            }
+            info!("🔓 Released validator {} from jail", validator_id);
+            false
+        });
+    }
+
+    /// Step a validator down after `max_declining_epochs` declining assessments
+    ///
+    /// The validator keeps being assessed while deactivated and is reactivated by the
+    /// first assessment whose trend is no longer declining, unless it is jailed.
+    fn track_emotional_decline(&self, validator: &EmotionalValidator, max_declining_epochs: u32) {
+        if max_declining_epochs == 0 {
+            return;
+        }
+
+        let validator_id = validator.id().to_string();
+        let declining = validator
+            .emotional_profile
+            .read()
+            .as_ref()
+            .is_some_and(|profile| profile.trend == EmotionalTrend::Declining);
+
+        if !declining {
+            let Some((_, count)) = self.declining_epochs.remove(&validator_id) else {
+                return;
+            };
+            if count < max_declining_epochs {
+                return;
+            }
+            // A jailed validator is reactivated on release instead
+            if let Some(mut jail) = self.jailed.get_mut(&validator_id) {
+                jail.reactivate = true;
+                return;
+            }
+            *validator.is_active.write() = true;
+            info!(
+                "📈 Validator {} reactivated after its emotional decline ended",
+                validator_id
+            );
+            let _ = self
+                .events
+                .send(ConsensusEvent::ValidatorReactivated { validator_id });
+            return;
+        }
+
+        let mut count = self
+            .declining_epochs
+            .entry(validator_id.clone())
+            .or_default();
+        *count += 1;
+        if *count != max_declining_epochs {
+            return;
+        }
+        let declining_epochs = *count;
+        drop(count);
+
+        *validator.is_active.write() = false;
+        warn!(
+            "📉 Deactivated validator {} after {} declining epochs",
+            validator_id, declining_epochs
+        );
+        let _ = self.events.send(ConsensusEvent::ValidatorDeactivated {
+            validator_id,
+            declining_epochs,
+        });
+    }
+
+    /// Whether the validator is currently jailed
+    pub fn is_jailed(&self, validator_id: &str) -> bool {
+        self.jailed.contains_key(validator_id)
+    }
+
+    /// Epoch at which a jailed validator is released
+    pub fn jailed_until(&self, validator_id: &str) -> Option<u64> {
+        self.jailed.get(validator_id).map(|jail| jail.release_epoch)
+    }
+
+    /// Slash a validator for a detected Byzantine offense unless the policy only warns
+    async fn slash_byzantine(&self, validator_id: &str, reason: &str) -> Result<()> {
+        let severity = self
+            .byzantine_detector
+            .latest_event_for(validator_id)
+            .await
+            .map_or(SlashingSeverity::Critical, |event| event.severity);
+        if severity == SlashingSeverity::Minor {
+            warn!(
+                "⚠️  Warning validator {} for a first offense: {}",
+                validator_id, reason
+            );
+            return Ok(());
+        }
+
+        self.slash_validator(validator_id, severity, reason).await
+    }
+
+    /// Subscribe to consensus events such as reorgs
+    pub fn subscribe_events(&self) -> broadcast::Receiver<ConsensusEvent> {
+        self.events.subscribe()
+    }
+
+    /// Stream every finalized block from `from_height` on, in height order
+    ///
+    /// Historical blocks are yielded first, then newly finalized ones as they arrive,
+    /// each height exactly once. Finalization never waits for the stream: if the
+    /// consumer falls behind the live channel, the missed blocks are read back from
+    /// history. An indexer resumes after a restart by passing the height after the
+    /// last block it stored.
+    pub fn finalized_block_stream(
+        &self,
+        from_height: u64,
+    ) -> impl futures::Stream<Item = Block> + Send + 'static {
+        // Subscribe before reading history so no block falls between the two
+        let live = self.finalized_block_sender.subscribe();
+        let blocks = Arc::clone(&self.finalized_blocks);
+
+        stream::unfold(
+            (blocks, live, from_height, VecDeque::<Block>::new()),
+            |(blocks, mut live, mut next_height, mut buffer)| async move {
+                loop {
+                    if let Some(block) = buffer.pop_front() {
+                        next_height = block.header.height + 1;
+                        return Some((block, (blocks, live, next_height, buffer)));
+                    }
+
+                    let history = blocks.read().await;
+                    let start = history.partition_point(|b| b.header.height < next_height);
+                    buffer.extend(history.iter().skip(start).take(BLOCK_STREAM_BATCH).cloned());
+                    drop(history);
+                    if !buffer.is_empty() {
+                        continue;
+                    }
+
+                    match live.recv().await {
+                        Ok(block) if block.header.height == next_height => buffer.push_back(block),
+                        // Already yielded, or ahead of a gap that history fills in
+                        Ok(_) => {}
+                        Err(broadcast::error::RecvError::Lagged(_)) => {}
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            },
+        )
+    }
+
+    /// Recent behavior of every registered validator, best ranked first
+    pub async fn get_scoreboard(&self) -> Vec<ValidatorScore> {
+        let byzantine_events = self.byzantine_detector.get_slashing_events().await;
+        let mut scoreboard: Vec<ValidatorScore> = self
+            .validators
+            .iter()
+            .map(|entry| {
+                let validator = entry.value();
+                let activity = self
+                    .activity
+                    .get(validator.id())
+                    .map(|activity| *activity)
+                    .unwrap_or_default();
+                let offenses = byzantine_events
+                    .iter()
+                    .filter(|event| event.validator_id == validator.id())
+                    .count();
+                ValidatorScore::new(
+                    validator.id().to_string(),
+                    activity,
+                    offenses,
+                    validator.get_reputation(),
+                    validator.get_emotional_score(),
+                )
+            })
+            .collect();
+
+        scoreboard.sort_by(|a, b| {
+            b.rank
+                .total_cmp(&a.rank)
+                .then_with(|| a.validator_id.cmp(&b.validator_id))
+        });
+        scoreboard
+    }
+
+    /// Get Byzantine slashing events
+    pub async fn get_byzantine_events(&self) -> Vec<crate::staking::SlashingEvent> {
+        self.byzantine_detector.get_slashing_events().await
+    }
+
+    /// Cleanup old Byzantine detection data
+    pub async fn cleanup_byzantine_data(&self) {
+        let (current_epoch, finalized_height) = {
+            let state = self.state.read().await;
+            (state.current_epoch, state.last_finalized_height)
+        };
+        let blocks_per_epoch = self.get_config().await.blocks_per_epoch;
+        // Keep last 100 epochs of data
+        self.byzantine_detector.cleanup_old_data(
+            current_epoch,
+            100,
+            finalized_height,
+            100u64.saturating_mul(blocks_per_epoch as u64),
+        );
+    }
+
+    // ========== Crash Recovery and Fault Tolerance ==========
+
+    /// Recover from a crash using checkpoints and block replay
+    ///
+    /// This method attempts to restore consensus state after a crash by:
+    /// 1. Loading the latest checkpoint
+    /// 2. Replaying finalized blocks since the checkpoint
+    /// 3. Rebuilding the randomness beacon from the finalized chain
+    /// 4. Validating state consistency
+    ///
+    /// Note: In a real implementation, this would sync with network peers
+    pub async fn recover_from_crash(&self) -> Result<()> {
+        info!("🔄 Attempting crash recovery...");
+
+        // 1. Load last checkpoint
+        if let Some(checkpoint) = self.checkpoint_manager.get_latest_checkpoint().await {
+            info!(
+                "📍 Found checkpoint at height {} (epoch {})",
+                checkpoint.height, checkpoint.epoch
+            );
+
+            // Restore from checkpoint
+            self.restore_from_checkpoint(&checkpoint).await?;
+
+            // 2. Replay blocks since checkpoint
+            let current_height = self.state.read().await.last_finalized_height;
+            if current_height > checkpoint.height {
+                info!(
+                    "🔁 Replaying {} blocks since checkpoint",
+                    current_height - checkpoint.height
+                );
+                self.replay_blocks_since_checkpoint(&checkpoint).await?;
+            }
+        } else {
+            info!("No checkpoint found, starting from genesis");
+        }
+
+        // 3. Rebuild the beacon, which may have observed blocks past the restored chain
+        self.rebuild_beacon().await;
+
+        // In a real implementation, sync with network
+        // self.sync_with_network().await?;
+
+        // 4. Validate state consistency
+        self.validate_state().await?;
+
+        info!("✅ Crash recovery complete");
+        Ok(())
+    }
+
+    /// Restore consensus state from a checkpoint
+    async fn restore_from_checkpoint(&self, checkpoint: &crate::checkpoint::Checkpoint) -> Result<()> {
+        // Verify checkpoint is valid
+        if !self.checkpoint_manager.verify_checkpoint(checkpoint).await? {
+            return Err(ConsensusError::internal("Invalid checkpoint"));
+        }
+
+        // Signatures alone do not tie the checkpoint to the chain being restored onto
+        let previous_consensus_root = self
+            .checkpoint_manager
+            .consensus_root_before(checkpoint.height)
+            .await;
+        let blocks = self.finalized_blocks.read().await;
+        let block = blocks
+            .iter()
+            .find(|block| block.header.height == checkpoint.height)
+            .ok_or_else(|| {
+                ConsensusError::internal(format!(
+                    "Checkpoint block at height {} is not in the finalized chain",
+                    checkpoint.height
+                ))
+            })?;
+        if block.hash != checkpoint.block_hash {
+            return Err(ConsensusError::internal(format!(
+                "Checkpoint block hash does not match the finalized block at height {}",
+                checkpoint.height
+            )));
+        }
+        if !checkpoint.verify_state(block, &previous_consensus_root) {
+            return Err(ConsensusError::internal(format!(
+                "Checkpoint state root does not match the finalized chain at height {}",
+                checkpoint.height
+            )));
+        }
+        drop(blocks);
+
+        // Restore state
+        let mut state = self.state.write().await;
+        state.current_epoch = checkpoint.epoch;
+        state.last_finalized_height = checkpoint.height;
+
+        // Note: In a full implementation, we would restore:
+        // - Complete finalized block chain
+        // - Validator states and stakes
+        // - Pending transaction pool
+        // - Metrics and statistics
+
+        info!(
+            "Restored state from checkpoint: height={}, epoch={}",
+            checkpoint.height, checkpoint.epoch
+        );
+
+        Ok(())
+    }
+
+    /// Reset the randomness beacon to the finalized chain
+    async fn rebuild_beacon(&self) {
+        let blocks = self.finalized_blocks.read().await;
+        let block_hashes: Vec<&str> = blocks.iter().map(|block| block.hash.as_str()).collect();
+        self.beacon.rebuild(&block_hashes);
+    }
+
+    /// Replay blocks since the last checkpoint to rebuild state
+    async fn replay_blocks_since_checkpoint(
+        &self,
+        checkpoint: &crate::checkpoint::Checkpoint,
+    ) -> Result<()> {
+        let blocks = self.finalized_blocks.read().await;
+
+        // Find blocks after checkpoint
+        let replay_blocks: Vec<_> = blocks
+            .iter()
+            .filter(|b| b.header.height > checkpoint.height)
+            .collect();
+
+        info!("Replaying {} blocks", replay_blocks.len());
+
+        for block in replay_blocks {
+            // Validate block
+            if !block.verify_hash() {
+                warn!("Block {} has invalid hash during replay", block.header.height);
+                continue;
+            }
+
+            // Update state from block
+            if let Some(metadata) = &block.consensus_metadata {
+                let mut state = self.state.write().await;
+                state.last_finalized_height = block.header.height;
+                state.consensus_strength = metadata.consensus_strength;
+                state.emotional_fitness = metadata.emotional_fitness;
+            }
+
+            // Record in fork detector
+            if let Err(e) = self.fork_detector.record_block(block).await {
+                warn!("Fork detected during replay at height {}: {}", block.header.height, e);
+                // Attempt to resolve the fork
+                let _ = self.fork_detector.resolve_fork(block.header.height).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate that the current state is consistent
+    async fn validate_state(&self) -> Result<()> {
+        let state = self.state.read().await;
+        let blocks = self.finalized_blocks.read().await;
+
+        // Check that last finalized height matches block count
+        if !blocks.is_empty() {
+            let last_block = blocks.last().unwrap();
+            if last_block.header.height != state.last_finalized_height {
+                return Err(ConsensusError::internal(format!(
+                    "State inconsistency: last_finalized_height={} but last block height={}",
+                    state.last_finalized_height, last_block.header.height
+                )));
+            }
+        }
+
+        // Verify chain continuity
+        for i in 1..blocks.len() {
+            let prev_block = &blocks[i - 1];
+            let curr_block = &blocks[i];
+
+            if curr_block.header.height != prev_block.header.height + 1 {
+                return Err(ConsensusError::internal(format!(
+                    "Chain discontinuity: block {} -> {}",
+                    prev_block.header.height, curr_block.header.height
+                )));
+            }
+
+            if curr_block.header.previous_hash != prev_block.hash {
+                return Err(ConsensusError::internal(format!(
+                    "Chain hash mismatch at height {}",
+                    curr_block.header.height
+                )));
+            }
+        }
+
+        info!("State validation passed: {} blocks verified", blocks.len());
+        Ok(())
+    }
+
+    /// Create a checkpoint if at checkpoint interval
+    pub async fn try_create_checkpoint(&self, block: &Block) -> Result<Option<crate::checkpoint::Checkpoint>> {
+        if !self.checkpoint_manager.should_create_checkpoint(block.header.height) {
+            return Ok(None);
+        }
+
+        info!("Creating checkpoint at height {}", block.header.height);
+
+        // In a real implementation, collect signatures from validators
+        // For now, create an empty checkpoint as a placeholder
+        let _validator_signatures: Vec<crate::checkpoint::ValidatorSignature> = vec![];
+
+        // Update total stake in checkpoint manager
+        self.checkpoint_manager
+            .update_total_stake(self.total_stake())
+            .await;
+
+        // Note: In production, this would fail without real validator signatures
+        // For testing/development, we skip this
+        info!(
+            "Checkpoint interval reached at height {} (signatures would be collected from validators)",
+            block.header.height
+        );
+
+        Ok(None)
+    }
+
+    /// Checkpoint `tip` signed by every active validator, unless already checkpointed
+    async fn create_final_checkpoint(&self, tip: &Block) -> Result<()> {
+        if let Some(latest) = self.checkpoint_manager.get_latest_checkpoint().await {
+            if latest.height >= tip.header.height {
+                return Ok(());
+            }
+        }
+
+        self.checkpoint_manager
+            .update_total_stake(self.total_stake())
+            .await;
+        let state_root = self.checkpoint_manager.checkpoint_state_root(tip).await;
+
+        let validators: Vec<Arc<EmotionalValidator>> = self
+            .validators
+            .iter()
+            .filter(|entry| *entry.value().is_active.read())
+            .map(|entry| entry.value().clone())
+            .collect();
+        let signatures = validators
+            .iter()
+            .map(|validator| {
+                let signature = self.checkpoint_manager.sign_checkpoint(
+                    tip.header.height,
+                    &tip.hash,
+                    tip.header.epoch,
+                    &state_root,
+                    &validator.key_pair,
+                )?;
+                Ok(crate::checkpoint::ValidatorSignature {
+                    validator_id: validator.id().to_string(),
+                    stake: validator.get_stake(),
+                    signature,
+                    public_key: validator.key_pair.public_key_hex(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.checkpoint_manager
+            .create_checkpoint(tip, signatures)
+            .await?;
+        Ok(())
+    }
+
+    /// Get fork detector for external access
+    pub fn get_fork_detector(&self) -> Arc<crate::fork::ForkDetector> {
+        Arc::clone(&self.fork_detector)
+    }
+
+    /// Get checkpoint manager for external access
+    pub fn get_checkpoint_manager(&self) -> Arc<crate::checkpoint::CheckpointManager> {
+        Arc::clone(&self.checkpoint_manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::VecAuditSink;
+    use crate::biometric::{BiometricType, EmotionalProfile, EmotionalTrend};
+    use crate::clock::MockClock;
+    use crate::committee::VerificationContext;
+    use crate::state::BalanceStateMachine;
+    use crate::zkp::{BiometricCircuit, MockZkProvider};
+    use crate::DEFAULT_BYZANTINE_THRESHOLD;
+
+    /// Device returning fixed heart rate, stress and focus values
+    struct FixedDevice {
+        values: [f64; 3],
+        age_ms: u64,
+    }
+
+    impl BiometricDevice for FixedDevice {
+        fn collect_readings(&self) -> Result<Vec<BiometricReading>> {
+            let timestamp = SystemClock.now_millis() - self.age_ms;
+            let types = [
+                BiometricType::HeartRate,
+                BiometricType::StressLevel,
+                BiometricType::FocusLevel,
+            ];
+            Ok(types
+                .into_iter()
+                .zip(self.values)
+                .map(|(biometric_type, value)| BiometricReading {
+                    device_id: "fixed".to_string(),
+                    biometric_type,
+                    value,
+                    quality: 1.0,
+                    timestamp,
+                    metadata: None,
+                })
+                .collect())
+        }
+
+        fn device_id(&self) -> &str {
+            "fixed"
+        }
+
+        fn is_healthy(&self) -> bool {
+            true
+        }
+    }
+
+    /// Device whose readings are each `gap_ms` older than the previous one
+    struct SpreadDevice {
+        gap_ms: u64,
+    }
+
+    impl BiometricDevice for SpreadDevice {
+        fn collect_readings(&self) -> Result<Vec<BiometricReading>> {
+            let now = SystemClock.now_millis();
+            let readings = FixedDevice {
+                values: [70.0, 10.0, 90.0],
+                age_ms: 0,
+            }
+            .collect_readings()?;
+            Ok(readings
+                .into_iter()
+                .enumerate()
+                .map(|(i, reading)| BiometricReading {
+                    timestamp: now - i as u64 * self.gap_ms,
+                    ..reading
+                })
+                .collect())
+        }
+
+        fn device_id(&self) -> &str {
+            "spread"
+        }
+
+        fn is_healthy(&self) -> bool {
+            true
+        }
+    }
+
+    struct FailingDevice;
+
+    impl BiometricDevice for FailingDevice {
+        fn collect_readings(&self) -> Result<Vec<BiometricReading>> {
+            Err(ConsensusError::biometric_validation_failed(
+                "sensor disconnected",
+            ))
+        }
+
+        fn device_id(&self) -> &str {
+            "failing"
+        }
+
+        fn is_healthy(&self) -> bool {
+            false
+        }
+    }
+
+    /// Device that fails a fixed number of reads before recovering
+    struct FlakyDevice {
+        failures_left: std::sync::atomic::AtomicU32,
+        inner: FixedDevice,
+    }
+
+    impl BiometricDevice for FlakyDevice {
+        fn collect_readings(&self) -> Result<Vec<BiometricReading>> {
+            let failures_left = self.failures_left.load(AtomicOrdering::SeqCst);
+            if failures_left > 0 {
+                self.failures_left
+                    .store(failures_left - 1, AtomicOrdering::SeqCst);
+                return Err(ConsensusError::biometric_validation_failed(
+                    "sensor timeout",
+                ));
+            }
+            self.inner.collect_readings()
+        }
+
+        fn device_id(&self) -> &str {
+            "flaky"
+        }
+
+        fn is_healthy(&self) -> bool {
+            true
+        }
+    }
+
+    /// Device that advances a mock clock by a longer delay on every read
+    struct SlowingDevice {
+        clock: Arc<MockClock>,
+        delays_ms: Mutex<VecDeque<u64>>,
+        inner: FixedDevice,
+    }
+
+    impl BiometricDevice for SlowingDevice {
+        fn collect_readings(&self) -> Result<Vec<BiometricReading>> {
+            let delay_ms = self.delays_ms.try_lock().unwrap().pop_front().unwrap_or(0);
+            self.clock.advance(delay_ms);
+            let mut readings = self.inner.collect_readings()?;
+            for reading in &mut readings {
+                reading.timestamp = self.clock.now_millis();
+            }
+            Ok(readings)
+        }
+
+        fn device_id(&self) -> &str {
+            "slowing"
+        }
+
+        fn is_healthy(&self) -> bool {
+            true
+        }
+    }
+
+    /// Device returning the next heart rate, stress and focus values on each read
+    struct ScriptedDevice {
+        values: parking_lot::Mutex<VecDeque<[f64; 3]>>,
+    }
+
+    impl BiometricDevice for ScriptedDevice {
+        fn collect_readings(&self) -> Result<Vec<BiometricReading>> {
+            let values = self.values.lock().pop_front().unwrap();
+            FixedDevice { values, age_ms: 0 }.collect_readings()
+        }
+
+        fn device_id(&self) -> &str {
+            "scripted"
+        }
+
+        fn is_healthy(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_declining_validator_is_deactivated_and_recovers() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            max_declining_epochs: 3,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let mut events = engine.subscribe_events();
+
+        // Stress climbs and focus drops every epoch, then levels off
+        let mut values: VecDeque<[f64; 3]> = (0..6)
+            .map(|i| [70.0, 10.0 + 12.0 * i as f64, 95.0 - 12.0 * i as f64])
+            .collect();
+        values.extend([[70.0, 70.0, 35.0]; 4]);
+        let device = ScriptedDevice {
+            values: parking_lot::Mutex::new(values),
+        };
+        let validator = EmotionalValidator::new("validator-0", 10_000)
+            .unwrap()
+            .with_device(Arc::new(device));
+        engine.register_validator(validator).await.unwrap();
+
+        // The first three assessments build the history the trend is measured over
+        let mut statuses = Vec::new();
+        for _ in 0..6 {
+            let assessment = engine.assess_validators().await.unwrap();
+            statuses.push(assessment[0].1.clone());
+        }
+        let (active, inactive) = statuses.split_at(5);
+        assert!(active.iter().all(|s| *s == EligibilityStatus::Eligible));
+        assert_eq!(
+            inactive,
+            [EligibilityStatus::Ineligible(IneligibilityReason::Inactive)]
+        );
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ConsensusEvent::ValidatorDeactivated {
+                validator_id: "validator-0".to_string(),
+                declining_epochs: 3,
+            }
+        );
+
+        // Stays out until the decline ends
+        let mut recovered = false;
+        for _ in 0..4 {
+            let assessment = engine.assess_validators().await.unwrap();
+            if assessment[0].1 == EligibilityStatus::Eligible {
+                recovered = true;
+                break;
+            }
+        }
+        assert!(recovered);
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ConsensusEvent::ValidatorReactivated {
+                validator_id: "validator-0".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clock_skew_flags_validation_divergence() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            check_validation_determinism: true,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for i in 0..4 {
+            let mut validator = EmotionalValidator::new(format!("validator-{}", i), 10_000)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [70.0, 10.0, 90.0],
+                    age_ms: 0,
+                }));
+            if i == 3 {
+                // Two hours ahead, so every block looks too old to this validator
+                let skewed = SystemClock.now_millis() + 2 * 3_600_000;
+                validator = validator.with_clock(Arc::new(MockClock::new(skewed)));
+            }
+            engine.register_validator(validator).await.unwrap();
+        }
+        engine.execute_epoch().await.unwrap();
+
+        assert_eq!(engine.get_metrics().await.validation_divergences, 1);
+        let health = crate::HealthStatus::from_consensus(&engine, 0).await;
+        assert!(health
+            .issues
+            .contains(&crate::health::HealthIssue::ValidationDivergence(1)));
+
+        // The issue clears once the divergence falls out of the recent window
+        for _ in 0..=crate::health::VALIDATION_DIVERGENCE_WINDOW_EPOCHS {
+            engine.record_metrics_snapshot().await;
+        }
+        assert_eq!(engine.get_metrics().await.validation_divergences, 1);
+        let health = crate::HealthStatus::from_consensus(&engine, 0).await;
+        assert!(!health
+            .issues
+            .iter()
+            .any(|issue| matches!(issue, crate::health::HealthIssue::ValidationDivergence(_))));
+    }
+
+    #[tokio::test]
+    async fn test_lockstep_scores_flagged_as_collusion() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            collusion_detection: Some(CollusionDetection {
+                window: 6,
+                correlation_threshold: 0.95,
+            }),
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let mut events = engine.subscribe_events();
+
+        let scripted = |stress: [f64; 6]| ScriptedDevice {
+            values: parking_lot::Mutex::new(stress.iter().map(|s| [70.0, *s, 80.0]).collect()),
+        };
+        let ring = [10.0, 40.0, 20.0, 50.0, 30.0, 60.0];
+        let series = [
+            ("ring-a", ring),
+            ("ring-b", ring),
+            ("independent", [35.0, 35.0, 60.0, 10.0, 15.0, 40.0]),
+        ];
+        for (id, stress) in series {
+            let validator = EmotionalValidator::new(id, 10_000)
+                .unwrap()
+                .with_device(Arc::new(scripted(stress)));
+            engine.register_validator(validator).await.unwrap();
+        }
+
+        for _ in 0..6 {
+            engine.assess_validators().await.unwrap();
+        }
+
+        let suspects = engine.get_collusion_suspects().await;
+        assert_eq!(suspects.len(), 1);
+        assert_eq!(
+            (suspects[0].first.as_str(), suspects[0].second.as_str()),
+            ("ring-a", "ring-b")
+        );
+        assert!(suspects[0].correlation >= 0.95);
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ConsensusEvent::CollusionSuspected {
+                first: "ring-a".to_string(),
+                second: "ring-b".to_string(),
+            }
+        );
+
+        // Flagged for investigation only
+        let ring_a = engine.validators.get("ring-a").unwrap();
+        assert_eq!(ring_a.get_reputation(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_identical_flat_scores_flagged_as_collusion() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            collusion_detection: Some(CollusionDetection {
+                window: 3,
+                correlation_threshold: 0.95,
+            }),
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for id in ["flat-a", "flat-b"] {
+            let validator = EmotionalValidator::new(id, 10_000)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [70.0, 10.0, 90.0],
+                    age_ms: 0,
+                }));
+            engine.register_validator(validator).await.unwrap();
+        }
+
+        for _ in 0..3 {
+            engine.assess_validators().await.unwrap();
+        }
+
+        // Constant series have no variance, but reporting the same one is still lockstep
+        let suspects = engine.get_collusion_suspects().await;
+        assert_eq!(suspects.len(), 1);
+        assert_eq!(suspects[0].correlation, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_epoch_duration_grows_with_slow_epochs() {
+        let config = ConsensusConfig {
+            epoch_duration: 20,
+            emotional_threshold: 60,
+            adaptive_epoch_duration: Some(AdaptiveEpochDuration {
+                min_ms: 10,
+                max_ms: 100,
+                target_utilization: 0.5,
+            }),
+            ..Default::default()
+        };
+        let clock = Arc::new(MockClock::new(SystemClock.now_millis()));
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_clock(clock.clone());
+        let device = SlowingDevice {
+            clock: clock.clone(),
+            delays_ms: Mutex::new(VecDeque::from([15, 30, 60])),
+            inner: FixedDevice {
+                values: [70.0, 10.0, 90.0],
+                age_ms: 0,
+            },
+        };
+        let validator = EmotionalValidator::new("validator-1", 10_000)
+            .unwrap()
+            .with_clock(clock)
+            .with_device(Arc::new(device));
+        engine.register_validator(validator).await.unwrap();
+        assert_eq!(engine.get_state().await.effective_epoch_duration, 20);
+
+        let mut durations = Vec::new();
+        for _ in 0..3 {
+            engine.run_epoch().await.unwrap();
+            durations.push(engine.get_state().await.effective_epoch_duration);
+        }
+
+        // Each epoch takes exactly as long as the device read advanced the clock
+        assert_eq!(durations, vec![30, 60, 100]);
+        assert_eq!(engine.get_metrics().await.successful_epochs, 3);
+    }
+
+    #[tokio::test]
+    async fn test_transient_device_failures_are_retried() {
+        let config = ConsensusConfig {
+            emotional_threshold: 60,
+            device_retry_backoff_ms: 1,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+
+        for (id, failures) in [("recovers", 2), ("gives-up", 3)] {
+            let device = FlakyDevice {
+                failures_left: std::sync::atomic::AtomicU32::new(failures),
+                inner: FixedDevice {
+                    values: [70.0, 10.0, 90.0],
+                    age_ms: 0,
+                },
+            };
+            let validator = EmotionalValidator::new(id, 10_000)
+                .unwrap()
+                .with_device(Arc::new(device));
+            engine.register_validator(validator).await.unwrap();
+        }
+
+        let assessment: HashMap<_, _> = engine
+            .assess_validators()
+            .await
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(assessment["recovers"], EligibilityStatus::Eligible);
+        assert!(matches!(
+            &assessment["gives-up"],
+            EligibilityStatus::Ineligible(IneligibilityReason::DeviceReadFailed(reason))
+                if reason.contains("after 3 attempts")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_assessment_captures_all_eligible() {
+        let mut eligible_sets = Vec::new();
+
+        for assessment_concurrency in [1, 8, 64] {
+            let config = ConsensusConfig {
+                emotional_threshold: 60,
+                assessment_concurrency,
+                device_retry_backoff_ms: 0,
+                ..Default::default()
+            };
+            let engine = ProofOfEmotionEngine::new(config).unwrap();
+
+            for i in 0..300 {
+                let device: Arc<dyn BiometricDevice> = if i % 3 == 0 {
+                    Arc::new(FailingDevice)
+                } else {
+                    Arc::new(FixedDevice {
+                        values: [70.0, 10.0, 90.0],
+                        age_ms: 0,
+                    })
+                };
+                let validator = EmotionalValidator::new(format!("validator-{:03}", i), 10_000)
+                    .unwrap()
+                    .with_device(device);
+                engine.register_validator(validator).await.unwrap();
+            }
+
+            let assessment = engine.assess_validators().await.unwrap();
+            assert_eq!(assessment.len(), 300);
+            assert!(assessment.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+            let eligible: Vec<String> = engine
+                .perform_emotional_assessment()
+                .await
+                .unwrap()
+                .iter()
+                .map(|validator| validator.id().to_string())
+                .collect();
+            assert_eq!(eligible.len(), 200);
+            eligible_sets.push(eligible);
+        }
+
+        assert!(eligible_sets.windows(2).all(|pair| pair[0] == pair[1]));
+    }
+
+    #[tokio::test]
+    async fn test_recorded_epoch_replays_to_same_result() {
+        let config = ConsensusConfig {
+            emotional_threshold: 60,
+            ..Default::default()
+        };
+        let recorder = Arc::new(EpochRecorder::default());
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_epoch_recorder(recorder.clone());
+        for i in 0..3 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [70.0, 10.0, 90.0],
+                    age_ms: 0,
+                }));
+            engine.register_validator(validator).await.unwrap();
+        }
+        engine.execute_epoch().await.unwrap();
+
+        let json = recorder.get(1).unwrap().to_json().unwrap();
+        let record = EpochRecord::from_json(&json).unwrap();
+        assert_eq!(record.committee.len(), 3);
+
+        let replayed = engine.replay_epoch(&record).unwrap();
+        let recorded = &record.voting_result;
+        assert!(replayed.success);
+        assert_eq!(replayed.consensus_strength, recorded.consensus_strength);
+        assert_eq!(replayed.participant_count, recorded.participant_count);
+        assert_eq!(replayed.votes, recorded.votes);
+
+        let mut tampered = record;
+        tampered.voting_result.votes[0].approved = false;
+        assert!(matches!(
+            engine.replay_epoch(&tampered),
+            Err(ConsensusError::ReplayDiverged { epoch: 1, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_assessment_reports_ineligibility_reasons() {
+        let config = ConsensusConfig {
+            emotional_threshold: 60,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+
+        let calm = || FixedDevice {
+            values: [70.0, 10.0, 90.0],
+            age_ms: 0,
+        };
+        let devices: Vec<(&str, Arc<dyn BiometricDevice>)> = vec![
+            ("eligible", Arc::new(calm())),
+            ("inactive", Arc::new(calm())),
+            ("low-stake", Arc::new(calm())),
+            (
+                "low-score",
+                Arc::new(FixedDevice {
+                    values: [130.0, 95.0, 5.0],
+                    age_ms: 0,
+                }),
+            ),
+            (
+                "stale",
+                Arc::new(FixedDevice {
+                    values: [70.0, 10.0, 90.0],
+                    age_ms: 10 * 60 * 1000,
+                }),
+            ),
+            ("broken", Arc::new(FailingDevice)),
+        ];
+        for (id, device) in devices {
+            let validator = EmotionalValidator::new(id, 10_000)
+                .unwrap()
+                .with_device(device);
+            engine.register_validator(validator).await.unwrap();
+        }
+        *engine.validators.get("inactive").unwrap().is_active.write() = false;
+        *engine.validators.get("low-stake").unwrap().stake.write() = 500;
+
+        let assessment: std::collections::HashMap<_, _> = engine
+            .assess_validators()
+            .await
+            .unwrap()
+            .into_iter()
+            .collect();
+
+        assert_eq!(assessment["eligible"], EligibilityStatus::Eligible);
+        assert_eq!(
+            assessment["inactive"],
+            EligibilityStatus::Ineligible(IneligibilityReason::Inactive)
+        );
+        assert_eq!(
+            assessment["low-stake"],
+            EligibilityStatus::Ineligible(IneligibilityReason::InsufficientStake {
+                stake: 500,
+                minimum: 10_000,
+            })
+        );
+        assert!(matches!(
+            assessment["low-score"],
+            EligibilityStatus::Ineligible(IneligibilityReason::LowEmotionalScore {
+                threshold: 60,
+                ..
+            })
+        ));
+        assert!(matches!(
+            assessment["stale"],
+            EligibilityStatus::Ineligible(IneligibilityReason::StaleProfile { .. })
+        ));
+        assert!(matches!(
+            assessment["broken"],
+            EligibilityStatus::Ineligible(IneligibilityReason::DeviceReadFailed(_))
+        ));
+        assert_eq!(engine.get_last_assessment().await.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_readings_must_fall_within_temporal_window() {
+        let config = ConsensusConfig::builder()
+            .temporal_window_ms(30_000)
+            .build()
+            .unwrap();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for (id, gap_ms) in [("together", 1_000), ("spread", 20_000)] {
+            let validator = EmotionalValidator::new(id, 10_000)
+                .unwrap()
+                .with_device(Arc::new(SpreadDevice { gap_ms }));
+            engine.register_validator(validator).await.unwrap();
+        }
+
+        let assessment: std::collections::HashMap<_, _> = engine
+            .assess_validators()
+            .await
+            .unwrap()
+            .into_iter()
+            .collect();
+
+        assert_eq!(assessment["together"], EligibilityStatus::Eligible);
+        assert!(matches!(
+            assessment["spread"],
+            EligibilityStatus::Ineligible(IneligibilityReason::ReadingsOutsideWindow {
+                window_ms: 30_000,
+                ..
+            })
+        ));
+
+        let zero_window = ConsensusConfig {
+            temporal_window_ms: 0,
+            ..Default::default()
+        };
+        assert!(zero_window.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_threshold_schedule_tightens_as_network_grows() {
+        let tier = |min_validators, max_validators, byzantine_threshold| ThresholdTier {
+            min_validators,
+            max_validators,
+            byzantine_threshold,
+            emotional_threshold: 0,
+        };
+        let schedule = ThresholdSchedule::new(vec![tier(0, Some(3), 51), tier(4, None, 90)]);
+        let config = ConsensusConfig {
+            threshold_schedule: Some(schedule),
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+
+        let validator = |i: usize| {
+            EmotionalValidator::new(format!("validator-{}", i), 10_000)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [70.0, 10.0, 90.0],
+                    age_ms: 0,
+                }))
+        };
+        // Two hours ahead, so this validator rejects every block as too old
+        let skewed = SystemClock.now_millis() + 2 * 3_600_000;
+        engine
+            .register_validator(validator(0).with_clock(Arc::new(MockClock::new(skewed))))
+            .await
+            .unwrap();
+        for i in 1..3 {
+            engine.register_validator(validator(i)).await.unwrap();
+        }
+
+        // Two of three approving clears the small network's threshold
+        engine.execute_epoch().await.unwrap();
+        let state = engine.get_state().await;
+        assert_eq!(state.effective_byzantine_threshold, 51);
+        assert_eq!(state.effective_emotional_threshold, 0);
+
+        // Four of five approving falls short once the larger network's threshold applies
+        for i in 3..5 {
+            engine.register_validator(validator(i)).await.unwrap();
+        }
+        assert!(engine.execute_epoch().await.is_err());
+        assert_eq!(engine.get_state().await.effective_byzantine_threshold, 90);
+
+        // The configured thresholds are left as set, so a patch is not overwritten
+        let config = engine.get_config().await;
+        assert_eq!(config.byzantine_threshold, DEFAULT_BYZANTINE_THRESHOLD);
+        assert_eq!(config.emotional_threshold, 75);
+        engine
+            .update_config(ConfigPatch {
+                byzantine_threshold: Some(80),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        engine.apply_pending_config().await;
+        engine.apply_threshold_schedule().await;
+        assert_eq!(engine.get_config().await.byzantine_threshold, 80);
+        assert_eq!(engine.get_state().await.effective_byzantine_threshold, 90);
+    }
+
+    #[test]
+    fn test_threshold_schedule_must_be_contiguous() {
+        let tier = |min_validators, max_validators, byzantine_threshold| ThresholdTier {
+            min_validators,
+            max_validators,
+            byzantine_threshold,
+            emotional_threshold: 75,
+        };
+        let build = |tiers| {
+            ConsensusConfig::builder()
+                .threshold_schedule(ThresholdSchedule::new(tiers))
+                .build()
+        };
+
+        assert!(build(vec![tier(0, Some(9), 67), tier(10, None, 75)]).is_ok());
+        assert!(build(vec![]).is_err());
+        assert!(build(vec![tier(1, None, 67)]).is_err());
+        assert!(build(vec![tier(0, Some(9), 67), tier(11, None, 75)]).is_err());
+        assert!(build(vec![tier(0, Some(9), 67), tier(5, None, 75)]).is_err());
+        assert!(build(vec![tier(0, Some(9), 67)]).is_err());
+        assert!(build(vec![tier(0, Some(9), 50), tier(10, None, 75)]).is_err());
+        assert!(build(vec![tier(0, None, 67), tier(10, None, 75)]).is_err());
+        assert!(build(vec![tier(0, Some(usize::MAX), 67), tier(0, None, 75)]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_metadata_fitness_is_stake_weighted() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            stake_weighted_voting: true,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let members = [
+            ("heavy", 90_000, [70.0, 10.0, 90.0]),
+            ("light", 10_000, [110.0, 90.0, 20.0]),
+        ];
+        for (id, stake, values) in members {
+            let validator = EmotionalValidator::new(id, stake)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice { values, age_ms: 0 }));
+            engine.register_validator(validator).await.unwrap();
+        }
+        engine.execute_epoch().await.unwrap();
+
+        let block = engine.get_finalized_blocks().await.pop().unwrap();
+        let metadata = block.consensus_metadata.unwrap();
+        let score = |id: &str| engine.validators.get(id).unwrap().get_emotional_score();
+        let (heavy, light) = (score("heavy"), score("light"));
+        assert!(heavy > light);
+
+        assert_eq!(metadata.mean_emotional_fitness, (heavy + light) / 2);
+        assert!(metadata.emotional_fitness > metadata.mean_emotional_fitness);
+        assert!(heavy - metadata.emotional_fitness < metadata.emotional_fitness - light);
+    }
+
+    #[test]
+    fn test_committee_emotional_fitness_is_stake_weighted() {
+        let committee: Vec<_> = [("heavy", 30_000, 90), ("light", 10_000, 50)]
+            .into_iter()
+            .map(|(id, stake, score)| {
+                let validator = EmotionalValidator::new(id, stake).unwrap();
+                validator.apply_proven_score(score);
+                Arc::new(validator)
+            })
+            .collect();
+
+        // (30_000 * 90 + 10_000 * 50) / 40_000 = 80
+        let fitness = ProofOfEmotionEngine::committee_emotional_fitness(&committee);
+        assert_eq!(fitness, 80);
+        assert_eq!(ProofOfEmotionEngine::committee_emotional_fitness(&[]), 0);
+
+        let mut block = Block::new(1, 1, "0".repeat(64), "heavy".to_string(), 90, vec![]);
+        let original_hash = block.hash.clone();
+        block.set_committee_emotional_fitness(fitness);
+        assert_eq!(block.header.committee_emotional_fitness, 80);
+        assert_eq!(block.header.emotional_score, 90);
+        assert_ne!(block.hash, original_hash);
+        assert!(block.verify_hash());
+    }
+
+    #[tokio::test]
+    async fn test_finalized_block_is_the_block_voted_on() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_epoch_bundle_retention(1);
+        for (id, values) in [("calm", [65.0, 10.0, 95.0]), ("tense", [110.0, 80.0, 30.0])] {
+            let validator = EmotionalValidator::new(id, 10_000)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice { values, age_ms: 0 }));
+            engine.register_validator(validator).await.unwrap();
+        }
+        engine.execute_epoch().await.unwrap();
+
+        let block = engine.get_finalized_blocks().await.pop().unwrap();
+        let bundle = engine.export_epoch_bundle(1).await.unwrap();
+        let round = &bundle.rounds[0];
+        assert_eq!(round.block.hash, block.hash);
+        assert!(round
+            .voting_result
+            .votes
+            .iter()
+            .all(|vote| vote.block_hash == block.hash));
+        assert!(block.verify_hash());
+        assert!(block.verify_signature().unwrap());
+        assert!(block.emotional_volatility() > 0);
+        assert!(block.header.committee_emotional_fitness > 0);
+    }
+
+    #[test]
+    fn test_spread_committee_is_more_volatile() {
+        let committee_of = |scores: &[u8]| -> Vec<Arc<EmotionalValidator>> {
+            scores
+                .iter()
+                .enumerate()
+                .map(|(i, &score)| {
+                    let validator =
+                        EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+                    validator.apply_proven_score(score);
+                    Arc::new(validator)
+                })
+                .collect()
+        };
+
+        let clustered =
+            ProofOfEmotionEngine::emotional_volatility(&committee_of(&[80, 81, 79, 80]));
+        let spread = ProofOfEmotionEngine::emotional_volatility(&committee_of(&[95, 40, 85, 55]));
+        // Variances are 0.5 and 492.1875
+        assert_eq!(clustered, 50);
+        assert_eq!(spread, 49_219);
+        assert!(spread > clustered);
+
+        let mut block = Block::new(1, 1, "0".repeat(64), "validator-0".to_string(), 80, vec![]);
+        let original_hash = block.hash.clone();
+        block.set_emotional_volatility(spread);
+        assert_eq!(block.emotional_volatility(), spread);
+        assert_ne!(block.hash, original_hash);
+        assert!(block.verify_hash());
+    }
+
+    #[tokio::test]
+    async fn test_allowlisted_validator_registers() {
+        let validator = EmotionalValidator::new("member", 10_000).unwrap();
+        let allowlist = HashSet::from([validator.public_key_hex()]);
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default())
+            .unwrap()
+            .with_permissioning(Permissioning::Allowlist(allowlist));
+
+        engine.register_validator(validator).await.unwrap();
+
+        let late_joiner = EmotionalValidator::new("late-joiner", 10_000).unwrap();
+        engine
+            .add_to_allowlist(late_joiner.public_key_hex())
+            .await
+            .unwrap();
+        engine.register_validator(late_joiner).await.unwrap();
+        assert_eq!(engine.get_validator_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_non_allowlisted_validator_rejected() {
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default())
+            .unwrap()
+            .with_permissioning(Permissioning::Allowlist(HashSet::new()));
+
+        let outsider = EmotionalValidator::new("outsider", 10_000).unwrap();
+        let outsider_key = outsider.public_key_hex();
+        let result = engine.register_validator(outsider).await;
+        assert!(matches!(
+            result,
+            Err(ConsensusError::ValidatorNotPermitted { ref validator_id }) if validator_id == "outsider"
+        ));
+        assert_eq!(engine.get_validator_count(), 0);
+
+        engine.add_to_allowlist(outsider_key.clone()).await.unwrap();
+        assert!(engine.remove_from_allowlist(&outsider_key).await.unwrap());
+        assert!(!engine.remove_from_allowlist(&outsider_key).await.unwrap());
+
+        // Open engines have no allowlist to edit
+        let open = ProofOfEmotionEngine::new(ConsensusConfig::default()).unwrap();
+        assert!(open.add_to_allowlist("key").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validator_set_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("validators.json");
+
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default()).unwrap();
+        let mut key_pairs = Vec::new();
+        for (id, stake, commission) in [
+            ("alice", 10_000, 5),
+            ("bob", 25_000, 10),
+            ("carol", 40_000, 0),
+        ] {
+            let validator = EmotionalValidator::new(id, stake)
+                .unwrap()
+                .with_commission(commission);
+            key_pairs.push(validator.key_pair.clone());
+            engine.register_validator(validator).await.unwrap();
+        }
+        let exported = engine.export_validator_set();
+        exported.to_json(&path).unwrap();
+
+        let fresh = ProofOfEmotionEngine::new(ConsensusConfig::default()).unwrap();
+        let count = fresh
+            .register_validators_from_file(&path, &key_pairs)
+            .await
+            .unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(fresh.export_validator_set(), exported);
+        assert_eq!(fresh.validators.get("bob").unwrap().get_stake(), 25_000);
+        assert_eq!(fresh.validators.get("bob").unwrap().commission(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_total_stake_cache() {
+        let engine = Arc::new(ProofOfEmotionEngine::new(ConsensusConfig::default()).unwrap());
+        let registrations = (1..=8).map(|i| {
+            let engine = engine.clone();
+            tokio::spawn(async move {
+                let id = format!("validator-{}", i);
+                let validator = EmotionalValidator::new(id, i * 10_000).unwrap();
+                engine.register_validator(validator).await.unwrap();
+            })
+        });
+        for registration in registrations.collect::<Vec<_>>() {
+            registration.await.unwrap();
+        }
+
+        let fresh_total = |engine: &ProofOfEmotionEngine| -> u64 {
+            engine.validators.iter().map(|v| v.get_stake()).sum()
+        };
+        assert_eq!(engine.total_stake(), 360_000);
+        assert_eq!(engine.total_stake(), fresh_total(&engine));
+
+        assert_eq!(engine.slash_stake("validator-2", 5_000).unwrap(), 5_000);
+        assert_eq!(engine.slash_stake("validator-1", 50_000).unwrap(), 10_000);
+        engine.deregister_validator("validator-8").unwrap();
+        let replacement = EmotionalValidator::new("validator-3", 50_000).unwrap();
+        engine.register_validator(replacement).await.unwrap();
+
+        assert_eq!(engine.total_stake(), 360_000 - 15_000 - 80_000 + 20_000);
+        assert_eq!(engine.total_stake(), fresh_total(&engine));
+        assert!(engine.slash_stake("validator-8", 1_000).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_consensus_engine_creation() {
+        let config = ConsensusConfig::default();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        assert_eq!(engine.get_validator_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_validator_registration() {
+        let config = ConsensusConfig::default();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+
+        let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+        engine.register_validator(validator).await.unwrap();
+
+        assert_eq!(engine.get_validator_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_insufficient_stake_registration() {
+        let config = ConsensusConfig::default();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+
+        let validator = EmotionalValidator::new("validator-1", 5_000).unwrap();
+        let result = engine.register_validator(validator).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_register_validators_is_all_or_nothing() {
+        let config = ConsensusConfig::default();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+
+        let validators = vec![
+            EmotionalValidator::new("validator-1", 10_000).unwrap(),
+            EmotionalValidator::new("validator-2", 5_000).unwrap(),
+            EmotionalValidator::new("validator-3", 20_000).unwrap(),
+        ];
+        let result = engine.register_validators(validators).await;
+
+        assert!(matches!(
+            result,
+            Err(ConsensusError::InsufficientStake { .. })
+        ));
+        assert_eq!(engine.get_validator_count(), 0);
+        assert_eq!(engine.total_stake(), 0);
+        assert_eq!(engine.get_state().await.total_validators, 0);
+
+        let validators = vec![
+            EmotionalValidator::new("validator-1", 10_000).unwrap(),
+            EmotionalValidator::new("validator-3", 20_000).unwrap(),
+        ];
+        engine.register_validators(validators).await.unwrap();
+        assert_eq!(engine.total_stake(), 30_000);
+        assert_eq!(engine.get_state().await.total_validators, 2);
+    }
+
+    #[tokio::test]
+    async fn test_update_config_applies_next_epoch() {
+        let config = ConsensusConfig::default();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+
+        engine
+            .update_config(ConfigPatch {
+                emotional_threshold: Some(40),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
 
-        Ok(())
+        // Not applied until the epoch boundary
+        assert_eq!(engine.get_config().await.emotional_threshold, 75);
+
+        // No validators registered, so the epoch itself fails after applying the update
+        assert!(engine.execute_epoch().await.is_err());
+        assert_eq!(engine.get_config().await.emotional_threshold, 40);
     }
 
-    /// Stop consensus engine
-    pub async fn stop(&self) -> Result<()> {
-        let mut running = self.is_running.write().await;
-        if !*running {
-            return Err(ConsensusError::NotRunning);
-        }
-        *running = false;
-        drop(running);
+    #[tokio::test]
+    async fn test_update_config_rejects_invalid_values() {
+        let config = ConsensusConfig::default();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
 
-        info!("🛑 Stopping Proof of Emotion consensus engine");
+        let result = engine
+            .update_config(ConfigPatch {
+                byzantine_threshold: Some(40),
+                ..Default::default()
+            })
+            .await;
+        assert!(result.is_err());
 
-        // Notify shutdown signal to immediately stop epoch loop
-        self.shutdown_signal.notify_waiters();
+        let result = engine
+            .update_config(ConfigPatch {
+                committee_size: Some(0),
+                ..Default::default()
+            })
+            .await;
+        assert!(result.is_err());
 
-        Ok(())
+        engine.execute_epoch().await.ok();
+        assert_eq!(engine.get_config().await.byzantine_threshold, 67);
+        assert_eq!(engine.get_config().await.committee_size, 21);
     }
 
-    /// Main epoch processing loop
-    async fn epoch_loop(&self) {
-        let mut interval = time::interval(Duration::from_millis(self.config.epoch_duration));
+    async fn empty_block_vote(engine: &ProofOfEmotionEngine) -> VotingResult {
+        let validator = engine.validators.get("validator-1").unwrap().clone();
+        let mut block = Block::new(1, 0, "0".repeat(64), "validator-1".to_string(), 85, vec![]);
+        block.set_chain_id(crate::DEFAULT_CHAIN_ID);
+        block.sign(&validator.key_pair).unwrap();
+        let result = engine.execute_voting(&[validator], &block, 0).await;
+        result.unwrap()
+    }
 
-        loop {
-            tokio::select! {
-                _ = interval.tick() => {
-                    // Check if we should continue running
-                    if !*self.is_running.read().await {
-                        break;
-                    }
+    #[tokio::test]
+    async fn test_block_for_other_chain_rejected() {
+        let config = ConsensusConfig::builder()
+            .chain_id("poe-testnet")
+            .build()
+            .unwrap();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+        engine.register_validator(validator).await.unwrap();
 
-                    match self.execute_epoch().await {
-                        Ok(_) => {
-                            let mut metrics = self.metrics.write().await;
-                            metrics.successful_epochs += 1;
-                        }
-                        Err(e) => {
-                            error!("❌ Epoch failed: {}", e);
-                            let mut metrics = self.metrics.write().await;
-                            metrics.failed_epochs += 1;
-                        }
-                    }
-                }
-                _ = self.shutdown_signal.notified() => {
-                    info!("🛑 Shutdown signal received, stopping epoch loop");
-                    break;
-                }
-            }
-        }
+        let result = empty_block_vote(&engine).await;
+        assert!(!result.success);
+        let reason = result.votes[0].reason.as_deref().unwrap();
+        assert!(reason.contains("expected \"poe-testnet\""));
     }
 
-    /// Execute a single epoch
-    async fn execute_epoch(&self) -> Result<()> {
-        let start_time = std::time::Instant::now();
+    #[tokio::test]
+    async fn test_wrong_epoch_vote_counted_as_rejected() {
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default()).unwrap();
+        let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+        engine.register_validator(validator).await.unwrap();
+        engine.state.write().await.current_epoch = 5;
+
+        // Votes carry the epoch of the block, which is from an earlier epoch
+        let validator = engine.validators.get("validator-1").unwrap().clone();
+        let mut block = Block::new(1, 4, "0".repeat(64), "validator-1".to_string(), 85, vec![]);
+        block.sign(&validator.key_pair).unwrap();
+        let result = engine.execute_voting(&[validator], &block, 0).await;
+
+        assert!(result.unwrap().votes.is_empty());
+        let metrics = engine.get_metrics().await;
+        assert_eq!(metrics.rejected_votes, 1);
+        assert_eq!(metrics.byzantine_failures, 0);
+        let reasons = engine.get_vote_rejection_reasons().await;
+        assert_eq!(reasons.get(&VoteRejectionReason::WrongEpoch), Some(&1));
+        assert_eq!(reasons.len(), 1);
+    }
 
-        let mut state = self.state.write().await;
-        state.current_epoch += 1;
-        let epoch = state.current_epoch;
-        drop(state);
+    #[tokio::test]
+    async fn test_empty_block_rejected_when_pending() {
+        let config = ConsensusConfig::builder()
+            .require_nonempty_blocks_when_pending(true)
+            .build()
+            .unwrap();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+        engine.register_validator(validator).await.unwrap();
 
-        info!("⏰ Starting epoch {}", epoch);
+        let tx = Transaction::new("addr1".to_string(), "addr2".to_string(), 1000, 10);
+        engine.submit_transaction(tx).await.unwrap();
 
-        let eligible_validators = self.perform_emotional_assessment().await?;
+        let result = empty_block_vote(&engine).await;
+        assert!(!result.success);
+        assert!(result.votes.iter().all(|vote| !vote.approved));
+        let reason = result.votes[0].reason.as_deref().unwrap();
+        assert!(reason.contains("Empty block"));
+    }
 
-        if eligible_validators.is_empty() {
-            return Err(ConsensusError::committee_selection_failed(
-                "No validators meet emotional fitness threshold",
-            ));
-        }
+    #[tokio::test]
+    async fn test_empty_block_accepted_with_empty_mempool() {
+        let config = ConsensusConfig::builder()
+            .require_nonempty_blocks_when_pending(true)
+            .build()
+            .unwrap();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+        engine.register_validator(validator).await.unwrap();
 
-        info!(
-            "💓 {}/{} validators eligible",
-            eligible_validators.len(),
-            self.validators.len()
-        );
+        let result = empty_block_vote(&engine).await;
+        assert!(result.success);
+    }
 
-        let committee = self.select_committee(&eligible_validators).await?;
+    #[tokio::test]
+    async fn test_transaction_expires_with_mock_clock() {
+        let clock = Arc::new(MockClock::new(1_700_000_000_000));
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default())
+            .unwrap()
+            .with_clock(clock.clone());
+
+        let tx = Transaction::new_with_clock(
+            "addr1".to_string(),
+            "addr2".to_string(),
+            1000,
+            10,
+            clock.as_ref(),
+        );
+        engine.submit_transaction(tx).await.unwrap();
 
-        info!("👥 Committee selected: {} validators", committee.len());
+        clock.advance(4 * 60 * 1000);
+        engine.cleanup_transaction_pool().await;
+        assert_eq!(engine.get_state().await.pending_transactions, 1);
 
-        let proposed_block = self.propose_block(&committee).await?;
+        clock.advance(2 * 60 * 1000);
+        engine.cleanup_transaction_pool().await;
+        assert_eq!(engine.get_state().await.pending_transactions, 0);
+    }
 
-        info!(
-            "📦 Block {} proposed by {}",
-            proposed_block.header.height, proposed_block.header.validator_id
-        );
+    #[tokio::test]
+    async fn test_transaction_valid_until_within_global_ttl() {
+        let clock = Arc::new(MockClock::new(1_700_000_000_000));
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default())
+            .unwrap()
+            .with_clock(clock.clone());
+        let now = clock.now_millis();
+
+        let short_lived = Transaction::new_with_clock(
+            "addr1".to_string(),
+            "addr2".to_string(),
+            1000,
+            10,
+            clock.as_ref(),
+        )
+        .with_valid_until(now + 60 * 1000);
+        let long_lived = Transaction::new_with_clock(
+            "addr3".to_string(),
+            "addr4".to_string(),
+            1000,
+            10,
+            clock.as_ref(),
+        )
+        .with_valid_until(now + 60 * 60 * 1000);
+        engine.submit_transaction(short_lived).await.unwrap();
+        engine.submit_transaction(long_lived).await.unwrap();
+
+        // The short-lived transaction expires at its own deadline
+        clock.advance(2 * 60 * 1000);
+        engine.cleanup_transaction_pool().await;
+        assert_eq!(engine.get_state().await.pending_transactions, 1);
+
+        // The long-lived transaction is still capped by the global TTL
+        clock.advance(4 * 60 * 1000);
+        engine.cleanup_transaction_pool().await;
+        assert_eq!(engine.get_state().await.pending_transactions, 0);
+    }
 
-        let voting_result = self.execute_voting(&committee, &proposed_block).await?;
+    /// Ten eligible validators with equal scores and increasing stake
+    fn staked_eligible() -> Vec<Arc<EmotionalValidator>> {
+        let mut eligible = Vec::new();
+        for i in 0..10u64 {
+            let validator =
+                EmotionalValidator::new(format!("validator-{}", i), 10_000 * (i + 1)).unwrap();
+            *validator.emotional_profile.write() = Some(EmotionalProfile {
+                emotional_score: 80,
+                trend: EmotionalTrend::Stable,
+                confidence: 90,
+                last_updated: 0,
+                recent_readings: Vec::new(),
+                anomaly_count: 0,
+            });
+            eligible.push(Arc::new(validator));
+        }
+        eligible
+    }
 
-        if !voting_result.success {
-            warn!("❌ Voting failed: {:?}", voting_result.reason);
-            return Err(ConsensusError::invalid_block(
-                voting_result
-                    .reason
-                    .unwrap_or_else(|| "Voting failed".to_string()),
-            ));
+    async fn committee_appearances(mode: SelectionMode, epochs: u64) -> Vec<usize> {
+        let config = ConsensusConfig {
+            committee_size: 3,
+            selection_mode: mode,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let eligible = staked_eligible();
+
+        let mut appearances = vec![0; eligible.len()];
+        for epoch in 1..=epochs {
+            engine.state.write().await.current_epoch = epoch;
+            let (committee, _) = engine.select_committee(&eligible).await.unwrap();
+            assert_eq!(committee.len(), 3);
+            for member in committee {
+                let index = eligible.iter().position(|v| v.id() == member.id()).unwrap();
+                appearances[index] += 1;
+            }
         }
+        appearances
+    }
 
-        info!(
-            "✅ Consensus reached: {}% strength",
-            voting_result.consensus_strength
-        );
+    #[tokio::test]
+    async fn test_weighted_random_selection_includes_lower_scores() {
+        let top_k = committee_appearances(SelectionMode::TopK, 200).await;
+        let weighted = committee_appearances(SelectionMode::WeightedRandom, 200).await;
 
-        self.finalize_block(proposed_block, voting_result).await?;
+        // TopK always picks the three highest stakes
+        assert_eq!(top_k[..7].iter().sum::<usize>(), 0);
+        assert_eq!(top_k[7..], [200, 200, 200]);
 
-        let duration = start_time.elapsed().as_millis() as u64;
-        let mut metrics = self.metrics.write().await;
-        metrics.total_epochs += 1;
-        metrics.average_duration_ms = (metrics.average_duration_ms * (metrics.total_epochs - 1)
-            + duration)
-            / metrics.total_epochs;
+        // Weighted random gives every eligible validator a chance
+        assert!(weighted.iter().all(|&count| count > 0));
+        assert!(weighted[9] > weighted[0]);
+    }
 
-        info!("✨ Epoch {} completed in {}ms", epoch, duration);
+    #[tokio::test]
+    async fn test_weighted_random_selection_is_reproducible() {
+        let first = committee_appearances(SelectionMode::WeightedRandom, 20).await;
+        let second = committee_appearances(SelectionMode::WeightedRandom, 20).await;
+        assert_eq!(first, second);
+    }
 
-        Ok(())
+    fn profiled_validator(id: &str, score: u8, confidence: u8) -> EmotionalValidator {
+        let validator = EmotionalValidator::new(id, 10_000).unwrap();
+        *validator.emotional_profile.write() = Some(EmotionalProfile {
+            emotional_score: score,
+            trend: EmotionalTrend::Stable,
+            confidence,
+            last_updated: 0,
+            recent_readings: Vec::new(),
+            anomaly_count: 0,
+        });
+        validator
     }
 
-    /// Phase 1: Perform emotional assessment
-    async fn perform_emotional_assessment(&self) -> Result<Vec<Arc<EmotionalValidator>>> {
-        let mut eligible = Vec::new();
+    #[test]
+    fn test_soft_eligibility_weighs_confidence() {
+        let hard = EligibilityPolicy::Hard;
+        let soft = EligibilityPolicy::Soft {
+            score_weight: 0.7,
+            confidence_weight: 0.3,
+            combined_cutoff: 75,
+        };
+
+        // Marginally below the threshold of 75, but very confident
+        let confident = profiled_validator("confident", 73, 98);
+        assert!(matches!(
+            hard.evaluate(&confident, 75, 0),
+            EligibilityStatus::Ineligible(IneligibilityReason::LowEmotionalScore { .. })
+        ));
+        assert_eq!(
+            soft.evaluate(&confident, 75, 0),
+            EligibilityStatus::Eligible
+        );
 
-        for validator_ref in self.validators.iter() {
-            let validator = validator_ref.value();
+        // Above the threshold, but the score is barely trustworthy
+        let uncertain = profiled_validator("uncertain", 80, 20);
+        assert_eq!(
+            hard.evaluate(&uncertain, 75, 0),
+            EligibilityStatus::Eligible
+        );
+        assert_eq!(
+            soft.evaluate(&uncertain, 75, 0),
+            EligibilityStatus::Ineligible(IneligibilityReason::LowCombinedScore {
+                combined: 62,
+                cutoff: 75,
+            })
+        );
 
-            let simulator =
-                BiometricSimulator::new(format!("device_{}", validator.id()), validator.id());
+        // Stake requirements still apply
+        assert!(matches!(
+            soft.evaluate(&confident, 75, 20_000),
+            EligibilityStatus::Ineligible(IneligibilityReason::InsufficientStake { .. })
+        ));
+    }
 
-            if let Ok(readings) = simulator.collect_readings() {
-                if let Ok(()) = validator.update_emotional_state(readings).await {
-                    if validator
-                        .is_eligible(self.config.emotional_threshold, self.config.minimum_stake)
-                    {
-                        eligible.push(Arc::clone(validator));
-                    }
-                }
-            }
+    #[tokio::test]
+    async fn test_selection_probabilities_sum_to_committee_size() {
+        let config = ConsensusConfig {
+            committee_size: 3,
+            selection_mode: SelectionMode::WeightedRandom,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let eligible = staked_eligible();
+        for validator in &eligible {
+            engine
+                .validators
+                .insert(validator.id().to_string(), Arc::clone(validator));
         }
+        *engine.last_assessment.write().await = eligible
+            .iter()
+            .map(|v| (v.id().to_string(), EligibilityStatus::Eligible))
+            .collect();
 
-        Ok(eligible)
+        let mut probabilities = Vec::new();
+        for validator in &eligible {
+            probabilities.push(engine.selection_probability(validator.id()).await);
+        }
+        let total: f64 = probabilities.iter().sum();
+        assert!((total - 3.0).abs() < 1e-9, "probabilities sum to {}", total);
+        assert!(probabilities.iter().all(|&p| p > 0.0 && p < 1.0));
+        assert!(probabilities[9] > probabilities[0]);
+        assert_eq!(engine.selection_probability("unknown").await, 0.0);
     }
 
-    /// Phase 2: Select committee (optimized with BinaryHeap)
-    ///
-    /// Uses a min-heap to efficiently select the top k validators by combined score.
-    /// Complexity: O(n log k) instead of O(n log n) where k = committee_size
-    async fn select_committee(
-        &self,
-        eligible: &[Arc<EmotionalValidator>],
-    ) -> Result<Vec<Arc<EmotionalValidator>>> {
-        if eligible.len() <= self.config.committee_size {
-            return Ok(eligible.to_vec());
+    struct FixedBeacon(parking_lot::Mutex<[u8; 32]>);
+
+    impl RandomnessBeacon for FixedBeacon {
+        fn value(&self, _epoch: u64) -> [u8; 32] {
+            *self.0.lock()
         }
+    }
 
-        // Helper struct for ordering validators by score in a heap
-        struct OrderedValidator {
-            score: u64, // Use integer to avoid f64 comparison issues
-            validator: Arc<EmotionalValidator>,
+    #[tokio::test]
+    async fn test_committee_selection_follows_beacon() {
+        let config = ConsensusConfig {
+            committee_size: 3,
+            selection_mode: SelectionMode::WeightedRandom,
+            ..Default::default()
+        };
+        let beacon = Arc::new(FixedBeacon(parking_lot::Mutex::new([0; 32])));
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_randomness_beacon(beacon.clone());
+        engine.state.write().await.current_epoch = 1;
+        let eligible = staked_eligible();
+
+        let ids = |committee: &[Arc<EmotionalValidator>]| -> Vec<String> {
+            committee.iter().map(|v| v.id().to_string()).collect()
+        };
+
+        let mut committees = HashSet::new();
+        for value in 0..10u8 {
+            *beacon.0.lock() = [value; 32];
+            let (first, proof) = engine.select_committee(&eligible).await.unwrap();
+            let (second, _) = engine.select_committee(&eligible).await.unwrap();
+
+            // Same beacon value and validator set, same committee
+            assert_eq!(ids(&first), ids(&second));
+            assert_eq!(proof.randomness, hex::encode([value; 32]));
+            committees.insert(ids(&first));
         }
+        assert!(committees.len() > 1);
+    }
 
-        impl PartialEq for OrderedValidator {
-            fn eq(&self, other: &Self) -> bool {
-                self.score == other.score
-            }
+    #[tokio::test]
+    async fn test_low_reputation_member_cannot_propose() {
+        let config = ConsensusConfig::builder()
+            .committee_size(3)
+            .proposer_eligibility(ProposerEligibility::TopByReputation(2))
+            .build()
+            .unwrap();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        engine.state.write().await.current_epoch = 1;
+
+        // The highest-stake validator stays the top pick but has the lowest reputation
+        let eligible = staked_eligible();
+        for validator in &eligible {
+            engine
+                .byzantine_detector
+                .register_validator_key(validator.id(), &validator.public_key_hex());
         }
+        let low_reputation = eligible[9].clone();
+        *low_reputation.reputation.write() = 90;
+
+        let (committee, proof) = engine.select_committee(&eligible).await.unwrap();
+        assert!(proof.members.contains(&low_reputation.id));
+        assert_ne!(proof.members[0], low_reputation.id);
+        assert_eq!(proof.proposers.len(), 2);
+        assert!(!proof.proposers.contains(&low_reputation.id));
+
+        let mut block = Block::new(1, 1, "0".repeat(64), low_reputation.id.clone(), 80, vec![]);
+        block.set_chain_id(crate::DEFAULT_CHAIN_ID);
+        block.sign(&low_reputation.key_pair).unwrap();
+        let result = engine.execute_voting(&committee, &block, 0).await.unwrap();
+
+        assert!(!result.success);
+        assert!(result.votes.iter().all(|vote| !vote.approved));
+        let reason = result.votes[0].reason.as_deref().unwrap();
+        assert!(reason.contains("not eligible to propose"));
+    }
 
-        impl Eq for OrderedValidator {}
+    #[tokio::test]
+    async fn test_committee_overlap_across_epochs() {
+        let config = ConsensusConfig::builder()
+            .committee_size(4)
+            .selection_mode(SelectionMode::WeightedRandom)
+            .committee_overlap_fraction(0.5)
+            .build()
+            .unwrap();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let eligible = staked_eligible();
 
-        impl PartialOrd for OrderedValidator {
-            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-                Some(self.cmp(other))
-            }
-        }
+        engine.state.write().await.current_epoch = 1;
+        let (_, first) = engine.select_committee(&eligible).await.unwrap();
+        assert!(first.retained.is_empty());
+        *engine.previous_committee.write().await = first.members.clone();
 
-        impl Ord for OrderedValidator {
-            fn cmp(&self, other: &Self) -> Ordering {
-                // Reverse ordering for max-heap behavior (highest scores first)
-                other.score.cmp(&self.score)
-            }
-        }
+        // Drop one previous member from the eligible set and add a newcomer
+        let dropped = &first.members[0];
+        let mut next_eligible: Vec<_> = eligible
+            .iter()
+            .filter(|v| v.id() != dropped)
+            .cloned()
+            .collect();
+        let newcomer = EmotionalValidator::new("newcomer", 50_000).unwrap();
+        *newcomer.emotional_profile.write() = eligible[0].emotional_profile.read().clone();
+        next_eligible.push(Arc::new(newcomer));
+
+        engine.state.write().await.current_epoch = 2;
+        let (_, second) = engine.select_committee(&next_eligible).await.unwrap();
+        assert_eq!(second.members.len(), 4);
+        assert_eq!(second.retained.len(), 2);
+        assert!(second.retained.iter().all(|id| first.members.contains(id)));
+        assert!(!second.members.contains(dropped));
+        let carried = second
+            .members
+            .iter()
+            .filter(|id| first.members.contains(id))
+            .count();
+        assert!(carried >= 2);
 
-        // Use a binary heap to maintain top k validators
-        let mut heap = BinaryHeap::with_capacity(self.config.committee_size + 1);
+        // The retained members are the highest-scoring previous members still eligible
+        let mut previous: Vec<_> = next_eligible
+            .iter()
+            .filter(|v| first.members.iter().any(|id| id == v.id()))
+            .collect();
+        previous.sort_by(|a, b| {
+            let score = |v| ProofOfEmotionEngine::combined_score(v, StakeWeightFn::Sqrt);
+            score(b).total_cmp(&score(a))
+        });
+        let expected: Vec<_> = previous[..2].iter().map(|v| v.id().to_string()).collect();
+        assert_eq!(second.retained, expected);
+
+        // A verifier recomputes the retained members from the committee it saw last
+        let config = engine.get_config().await;
+        let candidates = ProofOfEmotionEngine::selection_candidates(&next_eligible, &config, &[]);
+        let block = block_with_committee_proof(second);
+        let context = VerificationContext {
+            previous_committee: first.members.clone(),
+            committee_overlap_fraction: 0.5,
+            randomness: hex::encode(HashChainBeacon::new().value(2)),
+            ..Default::default()
+        };
+        assert!(committee::verify_committee_proof(&block, &candidates, &context).unwrap());
+
+        let unrelated = VerificationContext {
+            previous_committee: vec!["newcomer".to_string()],
+            ..context
+        };
+        assert!(!committee::verify_committee_proof(&block, &candidates, &unrelated).unwrap());
+    }
 
-        for validator in eligible {
-            let score = validator.get_emotional_score() as f64;
-            let stake_weight = (validator.get_stake() as f64).sqrt();
-            let reputation = validator.get_reputation() as f64 / 100.0;
-            let combined_score = score * stake_weight * reputation;
+    /// Unsigned block proposed by the proof's first member, carrying the proof
+    fn block_with_committee_proof(proof: CommitteeProof) -> Block {
+        let mut block = Block::new(
+            1,
+            proof.epoch,
+            proof.previous_hash.clone(),
+            proof.members[0].clone(),
+            80,
+            vec![],
+        );
+        block.consensus_metadata = Some(crate::types::ConsensusMetadata {
+            participant_count: proof.members.len(),
+            consensus_strength: 100,
+            emotional_fitness: 80,
+            mean_emotional_fitness: 80,
+            byzantine_failures: 0,
+            finalized_at: block.header.timestamp,
+            participants: proof.members.clone(),
+            committee_proof: Some(proof),
+            attestation_signature: String::new(),
+        });
+        block
+    }
 
-            // Convert to integer score for reliable comparison
-            // Scale by 1000 to preserve precision
-            let integer_score = (combined_score * 1000.0) as u64;
+    #[tokio::test]
+    async fn test_single_validator_epoch_finalizes() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
 
-            heap.push(OrderedValidator {
-                score: integer_score,
-                validator: Arc::clone(validator),
-            });
+        let validator = EmotionalValidator::new("solo-validator", 10_000).unwrap();
+        engine.register_validator(validator).await.unwrap();
 
-            // Keep heap size bounded to committee_size
-            if heap.len() > self.config.committee_size {
-                heap.pop();
-            }
-        }
+        engine.execute_epoch().await.unwrap();
 
-        // Extract validators from heap
-        let committee: Vec<_> = heap.into_iter().map(|ov| ov.validator).collect();
+        assert!(engine.is_solo_mode());
+        let blocks = engine.get_finalized_blocks().await;
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].header.validator_id, "solo-validator");
+    }
 
-        // Update committee size metrics
-        let mut metrics = self.metrics.write().await;
-        let committee_size = committee.len() as f64;
-        if metrics.total_epochs == 0 {
-            metrics.average_committee_size = committee_size;
-        } else {
-            metrics.average_committee_size =
-                (metrics.average_committee_size * metrics.total_epochs as f64 + committee_size)
-                / (metrics.total_epochs + 1) as f64;
+    async fn finalize_with_committee_proof(
+        proposer_policy: ProposerPolicy,
+    ) -> (Block, Vec<CommitteeCandidate>, VerificationContext) {
+        let config = ConsensusConfig {
+            committee_size: 3,
+            emotional_threshold: 0,
+            proposer_policy,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for i in 0..5u64 {
+            let validator =
+                EmotionalValidator::new(format!("validator-{}", i), 10_000 * (i + 1)).unwrap();
+            engine.register_validator(validator).await.unwrap();
         }
-        drop(metrics);
 
-        // TODO: Integrate stake locking when EmotionalStaking is added to consensus engine
-        // This prevents nothing-at-stake attacks by locking validator stake during consensus
-        // Example integration:
-        // for validator in &committee {
-        //     self.staking.lock_stake(validator.id(), validator.get_stake(), 1)?;
-        // }
+        engine.execute_epoch().await.unwrap();
 
-        Ok(committee)
+        let validators: Vec<_> = engine
+            .validators
+            .iter()
+            .map(|entry| Arc::clone(entry.value()))
+            .collect();
+        let candidates =
+            ProofOfEmotionEngine::committee_candidates(&validators, StakeWeightFn::Sqrt);
+        let block = engine.get_finalized_blocks().await.remove(0);
+        // The first block has no previous committee, and the beacon has seen no block
+        let context = VerificationContext {
+            stakes: validators
+                .iter()
+                .map(|v| (v.id().to_string(), v.get_stake()))
+                .collect(),
+            randomness: hex::encode(HashChainBeacon::new().value(block.header.epoch)),
+            stake_weight_fn: StakeWeightFn::Sqrt,
+            reputations: validators
+                .iter()
+                .map(|v| (v.id().to_string(), v.get_reputation()))
+                .collect(),
+            ..Default::default()
+        };
+        (block, candidates, context)
     }
 
-    /// Phase 3: Propose block
-    async fn propose_block(&self, committee: &[Arc<EmotionalValidator>]) -> Result<Block> {
-        let primary = committee
-            .first()
-            .ok_or_else(|| ConsensusError::committee_selection_failed("Empty committee"))?;
-
-        let pending_txs = self.pending_transactions.lock().await;
-        let transactions: Vec<_> = pending_txs.iter().take(1000).cloned().collect();
-        drop(pending_txs);
-
-        let finalized_blocks = self.finalized_blocks.read().await;
-        let last_height = finalized_blocks.len() as u64;
-        let previous_hash = finalized_blocks
-            .last()
-            .map(|block| block.hash.clone())
-            .unwrap_or_else(|| "0".repeat(64));
-        drop(finalized_blocks);
+    #[tokio::test]
+    async fn test_finalized_block_committee_proof_verifies() {
+        let (block, candidates, context) =
+            finalize_with_committee_proof(ProposerPolicy::Primary).await;
+
+        let metadata = block.consensus_metadata.as_ref().unwrap();
+        // The strength reached is recorded in the metadata, outside the voted-on header
+        assert_eq!(metadata.consensus_strength, 100);
+        assert!(block.verify_hash());
+        assert!(block.verify_signature().unwrap());
+
+        let proof = metadata.committee_proof.as_ref().unwrap();
+        assert_eq!(proof.members.len(), 3);
+        assert_eq!(proof.members[0], block.header.validator_id);
+        assert!(committee::verify_committee_proof(&block, &candidates, &context).unwrap());
+    }
 
-        // Get current epoch for replay attack prevention
-        let current_epoch = self.state.read().await.current_epoch;
+    #[tokio::test]
+    async fn test_double_signing_primary_is_replaced() {
+        let config = ConsensusConfig {
+            committee_size: 2,
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for (id, stake) in [
+            ("primary", 90_000),
+            ("backup-1", 20_000),
+            ("backup-2", 10_000),
+        ] {
+            let validator = EmotionalValidator::new(id, stake)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [70.0, 10.0, 90.0],
+                    age_ms: 0,
+                }));
+            engine.register_validator(validator).await.unwrap();
+        }
 
-        let mut block = Block::new(
-            last_height + 1,
-            current_epoch,
-            previous_hash,
-            primary.id().to_string(),
-            primary.get_emotional_score(),
-            transactions,
+        // The highest-stake validator leads the committee and has already signed a
+        // block at the next height
+        let mut other = Block::new(1, 1, "0".repeat(64), "primary".to_string(), 70, vec![]);
+        other
+            .sign(&engine.validators.get("primary").unwrap().key_pair)
+            .unwrap();
+        engine
+            .byzantine_detector
+            .record_signed_proposal(&other)
+            .await
+            .unwrap();
+
+        engine.execute_epoch().await.unwrap();
+
+        let block = engine.get_finalized_blocks().await.remove(0);
+        assert_ne!(block.header.validator_id, "primary");
+        assert_eq!(engine.get_metrics().await.proposer_replacements, 1);
+        assert!(engine.validators.get("primary").unwrap().get_reputation() < 100);
+
+        let proof = block
+            .consensus_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.committee_proof.as_ref())
+            .unwrap();
+        assert_eq!(proof.members[0], "primary");
+        assert_eq!(proof.replaced_proposers, ["primary"]);
+        assert_eq!(proof.double_signing_evidence[0].first.hash, other.hash);
+        assert_eq!(proof.members[1], block.header.validator_id);
+
+        // Candidates as they stood at selection, before the slash
+        *engine.validators.get("primary").unwrap().reputation.write() = 100;
+        let validators: Vec<_> = engine
+            .validators
+            .iter()
+            .map(|entry| Arc::clone(entry.value()))
+            .collect();
+        let candidates =
+            ProofOfEmotionEngine::committee_candidates(&validators, StakeWeightFn::Sqrt);
+        let context = VerificationContext {
+            randomness: hex::encode(HashChainBeacon::new().value(block.header.epoch)),
+            public_keys: validators
+                .iter()
+                .map(|v| (v.id().to_string(), v.key_pair.public_key_hex()))
+                .collect(),
+            ..Default::default()
+        };
+        assert!(committee::verify_committee_proof(&block, &candidates, &context).unwrap());
+
+        // The evidence must be signed with the replaced proposer's own key
+        let mut forged = context.clone();
+        forged.public_keys.insert(
+            "primary".to_string(),
+            KeyPair::generate().unwrap().public_key_hex(),
         );
+        assert!(!committee::verify_committee_proof(&block, &candidates, &forged).unwrap());
+    }
 
-        // Sign the block with the proposer's key pair
-        block
-            .sign(&primary.key_pair)
-            .map_err(|e| ConsensusError::internal(format!("Failed to sign block: {}", e)))?;
+    #[tokio::test]
+    async fn test_unproven_double_signing_fails_the_epoch() {
+        let config = ConsensusConfig {
+            committee_size: 2,
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for (id, stake) in [
+            ("primary", 90_000),
+            ("backup-1", 20_000),
+            ("backup-2", 10_000),
+        ] {
+            let validator = EmotionalValidator::new(id, stake)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [70.0, 10.0, 90.0],
+                    age_ms: 0,
+                }));
+            engine.register_validator(validator).await.unwrap();
+        }
 
-        // Record proposal for Byzantine detection (double signing detection)
-        if let Err(e) = self
+        // A bare hash is no evidence to hold against the primary
+        engine
             .byzantine_detector
-            .record_proposal(primary.id(), block.header.height, &block.hash)
+            .record_proposal("primary", 1, "other-block")
             .await
-        {
-            error!("🚨 Byzantine behavior detected during proposal: {}", e);
-            // Slash the validator for double signing
-            self.slash_validator(primary.id(), "Double signing detected")
-                .await?;
-            return Err(ConsensusError::invalid_block(e));
-        }
+            .unwrap();
 
-        Ok(block)
+        assert!(matches!(
+            engine.execute_epoch().await,
+            Err(ConsensusError::InvalidBlock { .. })
+        ));
+        assert_eq!(engine.get_metrics().await.proposer_replacements, 0);
     }
 
-    /// Phase 4: Execute voting
-    async fn execute_voting(
-        &self,
-        committee: &[Arc<EmotionalValidator>],
-        block: &Block,
-    ) -> Result<VotingResult> {
-        let mut votes = Vec::new();
-        let mut approved_count = 0;
-        let mut total_emotional_score = 0u32;
-        let mut byzantine_count = 0;
+    #[tokio::test]
+    async fn test_stake_weighted_proposer_proof_verifies() {
+        let (mut block, candidates, context) =
+            finalize_with_committee_proof(ProposerPolicy::StakeWeightedRandom).await;
+
+        let proof = block
+            .consensus_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.committee_proof.as_ref())
+            .unwrap();
+        assert_eq!(proof.proposer_policy, ProposerPolicy::StakeWeightedRandom);
+        assert_eq!(proof.member_stakes.len(), proof.members.len());
+        assert_eq!(proof.members[0], block.header.validator_id);
+        assert!(committee::verify_committee_proof(&block, &candidates, &context).unwrap());
+
+        let proof = block
+            .consensus_metadata
+            .as_mut()
+            .and_then(|metadata| metadata.committee_proof.as_mut())
+            .unwrap();
+        // The proposer is redrawn from the verifier's ledger, not the recorded stakes
+        proof.member_stakes.clear();
+        assert!(committee::verify_committee_proof(&block, &candidates, &context).unwrap());
+
+        let unknown_stakes = VerificationContext {
+            stakes: HashMap::new(),
+            ..context.clone()
+        };
+        assert!(!committee::verify_committee_proof(&block, &candidates, &unknown_stakes).unwrap());
+
+        // The proof must use the verifier's stake weighting curve
+        let linear = VerificationContext {
+            stake_weight_fn: StakeWeightFn::Linear,
+            ..context
+        };
+        assert!(!committee::verify_committee_proof(&block, &candidates, &linear).unwrap());
+    }
 
-        // Get expected previous hash, height, and epoch for validation
-        let finalized_blocks = self.finalized_blocks.read().await;
-        let expected_previous_hash = finalized_blocks
-            .last()
-            .map(|block| block.hash.clone())
-            .unwrap_or_else(|| "0".repeat(64));
-        let expected_height = finalized_blocks.len() as u64 + 1;
-        drop(finalized_blocks);
+    #[tokio::test]
+    async fn test_committee_proof_rejects_unselected_member() {
+        let (mut block, candidates, context) =
+            finalize_with_committee_proof(ProposerPolicy::Primary).await;
+
+        let proof = block
+            .consensus_metadata
+            .as_mut()
+            .and_then(|metadata| metadata.committee_proof.as_mut())
+            .unwrap();
+        let outsider = candidates
+            .iter()
+            .find(|candidate| !proof.members.contains(&candidate.validator_id))
+            .unwrap();
+        let last = proof.members.len() - 1;
+        proof.members[last] = outsider.validator_id.clone();
+
+        assert!(!committee::verify_committee_proof(&block, &candidates, &context).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_committee_proof_rejects_foreign_randomness() {
+        let (block, candidates, context) =
+            finalize_with_committee_proof(ProposerPolicy::Primary).await;
+        assert!(committee::verify_committee_proof(&block, &candidates, &context).unwrap());
+
+        // A verifier whose beacon has moved on does not accept the recorded seed
+        let beacon = HashChainBeacon::new();
+        beacon.on_block_finalized(&block.hash);
+        let moved_on = VerificationContext {
+            randomness: hex::encode(beacon.value(block.header.epoch)),
+            ..context
+        };
+        assert!(!committee::verify_committee_proof(&block, &candidates, &moved_on).unwrap());
+    }
 
-        let expected_epoch = self.state.read().await.current_epoch;
+    #[tokio::test]
+    async fn test_committee_proof_rejects_claimed_proposers() {
+        let (mut block, candidates, context) =
+            finalize_with_committee_proof(ProposerPolicy::Primary).await;
+        let proof = block
+            .consensus_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.committee_proof.as_ref())
+            .unwrap();
+        let members = proof.members.clone();
+
+        // Under a reputation rule the verifier recomputes who may propose
+        let mut ranked = context.clone();
+        ranked.proposer_eligibility = ProposerEligibility::TopByReputation(1);
+        assert!(!committee::verify_committee_proof(&block, &candidates, &ranked).unwrap());
+        for (i, id) in members.iter().enumerate() {
+            ranked.reputations.insert(id.clone(), 100 - i as u8);
+        }
+        let proof = block
+            .consensus_metadata
+            .as_mut()
+            .and_then(|metadata| metadata.committee_proof.as_mut())
+            .unwrap();
+        proof.proposers = vec![members[0].clone()];
+        assert!(committee::verify_committee_proof(&block, &candidates, &ranked).unwrap());
+
+        // Every member may propose under the verifier's own rule
+        assert!(!committee::verify_committee_proof(&block, &candidates, &context).unwrap());
+    }
 
-        for validator in committee {
-            // Perform actual block validation (includes epoch check for replay attack prevention)
-            let validation_result = validator.validate_block(
-                block,
-                &expected_previous_hash,
-                expected_height,
-                expected_epoch,
-            );
+    #[tokio::test]
+    async fn test_crash_recovery_rebuilds_beacon() {
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        })
+        .unwrap();
+        for i in 0..3 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
+        engine.execute_epoch().await.unwrap();
+        engine.execute_epoch().await.unwrap();
+        let expected = engine.beacon.value(3);
 
-            let (approved, reason) = match validation_result {
-                Ok(()) => (true, None),
-                Err(err_msg) => {
-                    warn!("Validator {} rejected block: {}", validator.id(), err_msg);
-                    (false, Some(err_msg))
-                }
-            };
+        // The beacon lost what it had observed
+        engine.beacon.rebuild(&[]);
+        assert_ne!(engine.beacon.value(3), expected);
 
-            let mut vote = Vote::new(
-                validator.id().to_string(),
-                block.hash.clone(),
-                block.header.epoch,
-                0, // round number (single round per epoch)
-                validator.get_emotional_score(),
-                approved,
-            );
-            vote.reason = reason.clone();
+        engine.recover_from_crash().await.unwrap();
+        assert_eq!(engine.beacon.value(3), expected);
+    }
 
-            // Record vote for Byzantine detection (double voting & equivocation detection)
-            if let Err(e) = self.byzantine_detector.record_vote(&vote).await {
-                warn!("🚨 Byzantine behavior detected during voting: {}", e);
-                byzantine_count += 1;
+    #[tokio::test]
+    async fn test_repeat_offender_is_jailed_and_released() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            jail_epochs: 2,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for i in 0..3 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
 
-                // Slash the validator for double voting or equivocation
-                if let Err(slash_err) = self
-                    .slash_validator(validator.id(), "Double voting or equivocation detected")
-                    .await
-                {
-                    error!(
-                        "Failed to slash validator {}: {}",
-                        validator.id(),
-                        slash_err
-                    );
-                }
+        for _ in 0..2 {
+            engine
+                .slash_validator("validator-0", SlashingSeverity::Critical, "test")
+                .await
+                .unwrap();
+        }
+        assert!(!engine.is_jailed("validator-0"));
+        engine
+            .slash_validator("validator-0", SlashingSeverity::Critical, "test")
+            .await
+            .unwrap();
+        assert!(engine.is_jailed("validator-0"));
+        assert_eq!(engine.jailed_until("validator-0"), Some(2));
+
+        engine.execute_epoch().await.unwrap();
+        let assessment: HashMap<_, _> = engine.get_last_assessment().await.into_iter().collect();
+        assert_eq!(
+            assessment["validator-0"],
+            EligibilityStatus::Ineligible(IneligibilityReason::Inactive)
+        );
+        let blocks = engine.get_finalized_blocks().await;
+        let participants = &blocks[0].consensus_metadata.as_ref().unwrap().participants;
+        assert!(!participants.contains(&"validator-0".to_string()));
+
+        engine.execute_epoch().await.unwrap();
+        assert!(!engine.is_jailed("validator-0"));
+        assert_eq!(engine.jailed_until("validator-0"), None);
+        let assessment: HashMap<_, _> = engine.get_last_assessment().await.into_iter().collect();
+        assert_eq!(assessment["validator-0"], EligibilityStatus::Eligible);
+
+        // A validator that was already inactive stays inactive after its release
+        let inactive = Arc::clone(&engine.validators.get("validator-1").unwrap());
+        *inactive.is_active.write() = false;
+        for _ in 0..3 {
+            engine
+                .slash_validator("validator-1", SlashingSeverity::Critical, "test")
+                .await
+                .unwrap();
+        }
+        assert_eq!(engine.jailed_until("validator-1"), Some(4));
+        engine.release_jailed_validators(4);
+        assert!(!engine.is_jailed("validator-1"));
+        assert!(!*inactive.is_active.read());
+    }
 
-                // Skip this vote - don't count Byzantine votes
-                continue;
-            }
+    #[test]
+    fn test_jailing_needs_a_slash_window() {
+        let build = |max_slashes_in_window, slash_window_epochs| {
+            ConsensusConfig::builder()
+                .max_slashes_in_window(max_slashes_in_window)
+                .slash_window_epochs(slash_window_epochs)
+                .build()
+        };
+
+        assert!(build(3, 0).is_err());
+        assert!(build(0, 0).is_ok());
+        assert!(build(3, 1).is_ok());
+    }
 
-            if vote.approved {
-                approved_count += 1;
-            }
-            total_emotional_score += validator.get_emotional_score() as u32;
-            votes.push(vote);
+    #[tokio::test]
+    async fn test_epoch_bundle_verifies() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_epoch_bundle_retention(1);
+        for i in 0..4 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
         }
 
-        let participant_count = votes.len();
-        let required_votes = (self.config.committee_size as f64
-            * (self.config.byzantine_threshold as f64 / 100.0))
-            .ceil() as usize;
+        engine.execute_epoch().await.unwrap();
+        let bundle = engine.export_epoch_bundle(1).await.unwrap();
+        assert!(bundle.verify().unwrap());
+        assert_eq!(bundle.committee, engine.get_current_committee().await);
+        assert_eq!(bundle.rounds.len(), 1);
+
+        let result = &bundle.rounds[0].voting_result;
+        let finalized = engine.get_finalized_blocks().await;
+        let metadata = finalized[0].consensus_metadata.as_ref().unwrap();
+        assert_eq!(result.votes.len(), result.participant_count);
+        assert_eq!(result.participant_count, metadata.participant_count);
+        assert_eq!(result.participants, metadata.participants);
+
+        let mut tampered = bundle.clone();
+        tampered.rounds[0].voting_result.votes.pop();
+        tampered.rounds[0].voting_result.participant_count -= 1;
+        assert!(!tampered.verify().unwrap());
+
+        // Only the most recent epoch is retained
+        engine.execute_epoch().await.unwrap();
+        assert!(engine.export_epoch_bundle(1).await.is_none());
+        assert!(engine.export_epoch_bundle(2).await.is_some());
+    }
 
-        let success = approved_count >= required_votes;
-        let consensus_strength = ((approved_count as f64 / committee.len() as f64) * 100.0) as u8;
-        let average_emotional_score = (total_emotional_score / participant_count as u32) as u8;
+    #[tokio::test]
+    async fn test_zero_participants_do_not_panic() {
+        let members: Vec<(String, u64)> = (0..3)
+            .map(|i| (format!("validator-{}", i), 10_000))
+            .collect();
 
-        // Update Byzantine failure metrics
-        if byzantine_count > 0 {
-            let mut metrics = self.metrics.write().await;
-            metrics.byzantine_failures += byzantine_count as u64;
+        // Every vote was discarded as Byzantine
+        let result = ProofOfEmotionEngine::tally_votes(&members, vec![], 3, 67, false);
+        assert!(!result.success);
+        assert_eq!(result.participant_count, 0);
+        assert_eq!(result.average_emotional_score, 0);
+
+        let result = ProofOfEmotionEngine::tally_votes(&[], vec![], 0, 67, false);
+        assert!(!result.success);
+        assert_eq!(result.headcount_strength, 0);
+        assert_eq!(result.stake_strength, 0);
+
+        // Finalizing with no registered validators leaves participation at zero
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default()).unwrap();
+        let block = Block::new(1, 1, "0".repeat(64), "validator-0".to_string(), 80, vec![]);
+        let proof = CommitteeProof::new(
+            1,
+            "0".repeat(64),
+            SelectionMode::TopK,
+            21,
+            &[],
+            vec!["validator-0".to_string()],
+        );
+        engine.finalize_block(block, result, proof).await.unwrap();
+        assert_eq!(engine.get_state().await.participation_rate, 0);
+        assert_eq!(engine.get_metrics().await.average_participation_rate, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_competing_block_at_finalized_height_is_rejected() {
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default()).unwrap();
+        let mut events = engine.subscribe_events();
+        let proof = || {
+            CommitteeProof::new(
+                1,
+                "0".repeat(64),
+                SelectionMode::TopK,
+                21,
+                &[],
+                vec!["validator-0".to_string()],
+            )
+        };
+        let result = || ProofOfEmotionEngine::tally_votes(&[], vec![], 0, 67, false);
+
+        let first = Block::new(1, 1, "0".repeat(64), "validator-0".to_string(), 70, vec![]);
+        let first_hash = first.hash.clone();
+        engine
+            .finalize_block(first, result(), proof())
+            .await
+            .unwrap();
+        assert_eq!(engine.get_metrics().await.fork_detections, 0);
+
+        // A higher emotional score does not displace a finalized block
+        let competing = Block::new(1, 1, "0".repeat(64), "validator-1".to_string(), 90, vec![]);
+        let competing_hash = competing.hash.clone();
+        assert!(engine
+            .finalize_block(competing, result(), proof())
+            .await
+            .is_err());
+        let metrics = engine.get_metrics().await;
+        assert_eq!(metrics.fork_detections, 1);
+        assert_eq!(metrics.rejected_blocks, 1);
+
+        let chain = engine.get_finalized_blocks().await;
+        assert_eq!(chain.len(), 1);
+        assert_eq!(chain[0].hash, first_hash);
+
+        match events.try_recv().unwrap() {
+            ConsensusEvent::ForkDetected {
+                height,
+                competing_hashes,
+            } => {
+                assert_eq!(height, 1);
+                assert_eq!(competing_hashes.len(), 2);
+                assert!(competing_hashes.contains(&competing_hash));
+            }
+            other => panic!("expected a fork event, got {:?}", other),
         }
 
-        Ok(VotingResult {
-            success,
-            consensus_strength,
-            participant_count,
-            byzantine_count,
-            average_emotional_score,
-            participants: committee.iter().map(|v| v.id().to_string()).collect(),
-            votes,
-            reason: if success {
-                None
-            } else {
-                Some("Insufficient votes".to_string())
-            },
-        })
+        let detector = engine.get_fork_detector();
+        let forks = detector.get_forks().await;
+        assert_eq!(forks.len(), 1);
+        assert_eq!(forks[0].winning_hash.as_deref(), Some(first_hash.as_str()));
+        assert_eq!(detector.get_canonical_chain().await, vec![first_hash]);
     }
 
-    /// Phase 5: Finalize block
-    async fn finalize_block(&self, mut block: Block, voting_result: VotingResult) -> Result<()> {
-        block.consensus_metadata = Some(crate::types::ConsensusMetadata {
-            participant_count: voting_result.participant_count,
-            consensus_strength: voting_result.consensus_strength,
-            emotional_fitness: voting_result.average_emotional_score,
-            byzantine_failures: voting_result.byzantine_count,
-            finalized_at: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map_err(|e| ConsensusError::internal(format!("System time error: {}", e)))?
-                .as_millis() as u64,
-            participants: voting_result.participants,
-        });
+    #[tokio::test]
+    async fn test_losing_block_transactions_return_to_mempool() {
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default()).unwrap();
+        let proof = || {
+            CommitteeProof::new(
+                1,
+                "0".repeat(64),
+                SelectionMode::TopK,
+                21,
+                &[],
+                vec!["validator-0".to_string()],
+            )
+        };
+        let result = || ProofOfEmotionEngine::tally_votes(&[], vec![], 0, 67, false);
+        let tx = |from: &str| Transaction::new(from.to_string(), "bob".to_string(), 10, 1);
+
+        let shared = tx("shared");
+        let orphaned = tx("orphaned");
+        let expired = tx("expired").with_valid_until(1);
+        let included = tx("included");
+
+        let finalized = Block::new(
+            1,
+            1,
+            "0".repeat(64),
+            "validator-0".to_string(),
+            70,
+            vec![shared.clone(), included.clone()],
+        );
+        let losing = Block::new(
+            1,
+            1,
+            "0".repeat(64),
+            "validator-1".to_string(),
+            90,
+            vec![shared.clone(), orphaned.clone(), expired.clone()],
+        );
+        engine
+            .finalize_block(finalized, result(), proof())
+            .await
+            .unwrap();
+        assert_eq!(engine.get_state().await.pending_transactions, 0);
+        assert!(engine
+            .finalize_block(losing, result(), proof())
+            .await
+            .is_err());
+        assert_eq!(engine.get_metrics().await.fork_detections, 1);
+
+        // Only the losing block's unexpired, unfinalized transaction is pending again
+        assert_eq!(engine.get_state().await.pending_transactions, 1);
+        assert_eq!(
+            engine.get_transaction_status(&orphaned.hash).await,
+            TxStatus::Pending
+        );
+        for finalized in [&shared, &included] {
+            assert_eq!(
+                engine.get_transaction_status(&finalized.hash).await,
+                TxStatus::Finalized { height: 1 }
+            );
+        }
+        assert_ne!(
+            engine.get_transaction_status(&expired.hash).await,
+            TxStatus::Pending
+        );
+        assert_eq!(engine.get_metrics().await.transactions_processed, 2);
+
+        assert!(matches!(
+            engine.submit_transaction(orphaned).await,
+            Err(ConsensusError::TransactionAlreadyPending { .. })
+        ));
+        assert!(matches!(
+            engine.submit_transaction(shared).await,
+            Err(ConsensusError::TransactionAlreadyFinalized { .. })
+        ));
+    }
 
-        let mut blocks = self.finalized_blocks.write().await;
-        blocks.push(block.clone());
+    #[test]
+    fn test_low_confidence_vote_has_reduced_influence() {
+        let members: Vec<(String, u64)> = (0..3)
+            .map(|i| (format!("validator-{}", i), 10_000))
+            .collect();
+        let votes = |low_confidence: u8| -> Vec<Vote> {
+            members
+                .iter()
+                .enumerate()
+                .map(|(i, (id, _))| {
+                    let mut vote = Vote::new(id.clone(), "block-hash".to_string(), 1, 0, 90, true);
+                    if i == 2 {
+                        vote.confidence = low_confidence;
+                    }
+                    vote
+                })
+                .collect()
+        };
+
+        let confident = ProofOfEmotionEngine::tally_votes(&members, votes(100), 0, 67, true);
+        let flaky = ProofOfEmotionEngine::tally_votes(&members, votes(20), 0, 67, true);
+        assert_eq!(confident.consensus_strength, 100);
+        assert_eq!(flaky.consensus_strength, 73);
+        assert_eq!(flaky.headcount_strength, 100);
+        assert!(flaky.success);
+
+        let flaky = ProofOfEmotionEngine::tally_votes(&members, votes(0), 0, 80, true);
+        assert!(!flaky.success);
+    }
 
-        let mut state = self.state.write().await;
-        state.last_finalized_height = block.header.height;
-        state.consensus_strength = voting_result.consensus_strength;
-        state.emotional_fitness = voting_result.average_emotional_score;
-        state.participation_rate =
-            ((voting_result.participant_count as f64 / self.validators.len() as f64) * 100.0) as u8;
+    #[test]
+    fn test_stake_strength_favors_high_stake_minority() {
+        let stakes = [1_000_000, 1_000_000, 10_000, 10_000, 10_000];
+        let committee: Vec<_> = stakes
+            .iter()
+            .enumerate()
+            .map(|(i, &stake)| {
+                Arc::new(EmotionalValidator::new(format!("validator-{}", i), stake).unwrap())
+            })
+            .collect();
 
-        let mut pending = self.pending_transactions.lock().await;
-        let finalized_hashes: std::collections::HashSet<_> = block
-            .transactions
+        // Only the two high-stake validators approve
+        let votes: Vec<_> = committee
             .iter()
-            .map(|tx| tx.hash.clone())
+            .map(|v| {
+                Vote::new(
+                    v.id().to_string(),
+                    "block-hash".to_string(),
+                    1,
+                    0,
+                    80,
+                    v.get_stake() > 10_000,
+                )
+            })
             .collect();
 
-        // Remove finalized AND expired transactions to prevent memory leak
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| ConsensusError::internal(format!("System time error: {}", e)))?
-            .as_millis() as u64;
-        const MAX_TX_AGE: u64 = 5 * 60 * 1000; // 5 minutes
+        let members = ProofOfEmotionEngine::member_stakes(&committee);
+        let stake_strength = ProofOfEmotionEngine::approving_stake_strength(&members, &votes);
+        assert!(stake_strength >= 98);
 
-        let initial_count = pending.len();
-        pending
-            .retain(|tx| !finalized_hashes.contains(&tx.hash) && !tx.is_expired(now, MAX_TX_AGE));
-        let removed_count = initial_count - pending.len();
+        let approved = votes.iter().filter(|v| v.approved).count();
+        let headcount_strength = (approved * 100 / committee.len()) as u8;
+        assert_eq!(headcount_strength, 40);
+        assert!(stake_strength >= DEFAULT_BYZANTINE_THRESHOLD);
+    }
 
-        if removed_count > 0 {
-            info!(
-                "🧹 Cleaned up {} transactions ({} finalized, {} expired)",
-                removed_count,
-                finalized_hashes.len(),
-                removed_count.saturating_sub(finalized_hashes.len())
-            );
-        }
+    #[tokio::test]
+    async fn test_finalized_transfer_updates_balances() {
+        let balances = Arc::new(BalanceStateMachine::with_balances([(
+            "alice".to_string(),
+            1_000,
+        )]));
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_state_machine(balances.clone());
 
-        state.pending_transactions = pending.len();
+        let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+        engine.register_validator(validator).await.unwrap();
 
-        info!(
-            "🎉 Block {} finalized with {} transactions",
-            block.header.height,
-            block.transactions.len()
-        );
+        let sender = crate::crypto::KeyPair::generate().unwrap();
+        let mut transfer = Transaction::new("alice".to_string(), "bob".to_string(), 400, 10);
+        let mut overdraft = Transaction::new("bob".to_string(), "carol".to_string(), 5_000, 10);
+        transfer.sign(&sender).unwrap();
+        overdraft.sign(&sender).unwrap();
+        engine.submit_transaction(transfer).await.unwrap();
+        engine.submit_transaction(overdraft).await.unwrap();
 
-        // Update comprehensive metrics
-        let mut metrics = self.metrics.write().await;
-        metrics.blocks_finalized += 1;
-        metrics.transactions_processed += block.transactions.len() as u64;
-        metrics.active_validators = self.validators.len();
+        engine.execute_epoch().await.unwrap();
 
-        // Update average participation rate
-        let new_participation = (voting_result.participant_count as f64 / self.validators.len() as f64) * 100.0;
-        if metrics.blocks_finalized == 1 {
-            metrics.average_participation_rate = new_participation;
-        } else {
-            metrics.average_participation_rate =
-                (metrics.average_participation_rate * (metrics.blocks_finalized - 1) as f64
-                 + new_participation) / metrics.blocks_finalized as f64;
-        }
+        assert_eq!(balances.balance("alice"), 590);
+        assert_eq!(balances.balance("bob"), 400);
+        assert_eq!(balances.balance("carol"), 0);
 
-        Ok(())
+        let blocks = engine.get_finalized_blocks().await;
+        assert_eq!(blocks[0].transactions.len(), 1);
+        assert_eq!(blocks[0].header.state_root, balances.root());
     }
 
-    /// Submit a transaction
-    pub async fn submit_transaction(&self, transaction: Transaction) -> Result<()> {
-        let mut pending = self.pending_transactions.lock().await;
-        pending.push(transaction);
+    async fn engine_with_pending(
+        config: ConsensusConfig,
+        transactions: Vec<Transaction>,
+    ) -> ProofOfEmotionEngine {
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+        engine.register_validator(validator).await.unwrap();
+        let sender = crate::crypto::KeyPair::generate().unwrap();
+        for mut tx in transactions {
+            tx.sign(&sender).unwrap();
+            engine.submit_transaction(tx).await.unwrap();
+        }
+        engine
+    }
 
-        let mut state = self.state.write().await;
-        state.pending_transactions = pending.len();
+    #[tokio::test]
+    async fn test_block_fills_by_weight_with_heavy_transactions() {
+        let heavy: Vec<_> = (0..6)
+            .map(|i| {
+                let mut tx = Transaction::new(format!("sender-{}", i), "bob".to_string(), 1, 1);
+                tx.set_payload(&crate::types::TransactionPayload::Raw(vec![0; 10_000]));
+                tx
+            })
+            .collect();
+        let tx_weight = heavy[0].weight();
+        assert_eq!(
+            tx_weight,
+            crate::TX_BASE_WEIGHT + 10_001 * crate::TX_WEIGHT_PER_DATA_BYTE
+        );
 
-        Ok(())
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            max_block_weight: 4 * tx_weight + tx_weight / 2,
+            ..Default::default()
+        };
+        let engine = engine_with_pending(config, heavy).await;
+        engine.execute_epoch().await.unwrap();
+
+        let block = engine.get_finalized_blocks().await.pop().unwrap();
+        assert_eq!(block.transactions.len(), 4);
+        assert_eq!(engine.get_state().await.pending_transactions, 2);
     }
 
-    /// Cleanup expired transactions from the transaction pool
-    ///
-    /// This method removes transactions that have exceeded their TTL (5 minutes).
-    /// It runs periodically to prevent memory leaks from rejected/invalid transactions.
-    async fn cleanup_transaction_pool(&self) {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_else(|e| {
-                warn!("System time error during cleanup: {}, using max duration", e);
-                std::time::Duration::from_secs(u64::MAX)
-            })
-            .as_millis() as u64;
-        const MAX_TX_AGE: u64 = 5 * 60 * 1000; // 5 minutes
+    #[tokio::test]
+    async fn test_block_fills_by_count_with_light_transactions() {
+        let light: Vec<_> = (0..crate::MAX_BLOCK_TRANSACTIONS + 50)
+            .map(|i| Transaction::new(format!("sender-{}", i), "bob".to_string(), 1, 1))
+            .collect();
+        assert_eq!(light[0].weight(), crate::TX_BASE_WEIGHT);
+
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = engine_with_pending(config, light).await;
+        engine.execute_epoch().await.unwrap();
+
+        let block = engine.get_finalized_blocks().await.pop().unwrap();
+        assert_eq!(block.transactions.len(), crate::MAX_BLOCK_TRANSACTIONS);
+        let block_weight: u64 = block.transactions.iter().map(Transaction::weight).sum();
+        assert!(block_weight < crate::DEFAULT_MAX_BLOCK_WEIGHT);
+    }
 
-        let mut pending = self.pending_transactions.lock().await;
-        let initial_count = pending.len();
-        pending.retain(|tx| !tx.is_expired(now, MAX_TX_AGE));
-        let removed_count = initial_count - pending.len();
+    #[tokio::test]
+    async fn test_proposer_collects_transaction_fees() {
+        let transactions: Vec<_> = [5, 10, 25]
+            .into_iter()
+            .enumerate()
+            .map(|(i, fee)| Transaction::new(format!("sender-{}", i), "bob".to_string(), 1, fee))
+            .collect();
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = engine_with_pending(config, transactions).await;
+        let balance_before = engine.validators.get("validator-1").unwrap().get_balance();
+
+        engine.execute_epoch().await.unwrap();
+
+        let block = engine.get_finalized_blocks().await.pop().unwrap();
+        assert_eq!(block.header.validator_id, "validator-1");
+        assert_eq!(block.transactions.len(), 3);
+        assert_eq!(block.total_fees(), 40);
+        let proposer = engine.validators.get("validator-1").unwrap();
+        assert_eq!(proposer.get_balance(), balance_before + 40);
+        assert_eq!(engine.get_metrics().await.total_fees_collected, 40);
+    }
 
-        if removed_count > 0 {
-            info!(
-                "🧹 Periodic cleanup: removed {} expired transactions",
-                removed_count
-            );
+    #[tokio::test]
+    async fn test_state_root_mismatch_rejects_block() {
+        let balances = Arc::new(BalanceStateMachine::with_balances([(
+            "alice".to_string(),
+            1_000,
+        )]));
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default())
+            .unwrap()
+            .with_state_machine(balances.clone());
+
+        let transfer = Transaction::new("alice".to_string(), "bob".to_string(), 400, 10);
+        let mut block = Block::new(
+            1,
+            1,
+            "0".repeat(64),
+            "validator-1".to_string(),
+            80,
+            vec![transfer],
+        );
+        block.set_state_root("0".repeat(64));
 
-            // Update state
-            let mut state = self.state.write().await;
-            state.pending_transactions = pending.len();
-        }
+        assert!(engine.verify_state_transition(&block).is_err());
+
+        let expected = balances.fork();
+        expected.apply(&block.transactions[0]).unwrap();
+        block.set_state_root(expected.root());
+
+        assert!(engine.verify_state_transition(&block).is_ok());
+        assert_eq!(balances.balance("alice"), 1_000);
     }
 
-    /// Get current consensus state
-    pub async fn get_state(&self) -> ConsensusState {
-        self.state.read().await.clone()
+    #[test]
+    fn test_config_builder_valid() {
+        let config = ConsensusConfig::builder()
+            .epoch_duration(20_000)
+            .emotional_threshold(60)
+            .committee_size(7)
+            .selection_mode(SelectionMode::WeightedRandom)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.epoch_duration, 20_000);
+        assert_eq!(config.emotional_threshold, 60);
+        assert_eq!(config.committee_size, 7);
+        assert_eq!(config.selection_mode, SelectionMode::WeightedRandom);
+        assert_eq!(config.byzantine_threshold, 67);
+        assert!(ProofOfEmotionEngine::new(config).is_ok());
     }
 
-    /// Get metrics
-    pub async fn get_metrics(&self) -> ConsensusMetrics {
-        self.metrics.read().await.clone()
+    #[test]
+    fn test_config_builder_rejects_invalid_values() {
+        let result = ConsensusConfig::builder().byzantine_threshold(40).build();
+        assert!(matches!(result, Err(ConsensusError::ConfigError { .. })));
+
+        let result = ConsensusConfig::builder().byzantine_threshold(101).build();
+        assert!(result.is_err());
+
+        // Cross-field: finality must complete within the voting window
+        let result = ConsensusConfig::builder()
+            .voting_timeout(1_000)
+            .finality_timeout(2_000)
+            .build();
+        assert!(result.is_err());
     }
 
-    /// Get validator count
-    pub fn get_validator_count(&self) -> usize {
-        self.validators.len()
+    #[tokio::test]
+    async fn test_audit_sink_records_finalization_and_slashing() {
+        let sink = Arc::new(VecAuditSink::new());
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_audit_sink(sink.clone());
+
+        for i in 1..=4 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
+
+        // validator-4 already voted for a different block in the upcoming epoch
+        let mut conflicting = Vote::new(
+            "validator-4".to_string(),
+            "conflicting-block".to_string(),
+            1,
+            0,
+            80,
+            true,
+        );
+        let key_pair = &engine.validators.get("validator-4").unwrap().key_pair;
+        conflicting.sign(key_pair).unwrap();
+        engine
+            .byzantine_detector
+            .record_vote(&conflicting)
+            .await
+            .unwrap();
+
+        engine.execute_epoch().await.unwrap();
+
+        assert_eq!(sink.finalized_blocks().len(), 1);
+        let slashings = sink.slashings_for("validator-4");
+        assert_eq!(slashings.len(), 1);
+        // Equivocation is a major offense
+        assert!(matches!(
+            &slashings[0],
+            AuditEntry::ValidatorSlashed {
+                reputation_after: 90,
+                ..
+            }
+        ));
     }
 
-    /// Get finalized blocks
-    pub async fn get_finalized_blocks(&self) -> Vec<Block> {
-        self.finalized_blocks.read().await.clone()
+    #[tokio::test]
+    async fn test_reputation_penalty_follows_severity() {
+        let config = ConsensusConfig {
+            reputation_penalties: ReputationPenalties {
+                minor: 3,
+                major: 12,
+                critical: 30,
+            },
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for id in ["minor", "critical"] {
+            let validator = EmotionalValidator::new(id, 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
+
+        engine
+            .slash_validator("minor", SlashingSeverity::Minor, "late vote")
+            .await
+            .unwrap();
+        engine
+            .slash_validator("critical", SlashingSeverity::Critical, "double signing")
+            .await
+            .unwrap();
+
+        let reputation = |id: &str| engine.validators.get(id).unwrap().get_reputation();
+        assert_eq!(reputation("minor"), 97);
+        assert_eq!(reputation("critical"), 70);
     }
 
-    /// Slash a validator for Byzantine behavior
-    ///
-    /// This reduces the validator's reputation and logs the offense
-    async fn slash_validator(&self, validator_id: &str, reason: &str) -> Result<()> {
-        if let Some(validator_ref) = self.validators.get(validator_id) {
-            let validator = validator_ref.value();
+    #[tokio::test]
+    async fn test_scoreboard_ranks_misbehaving_validator_last() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for i in 1..=4 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [70.0, 10.0, 90.0],
+                    age_ms: 0,
+                }));
+            engine.register_validator(validator).await.unwrap();
+        }
 
-            // Reduce reputation by 20 points for Byzantine behavior
-            validator.adjust_reputation(-20);
+        // validator-4 already voted for a different block in the first epoch
+        let mut conflicting = Vote::new(
+            "validator-4".to_string(),
+            "conflicting-block".to_string(),
+            1,
+            0,
+            80,
+            true,
+        );
+        let key_pair = &engine.validators.get("validator-4").unwrap().key_pair;
+        conflicting.sign(key_pair).unwrap();
+        engine
+            .byzantine_detector
+            .record_vote(&conflicting)
+            .await
+            .unwrap();
 
-            warn!(
-                "⚖️  Slashed validator {} (reputation now {}): {}",
-                validator_id,
-                validator.get_reputation(),
-                reason
-            );
+        for _ in 0..3 {
+            engine.execute_epoch().await.unwrap();
+        }
 
-            Ok(())
-        } else {
-            Err(ConsensusError::invalid_block(format!(
-                "Validator {} not found for slashing",
-                validator_id
-            )))
+        let scoreboard = engine.get_scoreboard().await;
+        assert_eq!(scoreboard.len(), 4);
+        assert_eq!(scoreboard.iter().map(|s| s.blocks_proposed).sum::<u64>(), 3);
+
+        let offender = scoreboard.last().unwrap();
+        assert_eq!(offender.validator_id, "validator-4");
+        assert_eq!(offender.slashing_count, 1);
+        assert_eq!(offender.byzantine_events, 1);
+        assert_eq!(offender.votes_cast, 2);
+        assert!(offender.reputation < 100);
+
+        for honest in &scoreboard[..3] {
+            assert_eq!(honest.slashing_count, 0);
+            assert_eq!(honest.votes_cast, 3);
+            assert_eq!(honest.approval_rate, 100);
+            assert_eq!(honest.uptime, 100);
+            assert!(honest.rank > offender.rank);
         }
     }
 
-    /// Get Byzantine slashing events
-    pub async fn get_byzantine_events(&self) -> Vec<crate::staking::SlashingEvent> {
-        self.byzantine_detector.get_slashing_events().await
+    #[tokio::test]
+    async fn test_graduated_policy_warns_on_first_offense() {
+        let sink = Arc::new(VecAuditSink::new());
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let start = SystemClock.now_millis();
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_clock(Arc::new(MockClock::new(start)))
+            .with_audit_sink(sink.clone());
+
+        for i in 1..=4 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
+
+        // Setting the policy keeps the keys registered above
+        let engine = engine.with_slashing_policy(SlashingPolicy::Graduated {
+            window_ms: 60 * 60 * 1000,
+        });
+
+        let mut conflicting = Vote::new(
+            "validator-4".to_string(),
+            "conflicting-block".to_string(),
+            1,
+            0,
+            80,
+            true,
+        );
+        let key_pair = &engine.validators.get("validator-4").unwrap().key_pair;
+        conflicting.sign(key_pair).unwrap();
+        engine
+            .byzantine_detector
+            .record_vote(&conflicting)
+            .await
+            .unwrap();
+
+        engine.execute_epoch().await.unwrap();
+
+        let events = engine.get_byzantine_events().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].severity, SlashingSeverity::Minor);
+        assert_eq!(events[0].timestamp, start);
+        assert!(sink.slashings_for("validator-4").is_empty());
+        let validator = engine.validators.get("validator-4").unwrap();
+        assert_eq!(validator.get_reputation(), 100);
     }
 
-    /// Cleanup old Byzantine detection data
-    pub async fn cleanup_byzantine_data(&self) {
-        let current_epoch = self.state.read().await.current_epoch;
-        // Keep last 100 epochs of data
-        self.byzantine_detector.cleanup_old_data(current_epoch, 100);
+    #[tokio::test]
+    async fn test_metrics_history_tracks_epochs() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_metrics_history_depth(3);
+        for i in 1..=4 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
+
+        for epoch in 1..=3 {
+            engine.run_epoch().await.unwrap();
+            assert_eq!(engine.get_metrics_history().await.len(), epoch);
+        }
+        engine.run_epoch().await.unwrap();
+
+        let history = engine.get_metrics_history().await;
+        assert_eq!(history.len(), 3);
+        assert!(history.windows(2).all(|pair| {
+            pair[0].0 <= pair[1].0 && pair[0].1.blocks_finalized <= pair[1].1.blocks_finalized
+        }));
+        let latest = &history[2].1;
+        assert_eq!(latest.successful_epochs + latest.failed_epochs, 4);
     }
 
-    // ========== Crash Recovery and Fault Tolerance ==========
+    #[tokio::test]
+    async fn test_network_health_drops_on_failed_epochs() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for i in 1..=4 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
 
-    /// Recover from a crash using checkpoints and block replay
-    ///
-    /// This method attempts to restore consensus state after a crash by:
-    /// 1. Loading the latest checkpoint
-    /// 2. Replaying finalized blocks since the checkpoint
-    /// 3. Validating state consistency
-    ///
-    /// Note: In a real implementation, this would sync with network peers
-    pub async fn recover_from_crash(&self) -> Result<()> {
-        info!("🔄 Attempting crash recovery...");
+        engine.run_epoch().await.unwrap();
+        let healthy = engine.get_state().await.network_health;
+        assert_eq!(engine.get_metrics().await.successful_epochs, 1);
 
-        // 1. Load last checkpoint
-        if let Some(checkpoint) = self.checkpoint_manager.get_latest_checkpoint().await {
-            info!(
-                "📍 Found checkpoint at height {} (epoch {})",
-                checkpoint.height, checkpoint.epoch
-            );
+        // Without validators every epoch fails
+        engine.validators.clear();
+        for _ in 0..3 {
+            engine.run_epoch().await.unwrap();
+        }
 
-            // Restore from checkpoint
-            self.restore_from_checkpoint(&checkpoint).await?;
+        assert_eq!(engine.get_metrics().await.failed_epochs, 3);
+        let degraded = engine.get_state().await.network_health;
+        assert!(degraded < 100);
+        assert!(degraded < healthy);
 
-            // 2. Replay blocks since checkpoint
-            let current_height = self.state.read().await.last_finalized_height;
-            if current_height > checkpoint.height {
-                info!(
-                    "🔁 Replaying {} blocks since checkpoint",
-                    current_height - checkpoint.height
-                );
-                self.replay_blocks_since_checkpoint(&checkpoint).await?;
-            }
-        } else {
-            info!("No checkpoint found, starting from genesis");
-        }
+        let weights = NetworkHealthWeights::default();
+        assert_eq!(weights.score(100, 100, 0.0, 0.0), 100);
+        assert_eq!(weights.score(0, 0, 1.0, 1.0), 0);
+    }
 
-        // 3. In a real implementation, sync with network
-        // self.sync_with_network().await?;
+    #[tokio::test]
+    async fn test_transaction_status_transitions() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+        engine.register_validator(validator).await.unwrap();
 
-        // 4. Validate state consistency
-        self.validate_state().await?;
+        let sender = KeyPair::generate().unwrap();
+        let mut tx = Transaction::new("alice".to_string(), "bob".to_string(), 400, 10);
+        tx.sign(&sender).unwrap();
+        let tx_hash = tx.hash.clone();
 
-        info!("✅ Crash recovery complete");
-        Ok(())
+        assert_eq!(
+            engine.get_transaction_status(&tx_hash).await,
+            TxStatus::Unknown
+        );
+        engine.submit_transaction(tx).await.unwrap();
+        assert_eq!(
+            engine.get_transaction_status(&tx_hash).await,
+            TxStatus::Pending
+        );
+
+        engine.execute_epoch().await.unwrap();
+        assert_eq!(
+            engine.get_transaction_status(&tx_hash).await,
+            TxStatus::Finalized { height: 1 }
+        );
     }
 
-    /// Restore consensus state from a checkpoint
-    async fn restore_from_checkpoint(&self, checkpoint: &crate::checkpoint::Checkpoint) -> Result<()> {
-        // Verify checkpoint is valid
-        if !self.checkpoint_manager.verify_checkpoint(checkpoint).await? {
-            return Err(ConsensusError::internal("Invalid checkpoint"));
-        }
+    #[tokio::test]
+    async fn test_duplicate_transaction_included_once() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+        engine.register_validator(validator).await.unwrap();
 
-        // Restore state
-        let mut state = self.state.write().await;
-        state.current_epoch = checkpoint.epoch;
-        state.last_finalized_height = checkpoint.height;
+        let mut tx = Transaction::new("alice".to_string(), "bob".to_string(), 400, 10);
+        tx.sign(&KeyPair::generate().unwrap()).unwrap();
+        let tx_hash = tx.hash.clone();
 
-        // Note: In a full implementation, we would restore:
-        // - Complete finalized block chain
-        // - Validator states and stakes
-        // - Pending transaction pool
-        // - Metrics and statistics
+        engine.submit_transaction(tx.clone()).await.unwrap();
+        assert!(matches!(
+            engine.submit_transaction(tx).await,
+            Err(ConsensusError::TransactionAlreadyPending { .. })
+        ));
+        assert_eq!(engine.get_state().await.pending_transactions, 1);
 
-        info!(
-            "Restored state from checkpoint: height={}, epoch={}",
-            checkpoint.height, checkpoint.epoch
-        );
+        engine.execute_epoch().await.unwrap();
+        let blocks = engine.get_finalized_blocks().await;
+        let included = blocks[0]
+            .transactions
+            .iter()
+            .filter(|tx| tx.hash == tx_hash)
+            .count();
+        assert_eq!(included, 1);
+    }
 
-        Ok(())
+    #[tokio::test]
+    async fn test_current_committee_matches_participants() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            committee_size: 3,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for i in 1..=5 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
+        assert!(engine.get_current_committee().await.is_empty());
+
+        engine.execute_epoch().await.unwrap();
+
+        let committee = engine.get_current_committee().await;
+        assert_eq!(committee.len(), 3);
+        let block = engine.get_finalized_blocks().await.remove(0);
+        let metadata = block.consensus_metadata.unwrap();
+        let mut participants = metadata.participants;
+        participants.sort();
+        let mut members = committee.clone();
+        members.sort();
+        assert_eq!(members, participants);
+        assert_eq!(committee[0], block.header.validator_id);
     }
 
-    /// Replay blocks since the last checkpoint to rebuild state
-    async fn replay_blocks_since_checkpoint(
-        &self,
-        checkpoint: &crate::checkpoint::Checkpoint,
-    ) -> Result<()> {
-        let blocks = self.finalized_blocks.read().await;
+    #[tokio::test]
+    async fn test_multiple_blocks_per_epoch() {
+        let config = ConsensusConfig::builder()
+            .emotional_threshold(0)
+            .committee_size(3)
+            .blocks_per_epoch(3)
+            .build()
+            .unwrap();
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for i in 1..=5 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
 
-        // Find blocks after checkpoint
-        let replay_blocks: Vec<_> = blocks
+        engine.execute_epoch().await.unwrap();
+        engine.execute_epoch().await.unwrap();
+
+        let blocks = engine.get_finalized_blocks().await;
+        let positions: Vec<_> = blocks
             .iter()
-            .filter(|b| b.header.height > checkpoint.height)
+            .map(|block| (block.header.height, block.header.epoch))
             .collect();
+        assert_eq!(positions, [(1, 1), (2, 1), (3, 1), (4, 2), (5, 2), (6, 2)]);
+        assert_eq!(engine.get_state().await.last_finalized_height, 6);
+        assert_eq!(engine.get_metrics().await.byzantine_failures, 0);
 
-        info!("Replaying {} blocks", replay_blocks.len());
+        for pair in blocks.windows(2) {
+            assert_eq!(pair[1].header.previous_hash, pair[0].hash);
+        }
+        for block in &blocks {
+            let metadata = block.consensus_metadata.as_ref().unwrap();
+            let proof = metadata.committee_proof.as_ref().unwrap();
+            assert_eq!(proof.previous_hash, block.header.previous_hash);
+        }
+    }
 
-        for block in replay_blocks {
-            // Validate block
-            if !block.verify_hash() {
-                warn!("Block {} has invalid hash during replay", block.header.height);
-                continue;
-            }
+    #[tokio::test]
+    async fn test_assessment_from_biometric_proof() {
+        let config = ConsensusConfig {
+            emotional_threshold: 70,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_zk_verifier(Arc::new(MockZkProvider));
+
+        // A device that would fail if the engine read raw biometrics
+        let validator = EmotionalValidator::new("validator-1", 10_000)
+            .unwrap()
+            .with_device(Arc::new(FailingDevice));
+        let circuit = BiometricCircuit {
+            emotional_score: Some(85),
+            heart_rate: Some(72.5),
+            stress_level: Some(25.0),
+            focus_level: Some(88.0),
+            skin_conductance: None,
+            skin_temperature: None,
+        };
+        validator.submit_biometric_proof(
+            MockZkProvider
+                .prove(circuit.clone(), "validator-1", 0)
+                .unwrap(),
+        );
+        engine.register_validator(validator).await.unwrap();
 
-            // Update state from block
-            if let Some(metadata) = &block.consensus_metadata {
-                let mut state = self.state.write().await;
-                state.last_finalized_height = block.header.height;
-                state.consensus_strength = metadata.consensus_strength;
-                state.emotional_fitness = metadata.emotional_fitness;
-            }
+        let assessment = engine.assess_validators().await.unwrap();
+        assert_eq!(
+            assessment,
+            vec![("validator-1".to_string(), EligibilityStatus::Eligible)]
+        );
+        let validator = engine.validators.get("validator-1").unwrap().clone();
+        assert_eq!(validator.get_emotional_score(), 85);
+        let profile = validator.emotional_profile.read().clone().unwrap();
+        assert!(profile.recent_readings.is_empty());
+
+        // The proof was consumed; the next assessment needs a fresh one
+        let assessment = engine.assess_validators().await.unwrap();
+        assert_eq!(
+            assessment[0].1,
+            EligibilityStatus::Ineligible(IneligibilityReason::MissingProof)
+        );
 
-            // Record in fork detector
-            if let Err(e) = self.fork_detector.record_block(block).await {
-                warn!("Fork detected during replay at height {}: {}", block.header.height, e);
-                // Attempt to resolve the fork
-                let _ = self.fork_detector.resolve_fork(block.header.height).await;
-            }
+        // Proofs made for another validator or another epoch are rejected
+        let invalid = EligibilityStatus::Ineligible(IneligibilityReason::InvalidProof(
+            "verification failed".to_string(),
+        ));
+        for (validator_id, epoch) in [("validator-2", 0), ("validator-1", 1)] {
+            let proof = MockZkProvider
+                .prove(circuit.clone(), validator_id, epoch)
+                .unwrap();
+            validator.submit_biometric_proof(proof);
+            let assessment = engine.assess_validators().await.unwrap();
+            assert_eq!(assessment[0].1, invalid);
         }
+    }
 
-        Ok(())
+    #[tokio::test]
+    async fn test_finalized_block_stream() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            committee_size: 3,
+            ..Default::default()
+        };
+        let engine = Arc::new(ProofOfEmotionEngine::new(config).unwrap());
+        for i in 1..=5 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
+        engine.execute_epoch().await.unwrap();
+
+        // One block in history, the rest finalized while the consumer is reading
+        let stream = engine.finalized_block_stream(0);
+        let consumer = tokio::spawn(async move {
+            stream
+                .take(5)
+                .map(|block| block.header.height)
+                .collect::<Vec<_>>()
+                .await
+        });
+        for _ in 0..4 {
+            engine.execute_epoch().await.unwrap();
+            tokio::task::yield_now().await;
+        }
+
+        let heights = time::timeout(Duration::from_secs(5), consumer)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(heights, vec![1, 2, 3, 4, 5]);
+
+        // Resuming after the last stored height yields only what follows
+        let resumed: Vec<u64> = engine
+            .finalized_block_stream(4)
+            .take(2)
+            .map(|block| block.header.height)
+            .take_until(time::sleep(Duration::from_millis(200)))
+            .collect()
+            .await;
+        assert_eq!(resumed, vec![4, 5]);
     }
 
-    /// Validate that the current state is consistent
-    async fn validate_state(&self) -> Result<()> {
-        let state = self.state.read().await;
-        let blocks = self.finalized_blocks.read().await;
+    #[tokio::test]
+    async fn test_restore_rejects_tampered_checkpoint_state() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            committee_size: 3,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for i in 1..=5 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
+        engine.execute_epoch().await.unwrap();
+        engine.execute_epoch().await.unwrap();
+        let block = engine.get_finalized_blocks().await.remove(1);
+
+        let manager = &engine.checkpoint_manager;
+        let key_pair = KeyPair::generate().unwrap();
+        let sign = |state_root: &str| crate::checkpoint::ValidatorSignature {
+            validator_id: "validator-1".to_string(),
+            stake: 10_000,
+            signature: manager
+                .sign_checkpoint(2, &block.hash, block.header.epoch, state_root, &key_pair)
+                .unwrap(),
+            public_key: key_pair.public_key_hex(),
+        };
+
+        let state_root = manager.checkpoint_state_root(&block).await;
+        let checkpoint = manager
+            .create_checkpoint(&block, vec![sign(&state_root)])
+            .await
+            .unwrap();
+        engine.restore_from_checkpoint(&checkpoint).await.unwrap();
+
+        // Properly signed, but committing to a different state
+        let tampered_root = "f".repeat(64);
+        let mut tampered = checkpoint.clone();
+        tampered.state_root = tampered_root.clone();
+        tampered.validator_signatures = vec![sign(&tampered_root)];
+        assert!(manager.verify_checkpoint(&tampered).await.unwrap());
+        let err = engine.restore_from_checkpoint(&tampered).await.unwrap_err();
+        assert!(err.to_string().contains("state root"));
+
+        let mut other_block = checkpoint;
+        other_block.height = 1;
+        other_block.validator_signatures = vec![crate::checkpoint::ValidatorSignature {
+            signature: manager
+                .sign_checkpoint(1, &block.hash, block.header.epoch, &state_root, &key_pair)
+                .unwrap(),
+            ..sign(&state_root)
+        }];
+        let result = engine.restore_from_checkpoint(&other_block).await;
+        assert!(result.unwrap_err().to_string().contains("block hash"));
+    }
 
-        // Check that last finalized height matches block count
-        if !blocks.is_empty() {
-            let last_block = blocks.last().unwrap();
-            if last_block.header.height != state.last_finalized_height {
-                return Err(ConsensusError::internal(format!(
-                    "State inconsistency: last_finalized_height={} but last block height={}",
-                    state.last_finalized_height, last_block.header.height
-                )));
-            }
+    #[tokio::test]
+    async fn test_epoch_without_eligible_validators_counts_emotional_failure() {
+        let config = ConsensusConfig {
+            emotional_threshold: 95,
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let mut events = engine.subscribe_events();
+        for i in 0..4 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [130.0, 95.0, 5.0],
+                    age_ms: 0,
+                }));
+            engine.register_validator(validator).await.unwrap();
         }
 
-        // Verify chain continuity
-        for i in 1..blocks.len() {
-            let prev_block = &blocks[i - 1];
-            let curr_block = &blocks[i];
+        engine.run_epoch().await.unwrap();
 
-            if curr_block.header.height != prev_block.header.height + 1 {
-                return Err(ConsensusError::internal(format!(
-                    "Chain discontinuity: block {} -> {}",
-                    prev_block.header.height, curr_block.header.height
-                )));
+        let metrics = engine.get_metrics().await;
+        assert_eq!(metrics.failed_epochs, 1);
+        assert_eq!(metrics.emotional_failures, 1);
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ConsensusEvent::NoEligibleValidators {
+                epoch: 1,
+                assessed: 4,
             }
+        );
+        let health = crate::HealthStatus::from_consensus(&engine, 0).await;
+        assert!(health
+            .issues
+            .contains(&crate::health::HealthIssue::NoEligibleValidators(4)));
+
+        // An epoch without any validators fails, but not for emotional fitness
+        let empty = ProofOfEmotionEngine::new(ConsensusConfig::default()).unwrap();
+        empty.run_epoch().await.unwrap();
+        let metrics = empty.get_metrics().await;
+        assert_eq!(metrics.failed_epochs, 1);
+        assert_eq!(metrics.emotional_failures, 0);
+    }
 
-            if curr_block.header.previous_hash != prev_block.hash {
-                return Err(ConsensusError::internal(format!(
-                    "Chain hash mismatch at height {}",
-                    curr_block.header.height
-                )));
-            }
+    /// Distinct proposers of the blocks finalized over `epochs` epochs
+    async fn proposers_over_epochs(config: ConsensusConfig, epochs: usize) -> HashSet<String> {
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for i in 0..5 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [70.0, 20.0, 90.0],
+                    age_ms: 0,
+                }));
+            engine.register_validator(validator).await.unwrap();
+        }
+        for _ in 0..epochs {
+            engine.run_epoch().await.unwrap();
         }
 
-        info!("State validation passed: {} blocks verified", blocks.len());
-        Ok(())
+        let blocks = engine.get_finalized_blocks().await;
+        assert_eq!(blocks.len(), epochs);
+        blocks
+            .into_iter()
+            .map(|block| block.header.validator_id)
+            .collect()
     }
 
-    /// Create a checkpoint if at checkpoint interval
-    pub async fn try_create_checkpoint(&self, block: &Block) -> Result<Option<crate::checkpoint::Checkpoint>> {
-        if !self.checkpoint_manager.should_create_checkpoint(block.header.height) {
-            return Ok(None);
+    #[tokio::test]
+    async fn test_proposer_cooldown_rotates_proposer() {
+        let config = ConsensusConfig {
+            committee_size: 3,
+            emotional_threshold: 0,
+            ..Default::default()
+        };
+        let plain = proposers_over_epochs(config.clone(), 6).await;
+        assert_eq!(plain.len(), 1);
+
+        let cooled = proposers_over_epochs(
+            ConsensusConfig {
+                proposer_cooldown: Some(ProposerCooldown {
+                    epochs: 2,
+                    weight_factor: 0.5,
+                }),
+                ..config
+            },
+            6,
+        )
+        .await;
+        assert!(cooled.len() > plain.len());
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_is_recorded_from_the_chain() {
+        let config = ConsensusConfig {
+            committee_size: 3,
+            emotional_threshold: 0,
+            proposer_cooldown: Some(ProposerCooldown {
+                epochs: 2,
+                weight_factor: 0.5,
+            }),
+            ..Default::default()
+        };
+        let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for i in 0..5 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [70.0, 20.0, 90.0],
+                    age_ms: 0,
+                }));
+            engine.register_validator(validator).await.unwrap();
         }
+        let cooled_down = |block: &Block| {
+            block
+                .consensus_metadata
+                .as_ref()
+                .and_then(|metadata| metadata.committee_proof.as_ref())
+                .map(|proof| proof.cooled_down.clone())
+                .unwrap()
+        };
+
+        engine.run_epoch().await.unwrap();
+        engine.run_epoch().await.unwrap();
+        let blocks = engine.get_finalized_blocks().await;
+        assert!(cooled_down(&blocks[0]).is_empty());
+        assert_eq!(
+            cooled_down(&blocks[1]),
+            [blocks[0].header.validator_id.clone()]
+        );
 
-        info!("Creating checkpoint at height {}", block.header.height);
+        // Selection follows the chain, not the in-memory scoreboard
+        engine.activity.clear();
+        engine.run_epoch().await.unwrap();
+        let blocks = engine.get_finalized_blocks().await;
+        let mut expected: Vec<_> = blocks[..2]
+            .iter()
+            .map(|block| block.header.validator_id.clone())
+            .collect();
+        expected.sort();
+        expected.dedup();
+        assert_eq!(cooled_down(&blocks[2]), expected);
+    }
 
-        // In a real implementation, collect signatures from validators
-        // For now, create an empty checkpoint as a placeholder
-        let _validator_signatures: Vec<crate::checkpoint::ValidatorSignature> = vec![];
+    #[test]
+    fn test_invalid_proposer_cooldown_rejected() {
+        for cooldown in [
+            ProposerCooldown {
+                epochs: 0,
+                weight_factor: 0.5,
+            },
+            ProposerCooldown {
+                epochs: 2,
+                weight_factor: 1.5,
+            },
+        ] {
+            let config = ConsensusConfig::builder()
+                .proposer_cooldown(cooldown)
+                .build();
+            assert!(config.is_err());
+        }
+    }
 
-        // Update total stake in checkpoint manager
-        let total_stake: u64 = self
-            .validators
-            .iter()
-            .map(|entry| entry.value().get_stake())
-            .sum();
+    #[tokio::test]
+    async fn test_engine_halts_after_tip_retries() {
+        // No validators, so every epoch fails at the genesis tip
+        let config = ConsensusConfig {
+            epoch_duration: 10,
+            max_tip_retries: 3,
+            ..Default::default()
+        };
+        let engine = Arc::new(ProofOfEmotionEngine::new(config).unwrap());
+        Arc::clone(&engine).start().await.unwrap();
+
+        let halted = time::timeout(Duration::from_secs(5), async {
+            while *engine.is_running.read().await {
+                time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await;
+
+        assert!(halted.is_ok(), "engine kept retrying the stuck tip");
+        assert_eq!(engine.current_tip_retry_count(), 3);
+        assert_eq!(engine.get_metrics().await.failed_epochs, 3);
+        assert!(matches!(
+            engine.stop().await,
+            Err(ConsensusError::NotRunning)
+        ));
+    }
 
-        self.checkpoint_manager.update_total_stake(total_stake).await;
+    #[tokio::test]
+    async fn test_stop_and_flush_checkpoints_and_persists_snapshot() {
+        let config = ConsensusConfig {
+            emotional_threshold: 60,
+            ..Default::default()
+        };
+        let (_registry, prometheus) = crate::metrics::create_default_registry().unwrap();
+        let store = Arc::new(crate::snapshot::MemorySnapshotStore::new());
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_prometheus_metrics(prometheus.clone())
+            .with_snapshot_store(store.clone());
+        for i in 0..3 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [70.0, 10.0, 90.0],
+                    age_ms: 0,
+                }));
+            engine.register_validator(validator).await.unwrap();
+        }
+        engine.execute_epoch().await.unwrap();
+        let tip = engine.get_finalized_blocks().await.pop().unwrap();
 
-        // Note: In production, this would fail without real validator signatures
-        // For testing/development, we skip this
-        info!(
-            "Checkpoint interval reached at height {} (signatures would be collected from validators)",
-            block.header.height
-        );
+        // Never started, but the flush still runs
+        engine.stop_and_flush().await.unwrap();
 
-        Ok(None)
+        let checkpoint = engine
+            .get_checkpoint_manager()
+            .get_latest_checkpoint()
+            .await
+            .unwrap();
+        assert_eq!(checkpoint.height, tip.header.height);
+        assert_eq!(checkpoint.block_hash, tip.hash);
+
+        let snapshot = store.latest().unwrap();
+        assert_eq!(snapshot.finalized_height, tip.header.height);
+        assert_eq!(snapshot.tip_hash, Some(tip.hash));
+        assert_eq!(snapshot.metrics.blocks_finalized, 1);
+        assert_eq!(prometheus.blocks_finalized.get(), 1.0);
+
+        // Flushing again is harmless
+        engine.stop_and_flush().await.unwrap();
     }
 
-    /// Get fork detector for external access
-    pub fn get_fork_detector(&self) -> Arc<crate::fork::ForkDetector> {
-        Arc::clone(&self.fork_detector)
-    }
+    #[tokio::test]
+    async fn test_stop_and_flush_persists_snapshot_when_checkpoint_cannot_be_signed() {
+        let config = ConsensusConfig {
+            emotional_threshold: 60,
+            ..Default::default()
+        };
+        let store = Arc::new(crate::snapshot::MemorySnapshotStore::new());
+        let engine = ProofOfEmotionEngine::new(config)
+            .unwrap()
+            .with_snapshot_store(store.clone());
+        for i in 0..3 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000)
+                .unwrap()
+                .with_device(Arc::new(FixedDevice {
+                    values: [70.0, 10.0, 90.0],
+                    age_ms: 0,
+                }));
+            engine.register_validator(validator).await.unwrap();
+        }
+        engine.execute_epoch().await.unwrap();
+        let tip = engine.get_finalized_blocks().await.pop().unwrap();
 
-    /// Get checkpoint manager for external access
-    pub fn get_checkpoint_manager(&self) -> Arc<crate::checkpoint::CheckpointManager> {
-        Arc::clone(&self.checkpoint_manager)
-    }
-}
+        // Nobody is left to sign the final checkpoint
+        for entry in engine.validators.iter() {
+            *entry.value().is_active.write() = false;
+        }
+        engine.stop_and_flush().await.unwrap();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        assert!(engine
+            .get_checkpoint_manager()
+            .get_latest_checkpoint()
+            .await
+            .is_none_or(|checkpoint| checkpoint.height < tip.header.height));
+        let snapshot = store.latest().unwrap();
+        assert_eq!(snapshot.finalized_height, tip.header.height);
+    }
 
     #[tokio::test]
-    async fn test_consensus_engine_creation() {
-        let config = ConsensusConfig::default();
-        let engine = ProofOfEmotionEngine::new(config).unwrap();
-        assert_eq!(engine.get_validator_count(), 0);
+    async fn test_emotional_state_survives_restart() {
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default())
+            .unwrap()
+            .with_emotional_state_persistence(true);
+        let validator = EmotionalValidator::new("validator-1", 10_000)
+            .unwrap()
+            .with_device(Arc::new(FixedDevice {
+                values: [70.0, 10.0, 90.0],
+                age_ms: 0,
+            }));
+        engine.register_validator(validator).await.unwrap();
+        for _ in 0..3 {
+            engine.assess_validators().await.unwrap();
+        }
+        let before = engine.validators.get("validator-1").unwrap().save_state();
+        let snapshot = engine.snapshot().await;
+        assert_eq!(snapshot.validator_states.len(), 1);
+
+        let restart = || async {
+            let restarted = ProofOfEmotionEngine::new(ConsensusConfig::default()).unwrap();
+            let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+            restarted.register_validator(validator).await.unwrap();
+            restarted
+        };
+
+        let restarted = restart().await;
+        assert_eq!(
+            restarted.restore_emotional_state(&snapshot).await.unwrap(),
+            1
+        );
+        let validator = restarted.validators.get("validator-1").unwrap();
+        let profile = before.profile.unwrap();
+        assert!(profile.emotional_score > 0);
+        assert_eq!(validator.get_emotional_score(), profile.emotional_score);
+        assert_eq!(validator.recent_scores(10).len(), 3);
+        assert_eq!(validator.save_state().score_history, before.score_history);
+        drop(validator);
+
+        // State older than an epoch does not carry over
+        let mut stale = snapshot.clone();
+        let epoch_duration = ConsensusConfig::default().epoch_duration;
+        let age = 2 * epoch_duration;
+        for state in &mut stale.validator_states {
+            if let Some(profile) = &mut state.profile {
+                profile.last_updated -= age;
+            }
+            for (_, timestamp) in &mut state.score_history {
+                *timestamp -= age;
+            }
+        }
+        let restarted = restart().await;
+        restarted.restore_emotional_state(&stale).await.unwrap();
+        let validator = restarted.validators.get("validator-1").unwrap();
+        assert!(validator.get_emotional_profile().is_none());
+        assert_eq!(validator.get_emotional_score(), 0);
+        assert!(validator.recent_scores(10).is_empty());
+
+        let mut foreign = snapshot.validator_states[0].clone();
+        foreign.validator_id = "validator-2".to_string();
+        assert!(validator.load_state(foreign, epoch_duration).is_err());
+
+        let without = ProofOfEmotionEngine::new(ConsensusConfig::default()).unwrap();
+        assert!(without.snapshot().await.validator_states.is_empty());
     }
 
     #[tokio::test]
-    async fn test_validator_registration() {
-        let config = ConsensusConfig::default();
+    async fn test_finalized_block_attestation() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
         let engine = ProofOfEmotionEngine::new(config).unwrap();
+        for i in 1..=4 {
+            let validator = EmotionalValidator::new(format!("validator-{}", i), 10_000).unwrap();
+            engine.register_validator(validator).await.unwrap();
+        }
 
-        let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
-        engine.register_validator(validator).await.unwrap();
+        engine.execute_epoch().await.unwrap();
 
-        assert_eq!(engine.get_validator_count(), 1);
+        let block = engine.get_finalized_blocks().await.remove(0);
+        let attestation = block.attestation().unwrap();
+        let proposer = engine.validators.get(&attestation.proposer_id).unwrap();
+        let public_key = proposer.public_key_hex();
+        assert!(crate::attestation::verify_attestation(&attestation, &public_key).unwrap());
+        assert_eq!(
+            attestation.emotional_fitness,
+            engine.get_state().await.emotional_fitness
+        );
     }
 
     #[tokio::test]
-    async fn test_insufficient_stake_registration() {
-        let config = ConsensusConfig::default();
+    async fn test_finalized_transaction_resubmission_rejected() {
+        let config = ConsensusConfig {
+            emotional_threshold: 0,
+            ..Default::default()
+        };
         let engine = ProofOfEmotionEngine::new(config).unwrap();
+        let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+        engine.register_validator(validator).await.unwrap();
 
-        let validator = EmotionalValidator::new("validator-1", 5_000).unwrap();
-        let result = engine.register_validator(validator).await;
+        let sender = KeyPair::generate().unwrap();
+        let mut tx = Transaction::new("alice".to_string(), "bob".to_string(), 400, 10);
+        tx.sign(&sender).unwrap();
+        engine.submit_transaction(tx.clone()).await.unwrap();
+        engine.execute_epoch().await.unwrap();
+
+        assert!(matches!(
+            engine.submit_transaction(tx).await,
+            Err(ConsensusError::TransactionAlreadyFinalized { .. })
+        ));
+
+        let mut fresh = Transaction::new("alice".to_string(), "carol".to_string(), 400, 10);
+        fresh.sign(&sender).unwrap();
+        engine.submit_transaction(fresh).await.unwrap();
+        assert_eq!(engine.get_state().await.pending_transactions, 1);
+
+        let certain_filter = ConsensusConfig {
+            finalized_tx_filter_false_positive_rate: 1.0,
+            ..Default::default()
+        };
+        assert!(certain_filter.validate().is_err());
+    }
 
-        assert!(result.is_err());
+    #[tokio::test]
+    async fn test_transaction_status_expired() {
+        let clock = Arc::new(MockClock::new(1_700_000_000_000));
+        let engine = ProofOfEmotionEngine::new(ConsensusConfig::default())
+            .unwrap()
+            .with_clock(clock.clone());
+
+        let tx = Transaction::new_with_clock(
+            "addr1".to_string(),
+            "addr2".to_string(),
+            1000,
+            10,
+            clock.as_ref(),
+        );
+        let tx_hash = tx.hash.clone();
+        engine.submit_transaction(tx).await.unwrap();
+
+        clock.advance(crate::TRANSACTION_TTL_MS + 1);
+        engine.cleanup_transaction_pool().await;
+        assert_eq!(
+            engine.get_transaction_status(&tx_hash).await,
+            TxStatus::Expired
+        );
     }
 }
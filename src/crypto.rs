@@ -1,12 +1,37 @@
 //! Cryptographic primitives for Proof of Emotion
 
 use crate::error::{ConsensusError, Result};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
 use secp256k1::{
-    ecdsa::{RecoverableSignature, RecoveryId},
+    ecdsa::{RecoverableSignature, RecoveryId, Signature as EcdsaSignature},
     Message, PublicKey, Secp256k1, SecretKey,
 };
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Current keystore file format version
+pub const KEYSTORE_VERSION: u8 = 1;
+
+/// Default span in milliseconds that an emotional proof's readings must fall within,
+/// and for which the proof stays valid
+pub const DEFAULT_TEMPORAL_WINDOW_MS: u64 = 300_000;
+
+/// scrypt cost parameter (log2 of N) used for new keystores
+const KEYSTORE_SCRYPT_LOG_N: u8 = 15;
+/// Highest scrypt cost parameter (log2 of N) accepted when loading a keystore
+///
+/// Together with the fixed `r` and `p` below, bounds the memory (128·r·N bytes) and
+/// time an untrusted keystore file can make key derivation use.
+const KEYSTORE_SCRYPT_MAX_LOG_N: u8 = 20;
+/// scrypt block size parameter; keystores with any other value are rejected
+const KEYSTORE_SCRYPT_R: u32 = 8;
+/// scrypt parallelization parameter; keystores with any other value are rejected
+const KEYSTORE_SCRYPT_P: u32 = 1;
 
 /// ECDSA key pair for validator identity
 #[derive(Clone)]
@@ -35,18 +60,147 @@ pub struct EmotionalProof {
     pub emotional_scores: std::collections::HashMap<String, u8>,
     /// Biometric data hashes (for privacy)
     pub biometric_hashes: std::collections::HashMap<String, String>,
-    /// Temporal window (milliseconds)
+    /// Span (milliseconds) the readings fell within; the proof expires this long after
+    /// its timestamp
     pub temporal_window: u64,
     /// Proof timestamp
     pub timestamp: u64,
     /// Consensus strength
     pub consensus_strength: u8,
-    /// Merkle root of proof data
+    /// Root of the Merkle tree over per-validator `(id, score, biometric hash)` leaves
     pub merkle_root: String,
     /// Proof signature
     pub signature: Signature,
 }
 
+/// Sibling hash on the path from a leaf to the Merkle root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleStep {
+    /// Sibling node hash (hex)
+    pub hash: String,
+    /// Whether the sibling is the left child
+    pub is_left: bool,
+}
+
+/// Proof that one validator's score is included under an [`EmotionalProof`] root
+///
+/// Reveals only that validator's leaf and the sibling hashes, not the other scores.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    /// Validator ID
+    pub validator_id: String,
+    /// Emotional score of the validator
+    pub emotional_score: u8,
+    /// Biometric data hash of the validator
+    pub biometric_hash: String,
+    /// Sibling hashes from the leaf up to the root
+    pub path: Vec<MerkleStep>,
+}
+
+impl InclusionProof {
+    /// Check the proof against a Merkle root
+    pub fn verify(&self, merkle_root: &str) -> bool {
+        let mut hash = merkle_leaf(
+            &self.validator_id,
+            self.emotional_score,
+            &self.biometric_hash,
+        );
+        for step in &self.path {
+            let Ok(sibling) = hex::decode(&step.hash) else {
+                return false;
+            };
+            hash = if step.is_left {
+                merkle_node(&sibling, &hash)
+            } else {
+                merkle_node(&hash, &sibling)
+            };
+        }
+        hex::encode(hash) == merkle_root
+    }
+}
+
+/// Leaf hash: `SHA-256(0x00 || len(id) || id || score || len(hash) || hash)`
+fn merkle_leaf(validator_id: &str, emotional_score: u8, biometric_hash: &str) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update([0u8]);
+    hasher.update((validator_id.len() as u64).to_le_bytes());
+    hasher.update(validator_id.as_bytes());
+    hasher.update([emotional_score]);
+    hasher.update((biometric_hash.len() as u64).to_le_bytes());
+    hasher.update(biometric_hash.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+/// Inner node hash: `SHA-256(0x01 || left || right)`
+fn merkle_node(left: &[u8], right: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update([1u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().to_vec()
+}
+
+/// Passphrase-encrypted key pair as stored on disk
+///
+/// The secret key is encrypted with XChaCha20-Poly1305 under a key derived from the
+/// passphrase with scrypt. The public key is stored in clear (so the file can be
+/// identified without the passphrase) and is bound to the ciphertext as associated
+/// data, so swapping it invalidates the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    /// Keystore format version
+    pub version: u8,
+    /// Public key (hex encoded)
+    pub public_key: String,
+    /// Key derivation parameters
+    pub kdf: KdfParams,
+    /// Cipher nonce (hex encoded)
+    pub nonce: String,
+    /// Encrypted secret key including authentication tag (hex encoded)
+    pub ciphertext: String,
+}
+
+/// scrypt key derivation parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// log2 of the scrypt cost parameter N
+    pub log_n: u8,
+    /// Block size parameter
+    pub r: u32,
+    /// Parallelization parameter
+    pub p: u32,
+    /// Salt (hex encoded)
+    pub salt: String,
+}
+
+impl KdfParams {
+    /// Derive a 256-bit encryption key from a passphrase
+    fn derive_key(&self, passphrase: &str) -> Result<[u8; 32]> {
+        let salt = hex::decode(&self.salt)
+            .map_err(|e| ConsensusError::storage_error(format!("Invalid keystore salt: {}", e)))?;
+        if self.log_n > KEYSTORE_SCRYPT_MAX_LOG_N {
+            return Err(ConsensusError::storage_error(format!(
+                "Keystore scrypt cost 2^{} exceeds the maximum of 2^{}",
+                self.log_n, KEYSTORE_SCRYPT_MAX_LOG_N
+            )));
+        }
+        if self.r != KEYSTORE_SCRYPT_R || self.p != KEYSTORE_SCRYPT_P {
+            return Err(ConsensusError::storage_error(format!(
+                "Unsupported keystore scrypt parameters r={}, p={} (expected r={}, p={})",
+                self.r, self.p, KEYSTORE_SCRYPT_R, KEYSTORE_SCRYPT_P
+            )));
+        }
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, 32).map_err(|e| {
+            ConsensusError::storage_error(format!("Invalid keystore KDF parameters: {}", e))
+        })?;
+
+        let mut key = [0u8; 32];
+        scrypt::scrypt(passphrase.as_bytes(), &salt, &params, &mut key)
+            .map_err(|e| ConsensusError::internal(format!("Key derivation failed: {}", e)))?;
+        Ok(key)
+    }
+}
+
 impl KeyPair {
     /// Generate a new random key pair
     pub fn generate() -> Result<Self> {
@@ -129,10 +283,190 @@ impl KeyPair {
         Ok(recovered_key == public_key)
     }
 
+    /// Verify many compact signatures over the same message
+    ///
+    /// The message is hashed and the verification context built once, and each
+    /// signature is checked directly against its public key rather than recovering the
+    /// key. Returns `Ok(false)` as soon as one signature does not match.
+    pub fn verify_batch(
+        message: &[u8],
+        signatures: &[[u8; 64]],
+        public_keys_hex: &[&str],
+    ) -> Result<bool> {
+        if signatures.len() != public_keys_hex.len() {
+            return Err(ConsensusError::signature_verification_failed(format!(
+                "Batch has {} signatures for {} public keys",
+                signatures.len(),
+                public_keys_hex.len()
+            )));
+        }
+
+        let secp = Secp256k1::verification_only();
+        let message_hash = Sha256::digest(message);
+        let message = Message::from_digest_slice(&message_hash)
+            .map_err(|e| ConsensusError::internal(format!("Invalid message: {}", e)))?;
+
+        for (signature_bytes, public_key_hex) in signatures.iter().zip(public_keys_hex) {
+            let public_key_bytes = hex::decode(public_key_hex)
+                .map_err(|e| ConsensusError::internal(format!("Invalid public key hex: {}", e)))?;
+            let public_key = PublicKey::from_slice(&public_key_bytes)
+                .map_err(|e| ConsensusError::internal(format!("Invalid public key: {}", e)))?;
+            let mut signature = EcdsaSignature::from_compact(signature_bytes)
+                .map_err(|e| ConsensusError::internal(format!("Invalid signature: {}", e)))?;
+            signature.normalize_s();
+
+            if secp
+                .verify_ecdsa(&message, &signature, &public_key)
+                .is_err()
+            {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
     /// Get the public key
     pub fn public_key(&self) -> &PublicKey {
         &self.public_key
     }
+
+    /// Encrypt the key pair with a passphrase
+    pub fn to_keystore(&self, passphrase: &str) -> Result<Keystore> {
+        let mut rng = rand::thread_rng();
+        let mut salt = [0u8; 32];
+        rng.fill_bytes(&mut salt);
+        let mut nonce = [0u8; 24];
+        rng.fill_bytes(&mut nonce);
+
+        let kdf = KdfParams {
+            log_n: KEYSTORE_SCRYPT_LOG_N,
+            r: KEYSTORE_SCRYPT_R,
+            p: KEYSTORE_SCRYPT_P,
+            salt: hex::encode(salt),
+        };
+        let key = kdf.derive_key(passphrase)?;
+
+        let public_key = self.public_key_hex();
+        let cipher = XChaCha20Poly1305::new((&key).into());
+        let ciphertext = cipher
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &self.secret_key.secret_bytes(),
+                    aad: public_key.as_bytes(),
+                },
+            )
+            .map_err(|_| ConsensusError::internal("Failed to encrypt secret key"))?;
+
+        Ok(Keystore {
+            version: KEYSTORE_VERSION,
+            public_key,
+            kdf,
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypt a key pair from a keystore
+    pub fn from_keystore(keystore: &Keystore, passphrase: &str) -> Result<Self> {
+        if keystore.version != KEYSTORE_VERSION {
+            return Err(ConsensusError::storage_error(format!(
+                "Unsupported keystore version: {}",
+                keystore.version
+            )));
+        }
+
+        let nonce = hex::decode(&keystore.nonce)
+            .map_err(|e| ConsensusError::storage_error(format!("Invalid keystore nonce: {}", e)))?;
+        if nonce.len() != 24 {
+            return Err(ConsensusError::storage_error(
+                "Invalid keystore nonce length",
+            ));
+        }
+        let ciphertext = hex::decode(&keystore.ciphertext).map_err(|e| {
+            ConsensusError::storage_error(format!("Invalid keystore ciphertext: {}", e))
+        })?;
+
+        let key = keystore.kdf.derive_key(passphrase)?;
+        let cipher = XChaCha20Poly1305::new((&key).into());
+        let secret = cipher
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: keystore.public_key.as_bytes(),
+                },
+            )
+            .map_err(|_| {
+                ConsensusError::storage_error(
+                    "Failed to decrypt keystore: wrong passphrase or corrupted file",
+                )
+            })?;
+
+        let key_pair = Self::from_secret_bytes(&secret)?;
+        if key_pair.public_key_hex() != keystore.public_key {
+            return Err(ConsensusError::storage_error(
+                "Keystore public key does not match decrypted secret key",
+            ));
+        }
+
+        Ok(key_pair)
+    }
+
+    /// Save the key pair to a passphrase-encrypted keystore file
+    ///
+    /// The keystore is written to a temporary file beside `path` and renamed over it, so
+    /// an interrupted write never leaves a truncated keystore. On Unix the file is only
+    /// readable and writable by its owner.
+    pub fn save_to_file(&self, path: impl AsRef<Path>, passphrase: &str) -> Result<()> {
+        let keystore = self.to_keystore(passphrase)?;
+        let json = serde_json::to_string_pretty(&keystore)
+            .map_err(|e| ConsensusError::internal(format!("Serialization error: {}", e)))?;
+        Self::write_keystore(path.as_ref(), json.as_bytes()).map_err(|e| {
+            ConsensusError::storage_error(format!(
+                "Failed to write keystore {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })
+    }
+
+    /// Write keystore contents to `path` through an owner-only temporary file
+    fn write_keystore(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let temp_path = path.with_extension("tmp");
+        // A leftover file from an interrupted write may have wider permissions
+        match std::fs::remove_file(&temp_path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, path)
+    }
+
+    /// Load a key pair from a passphrase-encrypted keystore file
+    pub fn load_from_file(path: impl AsRef<Path>, passphrase: &str) -> Result<Self> {
+        let json = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            ConsensusError::storage_error(format!(
+                "Failed to read keystore {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        let keystore: Keystore = serde_json::from_str(&json)
+            .map_err(|e| ConsensusError::storage_error(format!("Invalid keystore file: {}", e)))?;
+        Self::from_keystore(&keystore, passphrase)
+    }
 }
 
 impl EmotionalProof {
@@ -151,28 +485,10 @@ impl EmotionalProof {
 
         let consensus_strength = Self::calculate_consensus_strength(&emotional_scores);
 
-        let merkle_root = Self::calculate_merkle_root(
-            &validators,
-            &emotional_scores,
-            &biometric_hashes,
-            temporal_window,
-            timestamp,
-        );
-
-        let proof_data = format!(
-            "{}:{}:{}:{}:{}",
-            validators.join(","),
-            serde_json::to_string(&emotional_scores)
-                .map_err(|e| crate::error::ConsensusError::internal(format!("Serialization error: {}", e)))?,
-            serde_json::to_string(&biometric_hashes)
-                .map_err(|e| crate::error::ConsensusError::internal(format!("Serialization error: {}", e)))?,
-            temporal_window,
-            timestamp
-        );
-
-        let signature = key_pair.sign(proof_data.as_bytes())?;
+        let merkle_root =
+            Self::calculate_merkle_root(&validators, &emotional_scores, &biometric_hashes);
 
-        Ok(Self {
+        let mut proof = Self {
             validators,
             emotional_scores,
             biometric_hashes,
@@ -180,8 +496,30 @@ impl EmotionalProof {
             timestamp,
             consensus_strength,
             merkle_root,
-            signature,
-        })
+            signature: Signature {
+                signature: String::new(),
+                recovery_id: 0,
+                algorithm: String::new(),
+            },
+        };
+        proof.signature = key_pair.sign(proof.proof_data()?.as_bytes())?;
+
+        Ok(proof)
+    }
+
+    /// Data covered by the signature, including the Merkle root
+    fn proof_data(&self) -> Result<String> {
+        Ok(format!(
+            "{}:{}:{}:{}:{}:{}",
+            self.validators.join(","),
+            serde_json::to_string(&self.emotional_scores)
+                .map_err(|e| crate::error::ConsensusError::internal(format!("Serialization error: {}", e)))?,
+            serde_json::to_string(&self.biometric_hashes)
+                .map_err(|e| crate::error::ConsensusError::internal(format!("Serialization error: {}", e)))?,
+            self.temporal_window,
+            self.timestamp,
+            self.merkle_root
+        ))
     }
 
     /// Calculate consensus strength from emotional scores
@@ -207,47 +545,100 @@ impl EmotionalProof {
         (avg as f64 - variance_penalty).clamp(0.0, 100.0) as u8
     }
 
-    /// Calculate merkle root of proof data
+    /// Tree levels from the leaves (in `validators` order) up to the root
+    ///
+    /// A node without a sibling is carried up to the next level unchanged. Validators
+    /// missing from the maps get a score of 0 and an empty biometric hash.
+    fn merkle_levels(
+        validators: &[String],
+        emotional_scores: &std::collections::HashMap<String, u8>,
+        biometric_hashes: &std::collections::HashMap<String, String>,
+    ) -> Vec<Vec<Vec<u8>>> {
+        let leaves: Vec<Vec<u8>> = validators
+            .iter()
+            .map(|id| {
+                let score = emotional_scores.get(id).copied().unwrap_or(0);
+                let biometric_hash = biometric_hashes.get(id).map_or("", String::as_str);
+                merkle_leaf(id, score, biometric_hash)
+            })
+            .collect();
+
+        let mut levels = vec![leaves];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => merkle_node(left, right),
+                    [single] => single.clone(),
+                    _ => unreachable!("chunks(2) yields one or two nodes"),
+                })
+                .collect();
+            levels.push(next);
+        }
+        levels
+    }
+
+    /// Calculate the Merkle root over the per-validator leaves
     fn calculate_merkle_root(
         validators: &[String],
         emotional_scores: &std::collections::HashMap<String, u8>,
         biometric_hashes: &std::collections::HashMap<String, String>,
-        temporal_window: u64,
-        timestamp: u64,
     ) -> String {
-        let mut hasher = Sha256::new();
-
-        for validator in validators {
-            hasher.update(validator.as_bytes());
+        let levels = Self::merkle_levels(validators, emotional_scores, biometric_hashes);
+        match levels.last().and_then(|level| level.first()) {
+            Some(root) => hex::encode(root),
+            None => hex::encode(Sha256::digest(b"empty")),
         }
-        hasher.update(
-            serde_json::to_string(emotional_scores)
-                .expect("Failed to serialize emotional scores")
-                .as_bytes(),
-        );
-        hasher.update(
-            serde_json::to_string(biometric_hashes)
-                .expect("Failed to serialize biometric hashes")
-                .as_bytes(),
+    }
+
+    /// Prove that a validator's score is included under `merkle_root`
+    ///
+    /// Returns `None` if the validator is not part of the proof.
+    pub fn inclusion_proof(&self, validator_id: &str) -> Option<InclusionProof> {
+        let mut index = self.validators.iter().position(|id| id == validator_id)?;
+        let levels = Self::merkle_levels(
+            &self.validators,
+            &self.emotional_scores,
+            &self.biometric_hashes,
         );
-        hasher.update(temporal_window.to_le_bytes());
-        hasher.update(timestamp.to_le_bytes());
 
-        hex::encode(hasher.finalize())
+        let mut path = Vec::new();
+        for level in &levels[..levels.len() - 1] {
+            let sibling = index ^ 1;
+            if let Some(hash) = level.get(sibling) {
+                path.push(MerkleStep {
+                    hash: hex::encode(hash),
+                    is_left: sibling < index,
+                });
+            }
+            index /= 2;
+        }
+
+        let emotional_score = self.emotional_scores.get(validator_id).copied();
+        Some(InclusionProof {
+            validator_id: validator_id.to_string(),
+            emotional_score: emotional_score.unwrap_or(0),
+            biometric_hash: self
+                .biometric_hashes
+                .get(validator_id)
+                .cloned()
+                .unwrap_or_default(),
+            path,
+        })
     }
 
     /// Verify the emotional proof
-    pub fn verify(&self, public_key_hex: &str) -> Result<bool> {
-        let proof_data = format!(
-            "{}:{}:{}:{}:{}",
-            self.validators.join(","),
-            serde_json::to_string(&self.emotional_scores)
-                .map_err(|e| crate::error::ConsensusError::internal(format!("Serialization error: {}", e)))?,
-            serde_json::to_string(&self.biometric_hashes)
-                .map_err(|e| crate::error::ConsensusError::internal(format!("Serialization error: {}", e)))?,
-            self.temporal_window,
-            self.timestamp
-        );
+    ///
+    /// `temporal_window_ms` is the verifier's own window (normally
+    /// `ConsensusConfig::temporal_window_ms`). The window recorded in the proof is signed by
+    /// its creator, so it is only accepted when it is no wider than the verifier's, and the
+    /// proof's age is always measured against the verifier's window.
+    pub fn verify(&self, public_key_hex: &str, temporal_window_ms: u64) -> Result<bool> {
+        if self.temporal_window > temporal_window_ms {
+            return Ok(false);
+        }
+
+        let proof_data = self.proof_data()?;
 
         let signature_valid =
             KeyPair::verify(proof_data.as_bytes(), &self.signature, public_key_hex)?;
@@ -260,8 +651,6 @@ impl EmotionalProof {
             &self.validators,
             &self.emotional_scores,
             &self.biometric_hashes,
-            self.temporal_window,
-            self.timestamp,
         );
 
         if expected_merkle_root != self.merkle_root {
@@ -273,7 +662,7 @@ impl EmotionalProof {
             .map_err(|e| crate::error::ConsensusError::internal(format!("System time error: {}", e)))?
             .as_millis() as u64;
 
-        if now - self.timestamp > 300_000 {
+        if now.saturating_sub(self.timestamp) > temporal_window_ms {
             return Ok(false);
         }
 
@@ -286,6 +675,87 @@ impl EmotionalProof {
     }
 }
 
+/// Bounded cache of signature verification results
+///
+/// Committee members validate the same block independently, so without caching every
+/// transaction signature is recovered once per member. Entries are keyed by
+/// `(message digest, signature, public key)`, so a cached result can only be reused for
+/// exactly the same signed data. The engine clears the cache at each epoch boundary.
+pub struct SignatureCache {
+    entries: parking_lot::Mutex<lru::LruCache<[u8; 32], bool>>,
+    /// Number of ECDSA recoveries actually performed
+    verifications: AtomicU64,
+    /// Number of lookups answered from the cache
+    hits: AtomicU64,
+}
+
+impl SignatureCache {
+    /// Create a cache holding at most `capacity` results
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: parking_lot::Mutex::new(lru::LruCache::new(capacity)),
+            verifications: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Verify a signature, reusing a previous result for identical inputs
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &Signature,
+        public_key_hex: &str,
+    ) -> Result<bool> {
+        let key = Self::cache_key(message, signature, public_key_hex);
+
+        if let Some(valid) = self.entries.lock().get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(*valid);
+        }
+
+        self.verifications.fetch_add(1, Ordering::Relaxed);
+        let valid = KeyPair::verify(message, signature, public_key_hex)?;
+        self.entries.lock().put(key, valid);
+
+        Ok(valid)
+    }
+
+    /// Drop all cached results (called on epoch boundaries)
+    pub fn clear(&self) {
+        self.entries.lock().clear();
+    }
+
+    /// Number of cached results
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+
+    /// Number of signature recoveries performed (cache misses)
+    pub fn verification_count(&self) -> u64 {
+        self.verifications.load(Ordering::Relaxed)
+    }
+
+    /// Number of verifications answered from the cache
+    pub fn hit_count(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    fn cache_key(message: &[u8], signature: &Signature, public_key_hex: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(Sha256::digest(message));
+        hasher.update(signature.signature.as_bytes());
+        hasher.update([signature.recovery_id]);
+        hasher.update(public_key_hex.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
 /// Hash biometric data for privacy
 pub fn hash_biometric_data(data: &[u8]) -> String {
     hex::encode(Sha256::digest(data))
@@ -325,6 +795,74 @@ mod tests {
         assert!(!valid);
     }
 
+    #[test]
+    fn test_keystore_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("validator.key");
+
+        let keypair = KeyPair::generate().unwrap();
+        keypair.save_to_file(&path, "correct horse").unwrap();
+
+        let loaded = KeyPair::load_from_file(&path, "correct horse").unwrap();
+        assert_eq!(loaded.public_key_hex(), keypair.public_key_hex());
+        assert_eq!(loaded.secret_key_hex(), keypair.secret_key_hex());
+
+        let result = KeyPair::load_from_file(&path, "wrong passphrase");
+        assert!(result.is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert!(!path.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn test_keystore_kdf_cost_is_capped() {
+        let keypair = KeyPair::generate().unwrap();
+        let mut keystore = keypair.to_keystore("correct horse").unwrap();
+        keystore.kdf.log_n = KEYSTORE_SCRYPT_MAX_LOG_N + 1;
+
+        let Err(error) = KeyPair::from_keystore(&keystore, "correct horse") else {
+            panic!("keystore with an excessive scrypt cost was accepted");
+        };
+        assert!(error.to_string().contains("exceeds the maximum"));
+
+        let mut keystore = keypair.to_keystore("correct horse").unwrap();
+        keystore.kdf.r = 1 << 20;
+        let Err(error) = KeyPair::from_keystore(&keystore, "correct horse") else {
+            panic!("keystore with an excessive scrypt block size was accepted");
+        };
+        assert!(error.to_string().contains("Unsupported keystore scrypt"));
+    }
+
+    #[test]
+    fn test_signature_cache() {
+        let keypair = KeyPair::generate().unwrap();
+        let message = b"cached message";
+        let signature = keypair.sign(message).unwrap();
+        let cache = SignatureCache::new(16);
+
+        for _ in 0..5 {
+            assert!(cache
+                .verify(message, &signature, &keypair.public_key_hex())
+                .unwrap());
+        }
+        assert_eq!(cache.verification_count(), 1);
+        assert_eq!(cache.hit_count(), 4);
+
+        // Different message must not reuse the cached result
+        assert!(!cache
+            .verify(b"other message", &signature, &keypair.public_key_hex())
+            .unwrap_or(false));
+        assert_eq!(cache.verification_count(), 2);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
     #[test]
     fn test_emotional_proof_creation() {
         let keypair = KeyPair::generate().unwrap();
@@ -345,7 +883,43 @@ mod tests {
         )
         .unwrap();
 
-        assert!(proof.verify(&keypair.public_key_hex()).unwrap());
+        assert!(proof.verify(&keypair.public_key_hex(), 30000).unwrap());
+        assert!(proof
+            .verify(&keypair.public_key_hex(), DEFAULT_TEMPORAL_WINDOW_MS)
+            .unwrap());
+        // A proof claiming a wider window than the verifier allows is rejected
+        assert!(!proof.verify(&keypair.public_key_hex(), 10_000).unwrap());
+    }
+
+    #[test]
+    fn test_inclusion_proof_against_root() {
+        let keypair = KeyPair::generate().unwrap();
+        let validators: Vec<String> = (1..=5).map(|i| format!("validator{}", i)).collect();
+        let scores = validators
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.clone(), 80 + i as u8))
+            .collect();
+        let hashes = validators
+            .iter()
+            .map(|id| (id.clone(), format!("hash-{}", id)))
+            .collect();
+        let proof =
+            EmotionalProof::new(validators.clone(), scores, hashes, 30000, &keypair).unwrap();
+        assert!(proof.verify(&keypair.public_key_hex(), 30000).unwrap());
+
+        for id in &validators {
+            let inclusion = proof.inclusion_proof(id).unwrap();
+            assert!(inclusion.verify(&proof.merkle_root), "{}", id);
+        }
+
+        let inclusion = proof.inclusion_proof("validator3").unwrap();
+        assert_eq!(inclusion.emotional_score, 82);
+        let mut inflated = inclusion.clone();
+        inflated.emotional_score = 99;
+        assert!(!inflated.verify(&proof.merkle_root));
+        assert!(!inclusion.verify(&"0".repeat(64)));
+        assert!(proof.inclusion_proof("validator9").is_none());
     }
 
     #[test]
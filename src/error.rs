@@ -28,6 +28,10 @@ pub enum ConsensusError {
     #[error("Invalid block: {reason}")]
     InvalidBlock { reason: String },
 
+    /// Invalid transaction
+    #[error("Invalid transaction: {reason}")]
+    InvalidTransaction { reason: String },
+
     /// Invalid vote
     #[error("Invalid vote: {reason}")]
     InvalidVote { reason: String },
@@ -56,6 +60,38 @@ pub enum ConsensusError {
     #[error("Fork detected at height {height}")]
     ForkDetected { height: u64 },
 
+    /// Fork resolution would reorganize too many canonical blocks
+    #[error("Reorg depth {depth} exceeds maximum {max_depth}")]
+    ReorgTooDeep { depth: u64, max_depth: u64 },
+
+    /// Delegation would exceed a stake or delegator limit
+    #[error("Delegation limit exceeded: {reason}")]
+    DelegationLimitExceeded { reason: String },
+
+    /// Delegation is smaller than the configured minimum
+    #[error("Delegation of {amount} is below the minimum delegation of {minimum}")]
+    DelegationBelowMinimum { amount: u64, minimum: u64 },
+
+    /// Validator is not permitted to register
+    #[error("Validator {validator_id} is not on the allowlist")]
+    ValidatorNotPermitted { validator_id: String },
+
+    /// Transaction with the same hash is already waiting in the pool
+    #[error("Transaction {tx_hash} is already pending")]
+    TransactionAlreadyPending { tx_hash: String },
+
+    /// Transaction was already included in a recently finalized block
+    #[error("Transaction {tx_hash} is already finalized")]
+    TransactionAlreadyFinalized { tx_hash: String },
+
+    /// Replaying a recorded epoch produced a different outcome
+    #[error("Replay of epoch {epoch} diverged: {reason}")]
+    ReplayDiverged { epoch: u64, reason: String },
+
+    /// A shared lock could not be acquired in time
+    #[error("Timed out acquiring lock on {resource}")]
+    LockError { resource: String },
+
     /// Storage error
     #[error("Storage error: {message}")]
     StorageError { message: String },
@@ -107,6 +143,13 @@ impl ConsensusError {
         }
     }
 
+    /// Create an invalid transaction error
+    pub fn invalid_transaction(reason: impl Into<String>) -> Self {
+        Self::InvalidTransaction {
+            reason: reason.into(),
+        }
+    }
+
     /// Create an invalid vote error
     pub fn invalid_vote(reason: impl Into<String>) -> Self {
         Self::InvalidVote {
@@ -145,6 +188,59 @@ impl ConsensusError {
         Self::ForkDetected { height }
     }
 
+    /// Create a reorg too deep error
+    pub fn reorg_too_deep(depth: u64, max_depth: u64) -> Self {
+        Self::ReorgTooDeep { depth, max_depth }
+    }
+
+    /// Create a delegation limit exceeded error
+    pub fn delegation_limit_exceeded(reason: impl Into<String>) -> Self {
+        Self::DelegationLimitExceeded {
+            reason: reason.into(),
+        }
+    }
+
+    /// Create a delegation below minimum error
+    pub fn delegation_below_minimum(amount: u64, minimum: u64) -> Self {
+        Self::DelegationBelowMinimum { amount, minimum }
+    }
+
+    /// Create a validator not permitted error
+    pub fn validator_not_permitted(validator_id: impl Into<String>) -> Self {
+        Self::ValidatorNotPermitted {
+            validator_id: validator_id.into(),
+        }
+    }
+
+    /// Create a transaction already pending error
+    pub fn transaction_already_pending(tx_hash: impl Into<String>) -> Self {
+        Self::TransactionAlreadyPending {
+            tx_hash: tx_hash.into(),
+        }
+    }
+
+    /// Create a transaction already finalized error
+    pub fn transaction_already_finalized(tx_hash: impl Into<String>) -> Self {
+        Self::TransactionAlreadyFinalized {
+            tx_hash: tx_hash.into(),
+        }
+    }
+
+    /// Create a replay diverged error
+    pub fn replay_diverged(epoch: u64, reason: impl Into<String>) -> Self {
+        Self::ReplayDiverged {
+            epoch,
+            reason: reason.into(),
+        }
+    }
+
+    /// Create a lock error
+    pub fn lock_error(resource: impl Into<String>) -> Self {
+        Self::LockError {
+            resource: resource.into(),
+        }
+    }
+
     /// Create a storage error
     pub fn storage_error(message: impl Into<String>) -> Self {
         Self::StorageError {
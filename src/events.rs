@@ -0,0 +1,72 @@
+//! Consensus events published to subscribers
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+/// Capacity of the event broadcast channel; slow subscribers lose the oldest events
+pub const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Notable consensus state changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsensusEvent {
+    /// A block was recorded at a height that already holds a different block
+    ForkDetected {
+        /// Height of the competing blocks
+        height: u64,
+        /// Hashes of every block recorded at that height
+        competing_hashes: Vec<String>,
+    },
+    /// A validator was deactivated after its emotional trend kept declining
+    ValidatorDeactivated {
+        /// Deactivated validator
+        validator_id: String,
+        /// Consecutive declining assessments that triggered it
+        declining_epochs: u32,
+    },
+    /// A validator deactivated for emotional decline recovered and was reactivated
+    ValidatorReactivated {
+        /// Reactivated validator
+        validator_id: String,
+    },
+    /// An epoch failed because no validator was eligible to form a committee
+    NoEligibleValidators {
+        /// Failed epoch
+        epoch: u64,
+        /// Validators assessed, all of them ineligible
+        assessed: usize,
+    },
+    /// Two validators' recent emotional scores correlate suspiciously closely
+    CollusionSuspected {
+        /// Validator ID that sorts first
+        first: String,
+        /// Validator ID that sorts second
+        second: String,
+    },
+    /// Fork resolution switched the canonical chain to a competing block
+    Reorg {
+        /// Height at which the chains diverge
+        from_height: u64,
+        /// Canonical tip before the reorganization
+        old_tip: String,
+        /// Canonical tip after the reorganization
+        new_tip: String,
+        /// Number of canonical blocks replaced
+        depth: u64,
+    },
+    /// Fork resolution was refused because it would reorganize too deep
+    ReorgRejected {
+        /// Height at which the chains diverge
+        from_height: u64,
+        /// Winning block that was not adopted
+        rejected_hash: String,
+        /// Number of canonical blocks that would have been replaced
+        depth: u64,
+        /// Configured maximum reorganization depth
+        max_depth: u64,
+    },
+}
+
+/// Create a new event channel sender
+pub fn event_channel() -> broadcast::Sender<ConsensusEvent> {
+    broadcast::channel(EVENT_CHANNEL_CAPACITY).0
+}
@@ -1,14 +1,23 @@
 //! Fork detection and resolution for Proof of Emotion consensus
 
+use crate::audit::{AuditEntry, AuditHandle};
+use crate::clock::{Clock, SystemClock};
 use crate::error::{ConsensusError, Result};
+use crate::events::{event_channel, ConsensusEvent};
 use crate::types::Block;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tracing::{info, warn};
 
+/// Default limit on how many canonical blocks a fork resolution may replace
+pub const DEFAULT_MAX_REORG_DEPTH: u64 = 10;
+
+/// Default number of blocks for which fork records are kept
+pub const DEFAULT_FORK_HISTORY_RETENTION: u64 = 10_000;
+
 /// Information about a detected fork
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForkInfo {
@@ -34,6 +43,14 @@ pub struct ForkDetector {
     forks: Arc<RwLock<Vec<ForkInfo>>>,
     /// Block metadata for fork resolution
     block_metadata: DashMap<String, BlockMetadata>,
+    /// Maximum number of canonical blocks a fork resolution may replace
+    max_reorg_depth: u64,
+    /// Number of blocks for which fork records survive cleanup
+    fork_history_retention: u64,
+    /// Publisher for reorg events
+    events: broadcast::Sender<ConsensusEvent>,
+    /// Audit trail for fork resolutions
+    audit: AuditHandle,
 }
 
 /// Metadata about a block for fork resolution
@@ -53,9 +70,50 @@ impl ForkDetector {
             canonical_chain: Arc::new(RwLock::new(Vec::new())),
             forks: Arc::new(RwLock::new(Vec::new())),
             block_metadata: DashMap::new(),
+            max_reorg_depth: DEFAULT_MAX_REORG_DEPTH,
+            fork_history_retention: DEFAULT_FORK_HISTORY_RETENTION,
+            events: event_channel(),
+            audit: AuditHandle::default(),
         }
     }
 
+    /// Record fork resolutions through a shared audit handle
+    pub(crate) fn with_audit(mut self, audit: AuditHandle) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    /// Set the maximum reorganization depth
+    pub fn with_max_reorg_depth(mut self, max_reorg_depth: u64) -> Self {
+        self.max_reorg_depth = max_reorg_depth;
+        self
+    }
+
+    /// Set how many blocks of fork records to keep, independently of block metadata
+    pub fn with_fork_history_retention(mut self, fork_history_retention: u64) -> Self {
+        self.fork_history_retention = fork_history_retention;
+        self
+    }
+
+    /// Publish events on an existing channel instead of a private one
+    pub fn with_event_sender(mut self, events: broadcast::Sender<ConsensusEvent>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Subscribe to reorg events
+    pub fn subscribe(&self) -> broadcast::Receiver<ConsensusEvent> {
+        self.events.subscribe()
+    }
+
+    /// Height of the canonical tip, if any
+    fn canonical_tip_height(&self, canonical: &[String]) -> Option<u64> {
+        canonical
+            .last()
+            .and_then(|hash| self.block_metadata.get(hash))
+            .map(|meta| meta.height)
+    }
+
     /// Record a block and detect if it creates a fork
     pub async fn record_block(&self, block: &Block) -> Result<()> {
         let height = block.header.height;
@@ -67,7 +125,12 @@ impl ForkDetector {
             BlockMetadata {
                 height,
                 emotional_score: block.header.emotional_score,
-                consensus_strength: block.header.consensus_strength,
+                consensus_strength: block
+                    .consensus_metadata
+                    .as_ref()
+                    .map_or(block.header.consensus_strength, |metadata| {
+                        metadata.consensus_strength
+                    }),
                 timestamp: block.header.timestamp,
             },
         );
@@ -99,14 +162,15 @@ impl ForkDetector {
                     hashes.push(hash.clone());
                     hashes
                 },
-                detected_at: std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .expect("System time before UNIX_EPOCH")
-                    .as_millis() as u64,
+                detected_at: SystemClock.now_millis(),
                 resolution_method: None,
                 winning_hash: None,
             };
 
+            let _ = self.events.send(ConsensusEvent::ForkDetected {
+                height,
+                competing_hashes: fork_info.competing_hashes.clone(),
+            });
             self.forks.write().await.push(fork_info);
 
             return Err(ConsensusError::fork_detected(height));
@@ -117,7 +181,11 @@ impl ForkDetector {
 
         // Update canonical chain if this extends it
         let mut canonical = self.canonical_chain.write().await;
-        if canonical.is_empty() || height == canonical.len() as u64 {
+        let extends_tip = match self.canonical_tip_height(&canonical) {
+            Some(tip_height) => height == tip_height + 1,
+            None => true,
+        };
+        if extends_tip {
             canonical.push(hash);
         }
 
@@ -184,10 +252,12 @@ impl ForkDetector {
 
         let winning_hash = candidates[0].0.clone();
 
+        self.switch_canonical_chain(height, &winning_hash).await?;
+
         info!(
             "Fork resolved at height {}: winner = {}... (emotional_score={}, consensus_strength={})",
             height,
-            &winning_hash[..8],
+            &winning_hash[..8.min(winning_hash.len())],
             candidates[0].1.emotional_score,
             candidates[0].1.consensus_strength
         );
@@ -198,15 +268,129 @@ impl ForkDetector {
             fork.resolution_method = Some("Emotional Score Rule".to_string());
             fork.winning_hash = Some(winning_hash.clone());
         }
+        drop(forks);
+
+        self.audit.record(AuditEntry::ForkResolved {
+            height,
+            winning_hash: winning_hash.clone(),
+            timestamp: SystemClock.now_millis(),
+        });
 
         Ok(winning_hash)
     }
 
+    /// Resolve a fork at `height` in favour of the block finalized there
+    ///
+    /// A finalized block is irreversible, so it wins regardless of the fork choice
+    /// rule and the canonical chain is left as it is.
+    pub async fn resolve_fork_for_finalized(
+        &self,
+        height: u64,
+        finalized_hash: &str,
+    ) -> Result<()> {
+        let known = self
+            .blocks_at_height
+            .get(&height)
+            .is_some_and(|blocks| blocks.contains(finalized_hash));
+        if !known {
+            return Err(ConsensusError::internal(format!(
+                "Block {} is not recorded at height {}",
+                finalized_hash, height
+            )));
+        }
+
+        let mut forks = self.forks.write().await;
+        if let Some(fork) = forks
+            .iter_mut()
+            .find(|f| f.height == height && f.winning_hash.is_none())
+        {
+            fork.resolution_method = Some("Finality".to_string());
+            fork.winning_hash = Some(finalized_hash.to_string());
+        }
+        drop(forks);
+
+        self.audit.record(AuditEntry::ForkResolved {
+            height,
+            winning_hash: finalized_hash.to_string(),
+            timestamp: SystemClock.now_millis(),
+        });
+
+        Ok(())
+    }
+
+    /// Make `winning_hash` canonical at `height`, enforcing the reorg depth limit
+    ///
+    /// Canonical blocks from `height` up to the tip are replaced by the winner. If
+    /// that would replace more than `max_reorg_depth` blocks the canonical chain is
+    /// left untouched and a [`ConsensusEvent::ReorgRejected`] is emitted.
+    async fn switch_canonical_chain(&self, height: u64, winning_hash: &str) -> Result<()> {
+        let mut canonical = self.canonical_chain.write().await;
+
+        let Some(tip_height) = self.canonical_tip_height(&canonical) else {
+            return Ok(());
+        };
+        if height > tip_height {
+            return Ok(());
+        }
+
+        let offset = (tip_height - height) as usize;
+        if offset >= canonical.len() {
+            return Ok(());
+        }
+        let index = canonical.len() - 1 - offset;
+        if canonical[index] == winning_hash {
+            return Ok(());
+        }
+
+        let depth = offset as u64 + 1;
+        if depth > self.max_reorg_depth {
+            warn!(
+                "Rejected reorg at height {}: depth {} exceeds maximum {}",
+                height, depth, self.max_reorg_depth
+            );
+            let _ = self.events.send(ConsensusEvent::ReorgRejected {
+                from_height: height,
+                rejected_hash: winning_hash.to_string(),
+                depth,
+                max_depth: self.max_reorg_depth,
+            });
+            return Err(ConsensusError::reorg_too_deep(depth, self.max_reorg_depth));
+        }
+
+        let old_tip = canonical.last().cloned().unwrap_or_default();
+        canonical.truncate(index);
+        canonical.push(winning_hash.to_string());
+
+        warn!(
+            "Reorg at height {}: replaced {} canonical blocks",
+            height, depth
+        );
+        let _ = self.events.send(ConsensusEvent::Reorg {
+            from_height: height,
+            old_tip,
+            new_tip: winning_hash.to_string(),
+            depth,
+        });
+
+        Ok(())
+    }
+
     /// Get all detected forks
     pub async fn get_forks(&self) -> Vec<ForkInfo> {
         self.forks.read().await.clone()
     }
 
+    /// Get forks detected at heights in `from..=to`
+    pub async fn get_forks_in_range(&self, from: u64, to: u64) -> Vec<ForkInfo> {
+        self.forks
+            .read()
+            .await
+            .iter()
+            .filter(|fork| (from..=to).contains(&fork.height))
+            .cloned()
+            .collect()
+    }
+
     /// Get the canonical chain
     pub async fn get_canonical_chain(&self) -> Vec<String> {
         self.canonical_chain.read().await.clone()
@@ -222,32 +406,35 @@ impl ForkDetector {
 
     /// Clear old fork data (for memory management)
     ///
-    /// Removes fork data for heights more than `keep_height` blocks old
+    /// Removes block data for heights more than `keep_height` blocks old. Fork records
+    /// are kept for post-mortem analysis until they are older than the fork history
+    /// retention (see [`with_fork_history_retention`](Self::with_fork_history_retention)).
     pub async fn cleanup_old_forks(&self, current_height: u64, keep_height: u64) {
-        if current_height <= keep_height {
-            return;
-        }
+        if current_height > keep_height {
+            let cutoff = current_height - keep_height;
 
-        let cutoff = current_height - keep_height;
+            // Remove old blocks
+            self.blocks_at_height.retain(|height, _| *height > cutoff);
 
-        // Remove old blocks
-        self.blocks_at_height.retain(|height, _| *height > cutoff);
+            // Remove old metadata
+            self.block_metadata.retain(|_, meta| meta.height > cutoff);
+        }
 
-        // Remove old metadata
-        self.block_metadata.retain(|_, meta| meta.height > cutoff);
+        if current_height <= self.fork_history_retention {
+            return;
+        }
 
-        // Remove old fork records (keep for historical analysis)
-        // We keep forks for debugging, but could optionally clean them
-        let old_fork_count = self.forks.read().await.len();
+        let fork_cutoff = current_height - self.fork_history_retention;
         let mut forks = self.forks.write().await;
-        forks.retain(|fork| fork.height > cutoff);
+        let old_fork_count = forks.len();
+        forks.retain(|fork| fork.height > fork_cutoff);
         let new_fork_count = forks.len();
 
         if old_fork_count > new_fork_count {
             info!(
                 "Cleaned up {} old fork records (height <= {})",
                 old_fork_count - new_fork_count,
-                cutoff
+                fork_cutoff
             );
         }
     }
@@ -299,12 +486,15 @@ mod tests {
                 epoch: 0,
                 previous_hash: "0".repeat(64),
                 merkle_root: "merkle".to_string(),
+                state_root: String::new(),
                 timestamp: 1000000,
                 difficulty: 0,
                 nonce: 0,
                 validator_id: validator.to_string(),
                 emotional_score,
+                committee_emotional_fitness: 0,
                 consensus_strength: 80,
+                chain_id: String::new(),
             },
             hash: hash.to_string(),
             transactions: vec![],
@@ -359,6 +549,34 @@ mod tests {
         assert_eq!(winner, "hash2");
     }
 
+    #[tokio::test]
+    async fn test_fork_resolution_by_consensus_strength() {
+        let detector = ForkDetector::new();
+
+        // Same proposer score; only the finalized consensus strength differs
+        let mut weak = create_test_block(1, "hash1", 85, "validator1");
+        let mut strong = create_test_block(1, "hash2", 85, "validator2");
+        for (block, strength) in [(&mut weak, 70), (&mut strong, 95)] {
+            block.header.consensus_strength = 0;
+            block.consensus_metadata = Some(crate::types::ConsensusMetadata {
+                participant_count: 3,
+                consensus_strength: strength,
+                emotional_fitness: 85,
+                mean_emotional_fitness: 85,
+                byzantine_failures: 0,
+                finalized_at: 1000000,
+                participants: vec![],
+                committee_proof: None,
+                attestation_signature: String::new(),
+            });
+        }
+
+        detector.record_block(&weak).await.unwrap();
+        let _ = detector.record_block(&strong).await; // Fork!
+
+        assert_eq!(detector.resolve_fork(1).await.unwrap(), "hash2");
+    }
+
     #[tokio::test]
     async fn test_fork_statistics() {
         let detector = ForkDetector::new();
@@ -399,4 +617,111 @@ mod tests {
         assert!(!detector.blocks_at_height.contains_key(&2));
         assert!(detector.blocks_at_height.contains_key(&3));
     }
+
+    #[tokio::test]
+    async fn test_fork_history_outlives_block_metadata() {
+        let detector = ForkDetector::new().with_fork_history_retention(100);
+
+        for height in 1..=3 {
+            let block_a = create_test_block(height, &format!("hash{}a", height), 85, "validator1");
+            let block_b = create_test_block(height, &format!("hash{}b", height), 80, "validator2");
+            detector.record_block(&block_a).await.unwrap();
+            assert!(detector.record_block(&block_b).await.is_err());
+        }
+        assert_eq!(detector.get_forks().await.len(), 3);
+
+        detector.cleanup_old_forks(10, 8).await;
+
+        // Block metadata at heights 1 and 2 is pruned
+        assert!(!detector.blocks_at_height.contains_key(&1));
+        assert!(!detector.blocks_at_height.contains_key(&2));
+        assert!(!detector.block_metadata.contains_key("hash1a"));
+        assert!(detector.block_metadata.contains_key("hash3a"));
+
+        // Fork records at the same heights are retained
+        let forks = detector.get_forks_in_range(1, 2).await;
+        assert_eq!(forks.len(), 2);
+        assert!(forks.iter().all(|fork| fork.height <= 2));
+
+        // Fork records are pruned once they leave the history retention
+        detector.cleanup_old_forks(102, 8).await;
+        assert!(detector.get_forks_in_range(1, 2).await.is_empty());
+        assert_eq!(detector.get_forks().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shallow_reorg_allowed() {
+        let detector = ForkDetector::new().with_max_reorg_depth(2);
+        let mut events = detector.subscribe();
+
+        for height in 1..=3 {
+            let block = create_test_block(height, &format!("hash{}", height), 85, "validator1");
+            detector.record_block(&block).await.unwrap();
+        }
+
+        // Competing block at the tip with a higher emotional score
+        let competitor = create_test_block(3, "hash3b", 95, "validator2");
+        let _ = detector.record_block(&competitor).await;
+
+        let winner = detector.resolve_fork(3).await.unwrap();
+        assert_eq!(winner, "hash3b");
+        assert_eq!(
+            detector.get_canonical_chain().await,
+            vec!["hash1", "hash2", "hash3b"]
+        );
+
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            ConsensusEvent::ForkDetected { height: 3, .. }
+        ));
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ConsensusEvent::Reorg {
+                from_height: 3,
+                old_tip: "hash3".to_string(),
+                new_tip: "hash3b".to_string(),
+                depth: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deep_reorg_rejected() {
+        let detector = ForkDetector::new().with_max_reorg_depth(2);
+        let mut events = detector.subscribe();
+
+        for height in 1..=5 {
+            let block = create_test_block(height, &format!("hash{}", height), 85, "validator1");
+            detector.record_block(&block).await.unwrap();
+        }
+
+        // Competing block four blocks below the tip
+        let competitor = create_test_block(2, "hash2b", 95, "validator2");
+        let _ = detector.record_block(&competitor).await;
+
+        let result = detector.resolve_fork(2).await;
+        assert!(matches!(
+            result,
+            Err(ConsensusError::ReorgTooDeep {
+                depth: 4,
+                max_depth: 2
+            })
+        ));
+        assert_eq!(detector.get_canonical_chain().await.len(), 5);
+        assert_eq!(detector.get_fork_statistics().await.unresolved_forks, 1);
+
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            ConsensusEvent::ForkDetected { height: 2, .. }
+        ));
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ConsensusEvent::ReorgRejected {
+                from_height: 2,
+                rejected_hash: "hash2b".to_string(),
+                depth: 4,
+                max_depth: 2,
+            }
+        );
+    }
 }
@@ -1,8 +1,12 @@
 //! Health check system for consensus monitoring
 
+use crate::biometric::EligibilityStatus;
+use crate::clock::{Clock, SystemClock};
 use crate::consensus::ProofOfEmotionEngine;
 use serde::{Deserialize, Serialize};
-use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of recent epochs in which a validation divergence is reported as an issue
+pub const VALIDATION_DIVERGENCE_WINDOW_EPOCHS: usize = 10;
 
 /// Overall health status of the consensus engine
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -31,6 +35,9 @@ pub struct HealthStatus {
     pub issues: Vec<HealthIssue>,
     /// Timestamp when health check was performed
     pub checked_at: u64,
+    /// Report the degraded state as unavailable to HTTP probes
+    #[serde(default)]
+    pub degraded_unavailable: bool,
 }
 
 /// Health state categories
@@ -61,6 +68,11 @@ pub enum HealthIssue {
     NetworkUnresponsive,
     /// High rate of Byzantine failures
     HighByzantineRate(f64),
+    /// Committee members validated identical blocks differently this many times in the
+    /// last [`VALIDATION_DIVERGENCE_WINDOW_EPOCHS`] epochs
+    ValidationDivergence(u64),
+    /// None of this many assessed validators was eligible at the latest assessment
+    NoEligibleValidators(usize),
 }
 
 impl HealthStatus {
@@ -71,6 +83,7 @@ impl HealthStatus {
     ) -> HealthStatus {
         let state = engine.get_state().await;
         let metrics = engine.get_metrics().await;
+        let config = engine.get_config().await;
 
         let mut issues = Vec::new();
 
@@ -111,6 +124,24 @@ impl HealthStatus {
             }
         }
 
+        // Committee members should never disagree on identical inputs
+        let divergences = engine
+            .recent_validation_divergences(VALIDATION_DIVERGENCE_WINDOW_EPOCHS)
+            .await;
+        if divergences > 0 {
+            issues.push(HealthIssue::ValidationDivergence(divergences));
+        }
+
+        // Without an eligible validator no committee can form
+        let assessment = engine.get_last_assessment().await;
+        if !assessment.is_empty()
+            && assessment
+                .iter()
+                .all(|(_, status)| *status != EligibilityStatus::Eligible)
+        {
+            issues.push(HealthIssue::NoEligibleValidators(assessment.len()));
+        }
+
         // Determine overall health state
         let health_state = if issues.is_empty() {
             HealthState::Healthy
@@ -124,10 +155,7 @@ impl HealthStatus {
             HealthState::Critical
         };
 
-        let current_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_secs();
+        let current_time = SystemClock.now_millis() / 1000;
 
         HealthStatus {
             status: health_state,
@@ -142,6 +170,7 @@ impl HealthStatus {
             participation_rate: state.participation_rate,
             issues,
             checked_at: current_time,
+            degraded_unavailable: config.degraded_unavailable,
         }
     }
 
@@ -160,6 +189,35 @@ impl HealthStatus {
         matches!(self.status, HealthState::Critical)
     }
 
+    /// HTTP status code for load balancers: 200 when serving, 503 when not
+    ///
+    /// A degraded node answers 200 unless `degraded_unavailable` is set.
+    pub fn http_status_code(&self) -> u16 {
+        match self.status {
+            HealthState::Healthy => 200,
+            HealthState::Degraded if self.degraded_unavailable => 503,
+            HealthState::Degraded => 200,
+            HealthState::Critical => 503,
+        }
+    }
+
+    /// Compact JSON body for Kubernetes probes
+    pub fn as_probe_json(&self) -> String {
+        let status = match self.status {
+            HealthState::Healthy => "healthy",
+            HealthState::Degraded => "degraded",
+            HealthState::Critical => "critical",
+        };
+
+        serde_json::json!({
+            "status": status,
+            "code": self.http_status_code(),
+            "epoch": self.current_epoch,
+            "issues": self.issues.len(),
+        })
+        .to_string()
+    }
+
     /// Get a human-readable status message
     pub fn status_message(&self) -> String {
         match self.status {
@@ -201,6 +259,12 @@ impl HealthStatus {
                 HealthIssue::HighByzantineRate(rate) => {
                     format!("High Byzantine rate ({:.1}%)", rate * 100.0)
                 }
+                HealthIssue::ValidationDivergence(count) => {
+                    format!("Validation divergence ({})", count)
+                }
+                HealthIssue::NoEligibleValidators(count) => {
+                    format!("No eligible validators ({} assessed)", count)
+                }
             })
             .collect::<Vec<_>>()
             .join(", ")
@@ -218,10 +282,7 @@ impl LivenessCheck {
     pub fn new() -> Self {
         Self {
             alive: true,
-            timestamp: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("Time went backwards")
-                .as_secs(),
+            timestamp: SystemClock.now_millis() / 1000,
         }
     }
 }
@@ -256,6 +317,15 @@ impl ReadinessCheck {
             timestamp: health.checked_at,
         }
     }
+
+    /// HTTP status code for readiness probes: 200 when ready, 503 otherwise
+    pub fn http_status_code(&self) -> u16 {
+        if self.ready {
+            200
+        } else {
+            503
+        }
+    }
 }
 
 #[cfg(test)]
@@ -277,6 +347,7 @@ mod tests {
             participation_rate: 90,
             issues: vec![],
             checked_at: 1234567890,
+            degraded_unavailable: false,
         };
 
         assert!(health.is_healthy());
@@ -303,6 +374,7 @@ mod tests {
                 HealthIssue::LowParticipation(45),
             ],
             checked_at: 1234567890,
+            degraded_unavailable: false,
         };
 
         assert!(!health.is_healthy());
@@ -332,6 +404,7 @@ mod tests {
                 HealthIssue::TransactionBacklog(2000),
             ],
             checked_at: 1234567890,
+            degraded_unavailable: false,
         };
 
         assert!(!health.is_healthy());
@@ -362,6 +435,7 @@ mod tests {
             participation_rate: 90,
             issues: vec![],
             checked_at: 1234567890,
+            degraded_unavailable: false,
         };
 
         let readiness = ReadinessCheck::from_health(&health);
@@ -384,6 +458,7 @@ mod tests {
             participation_rate: 20,
             issues: vec![HealthIssue::LowConsensus(30)],
             checked_at: 1234567890,
+            degraded_unavailable: false,
         };
 
         let readiness = ReadinessCheck::from_health(&health);
@@ -415,6 +490,7 @@ mod tests {
             participation_rate: 40,
             issues,
             checked_at: 1234567890,
+            degraded_unavailable: false,
         };
 
         let summary = health.issues_summary();
@@ -425,4 +501,63 @@ mod tests {
         assert!(summary.contains("Transaction backlog"));
         assert!(summary.contains("High Byzantine rate"));
     }
+
+    #[test]
+    fn test_http_status_codes() {
+        let mut health = HealthStatus {
+            status: HealthState::Healthy,
+            version: "1.0.0".to_string(),
+            uptime_seconds: 3600,
+            current_epoch: 100,
+            consensus_strength: 60,
+            validator_count: 10,
+            active_validators: 9,
+            last_finalized_block: 100,
+            pending_transactions: 5,
+            participation_rate: 90,
+            issues: vec![],
+            checked_at: 1234567890,
+            degraded_unavailable: false,
+        };
+        assert_eq!(health.http_status_code(), 200);
+        assert_eq!(ReadinessCheck::from_health(&health).http_status_code(), 200);
+
+        health.status = HealthState::Degraded;
+        health.issues = vec![HealthIssue::LowConsensus(60)];
+        assert_eq!(health.http_status_code(), 200);
+        assert_eq!(ReadinessCheck::from_health(&health).http_status_code(), 200);
+
+        health.degraded_unavailable = true;
+        assert_eq!(health.http_status_code(), 503);
+
+        health.status = HealthState::Critical;
+        health.degraded_unavailable = false;
+        assert_eq!(health.http_status_code(), 503);
+        assert_eq!(ReadinessCheck::from_health(&health).http_status_code(), 503);
+    }
+
+    #[test]
+    fn test_probe_json() {
+        let health = HealthStatus {
+            status: HealthState::Degraded,
+            version: "1.0.0".to_string(),
+            uptime_seconds: 3600,
+            current_epoch: 100,
+            consensus_strength: 60,
+            validator_count: 10,
+            active_validators: 9,
+            last_finalized_block: 100,
+            pending_transactions: 5,
+            participation_rate: 90,
+            issues: vec![HealthIssue::LowConsensus(60)],
+            checked_at: 1234567890,
+            degraded_unavailable: true,
+        };
+
+        let probe: serde_json::Value = serde_json::from_str(&health.as_probe_json()).unwrap();
+        assert_eq!(probe["status"], "degraded");
+        assert_eq!(probe["code"], 503);
+        assert_eq!(probe["epoch"], 100);
+        assert_eq!(probe["issues"], 1);
+    }
 }
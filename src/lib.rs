@@ -1,31 +1,85 @@
+pub mod attestation;
+pub mod audit;
+pub mod beacon;
 pub mod biometric;
+pub mod bloom;
+pub mod bundle;
 pub mod byzantine;
+pub mod chain;
 pub mod checkpoint;
+pub mod clock;
+pub mod collusion;
+pub mod committee;
 pub mod consensus;
 pub mod crypto;
 pub mod error;
+pub mod events;
 pub mod fork;
 pub mod health;
 pub mod metrics;
+pub mod replay;
+pub mod scoreboard;
+pub mod snapshot;
 pub mod staking;
+pub mod state;
 pub mod types;
 pub mod utils;
+pub mod validator_set;
+pub mod wire;
 pub mod zkp;
 
-pub use biometric::{BiometricDevice, BiometricReading, EmotionalProfile, EmotionalValidator};
-pub use byzantine::ByzantineDetector;
-pub use checkpoint::{Checkpoint, CheckpointManager, CheckpointStatistics, ValidatorSignature};
+pub use attestation::{verify_attestation, EmotionalAttestation};
+pub use audit::{AuditEntry, AuditSink, VecAuditSink};
+pub use beacon::{HashChainBeacon, RandomnessBeacon};
+pub use biometric::{
+    BiometricConfig, BiometricDevice, BiometricReading, CalibrationProfile, EligibilityStatus,
+    EmotionalProfile, EmotionalValidator, IneligibilityReason, LinearCalibration,
+    ValidatorEmotionalState,
+};
+pub use bloom::{BloomFilter, RecentTransactionFilter};
+pub use bundle::{BundledRound, EpochBundle};
+pub use byzantine::{ByzantineDetector, DoubleSigningEvidence, SlashingPolicy, VoteRecord};
+pub use chain::verify_chain_segment;
+pub use checkpoint::{
+    AggregateSignature, AggregateSigner, Checkpoint, CheckpointManager, CheckpointStatistics,
+    ValidatorSignature,
+};
+pub use clock::{Clock, MockClock, MonotonicClock, SystemClock};
+pub use collusion::{CollusionDetection, CollusionSuspect};
+pub use committee::{
+    verify_committee_proof, CommitteeCandidate, CommitteeProof, ProposerCooldown,
+    ProposerEligibility, ProposerPolicy, VerificationContext,
+};
 pub use consensus::{
-    ConsensusConfig, ConsensusMetrics, ConsensusRound, ConsensusState, ProofOfEmotionEngine,
-    RoundPhase,
+    AdaptiveEpochDuration, ConfigPatch, ConsensusConfig, ConsensusConfigBuilder, ConsensusMetrics,
+    ConsensusRound, ConsensusState, EligibilityPolicy, NetworkHealthWeights, Permissioning,
+    ProofOfEmotionEngine, RoundPhase, SelectionMode, ThresholdSchedule, ThresholdTier,
+};
+pub use crypto::{
+    EmotionalProof, InclusionProof, KdfParams, KeyPair, Keystore, MerkleStep, Signature,
+    SignatureCache,
 };
-pub use crypto::{EmotionalProof, KeyPair, Signature};
 pub use error::{ConsensusError, Result};
+pub use events::ConsensusEvent;
 pub use fork::{ForkDetector, ForkInfo, ForkStatistics};
 pub use health::{HealthIssue, HealthState, HealthStatus, LivenessCheck, ReadinessCheck};
 pub use metrics::{create_default_registry, PrometheusMetrics};
-pub use staking::{EmotionalStaking, RewardDistribution, SlashingEvent, Validator};
-pub use types::{Block, BlockHeader, Transaction, Vote, VotingResult};
+pub use replay::{EpochRecord, EpochRecorder, RecordedValidator};
+pub use scoreboard::ValidatorScore;
+pub use snapshot::{FileSnapshotStore, MemorySnapshotStore, SnapshotStore, StateSnapshot};
+pub use staking::{
+    EmotionalStaking, ReputationPenalties, RewardDistribution, RewardModel, SlashingEvent,
+    UnbondingEntry, Validator, MAX_COMMISSION,
+};
+pub use state::{BalanceStateMachine, StateMachine};
+pub use types::{
+    Block, BlockHeader, LightBlock, SerializedBlock, Transaction, TransactionPayload, TxDiff,
+    TxStatus, Vote, VoteRejectionReason, VotingResult, BLOCK_FORMAT_VERSION,
+};
+pub use utils::StakeWeightFn;
+pub use validator_set::{ValidatorRecord, ValidatorSet};
+pub use wire::{decode_block, decode_vote, encode_block, encode_vote};
+pub use zkp::{BiometricProof, ZkBiometricProvider};
 
 pub const TICKER: &str = "POE";
 pub const MIN_VALIDATOR_STAKE: u64 = 10_000;
@@ -33,6 +87,18 @@ pub const MAX_COMMITTEE_SIZE: usize = 101;
 pub const DEFAULT_EMOTIONAL_THRESHOLD: u8 = 75;
 pub const DEFAULT_BYZANTINE_THRESHOLD: u8 = 67;
 pub const DEFAULT_EPOCH_DURATION: u64 = 30_000;
+/// Chain ID blocks are proposed and verified under unless configured otherwise
+pub const DEFAULT_CHAIN_ID: &str = "poe-mainnet";
+/// Maximum age of a pending transaction in milliseconds (5 minutes)
+pub const TRANSACTION_TTL_MS: u64 = 5 * 60 * 1000;
+/// Maximum number of transactions in a block
+pub const MAX_BLOCK_TRANSACTIONS: usize = 1000;
+/// Weight every transaction carries regardless of its size
+pub const TX_BASE_WEIGHT: u64 = 1_000;
+/// Weight added per byte of transaction data
+pub const TX_WEIGHT_PER_DATA_BYTE: u64 = 10;
+/// Default limit on the total transaction weight of a block
+pub const DEFAULT_MAX_BLOCK_WEIGHT: u64 = 2_000_000;
 pub const PROTOCOL_VERSION: &str = "1.0.0";
 /// Unbonding period in epochs (~21 days at 15min epochs)
 pub const UNBONDING_PERIOD_EPOCHS: u64 = 2016;
@@ -0,0 +1,121 @@
+//! Recording of epoch inputs for deterministic replay when debugging incidents
+
+use crate::consensus::SelectionMode;
+use crate::error::{ConsensusError, Result};
+use crate::types::VotingResult;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Default number of epoch records kept by an [`EpochRecorder`]
+pub const DEFAULT_EPOCH_RECORD_CAPACITY: usize = 100;
+
+/// An eligible validator as seen at committee selection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedValidator {
+    /// Validator ID
+    pub validator_id: String,
+    /// Stake at the time of the epoch
+    pub stake: u64,
+    /// Emotional score at the time of the epoch
+    pub emotional_score: u8,
+    /// Committee selection weight
+    pub weight: u64,
+}
+
+/// Inputs and outcome of one epoch's committee selection and voting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpochRecord {
+    /// Epoch number
+    pub epoch: u64,
+    /// Chain tip the committee was selected on
+    pub previous_hash: String,
+    /// Beacon value (hex) the selection was seeded with; empty if seeded from
+    /// `previous_hash`
+    #[serde(default)]
+    pub randomness: String,
+    /// Selection strategy in effect
+    pub selection_mode: SelectionMode,
+    /// Configured maximum committee size
+    pub committee_size: usize,
+    /// Byzantine threshold in effect (percentage)
+    pub byzantine_threshold: u8,
+    /// Whether votes were weighted by stake
+    pub stake_weighted_voting: bool,
+    /// Validators that passed the emotional assessment
+    pub eligible: Vec<RecordedValidator>,
+    /// Members carried over from the previous committee
+    #[serde(default)]
+    pub retained: Vec<String>,
+    /// Selected committee, primary first
+    pub committee: Vec<String>,
+    /// Hash of the proposed block
+    pub block_hash: String,
+    /// Outcome of the voting round, including the counted votes
+    pub voting_result: VotingResult,
+}
+
+impl EpochRecord {
+    /// Serialize the record to JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| ConsensusError::internal(format!("Serialization error: {}", e)))
+    }
+
+    /// Parse a record from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| ConsensusError::internal(format!("Invalid epoch record: {}", e)))
+    }
+}
+
+/// Bounded in-memory log of epoch records, oldest first
+#[derive(Debug)]
+pub struct EpochRecorder {
+    records: RwLock<VecDeque<EpochRecord>>,
+    capacity: usize,
+}
+
+impl Default for EpochRecorder {
+    fn default() -> Self {
+        Self::new(DEFAULT_EPOCH_RECORD_CAPACITY)
+    }
+}
+
+impl EpochRecorder {
+    /// Create a recorder keeping at most `capacity` records
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: RwLock::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    /// Store a record, dropping the oldest beyond the capacity
+    pub fn record(&self, record: EpochRecord) {
+        let mut records = self.records.write();
+        records.push_back(record);
+        while records.len() > self.capacity {
+            records.pop_front();
+        }
+    }
+
+    /// All kept records, oldest first
+    pub fn records(&self) -> Vec<EpochRecord> {
+        self.records.read().iter().cloned().collect()
+    }
+
+    /// Record of a specific epoch, if still kept
+    pub fn get(&self, epoch: u64) -> Option<EpochRecord> {
+        self.records
+            .read()
+            .iter()
+            .find(|record| record.epoch == epoch)
+            .cloned()
+    }
+
+    /// Most recent record
+    pub fn latest(&self) -> Option<EpochRecord> {
+        self.records.read().back().cloned()
+    }
+}
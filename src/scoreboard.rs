@@ -0,0 +1,82 @@
+//! Per-validator performance summary for operators
+
+use serde::{Deserialize, Serialize};
+
+/// Activity the engine counts for each validator as epochs run
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ValidatorActivity {
+    /// Finalized blocks the validator proposed
+    pub blocks_proposed: u64,
+    /// Votes the validator cast that were counted
+    pub votes_cast: u64,
+    /// Counted votes that approved the block
+    pub approvals: u64,
+    /// Slashes applied to the validator
+    pub slashes: u64,
+    /// Assessments the validator went through
+    pub assessments: u64,
+    /// Assessments that found the validator eligible
+    pub eligible_assessments: u64,
+}
+
+/// One row of the validator scoreboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorScore {
+    /// Validator ID
+    pub validator_id: String,
+    /// Finalized blocks proposed
+    pub blocks_proposed: u64,
+    /// Votes cast and counted
+    pub votes_cast: u64,
+    /// Percentage of counted votes that approved
+    pub approval_rate: u8,
+    /// Slashes applied by the engine
+    pub slashing_count: u64,
+    /// Byzantine offenses recorded by the detector, including warnings
+    pub byzantine_events: usize,
+    /// Current reputation
+    pub reputation: u8,
+    /// Current emotional score
+    pub emotional_score: u8,
+    /// Percentage of assessments the validator was eligible in
+    pub uptime: u8,
+    /// Composite rank the scoreboard is sorted by (0-100, higher is better)
+    pub rank: f64,
+}
+
+impl ValidatorScore {
+    /// Build a row from counted activity and the validator's current standing
+    pub(crate) fn new(
+        validator_id: String,
+        activity: ValidatorActivity,
+        byzantine_events: usize,
+        reputation: u8,
+        emotional_score: u8,
+    ) -> Self {
+        let approval_rate = crate::utils::calculate_percentage(
+            activity.approvals as usize,
+            activity.votes_cast as usize,
+        );
+        let uptime = crate::utils::calculate_percentage(
+            activity.eligible_assessments as usize,
+            activity.assessments as usize,
+        );
+
+        // Reputation carries slashing history; uptime and emotional score carry
+        // reliability and fitness
+        let rank = reputation as f64 * uptime as f64 * emotional_score as f64 / 10_000.0;
+
+        Self {
+            validator_id,
+            blocks_proposed: activity.blocks_proposed,
+            votes_cast: activity.votes_cast,
+            approval_rate,
+            slashing_count: activity.slashes,
+            byzantine_events,
+            reputation,
+            emotional_score,
+            uptime,
+            rank,
+        }
+    }
+}
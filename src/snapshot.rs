@@ -0,0 +1,142 @@
+//! Engine state snapshots persisted at shutdown
+
+use crate::biometric::ValidatorEmotionalState;
+use crate::consensus::{ConsensusMetrics, ConsensusState};
+use crate::error::{ConsensusError, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Consensus state and metrics at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    /// Time the snapshot was taken (Unix milliseconds)
+    pub taken_at: u64,
+    /// Height of the newest finalized block
+    pub finalized_height: u64,
+    /// Hash of the newest finalized block, if any
+    pub tip_hash: Option<String>,
+    /// Consensus state
+    pub state: ConsensusState,
+    /// Consensus metrics
+    pub metrics: ConsensusMetrics,
+    /// Emotional state of each validator, if the engine persists it
+    #[serde(default)]
+    pub validator_states: Vec<ValidatorEmotionalState>,
+}
+
+/// Destination for state snapshots
+pub trait SnapshotStore: Send + Sync {
+    /// Persist a snapshot, replacing any earlier one
+    fn persist(&self, snapshot: &StateSnapshot) -> Result<()>;
+}
+
+/// In-memory snapshot store for tests
+#[derive(Debug, Default)]
+pub struct MemorySnapshotStore {
+    latest: RwLock<Option<StateSnapshot>>,
+}
+
+impl MemorySnapshotStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The most recently persisted snapshot
+    pub fn latest(&self) -> Option<StateSnapshot> {
+        self.latest.read().clone()
+    }
+}
+
+impl SnapshotStore for MemorySnapshotStore {
+    fn persist(&self, snapshot: &StateSnapshot) -> Result<()> {
+        *self.latest.write() = Some(snapshot.clone());
+        Ok(())
+    }
+}
+
+/// Snapshot store writing JSON to a file
+///
+/// The snapshot is written beside the target, synced to disk and renamed over it, so a
+/// crash during the write leaves the previous snapshot intact.
+#[derive(Debug, Clone)]
+pub struct FileSnapshotStore {
+    path: PathBuf,
+}
+
+impl FileSnapshotStore {
+    /// Store snapshots at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Load the snapshot last written to `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<StateSnapshot> {
+        let json = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            ConsensusError::storage_error(format!(
+                "Failed to read snapshot {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        serde_json::from_str(&json)
+            .map_err(|e| ConsensusError::storage_error(format!("Invalid snapshot file: {}", e)))
+    }
+
+    /// Write `contents` to a temporary file, sync it and rename it over the target
+    fn write_synced(&self, contents: &[u8]) -> std::io::Result<()> {
+        let temp_path = self.path.with_extension("tmp");
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, &self.path)
+    }
+}
+
+impl SnapshotStore for FileSnapshotStore {
+    fn persist(&self, snapshot: &StateSnapshot) -> Result<()> {
+        let json = serde_json::to_string_pretty(snapshot)
+            .map_err(|e| ConsensusError::internal(format!("Serialization error: {}", e)))?;
+
+        self.write_synced(json.as_bytes()).map_err(|e| {
+            ConsensusError::storage_error(format!(
+                "Failed to write snapshot {}: {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::ProofOfEmotionEngine;
+
+    #[tokio::test]
+    async fn test_file_store_round_trip() {
+        let engine = ProofOfEmotionEngine::new(Default::default()).unwrap();
+        let snapshot = StateSnapshot {
+            taken_at: 1_700_000_000_000,
+            finalized_height: 0,
+            tip_hash: None,
+            state: engine.get_state().await,
+            metrics: engine.get_metrics().await,
+            validator_states: Vec::new(),
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        let store = FileSnapshotStore::new(&path);
+        store.persist(&snapshot).unwrap();
+        store.persist(&snapshot).unwrap();
+
+        let loaded = FileSnapshotStore::load(&path).unwrap();
+        assert_eq!(loaded.taken_at, snapshot.taken_at);
+        assert_eq!(loaded.state.current_epoch, snapshot.state.current_epoch);
+        assert!(!path.with_extension("tmp").exists());
+        assert!(FileSnapshotStore::load(dir.path().join("missing.json")).is_err());
+    }
+}
@@ -1,10 +1,25 @@
 //! Emotional staking engine with rewards and slashing
 
+use crate::clock::{Clock, SystemClock};
 use crate::error::{ConsensusError, Result};
-use parking_lot::RwLock;
+use crate::utils::{calculate_stake_weight, StakeWeightFn};
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Default smallest amount that can be delegated to a validator
+pub const DEFAULT_MIN_DELEGATION: u64 = 1_000;
+
+/// Default time to wait for a staking lock before giving up with a lock error
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default amount of POE distributed as rewards per epoch
+pub const DEFAULT_REWARD_POOL: u64 = 100_000;
+
+/// Highest commission (percentage) a validator may charge its delegators
+pub const MAX_COMMISSION: u8 = 20;
 
 /// Validator in the staking system
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,8 +34,9 @@ pub struct Validator {
     pub locked_stake: u64,
     /// Available stake (can be withdrawn)
     pub available_stake: u64,
-    /// Epoch when stake unlocks (for unbonding)
-    pub unlock_epoch: Option<u64>,
+    /// Stake being unbonded, one entry per request, oldest first
+    #[serde(default)]
+    pub unbonding: Vec<UnbondingEntry>,
     /// Emotional score
     pub emotional_score: u8,
     /// Reputation score
@@ -37,6 +53,22 @@ pub struct Validator {
     pub total_penalties: u64,
 }
 
+impl Validator {
+    /// Total stake currently unbonding
+    pub fn unbonding_stake(&self) -> u64 {
+        self.unbonding.iter().map(|entry| entry.amount).sum()
+    }
+}
+
+/// Stake withdrawn from a validator, withdrawable once its unlock epoch is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnbondingEntry {
+    /// Amount being unbonded
+    pub amount: u64,
+    /// Epoch from which the amount can be withdrawn
+    pub unlock_epoch: u64,
+}
+
 /// Stake entry for delegation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StakeEntry {
@@ -116,6 +148,72 @@ pub enum SlashingSeverity {
     Critical,
 }
 
+impl SlashingOffense {
+    /// Severity an offense is slashed with
+    pub fn severity(self) -> SlashingSeverity {
+        match self {
+            Self::PoorEmotionalBehavior => SlashingSeverity::Minor,
+            Self::MissedConsensus => SlashingSeverity::Minor,
+            Self::InvalidBiometric => SlashingSeverity::Major,
+            Self::DoubleSigning => SlashingSeverity::Critical,
+            Self::Downtime => SlashingSeverity::Minor,
+        }
+    }
+}
+
+/// Reputation points docked from a slashed validator, by severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReputationPenalties {
+    /// Penalty for a minor offense
+    pub minor: u8,
+    /// Penalty for a major offense
+    pub major: u8,
+    /// Penalty for a critical offense
+    pub critical: u8,
+}
+
+impl Default for ReputationPenalties {
+    fn default() -> Self {
+        Self {
+            minor: 5,
+            major: 10,
+            critical: 20,
+        }
+    }
+}
+
+impl ReputationPenalties {
+    /// Penalty for an offense of the given severity
+    pub fn for_severity(&self, severity: SlashingSeverity) -> u8 {
+        match severity {
+            SlashingSeverity::Minor => self.minor,
+            SlashingSeverity::Major => self.major,
+            SlashingSeverity::Critical => self.critical,
+        }
+    }
+}
+
+/// How the epoch reward pool is split between validators
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RewardModel {
+    /// Weight by stake weight, the square root of stake by default
+    #[default]
+    SqrtStake,
+    /// Weight by `emotional_score^2 * stake weight`, favoring high emotional fitness
+    QuadraticFitness,
+}
+
+impl RewardModel {
+    /// Reward weight of a validator, with stake dampened by `weight_fn`
+    pub fn weight(&self, stake: u64, emotional_score: u8, weight_fn: StakeWeightFn) -> f64 {
+        let stake_weight = calculate_stake_weight(stake, weight_fn);
+        match self {
+            Self::SqrtStake => stake_weight,
+            Self::QuadraticFitness => (emotional_score as f64).powi(2) * stake_weight,
+        }
+    }
+}
+
 /// Reward distribution for an epoch
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RewardDistribution {
@@ -143,8 +241,26 @@ pub struct EmotionalStaking {
     reward_history: Arc<RwLock<Vec<RewardDistribution>>>,
     /// Minimum stake
     min_stake: u64,
+    /// Minimum delegation amount
+    min_delegation: u64,
+    /// Reward weighting
+    reward_model: RewardModel,
+    /// Curve stake is dampened by when weighting rewards
+    stake_weight_fn: StakeWeightFn,
+    /// Amount distributed as rewards per epoch
+    reward_pool: u64,
+    /// Largest share of network stake a validator may hold including delegations
+    max_validator_stake_fraction: Option<f64>,
+    /// Largest number of delegators per validator
+    max_delegators_per_validator: Option<usize>,
     /// Current epoch
     current_epoch: Arc<RwLock<u64>>,
+    /// Epochs unbonding stake stays locked before it can be withdrawn
+    unbonding_period_epochs: u64,
+    /// Reputation docked per offense severity
+    reputation_penalties: ReputationPenalties,
+    /// How long fallible operations wait for a lock
+    lock_timeout: Duration,
 }
 
 impl EmotionalStaking {
@@ -156,10 +272,111 @@ impl EmotionalStaking {
             slashing_events: Arc::new(RwLock::new(Vec::new())),
             reward_history: Arc::new(RwLock::new(Vec::new())),
             min_stake,
+            min_delegation: DEFAULT_MIN_DELEGATION,
+            reward_model: RewardModel::default(),
+            stake_weight_fn: StakeWeightFn::default(),
+            reward_pool: DEFAULT_REWARD_POOL,
+            max_validator_stake_fraction: None,
+            max_delegators_per_validator: None,
             current_epoch: Arc::new(RwLock::new(0)),
+            unbonding_period_epochs: crate::UNBONDING_PERIOD_EPOCHS,
+            reputation_penalties: ReputationPenalties::default(),
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
         }
     }
 
+    /// Create a staking engine that follows a consensus configuration
+    ///
+    /// Takes the minimum stake and reputation penalties from `config`, so slashing
+    /// docks the same reputation here as in the consensus engine.
+    pub fn from_config(config: &crate::consensus::ConsensusConfig) -> Self {
+        Self::new(config.minimum_stake).with_reputation_penalties(config.reputation_penalties)
+    }
+
+    /// Set how long fallible operations wait for a lock before returning a lock error
+    pub fn with_lock_timeout(mut self, lock_timeout: Duration) -> Self {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+
+    /// Acquire a read lock, giving up with a lock error after the timeout
+    fn read<'a, T>(&self, lock: &'a RwLock<T>, resource: &str) -> Result<RwLockReadGuard<'a, T>> {
+        lock.try_read_for(self.lock_timeout)
+            .ok_or_else(|| ConsensusError::lock_error(resource))
+    }
+
+    /// Acquire a write lock, giving up with a lock error after the timeout
+    fn write<'a, T>(&self, lock: &'a RwLock<T>, resource: &str) -> Result<RwLockWriteGuard<'a, T>> {
+        lock.try_write_for(self.lock_timeout)
+            .ok_or_else(|| ConsensusError::lock_error(resource))
+    }
+
+    /// Set the smallest amount that can be delegated, which must be non-zero
+    pub fn with_min_delegation(mut self, min_delegation: u64) -> Result<Self> {
+        if min_delegation == 0 {
+            return Err(ConsensusError::config_error(
+                "Minimum delegation must be > 0",
+            ));
+        }
+        self.min_delegation = min_delegation;
+        Ok(self)
+    }
+
+    /// Choose how the reward pool is weighted between validators
+    pub fn with_reward_model(mut self, reward_model: RewardModel) -> Self {
+        self.reward_model = reward_model;
+        self
+    }
+
+    /// Choose the curve stake is dampened by when weighting rewards
+    pub fn with_stake_weight_fn(mut self, stake_weight_fn: StakeWeightFn) -> Result<Self> {
+        if !stake_weight_fn.is_valid() {
+            return Err(ConsensusError::config_error(
+                "Stake weight exponent must be in (0, 1000] thousandths",
+            ));
+        }
+        self.stake_weight_fn = stake_weight_fn;
+        Ok(self)
+    }
+
+    /// Set the amount distributed as rewards per epoch
+    pub fn with_reward_pool(mut self, reward_pool: u64) -> Self {
+        self.reward_pool = reward_pool;
+        self
+    }
+
+    /// Cap a validator's own stake plus delegations at a fraction of network stake in (0, 1]
+    pub fn with_max_validator_stake_fraction(mut self, fraction: f64) -> Result<Self> {
+        if !(fraction > 0.0 && fraction <= 1.0) {
+            return Err(ConsensusError::config_error(
+                "Max validator stake fraction must be in (0, 1]",
+            ));
+        }
+        self.max_validator_stake_fraction = Some(fraction);
+        Ok(self)
+    }
+
+    /// Set how many epochs unbonding stake stays locked
+    pub fn with_unbonding_period_epochs(mut self, epochs: u64) -> Self {
+        self.unbonding_period_epochs = epochs;
+        self
+    }
+
+    /// Set the reputation docked from slashed validators
+    ///
+    /// Prefer [`from_config`](Self::from_config) next to a consensus engine, so the
+    /// penalties cannot drift from `ConsensusConfig::reputation_penalties`.
+    pub fn with_reputation_penalties(mut self, penalties: ReputationPenalties) -> Self {
+        self.reputation_penalties = penalties;
+        self
+    }
+
+    /// Cap the number of delegators a validator may accept
+    pub fn with_max_delegators_per_validator(mut self, max_delegators: usize) -> Self {
+        self.max_delegators_per_validator = Some(max_delegators);
+        self
+    }
+
     /// Register a validator
     pub fn register_validator(
         &self,
@@ -175,8 +392,11 @@ impl EmotionalStaking {
             ));
         }
 
-        if commission > 20 {
-            return Err(ConsensusError::config_error("Commission must be <= 20%"));
+        if commission > MAX_COMMISSION {
+            return Err(ConsensusError::config_error(format!(
+                "Commission must be <= {}%",
+                MAX_COMMISSION
+            )));
         }
 
         let validator = Validator {
@@ -185,7 +405,7 @@ impl EmotionalStaking {
             stake: initial_stake,
             locked_stake: 0,
             available_stake: initial_stake,
-            unlock_epoch: None,
+            unbonding: Vec::new(),
             emotional_score: 0,
             reputation: 100,
             is_active: true,
@@ -195,7 +415,8 @@ impl EmotionalStaking {
             total_penalties: 0,
         };
 
-        self.validators.write().insert(id, validator);
+        self.write(&self.validators, "validators")?
+            .insert(id, validator);
         Ok(())
     }
 
@@ -207,11 +428,14 @@ impl EmotionalStaking {
         amount: u64,
         lockup_period: u64,
     ) -> Result<()> {
-        if amount < 1000 {
-            return Err(ConsensusError::insufficient_stake(amount, 1000));
+        if amount < self.min_delegation {
+            return Err(ConsensusError::delegation_below_minimum(
+                amount,
+                self.min_delegation,
+            ));
         }
 
-        let validators = self.validators.read();
+        let validators = self.read(&self.validators, "validators")?;
         let validator = validators
             .get(&validator_id)
             .ok_or_else(|| ConsensusError::validator_not_found(&validator_id))?;
@@ -219,9 +443,56 @@ impl EmotionalStaking {
         if !validator.is_active {
             return Err(ConsensusError::invalid_vote("Validator is not active"));
         }
-        drop(validators);
 
         let stake_id = format!("{}:{}", validator_id, delegator);
+        let mut stakes = self.write(&self.stakes, "stakes")?;
+        let active_delegations = || {
+            stakes
+                .values()
+                .filter(|entry| entry.status == StakeStatus::Active)
+        };
+        // Re-delegating replaces the delegator's previous amount
+        let previous_amount = stakes
+            .get(&stake_id)
+            .filter(|entry| entry.status == StakeStatus::Active)
+            .map(|entry| entry.amount)
+            .unwrap_or(0);
+
+        if let Some(max_delegators) = self.max_delegators_per_validator {
+            let delegators = active_delegations()
+                .filter(|entry| entry.validator_id == validator_id)
+                .count();
+            if previous_amount == 0 && delegators >= max_delegators {
+                return Err(ConsensusError::delegation_limit_exceeded(format!(
+                    "Validator {} already has the maximum of {} delegators",
+                    validator_id, max_delegators
+                )));
+            }
+        }
+
+        if let Some(fraction) = self.max_validator_stake_fraction {
+            let delegated_to_validator: u64 = active_delegations()
+                .filter(|entry| entry.validator_id == validator_id)
+                .map(|entry| entry.amount)
+                .sum();
+            let network_stake = validators.values().map(|v| v.stake).sum::<u64>()
+                + active_delegations().map(|entry| entry.amount).sum::<u64>();
+
+            let validator_total =
+                validator.stake + delegated_to_validator - previous_amount + amount;
+            let network_total = network_stake - previous_amount + amount;
+            if validator_total as f64 > network_total as f64 * fraction {
+                return Err(ConsensusError::delegation_limit_exceeded(format!(
+                    "Validator {} would hold {} of {} network stake, above the {}% cap",
+                    validator_id,
+                    validator_total,
+                    network_total,
+                    fraction * 100.0
+                )));
+            }
+        }
+        drop(validators);
+
         let stake = StakeEntry {
             validator_id,
             delegator,
@@ -232,7 +503,7 @@ impl EmotionalStaking {
             status: StakeStatus::Active,
         };
 
-        self.stakes.write().insert(stake_id, stake);
+        stakes.insert(stake_id, stake);
         Ok(())
     }
 
@@ -243,12 +514,12 @@ impl EmotionalStaking {
         offense: SlashingOffense,
         evidence: String,
     ) -> Result<()> {
-        let mut validators = self.validators.write();
+        let mut validators = self.write(&self.validators, "validators")?;
         let validator = validators
             .get_mut(validator_id)
             .ok_or_else(|| ConsensusError::validator_not_found(validator_id))?;
 
-        let severity = Self::determine_severity(offense, &evidence);
+        let severity = offense.severity();
         let slashing_rate = match severity {
             SlashingSeverity::Minor => 0.01,
             SlashingSeverity::Major => 0.05,
@@ -259,11 +530,7 @@ impl EmotionalStaking {
         validator.stake = validator.stake.saturating_sub(slash_amount);
         validator.total_penalties += slash_amount;
 
-        let reputation_penalty = match severity {
-            SlashingSeverity::Minor => 5,
-            SlashingSeverity::Major => 10,
-            SlashingSeverity::Critical => 20,
-        };
+        let reputation_penalty = self.reputation_penalties.for_severity(severity);
         validator.reputation = validator.reputation.saturating_sub(reputation_penalty);
 
         if validator.stake < self.min_stake {
@@ -283,7 +550,8 @@ impl EmotionalStaking {
             evidence,
         };
 
-        self.slashing_events.write().push(event);
+        self.write(&self.slashing_events, "slashing events")?
+            .push(event);
 
         Ok(())
     }
@@ -294,31 +562,51 @@ impl EmotionalStaking {
         validator_scores: HashMap<String, u8>,
     ) -> Result<RewardDistribution> {
         let epoch = {
-            let mut current = self.current_epoch.write();
+            let mut current = self.write(&self.current_epoch, "current epoch")?;
             *current += 1;
             *current
         };
 
-        let base_reward_pool = 100_000;
         let mut validator_rewards = HashMap::new();
         let mut delegator_rewards = HashMap::new();
 
-        let validators = self.validators.read();
-        let total_stake_weight: f64 = validators
+        let validators = self.read(&self.validators, "validators")?;
+        // Validators without a score this epoch count with their last known one
+        let total_weight: f64 = validators
             .values()
             .filter(|v| v.is_active)
-            .map(|v| (v.stake as f64).sqrt())
+            .map(|v| {
+                let score = validator_scores
+                    .get(&v.id)
+                    .copied()
+                    .unwrap_or(v.emotional_score);
+                self.reward_model
+                    .weight(v.stake, score, self.stake_weight_fn)
+            })
             .sum();
 
+        // Without any weight there is nothing to split the pool by
+        let reward_pool = if total_weight > 0.0 && total_weight.is_finite() {
+            self.reward_pool
+        } else {
+            0
+        };
+
         for (validator_id, emotional_score) in validator_scores {
+            if reward_pool == 0 {
+                break;
+            }
             if let Some(validator) = validators.get(&validator_id) {
                 if !validator.is_active {
                     continue;
                 }
 
-                let stake_weight = (validator.stake as f64).sqrt();
-                let base_reward =
-                    ((stake_weight / total_stake_weight) * base_reward_pool as f64) as u64;
+                let weight = self.reward_model.weight(
+                    validator.stake,
+                    emotional_score,
+                    self.stake_weight_fn,
+                );
+                let base_reward = Self::scale_reward(reward_pool, weight / total_weight);
 
                 let emotional_multiplier = if emotional_score >= 75 {
                     1.0 + ((emotional_score - 75) as f64 / 100.0) * 0.3
@@ -326,12 +614,13 @@ impl EmotionalStaking {
                     1.0 - ((75 - emotional_score) as f64 / 100.0) * 0.5
                 };
 
-                let total_reward = (base_reward as f64 * emotional_multiplier) as u64;
+                let total_reward = Self::scale_reward(base_reward, emotional_multiplier);
 
-                let commission_amount = (total_reward * validator.commission as u64) / 100;
+                let commission_amount =
+                    (total_reward as u128 * validator.commission as u128 / 100) as u64;
                 validator_rewards.insert(validator_id.clone(), commission_amount);
 
-                let delegator_reward = total_reward - commission_amount;
+                let delegator_reward = total_reward.saturating_sub(commission_amount);
                 delegator_rewards.insert(validator_id, delegator_reward);
             }
         }
@@ -340,33 +629,31 @@ impl EmotionalStaking {
         let distribution = RewardDistribution {
             epoch,
             timestamp: Self::current_timestamp(),
-            total_rewards: base_reward_pool,
+            total_rewards: reward_pool,
             validator_rewards,
             delegator_rewards,
         };
 
-        self.reward_history.write().push(distribution.clone());
+        self.write(&self.reward_history, "reward history")?
+            .push(distribution.clone());
 
         Ok(distribution)
     }
 
-    /// Determine slashing severity based on offense and evidence
-    fn determine_severity(offense: SlashingOffense, _evidence: &str) -> SlashingSeverity {
-        match offense {
-            SlashingOffense::PoorEmotionalBehavior => SlashingSeverity::Minor,
-            SlashingOffense::MissedConsensus => SlashingSeverity::Minor,
-            SlashingOffense::InvalidBiometric => SlashingSeverity::Major,
-            SlashingOffense::DoubleSigning => SlashingSeverity::Critical,
-            SlashingOffense::Downtime => SlashingSeverity::Minor,
+    /// Multiply a reward by a factor, saturating instead of overflowing
+    fn scale_reward(amount: u64, factor: f64) -> u64 {
+        let scaled = amount as f64 * factor;
+        if scaled.is_nan() || scaled <= 0.0 {
+            0
+        } else {
+            // Casting a float above u64::MAX saturates
+            scaled as u64
         }
     }
 
     /// Get current timestamp
     fn current_timestamp() -> u64 {
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .expect("System time before UNIX_EPOCH - clock may be misconfigured")
-            .as_millis() as u64
+        SystemClock.now_millis()
     }
 
     /// Get validator
@@ -394,7 +681,7 @@ impl EmotionalStaking {
     /// This prevents nothing-at-stake attacks by locking stake while
     /// the validator participates in consensus.
     pub fn lock_stake(&self, validator_id: &str, amount: u64, _epochs: u64) -> Result<()> {
-        let mut validators = self.validators.write();
+        let mut validators = self.write(&self.validators, "validators")?;
         let validator = validators
             .get_mut(validator_id)
             .ok_or_else(|| ConsensusError::validator_not_found(validator_id))?;
@@ -416,7 +703,7 @@ impl EmotionalStaking {
     ///
     /// This makes previously locked stake available again.
     pub fn unlock_stake(&self, validator_id: &str) -> Result<()> {
-        let mut validators = self.validators.write();
+        let mut validators = self.write(&self.validators, "validators")?;
         let validator = validators
             .get_mut(validator_id)
             .ok_or_else(|| ConsensusError::validator_not_found(validator_id))?;
@@ -430,21 +717,20 @@ impl EmotionalStaking {
         Ok(())
     }
 
-    /// Begin unbonding process for a validator
+    /// Begin unbonding part of a validator's available stake
     ///
-    /// Initiates the unbonding period. Stake will be locked for UNBONDING_PERIOD_EPOCHS
-    /// before it can be withdrawn. This prevents nothing-at-stake attacks.
+    /// The amount stays locked for the unbonding period before it can be withdrawn,
+    /// which prevents nothing-at-stake attacks. Several unbondings may be in flight at
+    /// once; the validator is deactivated only if its remaining bonded stake falls
+    /// below the minimum.
     pub fn begin_unbonding(&self, validator_id: &str, amount: u64) -> Result<()> {
-        let mut validators = self.validators.write();
+        let mut validators = self.write(&self.validators, "validators")?;
         let validator = validators
             .get_mut(validator_id)
             .ok_or_else(|| ConsensusError::validator_not_found(validator_id))?;
 
-        // Check if already unbonding
-        if validator.unlock_epoch.is_some() {
-            return Err(ConsensusError::config_error(
-                "Validator is already unbonding",
-            ));
+        if amount == 0 {
+            return Err(ConsensusError::config_error("Unbonding amount must be > 0"));
         }
 
         // Check if enough available stake
@@ -456,49 +742,60 @@ impl EmotionalStaking {
         }
 
         // Get current epoch and calculate unlock epoch
-        let current_epoch = *self.current_epoch.read();
-        let unlock_epoch = current_epoch + crate::UNBONDING_PERIOD_EPOCHS;
+        let current_epoch = *self.read(&self.current_epoch, "current epoch")?;
+        let unlock_epoch = current_epoch + self.unbonding_period_epochs;
 
-        // Start unbonding
         validator.available_stake = validator.available_stake.saturating_sub(amount);
-        validator.locked_stake = validator.locked_stake.saturating_add(amount);
-        validator.unlock_epoch = Some(unlock_epoch);
-        validator.is_active = false; // Deactivate validator during unbonding
+        validator.unbonding.push(UnbondingEntry {
+            amount,
+            unlock_epoch,
+        });
+
+        let bonded = validator.stake.saturating_sub(validator.unbonding_stake());
+        if bonded < self.min_stake {
+            validator.is_active = false;
+        }
 
         Ok(())
     }
 
-    /// Complete unbonding and withdraw stake
+    /// Withdraw every unbonding entry whose unlock epoch has been reached
     ///
-    /// Can only be called after the unbonding period has elapsed.
-    pub fn complete_unbonding(&self, validator_id: &str) -> Result<u64> {
-        let mut validators = self.validators.write();
+    /// Returns the withdrawn entries, oldest first; entries still locked are kept.
+    /// Fails if the validator is not unbonding or none of its entries has unlocked.
+    pub fn complete_unbonding(&self, validator_id: &str) -> Result<Vec<UnbondingEntry>> {
+        let mut validators = self.write(&self.validators, "validators")?;
         let validator = validators
             .get_mut(validator_id)
             .ok_or_else(|| ConsensusError::validator_not_found(validator_id))?;
 
-        let current_epoch = *self.current_epoch.read();
+        let current_epoch = *self.read(&self.current_epoch, "current epoch")?;
 
-        // Check if unbonding
-        let unlock_epoch = validator
-            .unlock_epoch
-            .ok_or_else(|| ConsensusError::config_error("Validator is not unbonding"))?;
+        let Some(next_unlock) = validator
+            .unbonding
+            .iter()
+            .map(|entry| entry.unlock_epoch)
+            .min()
+        else {
+            return Err(ConsensusError::config_error("Validator is not unbonding"));
+        };
 
-        // Check if unbonding period has elapsed
-        if current_epoch < unlock_epoch {
+        if current_epoch < next_unlock {
             return Err(ConsensusError::config_error(format!(
                 "Unbonding period not complete. {} epochs remaining",
-                unlock_epoch - current_epoch
+                next_unlock - current_epoch
             )));
         }
 
-        // Complete unbonding
-        let unbonded_amount = validator.locked_stake;
+        let (completed, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut validator.unbonding)
+            .into_iter()
+            .partition(|entry| entry.unlock_epoch <= current_epoch);
+        validator.unbonding = pending;
+
+        let unbonded_amount: u64 = completed.iter().map(|entry| entry.amount).sum();
         validator.stake = validator.stake.saturating_sub(unbonded_amount);
-        validator.locked_stake = 0;
-        validator.unlock_epoch = None;
 
-        Ok(unbonded_amount)
+        Ok(completed)
     }
 }
 
@@ -566,6 +863,142 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_delegation_stake_fraction_cap() {
+        for fraction in [0.0, 1.5, f64::NAN] {
+            assert!(EmotionalStaking::new(10_000)
+                .with_max_validator_stake_fraction(fraction)
+                .is_err());
+        }
+
+        let staking = EmotionalStaking::new(10_000)
+            .with_max_validator_stake_fraction(0.2)
+            .unwrap();
+        for i in 0..10 {
+            staking
+                .register_validator(format!("validator-{}", i), format!("addr{}", i), 10_000, 5)
+                .unwrap();
+        }
+
+        // 10_000 + 12_500 is exactly 20% of 112_500
+        staking
+            .delegate_stake(
+                "validator-0".to_string(),
+                "delegator1".to_string(),
+                12_500,
+                0,
+            )
+            .unwrap();
+
+        let result = staking.delegate_stake(
+            "validator-0".to_string(),
+            "delegator2".to_string(),
+            1_000,
+            0,
+        );
+        assert!(matches!(
+            result,
+            Err(ConsensusError::DelegationLimitExceeded { .. })
+        ));
+
+        // Other validators still have room
+        staking
+            .delegate_stake(
+                "validator-1".to_string(),
+                "delegator2".to_string(),
+                1_000,
+                0,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_delegator_count_cap() {
+        let staking = EmotionalStaking::new(10_000).with_max_delegators_per_validator(2);
+        staking
+            .register_validator("validator-1".to_string(), "addr1".to_string(), 10_000, 5)
+            .unwrap();
+
+        for delegator in ["delegator1", "delegator2"] {
+            staking
+                .delegate_stake("validator-1".to_string(), delegator.to_string(), 1_000, 0)
+                .unwrap();
+        }
+
+        let result = staking.delegate_stake(
+            "validator-1".to_string(),
+            "delegator3".to_string(),
+            1_000,
+            0,
+        );
+        assert!(matches!(
+            result,
+            Err(ConsensusError::DelegationLimitExceeded { .. })
+        ));
+
+        // An existing delegator can still change its delegation
+        staking
+            .delegate_stake(
+                "validator-1".to_string(),
+                "delegator1".to_string(),
+                2_000,
+                0,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_staggered_unbonding() {
+        let staking = EmotionalStaking::new(10_000).with_unbonding_period_epochs(3);
+        staking
+            .register_validator("validator-1".to_string(), "addr1".to_string(), 30_000, 5)
+            .unwrap();
+        let advance_epoch = || {
+            staking.distribute_rewards(HashMap::new()).unwrap();
+        };
+
+        // Unlocks at epoch 3, then at epoch 4
+        staking.begin_unbonding("validator-1", 5_000).unwrap();
+        advance_epoch();
+        staking.begin_unbonding("validator-1", 8_000).unwrap();
+
+        let validator = staking.get_validator("validator-1").unwrap();
+        assert_eq!(validator.unbonding.len(), 2);
+        assert_eq!(validator.available_stake, 17_000);
+        assert!(validator.is_active);
+
+        advance_epoch();
+        assert!(staking.complete_unbonding("validator-1").is_err());
+
+        advance_epoch();
+        let completed = staking.complete_unbonding("validator-1").unwrap();
+        assert_eq!(
+            completed,
+            vec![UnbondingEntry {
+                amount: 5_000,
+                unlock_epoch: 3
+            }]
+        );
+        let validator = staking.get_validator("validator-1").unwrap();
+        assert_eq!(validator.stake, 25_000);
+        assert_eq!(validator.unbonding_stake(), 8_000);
+        assert!(staking.complete_unbonding("validator-1").is_err());
+
+        advance_epoch();
+        let completed = staking.complete_unbonding("validator-1").unwrap();
+        assert_eq!(
+            completed,
+            vec![UnbondingEntry {
+                amount: 8_000,
+                unlock_epoch: 4
+            }]
+        );
+        let validator = staking.get_validator("validator-1").unwrap();
+        assert_eq!(validator.stake, 17_000);
+        assert!(validator.unbonding.is_empty());
+        assert!(staking.complete_unbonding("validator-1").is_err());
+    }
+
     #[test]
     fn test_slashing() {
         let staking = EmotionalStaking::new(10_000);
@@ -585,4 +1018,169 @@ mod tests {
         let validator = staking.get_validator("validator-1").unwrap();
         assert!(validator.stake < 10_000);
     }
+
+    #[test]
+    fn test_from_config_shares_reputation_penalties() {
+        let penalties = ReputationPenalties {
+            minor: 1,
+            major: 2,
+            critical: 30,
+        };
+        let config = crate::consensus::ConsensusConfig::builder()
+            .reputation_penalties(penalties)
+            .build()
+            .unwrap();
+        let staking = EmotionalStaking::from_config(&config);
+
+        staking
+            .register_validator("validator-1".to_string(), "addr1".to_string(), 20_000, 5)
+            .unwrap();
+        staking
+            .slash_validator(
+                "validator-1",
+                SlashingOffense::DoubleSigning,
+                "Conflicting blocks".to_string(),
+            )
+            .unwrap();
+
+        let validator = staking.get_validator("validator-1").unwrap();
+        assert_eq!(validator.reputation, 100 - penalties.critical);
+    }
+
+    #[test]
+    fn test_lock_timeout_returns_error() {
+        let staking = EmotionalStaking::new(10_000).with_lock_timeout(Duration::from_millis(10));
+        staking
+            .register_validator("validator-1".to_string(), "addr1".to_string(), 10_000, 5)
+            .unwrap();
+
+        let held = staking.validators.write();
+        let result = staking.lock_stake("validator-1", 1_000, 1);
+        assert!(matches!(result, Err(ConsensusError::LockError { .. })));
+        drop(held);
+
+        staking.lock_stake("validator-1", 1_000, 1).unwrap();
+    }
+
+    #[test]
+    fn test_custom_min_delegation() {
+        let zero_minimum = EmotionalStaking::new(10_000).with_min_delegation(0);
+        assert!(zero_minimum.is_err());
+
+        let staking = EmotionalStaking::new(10_000)
+            .with_min_delegation(5_000)
+            .unwrap();
+        staking
+            .register_validator("validator-1".to_string(), "addr1".to_string(), 10_000, 5)
+            .unwrap();
+
+        let err = staking
+            .delegate_stake("validator-1".to_string(), "alice".to_string(), 4_999, 0)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConsensusError::DelegationBelowMinimum { minimum: 5_000, .. }
+        ));
+        assert!(err.to_string().contains("5000"));
+
+        staking
+            .delegate_stake("validator-1".to_string(), "alice".to_string(), 5_000, 0)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_quadratic_fitness_favors_high_scores() {
+        let share_of_high_scorer = |reward_model| {
+            let staking = EmotionalStaking::new(10_000).with_reward_model(reward_model);
+            let scores = HashMap::from([
+                ("validator-1".to_string(), 95),
+                ("validator-2".to_string(), 60),
+            ]);
+            for id in scores.keys() {
+                staking
+                    .register_validator(id.clone(), format!("addr-{}", id), 40_000, 10)
+                    .unwrap();
+            }
+
+            let distribution = staking.distribute_rewards(scores).unwrap();
+            let reward =
+                |id: &str| distribution.validator_rewards[id] + distribution.delegator_rewards[id];
+            reward("validator-1") as f64 / (reward("validator-1") + reward("validator-2")) as f64
+        };
+
+        let sqrt_stake = share_of_high_scorer(RewardModel::SqrtStake);
+        let quadratic = share_of_high_scorer(RewardModel::QuadraticFitness);
+        assert!(sqrt_stake > 0.5);
+        assert!(quadratic > sqrt_stake);
+    }
+
+    #[test]
+    fn test_log_stake_weight_dampens_whale_rewards() {
+        let share_of_whale = |stake_weight_fn| {
+            let staking = EmotionalStaking::new(10_000)
+                .with_stake_weight_fn(stake_weight_fn)
+                .unwrap();
+            for (id, stake) in [("small", 10_000), ("whale", 1_000_000)] {
+                staking
+                    .register_validator(id.to_string(), format!("addr-{}", id), stake, 10)
+                    .unwrap();
+            }
+
+            let scores = HashMap::from([("small".to_string(), 80), ("whale".to_string(), 80)]);
+            let distribution = staking.distribute_rewards(scores).unwrap();
+            let reward =
+                |id: &str| distribution.validator_rewards[id] + distribution.delegator_rewards[id];
+            reward("whale") as f64 / (reward("small") + reward("whale")) as f64
+        };
+
+        let sqrt = share_of_whale(StakeWeightFn::Sqrt);
+        let log = share_of_whale(StakeWeightFn::Log);
+        assert!(log > 0.5);
+        assert!(log < sqrt);
+        assert!(sqrt < share_of_whale(StakeWeightFn::Linear));
+
+        assert!(EmotionalStaking::new(10_000)
+            .with_stake_weight_fn(StakeWeightFn::Power(2000))
+            .is_err());
+    }
+
+    #[test]
+    fn test_no_active_validators_yields_no_rewards() {
+        let staking =
+            EmotionalStaking::new(10_000).with_reward_model(RewardModel::QuadraticFitness);
+        let distribution = staking.distribute_rewards(HashMap::new()).unwrap();
+        assert!(distribution.validator_rewards.is_empty());
+        assert_eq!(distribution.total_rewards, 0);
+
+        // A zero fitness score gives zero weight under the quadratic model
+        staking
+            .register_validator("validator-1".to_string(), "addr-1".to_string(), 40_000, 10)
+            .unwrap();
+        let scores = HashMap::from([("validator-1".to_string(), 0)]);
+        let distribution = staking.distribute_rewards(scores).unwrap();
+        assert!(distribution.validator_rewards.is_empty());
+        assert!(distribution.delegator_rewards.is_empty());
+        assert_eq!(distribution.total_rewards, 0);
+    }
+
+    #[test]
+    fn test_large_reward_pool_does_not_overflow() {
+        let staking = EmotionalStaking::new(10_000).with_reward_pool(u64::MAX);
+        let scores = HashMap::from([
+            ("validator-1".to_string(), 100),
+            ("validator-2".to_string(), 40),
+        ]);
+        for id in scores.keys() {
+            staking
+                .register_validator(id.clone(), format!("addr-{}", id), u64::MAX / 4, 20)
+                .unwrap();
+        }
+
+        let distribution = staking.distribute_rewards(scores).unwrap();
+        assert_eq!(distribution.total_rewards, u64::MAX);
+        let reward = |id: &str| {
+            distribution.validator_rewards[id].checked_add(distribution.delegator_rewards[id])
+        };
+        assert!(reward("validator-1").unwrap() > reward("validator-2").unwrap());
+    }
 }
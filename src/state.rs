@@ -0,0 +1,229 @@
+//! Transaction execution against an application state model
+
+use crate::error::{ConsensusError, Result};
+use crate::types::Transaction;
+use parking_lot::RwLock;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// State machine that finalized transactions are applied to
+pub trait StateMachine: Send + Sync {
+    /// Apply a single transaction, leaving the state untouched on error
+    fn apply(&self, tx: &Transaction) -> Result<()>;
+
+    /// Commitment to the current state
+    fn root(&self) -> String;
+
+    /// Independent copy of the current state
+    ///
+    /// Used to execute a block speculatively when proposing and before committing it.
+    fn fork(&self) -> Box<dyn StateMachine>;
+}
+
+/// Balance key: address and asset, with `None` for the native POE token
+type BalanceKey = (String, Option<String>);
+
+/// Account balance model: transfers move `amount` of the transaction's asset and burn
+/// `fee` in POE
+#[derive(Debug, Default)]
+pub struct BalanceStateMachine {
+    balances: RwLock<BTreeMap<BalanceKey, u64>>,
+}
+
+impl BalanceStateMachine {
+    /// Create an empty state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a state with initial POE balances
+    pub fn with_balances(balances: impl IntoIterator<Item = (String, u64)>) -> Self {
+        Self {
+            balances: RwLock::new(
+                balances
+                    .into_iter()
+                    .map(|(address, amount)| ((address, None), amount))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Set the POE balance of an address
+    pub fn set_balance(&self, address: impl Into<String>, amount: u64) {
+        self.balances.write().insert((address.into(), None), amount);
+    }
+
+    /// Get the POE balance of an address
+    pub fn balance(&self, address: &str) -> u64 {
+        self.balance_of(&(address.to_string(), None))
+    }
+
+    /// Set the balance of an address in a non-native asset
+    pub fn set_asset_balance(
+        &self,
+        address: impl Into<String>,
+        asset_id: impl Into<String>,
+        amount: u64,
+    ) {
+        let key = (address.into(), Some(asset_id.into()));
+        self.balances.write().insert(key, amount);
+    }
+
+    /// Get the balance of an address in a non-native asset
+    pub fn asset_balance(&self, address: &str, asset_id: &str) -> u64 {
+        self.balance_of(&(address.to_string(), Some(asset_id.to_string())))
+    }
+
+    fn balance_of(&self, key: &BalanceKey) -> u64 {
+        self.balances.read().get(key).copied().unwrap_or(0)
+    }
+}
+
+fn describe(key: &BalanceKey) -> String {
+    match &key.1 {
+        Some(asset_id) => format!("{} ({})", key.0, asset_id),
+        None => key.0.clone(),
+    }
+}
+
+impl StateMachine for BalanceStateMachine {
+    fn apply(&self, tx: &Transaction) -> Result<()> {
+        let mut balances = self.balances.write();
+
+        let sender_key = (tx.from.clone(), tx.asset_id.clone());
+        let recipient_key = (tx.to.clone(), tx.asset_id.clone());
+        let fee_key = (tx.from.clone(), None);
+
+        // The amount and the fee come out of the same balance for POE transfers
+        let mut debits = BTreeMap::new();
+        debits.insert(sender_key.clone(), tx.amount);
+        let fee_debit = debits.entry(fee_key).or_insert(0);
+        *fee_debit = fee_debit.checked_add(tx.fee).ok_or_else(|| {
+            ConsensusError::invalid_transaction(format!("Amount overflow in {}", tx.hash))
+        })?;
+
+        for (key, debit) in &debits {
+            let balance = balances.get(key).copied().unwrap_or(0);
+            if balance < *debit {
+                return Err(ConsensusError::invalid_transaction(format!(
+                    "Insufficient balance for {}: {} < {}",
+                    describe(key),
+                    balance,
+                    debit
+                )));
+            }
+        }
+
+        let recipient_balance = balances.get(&recipient_key).copied().unwrap_or(0);
+        if sender_key != recipient_key && recipient_balance.checked_add(tx.amount).is_none() {
+            return Err(ConsensusError::invalid_transaction(format!(
+                "Balance overflow for {}",
+                describe(&recipient_key)
+            )));
+        }
+
+        for (key, debit) in debits {
+            *balances.entry(key).or_insert(0) -= debit;
+        }
+        *balances.entry(recipient_key).or_insert(0) += tx.amount;
+
+        Ok(())
+    }
+
+    fn root(&self) -> String {
+        let mut hasher = Sha256::new();
+        for ((address, asset_id), balance) in self.balances.read().iter() {
+            hasher.update((address.len() as u64).to_le_bytes());
+            hasher.update(address.as_bytes());
+            // Tag the asset so a native balance cannot be read as part of an asset entry
+            match asset_id {
+                None => hasher.update([0]),
+                Some(asset_id) => {
+                    hasher.update([1]);
+                    hasher.update((asset_id.len() as u64).to_le_bytes());
+                    hasher.update(asset_id.as_bytes());
+                }
+            }
+            hasher.update(balance.to_le_bytes());
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    fn fork(&self) -> Box<dyn StateMachine> {
+        Box::new(Self {
+            balances: RwLock::new(self.balances.read().clone()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_moves_balance() {
+        let state = BalanceStateMachine::with_balances([("alice".to_string(), 1_000)]);
+        let tx = Transaction::new("alice".to_string(), "bob".to_string(), 300, 10);
+
+        state.apply(&tx).unwrap();
+
+        assert_eq!(state.balance("alice"), 690);
+        assert_eq!(state.balance("bob"), 300);
+    }
+
+    #[test]
+    fn test_insufficient_balance_rejected() {
+        let state = BalanceStateMachine::with_balances([("alice".to_string(), 100)]);
+        let root = state.root();
+        let tx = Transaction::new("alice".to_string(), "bob".to_string(), 300, 10);
+
+        assert!(state.apply(&tx).is_err());
+        assert_eq!(state.root(), root);
+    }
+
+    #[test]
+    fn test_fork_is_independent() {
+        let state = BalanceStateMachine::with_balances([("alice".to_string(), 1_000)]);
+        let fork = state.fork();
+        let tx = Transaction::new("alice".to_string(), "bob".to_string(), 300, 0);
+
+        fork.apply(&tx).unwrap();
+
+        assert_ne!(fork.root(), state.root());
+        state.apply(&tx).unwrap();
+        assert_eq!(fork.root(), state.root());
+    }
+
+    #[test]
+    fn test_asset_transfer_keeps_balances_separate() {
+        let state = BalanceStateMachine::with_balances([("alice".to_string(), 100)]);
+        state.set_asset_balance("alice", "USDX", 1_000);
+        let tx =
+            Transaction::new("alice".to_string(), "bob".to_string(), 300, 10).with_asset("USDX");
+
+        state.apply(&tx).unwrap();
+
+        assert_eq!(state.asset_balance("alice", "USDX"), 700);
+        assert_eq!(state.asset_balance("bob", "USDX"), 300);
+        assert_eq!(state.balance("alice"), 90);
+        assert_eq!(state.balance("bob"), 0);
+
+        let too_large =
+            Transaction::new("alice".to_string(), "bob".to_string(), 800, 10).with_asset("USDX");
+        assert!(state.apply(&too_large).is_err());
+    }
+
+    #[test]
+    fn test_root_distinguishes_native_and_asset_balances() {
+        // Without a tag, native balances of "a" (9) and "b" (500) hash like one asset
+        // balance whose ID spells out the length prefix and address of "b"
+        let native =
+            BalanceStateMachine::with_balances([("a".to_string(), 9), ("b".to_string(), 500)]);
+        let asset = BalanceStateMachine::new();
+        let mut asset_id = 1u64.to_le_bytes().to_vec();
+        asset_id.push(b'b');
+        asset.set_asset_balance("a", String::from_utf8(asset_id).unwrap(), 500);
+
+        assert_ne!(native.root(), asset.root());
+    }
+}
@@ -1,11 +1,13 @@
 //! Core types for Proof of Emotion consensus
 
+use crate::clock::{Clock, SystemClock};
+use crate::committee::CommitteeProof;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fmt;
 
 /// Block header containing metadata
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct BlockHeader {
     /// Block height
     pub height: u64,
@@ -15,9 +17,14 @@ pub struct BlockHeader {
     pub previous_hash: String,
     /// Merkle root of transactions
     pub merkle_root: String,
+    /// State root after applying this block's transactions (empty without a state machine)
+    #[serde(default)]
+    pub state_root: String,
     /// Block timestamp (Unix milliseconds)
     pub timestamp: u64,
-    /// Block difficulty (unused in POE)
+    /// Emotional volatility index: variance of the committee's emotional scores, in hundredths
+    ///
+    /// Kept under the `difficulty` name for wire compatibility.
     pub difficulty: u32,
     /// Nonce (unused in POE)
     pub nonce: u64,
@@ -25,8 +32,20 @@ pub struct BlockHeader {
     pub validator_id: String,
     /// Emotional score of proposing validator
     pub emotional_score: u8,
-    /// Consensus strength (percentage)
+    /// Stake-weighted average emotional score of the committee the block was proposed to
+    ///
+    /// Taken over the whole committee at proposal time rather than over the approving
+    /// members at finalization: the header is hashed and signed before anyone votes.
+    /// The approving members' fitness is recorded in [`ConsensusMetadata`].
+    #[serde(default)]
+    pub committee_emotional_fitness: u8,
+    /// Consensus strength (percentage); the engine records the strength reached in
+    /// [`ConsensusMetadata`] instead, since the header is fixed before voting
     pub consensus_strength: u8,
+    /// Chain the block was proposed on; covered by the hash and signature so a block
+    /// cannot be replayed on another chain
+    #[serde(default)]
+    pub chain_id: String,
 }
 
 /// Transaction structure
@@ -50,6 +69,105 @@ pub struct Transaction {
     pub public_key: String,
     /// Optional transaction data
     pub data: Vec<u8>,
+    /// Optional expiry time (Unix milliseconds); never extends past the global TTL
+    #[serde(default)]
+    pub valid_until: Option<u64>,
+    /// Asset moved by `amount`; `None` is the native POE token
+    #[serde(default)]
+    pub asset_id: Option<String>,
+}
+
+/// Typed contents of `Transaction::data`
+///
+/// Encoded as a one-byte tag followed by the variant body. Contract calls put the
+/// method name behind a little-endian `u32` length prefix, followed by the arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionPayload {
+    /// Opaque bytes
+    Raw(Vec<u8>),
+    /// UTF-8 text note
+    Memo(String),
+    /// Contract call
+    Contract {
+        /// Method to invoke
+        method: String,
+        /// Encoded call arguments
+        args: Vec<u8>,
+    },
+}
+
+const PAYLOAD_TAG_RAW: u8 = 0;
+const PAYLOAD_TAG_MEMO: u8 = 1;
+const PAYLOAD_TAG_CONTRACT: u8 = 2;
+
+impl TransactionPayload {
+    /// Encode the payload for `Transaction::data`
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::Raw(bytes) => {
+                let mut data = vec![PAYLOAD_TAG_RAW];
+                data.extend_from_slice(bytes);
+                data
+            }
+            Self::Memo(memo) => {
+                let mut data = vec![PAYLOAD_TAG_MEMO];
+                data.extend_from_slice(memo.as_bytes());
+                data
+            }
+            Self::Contract { method, args } => {
+                let mut data = vec![PAYLOAD_TAG_CONTRACT];
+                data.extend_from_slice(&(method.len() as u32).to_le_bytes());
+                data.extend_from_slice(method.as_bytes());
+                data.extend_from_slice(args);
+                data
+            }
+        }
+    }
+
+    /// Decode a payload produced by [`TransactionPayload::encode`]
+    pub fn decode(data: &[u8]) -> Result<Self, String> {
+        let (tag, body) = data
+            .split_first()
+            .ok_or_else(|| "Transaction has no payload".to_string())?;
+
+        match *tag {
+            PAYLOAD_TAG_RAW => Ok(Self::Raw(body.to_vec())),
+            PAYLOAD_TAG_MEMO => String::from_utf8(body.to_vec())
+                .map(Self::Memo)
+                .map_err(|e| format!("Memo is not valid UTF-8: {}", e)),
+            PAYLOAD_TAG_CONTRACT => {
+                if body.len() < 4 {
+                    return Err("Contract payload is truncated".to_string());
+                }
+                let (len, rest) = body.split_at(4);
+                let len = u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize;
+                if rest.len() < len {
+                    return Err("Contract method is truncated".to_string());
+                }
+                let (method, args) = rest.split_at(len);
+                let method = String::from_utf8(method.to_vec())
+                    .map_err(|e| format!("Contract method is not valid UTF-8: {}", e))?;
+
+                Ok(Self::Contract {
+                    method,
+                    args: args.to_vec(),
+                })
+            }
+            tag => Err(format!("Unknown payload tag {}", tag)),
+        }
+    }
+}
+
+/// Current version of the binary block format
+pub const BLOCK_FORMAT_VERSION: u16 = 1;
+
+/// Versioned envelope for blocks in the block store and on the wire
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SerializedBlock {
+    /// Format of the encoded block; always the first field of the encoding
+    pub format_version: u16,
+    /// The block
+    pub block: Block,
 }
 
 /// Block structure
@@ -71,6 +189,26 @@ pub struct Block {
     pub consensus_metadata: Option<ConsensusMetadata>,
 }
 
+/// Block header without transaction bodies, for bandwidth-constrained peers
+///
+/// Transaction hashes are kept because both the block hash and the proposer signature
+/// commit to them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LightBlock {
+    /// Block header
+    pub header: BlockHeader,
+    /// Block hash
+    pub hash: String,
+    /// Number of transactions in the full block
+    pub transaction_count: usize,
+    /// Hashes of the block's transactions, in block order
+    pub transaction_hashes: Vec<String>,
+    /// Block signature by proposer
+    pub signature: String,
+    /// Proposer's public key
+    pub proposer_public_key: String,
+}
+
 /// Consensus metadata attached to finalized blocks
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ConsensusMetadata {
@@ -78,14 +216,39 @@ pub struct ConsensusMetadata {
     pub participant_count: usize,
     /// Consensus strength achieved (percentage)
     pub consensus_strength: u8,
-    /// Average emotional fitness of participants
+    /// Average emotional fitness of participants, stake-weighted under stake-weighted voting
     pub emotional_fitness: u8,
+    /// Unweighted mean emotional fitness of participants
+    #[serde(default)]
+    pub mean_emotional_fitness: u8,
     /// Number of Byzantine failures detected
     pub byzantine_failures: usize,
     /// Timestamp when block was finalized
     pub finalized_at: u64,
     /// List of validator IDs who participated
     pub participants: Vec<String>,
+    /// Proof that the committee was selected correctly
+    #[serde(default)]
+    pub committee_proof: Option<CommitteeProof>,
+    /// Proposer signature over the block's [attestation](Block::attestation)
+    #[serde(default)]
+    pub attestation_signature: String,
+}
+
+/// Confirmation status of a submitted transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxStatus {
+    /// Waiting in the transaction pool
+    Pending,
+    /// Included in a finalized block
+    Finalized {
+        /// Height of the block containing the transaction
+        height: u64,
+    },
+    /// Dropped from the pool after expiring
+    Expired,
+    /// Never seen, or forgotten by the bounded status index
+    Unknown,
 }
 
 /// Vote cast by a validator
@@ -101,6 +264,9 @@ pub struct Vote {
     pub round: u32,
     /// Emotional score of validator at vote time
     pub emotional_score: u8,
+    /// Confidence (0-100) behind the emotional score
+    #[serde(default = "full_confidence")]
+    pub confidence: u8,
     /// Vote signature
     pub signature: String,
     /// Vote timestamp
@@ -111,6 +277,80 @@ pub struct Vote {
     pub reason: Option<String>,
 }
 
+/// Why a vote was dropped without being treated as Byzantine behavior
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VoteRejectionReason {
+    /// Signature missing, malformed or not made by the validator's key
+    InvalidSignature,
+    /// Vote cast for an epoch other than the current one
+    WrongEpoch,
+    /// Vote cast for a block other than the one being voted on
+    StaleBlockHash,
+    /// Vote from a validator without a registered public key
+    UnknownValidator,
+}
+
+impl fmt::Display for VoteRejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSignature => write!(f, "invalid signature"),
+            Self::WrongEpoch => write!(f, "wrong epoch"),
+            Self::StaleBlockHash => write!(f, "stale block hash"),
+            Self::UnknownValidator => write!(f, "unknown validator"),
+        }
+    }
+}
+
+/// Index-level difference between a block's transactions and another transaction list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxDiff {
+    /// Both lists hold a transaction at `index`, with different hashes
+    Changed {
+        /// Position in both lists
+        index: usize,
+        /// Hash of the block's transaction
+        block_hash: String,
+        /// Hash of the other list's transaction
+        other_hash: String,
+    },
+    /// Only the block holds a transaction at `index`
+    OnlyInBlock {
+        /// Position in the block
+        index: usize,
+        /// Hash of the block's transaction
+        hash: String,
+    },
+    /// Only the other list holds a transaction at `index`
+    OnlyInOther {
+        /// Position in the other list
+        index: usize,
+        /// Hash of the other list's transaction
+        hash: String,
+    },
+}
+
+impl fmt::Display for TxDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Changed {
+                index,
+                block_hash,
+                other_hash,
+            } => write!(
+                f,
+                "tx {}: block has {}, other has {}",
+                index, block_hash, other_hash
+            ),
+            Self::OnlyInBlock { index, hash } => {
+                write!(f, "tx {}: only in block ({})", index, hash)
+            }
+            Self::OnlyInOther { index, hash } => {
+                write!(f, "tx {}: only in other ({})", index, hash)
+            }
+        }
+    }
+}
+
 /// Result of a voting round
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VotingResult {
@@ -118,6 +358,12 @@ pub struct VotingResult {
     pub success: bool,
     /// Consensus strength (percentage)
     pub consensus_strength: u8,
+    /// Share of committee members that approved (percentage)
+    #[serde(default)]
+    pub headcount_strength: u8,
+    /// Share of committee stake held by approving members (percentage)
+    #[serde(default)]
+    pub stake_strength: u8,
     /// Number of participants
     pub participant_count: usize,
     /// Number of Byzantine failures
@@ -142,10 +388,28 @@ impl Block {
         emotional_score: u8,
         transactions: Vec<Transaction>,
     ) -> Self {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .expect("System time before UNIX_EPOCH - clock may be misconfigured")
-            .as_millis() as u64;
+        Self::new_with_clock(
+            height,
+            epoch,
+            previous_hash,
+            validator_id,
+            emotional_score,
+            transactions,
+            &SystemClock,
+        )
+    }
+
+    /// Create a new block timestamped by the given clock
+    pub fn new_with_clock(
+        height: u64,
+        epoch: u64,
+        previous_hash: String,
+        validator_id: String,
+        emotional_score: u8,
+        transactions: Vec<Transaction>,
+        clock: &dyn Clock,
+    ) -> Self {
+        let timestamp = clock.now_millis();
 
         let merkle_root = Self::calculate_merkle_root(&transactions);
 
@@ -154,12 +418,15 @@ impl Block {
             epoch,
             previous_hash,
             merkle_root,
+            state_root: String::new(),
             timestamp,
             difficulty: 0,
             nonce: 0,
             validator_id: validator_id.clone(),
             emotional_score,
+            committee_emotional_fitness: 0,
             consensus_strength: 0,
+            chain_id: String::new(),
         };
 
         let hash = Self::calculate_block_hash(&header, &transactions);
@@ -175,36 +442,127 @@ impl Block {
         }
     }
 
+    /// Rebuild a block from untrusted parts, checking them against each other
+    ///
+    /// Every transaction must match its own hash and the header's Merkle root must
+    /// match the transactions; the block hash is recomputed rather than taken from the
+    /// input. The signature is not checked, so call [`Block::verify_signature`] next.
+    pub fn from_parts(
+        header: BlockHeader,
+        transactions: Vec<Transaction>,
+        signature: String,
+        proposer_public_key: String,
+    ) -> Result<Self, String> {
+        if transactions.len() > crate::MAX_BLOCK_TRANSACTIONS {
+            return Err(format!(
+                "Block has {} transactions (limit {})",
+                transactions.len(),
+                crate::MAX_BLOCK_TRANSACTIONS
+            ));
+        }
+        if let Some(tx) = transactions.iter().find(|tx| !tx.verify_hash()) {
+            return Err(format!("Transaction {} does not match its hash", tx.hash));
+        }
+
+        let merkle_root = Self::calculate_merkle_root(&transactions);
+        if merkle_root != header.merkle_root {
+            return Err(format!(
+                "Merkle root mismatch: header has {}, transactions give {}",
+                header.merkle_root, merkle_root
+            ));
+        }
+
+        let hash = Self::calculate_block_hash(&header, &transactions);
+        Ok(Self {
+            header,
+            hash,
+            transactions,
+            signature,
+            proposer_public_key,
+            emotional_proof: None,
+            consensus_metadata: None,
+        })
+    }
+
     /// Calculate block hash
     pub fn calculate_block_hash(header: &BlockHeader, transactions: &[Transaction]) -> String {
+        Self::hash_header(header, transactions.iter().map(|tx| tx.hash.as_str()))
+    }
+
+    fn hash_header<'a>(
+        header: &BlockHeader,
+        tx_hashes: impl IntoIterator<Item = &'a str>,
+    ) -> String {
         let mut hasher = Sha256::new();
 
+        hasher.update((header.chain_id.len() as u64).to_le_bytes());
+        hasher.update(header.chain_id.as_bytes());
         hasher.update(header.height.to_le_bytes());
         hasher.update(header.epoch.to_le_bytes());
         hasher.update(header.previous_hash.as_bytes());
         hasher.update(header.merkle_root.as_bytes());
+        hasher.update(header.state_root.as_bytes());
         hasher.update(header.timestamp.to_le_bytes());
         hasher.update(header.validator_id.as_bytes());
         hasher.update([header.emotional_score]);
+        hasher.update([header.committee_emotional_fitness]);
+        hasher.update([header.consensus_strength]);
+        hasher.update(header.difficulty.to_le_bytes());
 
-        for tx in transactions {
-            hasher.update(tx.hash.as_bytes());
+        for tx_hash in tx_hashes {
+            hasher.update(tx_hash.as_bytes());
         }
 
         hex::encode(hasher.finalize())
     }
 
+    /// Differences between this block's transactions and `other`, index by index
+    ///
+    /// Transactions are compared by hash, so an empty result means both lists produce
+    /// the same Merkle root. Useful to find which transaction two nodes disagree on
+    /// after a Merkle root mismatch.
+    pub fn diff_transactions(&self, other: &[Transaction]) -> Vec<TxDiff> {
+        let len = self.transactions.len().max(other.len());
+        let mut diffs = Vec::new();
+
+        for index in 0..len {
+            let diff = match (self.transactions.get(index), other.get(index)) {
+                (Some(ours), Some(theirs)) if ours.hash != theirs.hash => TxDiff::Changed {
+                    index,
+                    block_hash: ours.hash.clone(),
+                    other_hash: theirs.hash.clone(),
+                },
+                (Some(ours), None) => TxDiff::OnlyInBlock {
+                    index,
+                    hash: ours.hash.clone(),
+                },
+                (None, Some(theirs)) => TxDiff::OnlyInOther {
+                    index,
+                    hash: theirs.hash.clone(),
+                },
+                _ => continue,
+            };
+            diffs.push(diff);
+        }
+
+        diffs
+    }
+
     /// Calculate Merkle root of transactions
     pub fn calculate_merkle_root(transactions: &[Transaction]) -> String {
-        if transactions.is_empty() {
-            return hex::encode(Sha256::digest(b"empty"));
-        }
+        Self::merkle_root_from_hashes(transactions.iter().map(|tx| tx.hash.as_str()))
+    }
 
-        let mut hashes: Vec<Vec<u8>> = transactions
-            .iter()
-            .map(|tx| hex::decode(&tx.hash).unwrap_or_default())
+    fn merkle_root_from_hashes<'a>(tx_hashes: impl IntoIterator<Item = &'a str>) -> String {
+        let mut hashes: Vec<Vec<u8>> = tx_hashes
+            .into_iter()
+            .map(|tx_hash| hex::decode(tx_hash).unwrap_or_default())
             .collect();
 
+        if hashes.is_empty() {
+            return hex::encode(Sha256::digest(b"empty"));
+        }
+
         while hashes.len() > 1 {
             let mut next_level = Vec::new();
 
@@ -231,36 +589,88 @@ impl Block {
         calculated_hash == self.hash
     }
 
+    /// Set the state root and recompute the block hash
+    ///
+    /// Must be called before signing, since the signature covers the hash.
+    pub fn set_state_root(&mut self, state_root: String) {
+        self.header.state_root = state_root;
+        self.hash = Self::calculate_block_hash(&self.header, &self.transactions);
+    }
+
+    /// Set the committee emotional fitness and recompute the block hash
+    ///
+    /// Must be called before signing, since the signature covers the hash.
+    pub fn set_committee_emotional_fitness(&mut self, committee_emotional_fitness: u8) {
+        self.header.committee_emotional_fitness = committee_emotional_fitness;
+        self.hash = Self::calculate_block_hash(&self.header, &self.transactions);
+    }
+
+    /// Set the chain the block is proposed on and recompute the block hash
+    ///
+    /// Must be called before signing, since the signature covers the hash.
+    pub fn set_chain_id(&mut self, chain_id: impl Into<String>) {
+        self.header.chain_id = chain_id.into();
+        self.hash = Self::calculate_block_hash(&self.header, &self.transactions);
+    }
+
+    /// Set the emotional volatility index and recompute the block hash
+    ///
+    /// Must be called before signing, since the signature covers the hash.
+    pub fn set_emotional_volatility(&mut self, volatility: u32) {
+        self.header.difficulty = volatility;
+        self.hash = Self::calculate_block_hash(&self.header, &self.transactions);
+    }
+
+    /// Emotional volatility index recorded in the header
+    pub fn emotional_volatility(&self) -> u32 {
+        self.header.difficulty
+    }
+
+    /// Encode the block in the versioned binary format
+    pub fn serialize(&self) -> Result<Vec<u8>, String> {
+        bincode::serialize(&SerializedBlock {
+            format_version: BLOCK_FORMAT_VERSION,
+            block: self.clone(),
+        })
+        .map_err(|e| format!("Failed to serialize block: {}", e))
+    }
+
+    /// Decode a block written by [`Block::serialize`]
+    ///
+    /// The version is read on its own first, so blocks from an unknown format are
+    /// rejected with a clear error instead of being misparsed.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, String> {
+        let format_version: u16 = bincode::deserialize(bytes)
+            .map_err(|e| format!("Failed to read block format version: {}", e))?;
+        if format_version != BLOCK_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported block format version {} (expected {})",
+                format_version, BLOCK_FORMAT_VERSION
+            ));
+        }
+
+        bincode::deserialize::<SerializedBlock>(bytes)
+            .map(|serialized| serialized.block)
+            .map_err(|e| format!("Failed to deserialize block: {}", e))
+    }
+
     /// Get block size in bytes
     pub fn size(&self) -> usize {
         bincode::serialize(self).map(|b| b.len()).unwrap_or(0)
     }
 
+    /// Sum of the fees of the block's transactions
+    pub fn total_fees(&self) -> u64 {
+        self.transactions
+            .iter()
+            .fold(0, |total, tx| total.saturating_add(tx.fee))
+    }
+
     /// Sign the block with a key pair
     pub fn sign(&mut self, key_pair: &crate::crypto::KeyPair) -> Result<(), String> {
-        // Serialize the data to be signed (header + transactions)
-        let mut data_to_sign = Vec::new();
-
-        // Include all header fields
-        data_to_sign.extend_from_slice(&self.header.height.to_le_bytes());
-        data_to_sign.extend_from_slice(&self.header.epoch.to_le_bytes());
-        data_to_sign.extend_from_slice(self.header.previous_hash.as_bytes());
-        data_to_sign.extend_from_slice(self.header.merkle_root.as_bytes());
-        data_to_sign.extend_from_slice(&self.header.timestamp.to_le_bytes());
-        data_to_sign.extend_from_slice(self.header.validator_id.as_bytes());
-        data_to_sign.push(self.header.emotional_score);
-
-        // Include block hash
-        data_to_sign.extend_from_slice(self.hash.as_bytes());
-
-        // Include all transaction hashes
-        for tx in &self.transactions {
-            data_to_sign.extend_from_slice(tx.hash.as_bytes());
-        }
-
-        // Sign the data
+        // Sign the data (header + transactions)
         let sig = key_pair
-            .sign(&data_to_sign)
+            .sign(&self.signing_data())
             .map_err(|e| format!("Failed to sign block: {}", e))?;
 
         // Serialize signature to JSON string
@@ -273,52 +683,146 @@ impl Block {
 
     /// Verify the block signature
     pub fn verify_signature(&self) -> Result<bool, String> {
-        if self.signature.is_empty() {
+        let sig = self.parse_signature()?;
+
+        // Verify signature
+        crate::crypto::KeyPair::verify(&self.signing_data(), &sig, &self.proposer_public_key)
+            .map_err(|e| format!("Signature verification failed: {}", e))
+    }
+
+    /// Verify the block signature, reusing cached results for identical signatures
+    pub fn verify_signature_cached(
+        &self,
+        cache: &crate::crypto::SignatureCache,
+    ) -> Result<bool, String> {
+        let sig = self.parse_signature()?;
+
+        cache
+            .verify(&self.signing_data(), &sig, &self.proposer_public_key)
+            .map_err(|e| format!("Signature verification failed: {}", e))
+    }
+
+    /// Data covered by the proposer signature
+    pub fn signing_data(&self) -> Vec<u8> {
+        Self::signing_data_for(
+            &self.header,
+            &self.hash,
+            self.transactions.iter().map(|tx| tx.hash.as_str()),
+        )
+    }
+
+    fn signing_data_for<'a>(
+        header: &BlockHeader,
+        hash: &str,
+        tx_hashes: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        // Include all header fields, starting with the length-prefixed chain ID
+        data.extend_from_slice(&(header.chain_id.len() as u64).to_le_bytes());
+        data.extend_from_slice(header.chain_id.as_bytes());
+        data.extend_from_slice(&header.height.to_le_bytes());
+        data.extend_from_slice(&header.epoch.to_le_bytes());
+        data.extend_from_slice(header.previous_hash.as_bytes());
+        data.extend_from_slice(header.merkle_root.as_bytes());
+        data.extend_from_slice(header.state_root.as_bytes());
+        data.extend_from_slice(&header.timestamp.to_le_bytes());
+        data.extend_from_slice(header.validator_id.as_bytes());
+        data.push(header.emotional_score);
+        data.push(header.committee_emotional_fitness);
+        data.push(header.consensus_strength);
+        data.extend_from_slice(&header.difficulty.to_le_bytes());
+
+        // Include block hash
+        data.extend_from_slice(hash.as_bytes());
+
+        // Include all transaction hashes
+        for tx_hash in tx_hashes {
+            data.extend_from_slice(tx_hash.as_bytes());
+        }
+
+        data
+    }
+
+    /// Strip the transactions, keeping what is needed to verify the header
+    pub fn to_header_only(&self) -> LightBlock {
+        LightBlock {
+            header: self.header.clone(),
+            hash: self.hash.clone(),
+            transaction_count: self.transactions.len(),
+            transaction_hashes: self.transactions.iter().map(|tx| tx.hash.clone()).collect(),
+            signature: self.signature.clone(),
+            proposer_public_key: self.proposer_public_key.clone(),
+        }
+    }
+
+    fn parse_signature(&self) -> Result<crate::crypto::Signature, String> {
+        Self::parse_proposer_signature(&self.signature, &self.proposer_public_key)
+    }
+
+    fn parse_proposer_signature(
+        signature: &str,
+        proposer_public_key: &str,
+    ) -> Result<crate::crypto::Signature, String> {
+        if signature.is_empty() {
             return Err("Block has no signature".to_string());
         }
 
-        if self.proposer_public_key.is_empty() {
+        if proposer_public_key.is_empty() {
             return Err("Block has no public key".to_string());
         }
 
         // Deserialize signature from JSON
-        let sig: crate::crypto::Signature = serde_json::from_str(&self.signature)
-            .map_err(|e| format!("Failed to deserialize signature: {}", e))?;
+        serde_json::from_str(signature)
+            .map_err(|e| format!("Failed to deserialize signature: {}", e))
+    }
+}
 
-        // Reconstruct the signed data
-        let mut data_to_verify = Vec::new();
+impl LightBlock {
+    /// Verify header integrity: transaction commitments, block hash and signature
+    pub fn verify(&self) -> Result<bool, String> {
+        if self.transaction_hashes.len() != self.transaction_count {
+            return Ok(false);
+        }
 
-        data_to_verify.extend_from_slice(&self.header.height.to_le_bytes());
-        data_to_verify.extend_from_slice(&self.header.epoch.to_le_bytes());
-        data_to_verify.extend_from_slice(self.header.previous_hash.as_bytes());
-        data_to_verify.extend_from_slice(self.header.merkle_root.as_bytes());
-        data_to_verify.extend_from_slice(&self.header.timestamp.to_le_bytes());
-        data_to_verify.extend_from_slice(self.header.validator_id.as_bytes());
-        data_to_verify.push(self.header.emotional_score);
-        data_to_verify.extend_from_slice(self.hash.as_bytes());
+        let tx_hashes = || self.transaction_hashes.iter().map(String::as_str);
 
-        for tx in &self.transactions {
-            data_to_verify.extend_from_slice(tx.hash.as_bytes());
+        if Block::merkle_root_from_hashes(tx_hashes()) != self.header.merkle_root {
+            return Ok(false);
         }
 
-        // Verify signature
-        crate::crypto::KeyPair::verify(&data_to_verify, &sig, &self.proposer_public_key)
-            .map_err(|e| format!("Signature verification failed: {}", e))
+        if Block::hash_header(&self.header, tx_hashes()) != self.hash {
+            return Ok(false);
+        }
+
+        let sig = Block::parse_proposer_signature(&self.signature, &self.proposer_public_key)?;
+        crate::crypto::KeyPair::verify(
+            &Block::signing_data_for(&self.header, &self.hash, tx_hashes()),
+            &sig,
+            &self.proposer_public_key,
+        )
+        .map_err(|e| format!("Signature verification failed: {}", e))
     }
 }
 
 impl Transaction {
     /// Create a new transaction
     pub fn new(from: String, to: String, amount: u64, fee: u64) -> Self {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .expect("System time before UNIX_EPOCH - clock may be misconfigured")
-            .as_millis() as u64;
+        Self::new_with_clock(from, to, amount, fee, &SystemClock)
+    }
 
-        let hash = Self::calculate_tx_hash(&from, &to, amount, fee, timestamp);
+    /// Create a new transaction timestamped by the given clock
+    pub fn new_with_clock(
+        from: String,
+        to: String,
+        amount: u64,
+        fee: u64,
+        clock: &dyn Clock,
+    ) -> Self {
+        let timestamp = clock.now_millis();
 
-        Self {
-            hash,
+        let mut tx = Self {
+            hash: String::new(),
             from,
             to,
             amount,
@@ -327,49 +831,90 @@ impl Transaction {
             signature: String::new(),
             public_key: String::new(),
             data: Vec::new(),
-        }
+            valid_until: None,
+            asset_id: None,
+        };
+        tx.hash = tx.calculate_tx_hash();
+        tx
+    }
+
+    /// Set an explicit expiry time, recomputing the hash
+    ///
+    /// Must be called before signing, since the expiry is covered by the signature.
+    pub fn with_valid_until(mut self, valid_until: u64) -> Self {
+        self.valid_until = Some(valid_until);
+        self.hash = self.calculate_tx_hash();
+        self
+    }
+
+    /// Move a non-native asset instead of POE, recomputing the hash
+    ///
+    /// Must be called before signing, since the asset is covered by the signature.
+    /// The fee is still paid in POE.
+    pub fn with_asset(mut self, asset_id: impl Into<String>) -> Self {
+        self.asset_id = Some(asset_id.into());
+        self.hash = self.calculate_tx_hash();
+        self
+    }
+
+    /// Store a typed payload in `data`, recomputing the hash
+    ///
+    /// Must be called before signing, since `data` is covered by the signature.
+    pub fn set_payload(&mut self, payload: &TransactionPayload) {
+        self.data = payload.encode();
+        self.hash = self.calculate_tx_hash();
+    }
+
+    /// Cost of validating and storing the transaction, which grows with its data
+    pub fn weight(&self) -> u64 {
+        let data_weight = (self.data.len() as u64).saturating_mul(crate::TX_WEIGHT_PER_DATA_BYTE);
+        crate::TX_BASE_WEIGHT.saturating_add(data_weight)
+    }
+
+    /// Decode `data` as a typed payload
+    ///
+    /// Fails if `data` was not written by [`Transaction::set_payload`].
+    pub fn decode_payload(&self) -> Result<TransactionPayload, String> {
+        TransactionPayload::decode(&self.data)
     }
 
     /// Calculate transaction hash
-    pub fn calculate_tx_hash(
-        from: &str,
-        to: &str,
-        amount: u64,
-        fee: u64,
-        timestamp: u64,
-    ) -> String {
+    ///
+    /// Covers every field except the hash, signature and public key. `data` is only
+    /// hashed when present, so plain transfers keep their hash.
+    pub fn calculate_tx_hash(&self) -> String {
         let mut hasher = Sha256::new();
-        hasher.update(from.as_bytes());
-        hasher.update(to.as_bytes());
-        hasher.update(amount.to_le_bytes());
-        hasher.update(fee.to_le_bytes());
-        hasher.update(timestamp.to_le_bytes());
+        hasher.update(self.from.as_bytes());
+        hasher.update(self.to.as_bytes());
+        hasher.update(self.amount.to_le_bytes());
+        hasher.update(self.fee.to_le_bytes());
+        hasher.update(self.timestamp.to_le_bytes());
+        if let Some(valid_until) = self.valid_until {
+            hasher.update(valid_until.to_le_bytes());
+        }
+        if let Some(asset_id) = &self.asset_id {
+            hasher.update(b"asset");
+            hasher.update((asset_id.len() as u64).to_le_bytes());
+            hasher.update(asset_id.as_bytes());
+        }
+        if !self.data.is_empty() {
+            hasher.update(b"data");
+            hasher.update((self.data.len() as u64).to_le_bytes());
+            hasher.update(&self.data);
+        }
         hex::encode(hasher.finalize())
     }
 
     /// Verify transaction hash
     pub fn verify_hash(&self) -> bool {
-        let calculated_hash =
-            Self::calculate_tx_hash(&self.from, &self.to, self.amount, self.fee, self.timestamp);
-        calculated_hash == self.hash
+        self.calculate_tx_hash() == self.hash
     }
 
     /// Sign the transaction with a key pair
     pub fn sign(&mut self, key_pair: &crate::crypto::KeyPair) -> Result<(), String> {
-        // Serialize the transaction data to be signed
-        let mut data_to_sign = Vec::new();
-
-        data_to_sign.extend_from_slice(self.hash.as_bytes());
-        data_to_sign.extend_from_slice(self.from.as_bytes());
-        data_to_sign.extend_from_slice(self.to.as_bytes());
-        data_to_sign.extend_from_slice(&self.amount.to_le_bytes());
-        data_to_sign.extend_from_slice(&self.fee.to_le_bytes());
-        data_to_sign.extend_from_slice(&self.timestamp.to_le_bytes());
-        data_to_sign.extend_from_slice(&self.data);
-
         // Sign the data
         let sig = key_pair
-            .sign(&data_to_sign)
+            .sign(&self.signing_data())
             .map_err(|e| format!("Failed to sign transaction: {}", e))?;
 
         // Serialize signature to JSON string
@@ -382,6 +927,49 @@ impl Transaction {
 
     /// Verify the transaction signature
     pub fn verify_signature(&self) -> Result<bool, String> {
+        let sig = self.parse_signature()?;
+
+        // Verify signature
+        crate::crypto::KeyPair::verify(&self.signing_data(), &sig, &self.public_key)
+            .map_err(|e| format!("Transaction signature verification failed: {}", e))
+    }
+
+    /// Verify the transaction signature, reusing cached results for identical signatures
+    pub fn verify_signature_cached(
+        &self,
+        cache: &crate::crypto::SignatureCache,
+    ) -> Result<bool, String> {
+        let sig = self.parse_signature()?;
+
+        cache
+            .verify(&self.signing_data(), &sig, &self.public_key)
+            .map_err(|e| format!("Transaction signature verification failed: {}", e))
+    }
+
+    /// Data covered by the sender signature
+    pub fn signing_data(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        data.extend_from_slice(self.hash.as_bytes());
+        data.extend_from_slice(self.from.as_bytes());
+        data.extend_from_slice(self.to.as_bytes());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.fee.to_le_bytes());
+        data.extend_from_slice(&self.timestamp.to_le_bytes());
+        data.extend_from_slice(&self.data);
+        if let Some(valid_until) = self.valid_until {
+            data.extend_from_slice(&valid_until.to_le_bytes());
+        }
+        if let Some(asset_id) = &self.asset_id {
+            data.extend_from_slice(b"asset");
+            data.extend_from_slice(&(asset_id.len() as u64).to_le_bytes());
+            data.extend_from_slice(asset_id.as_bytes());
+        }
+
+        data
+    }
+
+    fn parse_signature(&self) -> Result<crate::crypto::Signature, String> {
         if self.signature.is_empty() {
             return Err("Transaction has no signature".to_string());
         }
@@ -391,36 +979,30 @@ impl Transaction {
         }
 
         // Deserialize signature from JSON
-        let sig: crate::crypto::Signature = serde_json::from_str(&self.signature)
-            .map_err(|e| format!("Failed to deserialize signature: {}", e))?;
-
-        // Reconstruct the signed data
-        let mut data_to_verify = Vec::new();
-
-        data_to_verify.extend_from_slice(self.hash.as_bytes());
-        data_to_verify.extend_from_slice(self.from.as_bytes());
-        data_to_verify.extend_from_slice(self.to.as_bytes());
-        data_to_verify.extend_from_slice(&self.amount.to_le_bytes());
-        data_to_verify.extend_from_slice(&self.fee.to_le_bytes());
-        data_to_verify.extend_from_slice(&self.timestamp.to_le_bytes());
-        data_to_verify.extend_from_slice(&self.data);
-
-        // Verify signature
-        crate::crypto::KeyPair::verify(&data_to_verify, &sig, &self.public_key)
-            .map_err(|e| format!("Transaction signature verification failed: {}", e))
+        serde_json::from_str(&self.signature)
+            .map_err(|e| format!("Failed to deserialize signature: {}", e))
     }
 
     /// Check if transaction has expired
     ///
     /// Transactions older than max_age_ms are considered expired and should be removed
-    /// from the pending pool to prevent memory leaks.
+    /// from the pending pool to prevent memory leaks. A transaction with `valid_until`
+    /// expires at whichever of the two deadlines comes first.
     pub fn is_expired(&self, now: u64, max_age_ms: u64) -> bool {
-        now.saturating_sub(self.timestamp) > max_age_ms
+        let ttl_deadline = self.timestamp.saturating_add(max_age_ms);
+        let deadline = self
+            .valid_until
+            .map_or(ttl_deadline, |valid_until| valid_until.min(ttl_deadline));
+        now > deadline
     }
 }
 
+fn full_confidence() -> u8 {
+    100
+}
+
 impl Vote {
-    /// Create a new vote
+    /// Create a new vote with full confidence
     pub fn new(
         validator_id: String,
         block_hash: String,
@@ -429,10 +1011,28 @@ impl Vote {
         emotional_score: u8,
         approved: bool,
     ) -> Self {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .expect("System time before UNIX_EPOCH - clock may be misconfigured")
-            .as_millis() as u64;
+        Self::new_with_clock(
+            validator_id,
+            block_hash,
+            epoch,
+            round,
+            emotional_score,
+            approved,
+            &SystemClock,
+        )
+    }
+
+    /// Create a new vote timestamped by the given clock
+    pub fn new_with_clock(
+        validator_id: String,
+        block_hash: String,
+        epoch: u64,
+        round: u32,
+        emotional_score: u8,
+        approved: bool,
+        clock: &dyn Clock,
+    ) -> Self {
+        let timestamp = clock.now_millis();
 
         Self {
             validator_id,
@@ -440,12 +1040,80 @@ impl Vote {
             epoch,
             round,
             emotional_score,
+            confidence: full_confidence(),
             signature: String::new(),
             timestamp,
             approved,
             reason: None,
         }
     }
+
+    /// Sign the vote with the validator's key pair
+    pub fn sign(&mut self, key_pair: &crate::crypto::KeyPair) -> Result<(), String> {
+        let sig = key_pair
+            .sign(&self.signing_data())
+            .map_err(|e| format!("Failed to sign vote: {}", e))?;
+
+        self.signature = serde_json::to_string(&sig)
+            .map_err(|e| format!("Failed to serialize signature: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Verify the vote signature against the validator's public key (hex)
+    pub fn verify_signature(&self, public_key: &str) -> Result<bool, String> {
+        if self.signature.is_empty() {
+            return Err("Vote has no signature".to_string());
+        }
+
+        let sig: crate::crypto::Signature = serde_json::from_str(&self.signature)
+            .map_err(|e| format!("Failed to deserialize signature: {}", e))?;
+
+        crate::crypto::KeyPair::verify(&self.signing_data(), &sig, public_key)
+            .map_err(|e| format!("Vote signature verification failed: {}", e))
+    }
+
+    /// Data covered by the validator signature
+    pub fn signing_data(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        data.extend_from_slice(&(self.validator_id.len() as u64).to_le_bytes());
+        data.extend_from_slice(self.validator_id.as_bytes());
+        data.extend_from_slice(self.block_hash.as_bytes());
+        data.extend_from_slice(&self.epoch.to_le_bytes());
+        data.extend_from_slice(&self.round.to_le_bytes());
+        data.push(self.emotional_score);
+        data.push(self.confidence);
+        data.push(self.approved as u8);
+
+        data
+    }
+
+    /// Reason to drop this vote when counting votes on `block_hash` in `epoch`
+    ///
+    /// Returns `None` if the vote is for that block and epoch and is signed by
+    /// `public_key` (hex).
+    pub fn rejection_reason(
+        &self,
+        block_hash: &str,
+        epoch: u64,
+        public_key: &str,
+    ) -> Option<VoteRejectionReason> {
+        if self.epoch != epoch {
+            Some(VoteRejectionReason::WrongEpoch)
+        } else if self.block_hash != block_hash {
+            Some(VoteRejectionReason::StaleBlockHash)
+        } else if self.verify_signature(public_key) != Ok(true) {
+            Some(VoteRejectionReason::InvalidSignature)
+        } else {
+            None
+        }
+    }
+
+    /// Identifier of the vote content (hex SHA-256 of the signing data)
+    pub fn id(&self) -> String {
+        hex::encode(Sha256::digest(self.signing_data()))
+    }
 }
 
 impl fmt::Display for Block {
@@ -487,6 +1155,27 @@ mod tests {
         assert_eq!(tx.fee, 10);
     }
 
+    #[test]
+    fn test_asset_changes_transaction_hash() {
+        let native = Transaction::new("addr1".to_string(), "addr2".to_string(), 1000, 10);
+        let token = native.clone().with_asset("USDX");
+        let other_token = native.clone().with_asset("EURX");
+
+        assert!(token.verify_hash());
+        assert_ne!(native.hash, token.hash);
+        assert_ne!(token.hash, other_token.hash);
+
+        let key_pair = crate::crypto::KeyPair::generate().unwrap();
+        let mut signed = token.clone();
+        signed.sign(&key_pair).unwrap();
+        signed.asset_id = Some("EURX".to_string());
+        assert!(!signed.verify_signature().unwrap());
+
+        let legacy = r#"{"hash":"h","from":"a","to":"b","amount":1,"fee":0,"timestamp":0,"signature":"","public_key":"","data":[]}"#;
+        let decoded: Transaction = serde_json::from_str(legacy).unwrap();
+        assert_eq!(decoded.asset_id, None);
+    }
+
     #[test]
     fn test_block_creation() {
         let txs = vec![
@@ -501,6 +1190,139 @@ mod tests {
         assert_eq!(block.transactions.len(), 2);
     }
 
+    #[test]
+    fn test_block_serialization_round_trip() {
+        let key_pair = crate::crypto::KeyPair::generate().unwrap();
+        let tx = Transaction::new("addr1".to_string(), "addr2".to_string(), 1000, 10);
+        let mut block = Block::new(
+            1,
+            1,
+            "0".repeat(64),
+            "validator-1".to_string(),
+            85,
+            vec![tx],
+        );
+        block.sign(&key_pair).unwrap();
+
+        let bytes = block.serialize().unwrap();
+        let decoded = Block::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, block);
+        assert!(decoded.verify_signature().unwrap());
+
+        assert!(Block::deserialize(&bytes[..1]).is_err());
+        assert!(Block::deserialize(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_block_from_parts() {
+        let key_pair = crate::crypto::KeyPair::generate().unwrap();
+        let txs: Vec<Transaction> = (0..3)
+            .map(|i| Transaction::new(format!("from{}", i), "to".to_string(), 100, 1))
+            .collect();
+        let mut block = Block::new(1, 1, "0".repeat(64), "validator-1".to_string(), 85, txs);
+        block.sign(&key_pair).unwrap();
+        let parts = || {
+            (
+                block.header.clone(),
+                block.transactions.clone(),
+                block.signature.clone(),
+                block.proposer_public_key.clone(),
+            )
+        };
+
+        let (header, transactions, signature, public_key) = parts();
+        let rebuilt = Block::from_parts(header, transactions, signature, public_key).unwrap();
+        assert_eq!(rebuilt, block);
+        assert!(rebuilt.verify_signature().unwrap());
+
+        // Transaction altered without updating its hash
+        let (header, mut transactions, signature, public_key) = parts();
+        transactions[1].amount = 1_000_000;
+        let err = Block::from_parts(header, transactions, signature, public_key).unwrap_err();
+        assert!(err.contains("does not match its hash"));
+
+        // Transaction swapped for another well-formed one
+        let (header, mut transactions, signature, public_key) = parts();
+        transactions[1] = Transaction::new("intruder".to_string(), "to".to_string(), 100, 1);
+        let err = Block::from_parts(header, transactions, signature, public_key).unwrap_err();
+        assert!(err.contains("Merkle root mismatch"));
+
+        // Header altered: the recomputed hash no longer matches the signature
+        let (mut header, transactions, signature, public_key) = parts();
+        header.height = 2;
+        let tampered = Block::from_parts(header, transactions, signature, public_key).unwrap();
+        assert_ne!(tampered.hash, block.hash);
+        assert!(!tampered.verify_signature().unwrap_or(false));
+    }
+
+    #[test]
+    fn test_chain_id_covered_by_signature() {
+        let key_pair = crate::crypto::KeyPair::generate().unwrap();
+        let mut block = Block::new(1, 1, "0".repeat(64), "validator-1".to_string(), 85, vec![]);
+        let unbound_hash = block.hash.clone();
+        block.set_chain_id("poe-testnet");
+        assert_ne!(block.hash, unbound_hash);
+        block.sign(&key_pair).unwrap();
+        assert!(block.verify_signature().unwrap());
+
+        // The same block presented as one of another chain fails verification
+        let mut header = block.header.clone();
+        header.chain_id = "poe-mainnet".to_string();
+        let replayed = Block::from_parts(
+            header,
+            vec![],
+            block.signature.clone(),
+            block.proposer_public_key.clone(),
+        )
+        .unwrap();
+        assert!(!replayed.verify_signature().unwrap());
+    }
+
+    #[test]
+    fn test_unknown_block_format_version_rejected() {
+        let block = Block::new(1, 1, "0".repeat(64), "validator-1".to_string(), 85, vec![]);
+        let mut bytes = block.serialize().unwrap();
+        bytes[..2].copy_from_slice(&(BLOCK_FORMAT_VERSION + 1).to_le_bytes());
+
+        let err = Block::deserialize(&bytes).unwrap_err();
+        assert!(err.contains("Unsupported block format version"));
+    }
+
+    #[test]
+    fn test_diff_transactions_reports_differing_index() {
+        let txs: Vec<Transaction> = (0..4)
+            .map(|i| Transaction::new(format!("from{}", i), "to".to_string(), 100, 1))
+            .collect();
+        let block = Block::new(1, 1, "0".repeat(64), "v1".to_string(), 80, txs.clone());
+        assert!(block.diff_transactions(&txs).is_empty());
+
+        let mut other = txs.clone();
+        other[2] = Transaction::new("intruder".to_string(), "to".to_string(), 100, 1);
+        assert_ne!(
+            Block::calculate_merkle_root(&other),
+            block.header.merkle_root
+        );
+
+        let diffs = block.diff_transactions(&other);
+        assert_eq!(
+            diffs,
+            vec![TxDiff::Changed {
+                index: 2,
+                block_hash: txs[2].hash.clone(),
+                other_hash: other[2].hash.clone(),
+            }]
+        );
+
+        let diffs = block.diff_transactions(&txs[..3]);
+        assert_eq!(
+            diffs,
+            vec![TxDiff::OnlyInBlock {
+                index: 3,
+                hash: txs[3].hash.clone(),
+            }]
+        );
+    }
+
     #[test]
     fn test_merkle_root() {
         let txs = vec![Transaction::new(
@@ -517,6 +1339,32 @@ mod tests {
         assert!(!root1.is_empty());
     }
 
+    #[test]
+    fn test_light_block_verifies() {
+        let txs = vec![
+            Transaction::new("addr1".to_string(), "addr2".to_string(), 1000, 10),
+            Transaction::new("addr3".to_string(), "addr4".to_string(), 2000, 20),
+            Transaction::new("addr5".to_string(), "addr6".to_string(), 3000, 30),
+        ];
+        let mut block = Block::new(1, 0, "0".repeat(64), "validator1".to_string(), 85, txs);
+        block
+            .sign(&crate::crypto::KeyPair::generate().unwrap())
+            .unwrap();
+
+        let light = block.to_header_only();
+
+        assert_eq!(light.transaction_count, 3);
+        assert_eq!(
+            light.header.merkle_root,
+            Block::calculate_merkle_root(&block.transactions)
+        );
+        assert!(light.verify().unwrap());
+
+        let mut tampered = light.clone();
+        tampered.transaction_hashes.swap(0, 1);
+        assert!(!tampered.verify().unwrap());
+    }
+
     #[test]
     fn test_vote_creation() {
         let vote = Vote::new(
@@ -531,4 +1379,77 @@ mod tests {
         assert!(vote.approved);
         assert_eq!(vote.emotional_score, 85);
     }
+
+    #[test]
+    fn test_vote_signature() {
+        let key_pair = crate::crypto::KeyPair::generate().unwrap();
+        let mut vote = Vote::new(
+            "validator1".to_string(),
+            "blockhash123".to_string(),
+            1,
+            0,
+            85,
+            true,
+        );
+        assert!(vote.verify_signature(&key_pair.public_key_hex()).is_err());
+
+        vote.sign(&key_pair).unwrap();
+        assert!(vote.verify_signature(&key_pair.public_key_hex()).unwrap());
+
+        // Flipping the decision invalidates the signature
+        let mut forged = vote.clone();
+        forged.approved = false;
+        assert!(!forged.verify_signature(&key_pair.public_key_hex()).unwrap());
+
+        // A signature by another key does not verify for this validator
+        let attacker = crate::crypto::KeyPair::generate().unwrap();
+        let mut forged = vote.clone();
+        forged.sign(&attacker).unwrap();
+        assert!(!forged.verify_signature(&key_pair.public_key_hex()).unwrap());
+    }
+
+    #[test]
+    fn test_transaction_payload_round_trip() {
+        let payloads = vec![
+            TransactionPayload::Raw(vec![0, 1, 2, 255]),
+            TransactionPayload::Raw(vec![]),
+            TransactionPayload::Memo("invoice #42 ✓".to_string()),
+            TransactionPayload::Contract {
+                method: "transfer".to_string(),
+                args: vec![7, 8, 9],
+            },
+        ];
+
+        for payload in payloads {
+            let mut tx = Transaction::new("addr1".to_string(), "addr2".to_string(), 1000, 10);
+            tx.set_payload(&payload);
+            assert!(tx.verify_hash());
+            assert_eq!(tx.decode_payload().unwrap(), payload);
+        }
+    }
+
+    #[test]
+    fn test_transaction_hash_covers_data() {
+        let mut tx = Transaction::new("addr1".to_string(), "addr2".to_string(), 1000, 10);
+        let plain_hash = tx.hash.clone();
+        tx.set_payload(&TransactionPayload::Memo("invoice #42".to_string()));
+        assert_ne!(tx.hash, plain_hash);
+
+        // Swapping the payload after the fact no longer matches the hash
+        tx.data = TransactionPayload::Memo("invoice #43".to_string()).encode();
+        assert!(!tx.verify_hash());
+    }
+
+    #[test]
+    fn test_invalid_memo_payload_rejected() {
+        let mut tx = Transaction::new("addr1".to_string(), "addr2".to_string(), 1000, 10);
+        tx.data = vec![PAYLOAD_TAG_MEMO, 0xff, 0xfe];
+        assert!(tx.decode_payload().unwrap_err().contains("UTF-8"));
+
+        tx.data = vec![PAYLOAD_TAG_CONTRACT, 10, 0, 0, 0, b'a'];
+        assert!(tx.decode_payload().is_err());
+
+        tx.data = vec![];
+        assert!(tx.decode_payload().is_err());
+    }
 }
@@ -1,8 +1,42 @@
 //! Utility functions for Proof of Emotion consensus
 
-/// Calculate stake weight with square root to reduce whale dominance
-pub fn calculate_stake_weight(stake: u64) -> f64 {
-    (stake as f64).sqrt()
+use serde::{Deserialize, Serialize};
+
+/// Curve mapping stake to influence, dampening whale dominance
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StakeWeightFn {
+    /// Square root of stake
+    #[default]
+    Sqrt,
+    /// Natural logarithm of `1 + stake`, dampening large stakes the most
+    Log,
+    /// Stake itself, without dampening
+    Linear,
+    /// Stake raised to the exponent given in thousandths, which must be in (0, 1000]
+    ///
+    /// Fixed-point so the curve can be compared exactly, e.g. in committee proofs.
+    Power(u16),
+}
+
+impl StakeWeightFn {
+    /// Whether the curve is monotonic and does not amplify stake
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::Power(exponent_millis) => (1..=1000).contains(exponent_millis),
+            _ => true,
+        }
+    }
+}
+
+/// Calculate stake weight under the given curve
+pub fn calculate_stake_weight(stake: u64, weight_fn: StakeWeightFn) -> f64 {
+    let stake = stake as f64;
+    match weight_fn {
+        StakeWeightFn::Sqrt => stake.sqrt(),
+        StakeWeightFn::Log => stake.ln_1p(),
+        StakeWeightFn::Linear => stake,
+        StakeWeightFn::Power(exponent_millis) => stake.powf(f64::from(exponent_millis) / 1000.0),
+    }
 }
 
 /// Calculate emotional bonus multiplier
@@ -127,8 +161,31 @@ mod tests {
 
     #[test]
     fn test_stake_weight() {
-        assert_eq!(calculate_stake_weight(10000), 100.0);
-        assert_eq!(calculate_stake_weight(40000), 200.0);
+        assert_eq!(calculate_stake_weight(10000, StakeWeightFn::Sqrt), 100.0);
+        assert_eq!(calculate_stake_weight(40000, StakeWeightFn::Sqrt), 200.0);
+        assert_eq!(
+            calculate_stake_weight(40000, StakeWeightFn::Linear),
+            40000.0
+        );
+        assert_eq!(
+            calculate_stake_weight(40000, StakeWeightFn::Power(500)),
+            200.0
+        );
+        assert_eq!(calculate_stake_weight(0, StakeWeightFn::Log), 0.0);
+    }
+
+    #[test]
+    fn test_log_dampens_whales_more_than_sqrt() {
+        let ratio = |weight_fn| {
+            calculate_stake_weight(1_000_000, weight_fn) / calculate_stake_weight(10_000, weight_fn)
+        };
+        assert!((ratio(StakeWeightFn::Sqrt) - 10.0).abs() < 1e-9);
+        assert!(ratio(StakeWeightFn::Log) < ratio(StakeWeightFn::Sqrt));
+        assert!(ratio(StakeWeightFn::Sqrt) < ratio(StakeWeightFn::Linear));
+
+        assert!(StakeWeightFn::Power(1000).is_valid());
+        assert!(!StakeWeightFn::Power(0).is_valid());
+        assert!(!StakeWeightFn::Power(1500).is_valid());
     }
 
     #[test]
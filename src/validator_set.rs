@@ -0,0 +1,157 @@
+//! Validator set definitions stored as JSON, for bootstrapping networks from a file
+
+use crate::biometric::EmotionalValidator;
+use crate::crypto::KeyPair;
+use crate::error::{ConsensusError, Result};
+use crate::staking::MAX_COMMISSION;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A validator entry in a validator set file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorRecord {
+    /// Validator ID
+    pub id: String,
+    /// Stake in POE tokens
+    pub stake: u64,
+    /// Commission charged to delegators (percentage)
+    #[serde(default)]
+    pub commission: u8,
+    /// Public key (hex); a fresh key pair is generated when absent
+    #[serde(default)]
+    pub public_key: Option<String>,
+}
+
+/// List of validators to register, e.g. the initial set of a testnet
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorSet {
+    /// Validator entries
+    pub validators: Vec<ValidatorRecord>,
+}
+
+impl ValidatorSet {
+    /// Create an empty validator set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a validator set from a JSON file
+    pub fn from_json(path: impl AsRef<Path>) -> Result<Self> {
+        let json = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            ConsensusError::storage_error(format!(
+                "Failed to read validator set {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        serde_json::from_str(&json).map_err(|e| {
+            ConsensusError::storage_error(format!("Invalid validator set file: {}", e))
+        })
+    }
+
+    /// Write the validator set to a JSON file
+    pub fn to_json(&self, path: impl AsRef<Path>) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| ConsensusError::internal(format!("Serialization error: {}", e)))?;
+        std::fs::write(path.as_ref(), json).map_err(|e| {
+            ConsensusError::storage_error(format!(
+                "Failed to write validator set {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })
+    }
+
+    /// Build validators from the records
+    ///
+    /// A record with a public key needs the matching key pair in `key_pairs`, since the
+    /// file never contains secret keys. Records without one get a generated key pair.
+    /// Commissions above [`MAX_COMMISSION`] are rejected.
+    pub fn into_validators(self, key_pairs: &[KeyPair]) -> Result<Vec<EmotionalValidator>> {
+        self.validators
+            .into_iter()
+            .map(|record| {
+                if record.commission > MAX_COMMISSION {
+                    return Err(ConsensusError::config_error(format!(
+                        "Commission of validator {} must be <= {}%",
+                        record.id, MAX_COMMISSION
+                    )));
+                }
+                let validator = match &record.public_key {
+                    Some(public_key) => key_pairs
+                        .iter()
+                        .find(|key_pair| key_pair.public_key_hex() == *public_key)
+                        .map(|key_pair| {
+                            EmotionalValidator::from_keypair(
+                                &record.id,
+                                record.stake,
+                                key_pair.clone(),
+                            )
+                        })
+                        .ok_or_else(|| {
+                            ConsensusError::config_error(format!(
+                                "No key pair for validator {} with public key {}",
+                                record.id, public_key
+                            ))
+                        })?,
+                    None => EmotionalValidator::new(record.id, record.stake)?,
+                };
+                Ok(validator.with_commission(record.commission))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_public_key_generates_key_pair() {
+        let set = ValidatorSet {
+            validators: vec![ValidatorRecord {
+                id: "validator-1".to_string(),
+                stake: 10_000,
+                commission: 5,
+                public_key: None,
+            }],
+        };
+
+        let validators = set.into_validators(&[]).unwrap();
+        assert_eq!(validators.len(), 1);
+        assert_eq!(validators[0].get_stake(), 10_000);
+        assert_eq!(validators[0].commission(), 5);
+    }
+
+    #[test]
+    fn test_excessive_commission_rejected() {
+        let set = ValidatorSet {
+            validators: vec![ValidatorRecord {
+                id: "validator-1".to_string(),
+                stake: 10_000,
+                commission: MAX_COMMISSION + 1,
+                public_key: None,
+            }],
+        };
+
+        assert!(set.into_validators(&[]).is_err());
+    }
+
+    #[test]
+    fn test_public_key_without_key_pair_rejected() {
+        let key_pair = KeyPair::generate().unwrap();
+        let set = ValidatorSet {
+            validators: vec![ValidatorRecord {
+                id: "validator-1".to_string(),
+                stake: 10_000,
+                commission: 5,
+                public_key: Some(key_pair.public_key_hex()),
+            }],
+        };
+
+        assert!(set.clone().into_validators(&[]).is_err());
+        let public_key = key_pair.public_key_hex();
+        let validators = set.into_validators(&[key_pair]).unwrap();
+        assert_eq!(validators[0].public_key_hex(), public_key);
+    }
+}
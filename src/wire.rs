@@ -0,0 +1,195 @@
+//! Compact binary framing of votes and blocks for gossip
+//!
+//! Every frame is an 8-byte header followed by a bincode payload:
+//!
+//! | bytes | field                                   |
+//! |-------|-----------------------------------------|
+//! | 0..2  | magic `b"PE"`                           |
+//! | 2     | wire format version                     |
+//! | 3     | message kind (vote or block)            |
+//! | 4..8  | payload length, little-endian `u32`     |
+//!
+//! Payloads use variable-length integers and are capped per kind, so a peer cannot
+//! make a decoder allocate more than the cap by lying about a length.
+
+use crate::error::{ConsensusError, Result};
+use crate::types::{Block, Vote};
+use bincode::Options;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Marks the start of a frame
+pub const WIRE_MAGIC: [u8; 2] = *b"PE";
+
+/// Current wire format version
+pub const WIRE_VERSION: u8 = 1;
+
+/// Size of the frame header preceding the payload
+pub const FRAME_HEADER_LEN: usize = 8;
+
+/// Largest vote payload accepted
+pub const MAX_VOTE_PAYLOAD: usize = 4 * 1024;
+
+/// Largest block payload accepted
+pub const MAX_BLOCK_PAYLOAD: usize = 4 * 1024 * 1024;
+
+const KIND_VOTE: u8 = 1;
+const KIND_BLOCK: u8 = 2;
+
+fn options(limit: usize) -> impl Options {
+    bincode::DefaultOptions::new().with_limit(limit as u64)
+}
+
+/// Frame `message` as `kind`, failing if the payload exceeds `limit`
+fn encode<T: Serialize>(
+    message: &T,
+    kind: u8,
+    limit: usize,
+) -> std::result::Result<Vec<u8>, String> {
+    let payload = options(limit)
+        .serialize(message)
+        .map_err(|e| format!("Failed to encode payload: {}", e))?;
+
+    let mut frame = Vec::with_capacity(FRAME_HEADER_LEN + payload.len());
+    frame.extend_from_slice(&WIRE_MAGIC);
+    frame.push(WIRE_VERSION);
+    frame.push(kind);
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+/// Check the header of a `kind` frame and decode its payload
+fn decode<T: DeserializeOwned>(
+    frame: &[u8],
+    kind: u8,
+    limit: usize,
+) -> std::result::Result<T, String> {
+    if frame.len() < FRAME_HEADER_LEN {
+        return Err(format!("Frame too short ({} bytes)", frame.len()));
+    }
+    if frame[..2] != WIRE_MAGIC {
+        return Err("Bad frame magic".to_string());
+    }
+    if frame[2] != WIRE_VERSION {
+        return Err(format!(
+            "Unsupported wire version {} (expected {})",
+            frame[2], WIRE_VERSION
+        ));
+    }
+    if frame[3] != kind {
+        return Err(format!("Unexpected message kind {}", frame[3]));
+    }
+
+    let length = u32::from_le_bytes([frame[4], frame[5], frame[6], frame[7]]) as usize;
+    if length > limit {
+        return Err(format!(
+            "Payload of {} bytes exceeds {} byte limit",
+            length, limit
+        ));
+    }
+    let payload = &frame[FRAME_HEADER_LEN..];
+    if payload.len() != length {
+        return Err(format!(
+            "Payload is {} bytes, header says {}",
+            payload.len(),
+            length
+        ));
+    }
+
+    options(limit)
+        .deserialize(payload)
+        .map_err(|e| format!("Failed to decode payload: {}", e))
+}
+
+/// Encode a vote as a wire frame
+pub fn encode_vote(vote: &Vote) -> Result<Vec<u8>> {
+    encode(vote, KIND_VOTE, MAX_VOTE_PAYLOAD).map_err(ConsensusError::invalid_vote)
+}
+
+/// Decode a vote frame written by [`encode_vote`]
+pub fn decode_vote(frame: &[u8]) -> Result<Vote> {
+    decode(frame, KIND_VOTE, MAX_VOTE_PAYLOAD).map_err(ConsensusError::invalid_vote)
+}
+
+/// Encode a block as a wire frame
+pub fn encode_block(block: &Block) -> Result<Vec<u8>> {
+    encode(block, KIND_BLOCK, MAX_BLOCK_PAYLOAD).map_err(ConsensusError::invalid_block)
+}
+
+/// Decode a block frame written by [`encode_block`]
+pub fn decode_block(frame: &[u8]) -> Result<Block> {
+    decode(frame, KIND_BLOCK, MAX_BLOCK_PAYLOAD).map_err(ConsensusError::invalid_block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::KeyPair;
+    use crate::types::Transaction;
+
+    fn signed_vote() -> Vote {
+        let key_pair = KeyPair::generate().unwrap();
+        let mut vote = Vote::new("validator-1".to_string(), "a".repeat(64), 3, 1, 82, true);
+        vote.sign(&key_pair).unwrap();
+        vote
+    }
+
+    #[test]
+    fn test_vote_round_trip_is_compact() {
+        let vote = signed_vote();
+        let frame = encode_vote(&vote).unwrap();
+        assert_eq!(decode_vote(&frame).unwrap(), vote);
+
+        let json = serde_json::to_vec(&vote).unwrap();
+        assert!(frame.len() < json.len());
+        assert!(frame.len() <= FRAME_HEADER_LEN + MAX_VOTE_PAYLOAD);
+    }
+
+    #[test]
+    fn test_block_round_trip() {
+        let key_pair = KeyPair::generate().unwrap();
+        let txs = (0..3)
+            .map(|i| Transaction::new(format!("from{}", i), "to".to_string(), 100, 1))
+            .collect();
+        let mut block = Block::new(1, 1, "0".repeat(64), "validator-1".to_string(), 85, txs);
+        block.sign(&key_pair).unwrap();
+
+        let frame = encode_block(&block).unwrap();
+        let decoded = decode_block(&frame).unwrap();
+        assert_eq!(decoded, block);
+        assert!(decoded.verify_signature().unwrap());
+        assert!(frame.len() < block.serialize().unwrap().len());
+
+        // A block frame is not a vote
+        assert!(decode_vote(&frame).is_err());
+    }
+
+    #[test]
+    fn test_corrupted_frames_rejected() {
+        let frame = encode_vote(&signed_vote()).unwrap();
+
+        for len in 0..frame.len() {
+            assert!(decode_vote(&frame[..len]).is_err());
+        }
+
+        let mut bad_magic = frame.clone();
+        bad_magic[0] ^= 0xFF;
+        assert!(decode_vote(&bad_magic).is_err());
+
+        let mut bad_version = frame.clone();
+        bad_version[2] = WIRE_VERSION + 1;
+        assert!(decode_vote(&bad_version).is_err());
+
+        // A length beyond the cap is refused before anything is decoded
+        let mut oversized = frame.clone();
+        oversized[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(decode_vote(&oversized).is_err());
+
+        let mut garbled = frame;
+        for byte in &mut garbled[FRAME_HEADER_LEN..] {
+            *byte = 0xFF;
+        }
+        assert!(decode_vote(&garbled).is_err());
+    }
+}
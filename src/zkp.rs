@@ -23,6 +23,15 @@
 //! - Significantly improved privacy
 //! - Same security guarantees as current system
 //!
+//! ## Integration Seam
+//!
+//! The engine already accepts proofs through [`ZkBiometricProvider`]: with a verifier
+//! set via [`ProofOfEmotionEngine::with_zk_verifier`](crate::ProofOfEmotionEngine::with_zk_verifier),
+//! validators are assessed from a submitted [`BiometricProof`] carrying only their
+//! emotional score, and no raw readings are collected. Each proof is bound to the
+//! validator and epoch it was made for. [`MockZkProvider`] stands in for a real proving
+//! system in tests until one is integrated.
+//!
 //! ## Dependencies
 //!
 //! Add to Cargo.toml:
@@ -54,7 +63,9 @@
 //! verify_proof(&proof, &public_inputs, &verifying_key)?;
 //! ```
 
+use crate::error::{self, ConsensusError};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 /// Biometric validation circuit (placeholder)
 ///
@@ -88,6 +99,104 @@ pub struct BiometricProof {
 
     /// Public inputs (just emotional score)
     pub public_inputs: Vec<u8>,
+
+    /// Validator the proof was made for
+    #[serde(default)]
+    pub validator_id: String,
+
+    /// Epoch the proof was made for
+    #[serde(default)]
+    pub epoch: u64,
+}
+
+impl BiometricProof {
+    /// Emotional score claimed by the public inputs, if they hold exactly one valid score
+    pub fn emotional_score(&self) -> Option<u8> {
+        match self.public_inputs.as_slice() {
+            [score] if *score <= 100 => Some(*score),
+            _ => None,
+        }
+    }
+}
+
+/// Proving system for biometric circuits
+///
+/// Validators call [`prove`](Self::prove) locally on their private readings; the engine
+/// only ever calls [`verify`](Self::verify).
+///
+/// The validator ID and epoch are part of the proven statement, so a proof cannot be
+/// replayed by another validator or in another epoch.
+pub trait ZkBiometricProvider: Send + Sync {
+    /// Prove that the circuit's private inputs yield its public emotional score, for
+    /// `validator_id` in `epoch`
+    fn prove(
+        &self,
+        circuit: BiometricCircuit,
+        validator_id: &str,
+        epoch: u64,
+    ) -> error::Result<BiometricProof>;
+
+    /// Check a proof against the public inputs it claims, for `validator_id` in `epoch`
+    fn verify(
+        &self,
+        proof: &BiometricProof,
+        validator_id: &str,
+        epoch: u64,
+        public_inputs: &[u8],
+    ) -> error::Result<bool>;
+}
+
+/// Pass-through provider for testing the proof path
+///
+/// Provides no zero-knowledge or soundness guarantees: the "proof" is a hash of the
+/// public emotional score, so anyone can produce one for any score.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MockZkProvider;
+
+impl MockZkProvider {
+    fn proof_data(validator_id: &str, epoch: u64, public_inputs: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(b"mock-zk-biometric");
+        hasher.update((validator_id.len() as u64).to_le_bytes());
+        hasher.update(validator_id.as_bytes());
+        hasher.update(epoch.to_le_bytes());
+        hasher.update(public_inputs);
+        hasher.finalize().to_vec()
+    }
+}
+
+impl ZkBiometricProvider for MockZkProvider {
+    fn prove(
+        &self,
+        circuit: BiometricCircuit,
+        validator_id: &str,
+        epoch: u64,
+    ) -> error::Result<BiometricProof> {
+        let score = circuit.emotional_score.ok_or_else(|| {
+            ConsensusError::biometric_validation_failed("Circuit has no emotional score")
+        })?;
+        let public_inputs = vec![score];
+
+        Ok(BiometricProof {
+            proof_data: Self::proof_data(validator_id, epoch, &public_inputs),
+            public_inputs,
+            validator_id: validator_id.to_string(),
+            epoch,
+        })
+    }
+
+    fn verify(
+        &self,
+        proof: &BiometricProof,
+        validator_id: &str,
+        epoch: u64,
+        public_inputs: &[u8],
+    ) -> error::Result<bool> {
+        Ok(proof.public_inputs == public_inputs
+            && proof.validator_id == validator_id
+            && proof.epoch == epoch
+            && proof.proof_data == Self::proof_data(validator_id, epoch, public_inputs))
+    }
 }
 
 /// Proving key for generating proofs
@@ -205,4 +314,33 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("not yet implemented"));
     }
+
+    #[test]
+    fn test_mock_provider_round_trip() {
+        let provider = MockZkProvider;
+        let circuit = BiometricCircuit {
+            emotional_score: Some(85),
+            heart_rate: Some(72.5),
+            stress_level: Some(25.0),
+            focus_level: Some(88.0),
+            skin_conductance: None,
+            skin_temperature: None,
+        };
+
+        let proof = provider.prove(circuit, "validator-1", 3).unwrap();
+        assert_eq!(proof.emotional_score(), Some(85));
+        assert!(provider.verify(&proof, "validator-1", 3, &[85]).unwrap());
+        assert!(!provider.verify(&proof, "validator-1", 3, &[95]).unwrap());
+
+        let mut inflated = proof.clone();
+        inflated.public_inputs = vec![95];
+        assert!(!provider.verify(&inflated, "validator-1", 3, &[95]).unwrap());
+
+        // Bound to the validator and epoch it was made for
+        assert!(!provider.verify(&proof, "validator-2", 3, &[85]).unwrap());
+        assert!(!provider.verify(&proof, "validator-1", 4, &[85]).unwrap());
+        let mut replayed = proof;
+        replayed.validator_id = "validator-2".to_string();
+        assert!(!provider.verify(&replayed, "validator-2", 3, &[85]).unwrap());
+    }
 }
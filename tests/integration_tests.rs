@@ -1,5 +1,6 @@
 //! Integration tests for Proof of Emotion consensus
 
+use futures::StreamExt;
 use proof_of_emotion::*;
 use std::sync::Arc;
 use std::time::Duration;
@@ -37,6 +38,61 @@ async fn test_basic_consensus_flow() {
     engine.stop().await.unwrap();
 }
 
+#[tokio::test]
+async fn test_pause_and_resume_keeps_transactions() {
+    let config = ConsensusConfig {
+        epoch_duration: 500,
+        emotional_threshold: 0,
+        voting_timeout: 400,
+        proposal_timeout: 100,
+        finality_timeout: 300,
+        ..Default::default()
+    };
+
+    let engine = Arc::new(ProofOfEmotionEngine::new(config).unwrap());
+    let validator = EmotionalValidator::new("validator-1", 10_000).unwrap();
+    engine.register_validator(validator).await.unwrap();
+
+    Arc::clone(&engine).start().await.unwrap();
+    // Returns once an epoch already in flight has finished
+    engine.pause().await.unwrap();
+    assert!(engine.get_state().await.paused);
+    let paused_height = engine.get_state().await.last_finalized_height;
+    let mut blocks = Box::pin(engine.finalized_block_stream(paused_height + 1));
+
+    let keypair = crypto::KeyPair::generate().unwrap();
+    let mut hashes = Vec::new();
+    for i in 0..3 {
+        let mut tx = Transaction::new("sender".to_string(), format!("receiver-{}", i), 100, 1);
+        tx.sign(&keypair).unwrap();
+        hashes.push(tx.hash.clone());
+        engine.submit_transaction(tx).await.unwrap();
+    }
+
+    // Nothing is finalized while paused, however many epoch ticks pass
+    assert!(time::timeout(Duration::from_millis(1_500), blocks.next())
+        .await
+        .is_err());
+    let state = engine.get_state().await;
+    assert_eq!(state.last_finalized_height, paused_height);
+    assert_eq!(state.pending_transactions, 3);
+
+    engine.resume().await.unwrap();
+    assert!(!engine.get_state().await.paused);
+
+    // The next block is finalized after resuming and picks up the pending transactions
+    let block = time::timeout(Duration::from_secs(10), blocks.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(block.header.height, paused_height + 1);
+    for hash in &hashes {
+        assert!(block.transactions.iter().any(|tx| &tx.hash == hash));
+    }
+
+    engine.stop().await.unwrap();
+}
+
 #[tokio::test]
 async fn test_validator_registration() {
     let config = ConsensusConfig::default();
@@ -92,16 +148,19 @@ async fn test_emotional_proof() {
     hashes.insert("v1".to_string(), "hash1".to_string());
     hashes.insert("v2".to_string(), "hash2".to_string());
 
+    let config = ConsensusConfig::default();
     let proof = crypto::EmotionalProof::new(
         vec!["v1".to_string(), "v2".to_string()],
         scores,
         hashes,
-        30_000,
+        config.temporal_window_ms,
         &keypair,
     )
     .unwrap();
 
-    assert!(proof.verify(&keypair.public_key_hex()).unwrap());
+    assert!(proof
+        .verify(&keypair.public_key_hex(), config.temporal_window_ms)
+        .unwrap());
 }
 
 #[tokio::test]
@@ -222,9 +281,11 @@ async fn test_double_voting_detection() {
     use proof_of_emotion::byzantine::ByzantineDetector;
 
     let detector = Arc::new(ByzantineDetector::new());
+    let key_pair = crypto::KeyPair::generate().unwrap();
+    detector.register_validator_key("byzantine-validator", &key_pair.public_key_hex());
 
     // Create first vote (approve)
-    let vote1 = Vote::new(
+    let mut vote1 = Vote::new(
         "byzantine-validator".to_string(),
         "block-hash-abc".to_string(),
         1, // epoch
@@ -232,11 +293,12 @@ async fn test_double_voting_detection() {
         80,
         true, // approved
     );
+    vote1.sign(&key_pair).unwrap();
 
     detector.record_vote(&vote1).await.unwrap();
 
     // Byzantine validator tries to vote differently on same block
-    let vote2 = Vote::new(
+    let mut vote2 = Vote::new(
         "byzantine-validator".to_string(),
         "block-hash-abc".to_string(),
         1, // same epoch
@@ -244,6 +306,7 @@ async fn test_double_voting_detection() {
         80,
         false, // rejected - Byzantine behavior!
     );
+    vote2.sign(&key_pair).unwrap();
 
     // Should detect double voting
     let result = detector.record_vote(&vote2).await;
@@ -261,9 +324,11 @@ async fn test_equivocation_detection() {
     use proof_of_emotion::byzantine::ByzantineDetector;
 
     let detector = Arc::new(ByzantineDetector::new());
+    let key_pair = crypto::KeyPair::generate().unwrap();
+    detector.register_validator_key("equivocating-validator", &key_pair.public_key_hex());
 
     // Vote on first block
-    let vote1 = Vote::new(
+    let mut vote1 = Vote::new(
         "equivocating-validator".to_string(),
         "block-hash-1".to_string(),
         1, // epoch
@@ -271,10 +336,11 @@ async fn test_equivocation_detection() {
         80,
         true,
     );
+    vote1.sign(&key_pair).unwrap();
     detector.record_vote(&vote1).await.unwrap();
 
     // Vote on different block in same epoch - equivocation!
-    let vote2 = Vote::new(
+    let mut vote2 = Vote::new(
         "equivocating-validator".to_string(),
         "block-hash-2".to_string(),
         1, // same epoch - equivocation!
@@ -282,6 +348,7 @@ async fn test_equivocation_detection() {
         80,
         true,
     );
+    vote2.sign(&key_pair).unwrap();
 
     let result = detector.record_vote(&vote2).await;
     assert!(result.is_err(), "Equivocation should be detected");
@@ -401,6 +468,31 @@ async fn test_future_timestamp_rejection() {
     );
 }
 
+#[tokio::test]
+async fn test_expired_transaction_rejection() {
+    let validator = EmotionalValidator::new("test-validator", 10_000).unwrap();
+
+    let tx = Transaction::new("sender".to_string(), "receiver".to_string(), 1000, 10);
+    let valid_until = tx.timestamp + 1_000;
+    let tx = tx.with_valid_until(valid_until);
+    let mut block = Block::new(
+        1,
+        0,
+        "0".repeat(64),
+        "test-validator".to_string(),
+        80,
+        vec![tx],
+    );
+
+    // Produce the block after the transaction's own deadline
+    block.header.timestamp = valid_until + 1;
+    block.hash = Block::calculate_block_hash(&block.header, &block.transactions);
+    block.sign(&validator.key_pair).unwrap();
+
+    let result = validator.validate_block(&block, &"0".repeat(64), 1, 0);
+    assert!(result.unwrap_err().contains("expired"));
+}
+
 #[tokio::test]
 async fn test_replay_attack_prevention() {
     let validator = EmotionalValidator::new("test-validator", 10_000).unwrap();
@@ -451,3 +543,52 @@ async fn test_transaction_expiration() {
         "Old transaction should be expired"
     );
 }
+
+// ============================================================================
+// SIGNATURE VERIFICATION CACHE TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_committee_validation_uses_signature_cache() {
+    const COMMITTEE_SIZE: usize = 20;
+    const TX_COUNT: usize = 100;
+
+    let sender = KeyPair::generate().unwrap();
+    let transactions: Vec<Transaction> = (0..TX_COUNT)
+        .map(|i| {
+            let mut tx =
+                Transaction::new("sender".to_string(), format!("receiver-{}", i), 1000, 10);
+            tx.sign(&sender).unwrap();
+            tx
+        })
+        .collect();
+
+    let proposer = EmotionalValidator::new("proposer", 10_000).unwrap();
+    let mut block = Block::new(
+        1,
+        0,
+        "0".repeat(64),
+        "proposer".to_string(),
+        80,
+        transactions,
+    );
+    block.sign(&proposer.key_pair).unwrap();
+
+    let cache = SignatureCache::new(1_000);
+    for i in 0..COMMITTEE_SIZE {
+        let member = EmotionalValidator::new(format!("member-{}", i), 10_000).unwrap();
+        member
+            .validate_block_cached(&block, &"0".repeat(64), 1, 0, &cache)
+            .unwrap();
+    }
+
+    // Naive validation recovers the block signature plus every transaction signature
+    // once per committee member; with the cache each is recovered exactly once.
+    let naive_recoveries = (COMMITTEE_SIZE * (TX_COUNT + 1)) as u64;
+    assert_eq!(cache.verification_count(), (TX_COUNT + 1) as u64);
+    assert_eq!(
+        cache.hit_count(),
+        naive_recoveries - cache.verification_count()
+    );
+    assert!(cache.verification_count() * 10 < naive_recoveries);
+}
@@ -264,7 +264,10 @@ async fn test_byzantine_attacks_at_scale() {
 
     // Simulate 100 double voting attempts
     for i in 0..100 {
-        let vote1 = types::Vote::new(
+        let key_pair = crypto::KeyPair::generate().unwrap();
+        detector.register_validator_key(&format!("byzantine-{}", i), &key_pair.public_key_hex());
+
+        let mut vote1 = types::Vote::new(
             format!("byzantine-{}", i),
             "block-abc".to_string(),
             1,
@@ -272,9 +275,10 @@ async fn test_byzantine_attacks_at_scale() {
             80,
             true,
         );
+        vote1.sign(&key_pair).unwrap();
         detector.record_vote(&vote1).await.unwrap();
 
-        let vote2 = types::Vote::new(
+        let mut vote2 = types::Vote::new(
             format!("byzantine-{}", i),
             "block-abc".to_string(),
             1,
@@ -282,6 +286,7 @@ async fn test_byzantine_attacks_at_scale() {
             80,
             false, // Different vote!
         );
+        vote2.sign(&key_pair).unwrap();
 
         let result = detector.record_vote(&vote2).await;
         assert!(result.is_err(), "Attack {} should be detected", i);